@@ -0,0 +1,6 @@
+-- Migration: Add word-level timestamps to transcript segments
+-- Stores a JSON array of {text, start, end, confidence?} objects, with times in
+-- seconds from recording start, so the UI can highlight and seek to single words.
+-- NULL for segments from engines without word timing and for older meetings.
+
+ALTER TABLE transcripts ADD COLUMN words TEXT;
@@ -0,0 +1,17 @@
+-- Migration: Add speaker diarization
+-- transcripts.speaker keeps the capture stream ('mic' or 'system');
+-- transcripts.speaker_id holds the stable per-meeting speaker ID from diarization
+-- ('me' for the microphone, 'speaker_1', 'speaker_2', ... for system audio).
+-- The speakers table stores user-editable display names for those IDs.
+
+ALTER TABLE transcripts ADD COLUMN speaker_id TEXT;
+
+CREATE TABLE IF NOT EXISTS speakers (
+    meeting_id TEXT NOT NULL,
+    speaker_id TEXT NOT NULL,
+    label TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (meeting_id, speaker_id),
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
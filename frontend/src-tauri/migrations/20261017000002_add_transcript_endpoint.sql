@@ -0,0 +1,6 @@
+-- Migration: Add OpenAI-compatible transcription endpoint
+-- transcriptEndpoint is the API root of a self-hosted /audio/transcriptions server
+-- (provider 'openaiCompatible'); its optional key is stored like the other providers.
+
+ALTER TABLE transcript_settings ADD COLUMN transcriptEndpoint TEXT;
+ALTER TABLE transcript_settings ADD COLUMN openaiCompatibleApiKey TEXT;
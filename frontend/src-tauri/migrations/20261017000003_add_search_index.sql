@@ -0,0 +1,151 @@
+-- Full-text search over transcripts, summaries and meeting notes.
+-- search_documents maps each indexed row to its source; search_fts holds its
+-- text under the same rowid. Triggers keep both in sync with the source tables.
+CREATE TABLE IF NOT EXISTS search_documents (
+    id INTEGER PRIMARY KEY,
+    source TEXT NOT NULL,       -- 'transcript', 'summary' or 'notes'
+    source_id TEXT NOT NULL,    -- transcripts.id, or the meeting_id for summaries and notes
+    meeting_id TEXT NOT NULL,
+    UNIQUE (source, source_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_search_documents_meeting_id ON search_documents(meeting_id);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS search_fts USING fts5(
+    content,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+-- Backfill existing data
+INSERT OR IGNORE INTO search_documents (source, source_id, meeting_id)
+SELECT 'transcript', id, meeting_id FROM transcripts;
+
+INSERT OR IGNORE INTO search_documents (source, source_id, meeting_id)
+SELECT 'summary', meeting_id, meeting_id FROM summary_processes WHERE result IS NOT NULL;
+
+INSERT OR IGNORE INTO search_documents (source, source_id, meeting_id)
+SELECT 'notes', meeting_id, meeting_id FROM meeting_notes WHERE notes_markdown IS NOT NULL;
+
+INSERT INTO search_fts (rowid, content)
+SELECT d.id, t.transcript
+FROM search_documents d JOIN transcripts t ON t.id = d.source_id
+WHERE d.source = 'transcript';
+
+-- Summaries are JSON; index the Markdown when present, else the raw result
+INSERT INTO search_fts (rowid, content)
+SELECT d.id,
+       CASE WHEN json_valid(s.result)
+            THEN COALESCE(json_extract(s.result, '$.markdown'), s.result)
+            ELSE s.result END
+FROM search_documents d JOIN summary_processes s ON s.meeting_id = d.source_id
+WHERE d.source = 'summary';
+
+INSERT INTO search_fts (rowid, content)
+SELECT d.id, n.notes_markdown
+FROM search_documents d JOIN meeting_notes n ON n.meeting_id = d.source_id
+WHERE d.source = 'notes';
+
+-- Transcripts
+CREATE TRIGGER IF NOT EXISTS transcripts_search_insert AFTER INSERT ON transcripts
+BEGIN
+    DELETE FROM search_fts WHERE rowid IN
+        (SELECT id FROM search_documents WHERE source = 'transcript' AND source_id = NEW.id);
+    DELETE FROM search_documents WHERE source = 'transcript' AND source_id = NEW.id;
+    INSERT INTO search_documents (source, source_id, meeting_id)
+    VALUES ('transcript', NEW.id, NEW.meeting_id);
+    INSERT INTO search_fts (rowid, content)
+    VALUES ((SELECT id FROM search_documents WHERE source = 'transcript' AND source_id = NEW.id),
+            NEW.transcript);
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcripts_search_update AFTER UPDATE OF transcript, meeting_id ON transcripts
+BEGIN
+    DELETE FROM search_fts WHERE rowid IN
+        (SELECT id FROM search_documents WHERE source = 'transcript' AND source_id = OLD.id);
+    DELETE FROM search_documents WHERE source = 'transcript' AND source_id = OLD.id;
+    INSERT INTO search_documents (source, source_id, meeting_id)
+    VALUES ('transcript', NEW.id, NEW.meeting_id);
+    INSERT INTO search_fts (rowid, content)
+    VALUES ((SELECT id FROM search_documents WHERE source = 'transcript' AND source_id = NEW.id),
+            NEW.transcript);
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcripts_search_delete AFTER DELETE ON transcripts
+BEGIN
+    DELETE FROM search_fts WHERE rowid IN
+        (SELECT id FROM search_documents WHERE source = 'transcript' AND source_id = OLD.id);
+    DELETE FROM search_documents WHERE source = 'transcript' AND source_id = OLD.id;
+END;
+
+-- Summaries (indexed once a result exists)
+CREATE TRIGGER IF NOT EXISTS summaries_search_insert AFTER INSERT ON summary_processes
+WHEN NEW.result IS NOT NULL
+BEGIN
+    DELETE FROM search_fts WHERE rowid IN
+        (SELECT id FROM search_documents WHERE source = 'summary' AND source_id = NEW.meeting_id);
+    DELETE FROM search_documents WHERE source = 'summary' AND source_id = NEW.meeting_id;
+    INSERT INTO search_documents (source, source_id, meeting_id)
+    VALUES ('summary', NEW.meeting_id, NEW.meeting_id);
+    INSERT INTO search_fts (rowid, content)
+    VALUES ((SELECT id FROM search_documents WHERE source = 'summary' AND source_id = NEW.meeting_id),
+            CASE WHEN json_valid(NEW.result)
+                 THEN COALESCE(json_extract(NEW.result, '$.markdown'), NEW.result)
+                 ELSE NEW.result END);
+END;
+
+CREATE TRIGGER IF NOT EXISTS summaries_search_update AFTER UPDATE OF result ON summary_processes
+BEGIN
+    DELETE FROM search_fts WHERE rowid IN
+        (SELECT id FROM search_documents WHERE source = 'summary' AND source_id = OLD.meeting_id);
+    DELETE FROM search_documents WHERE source = 'summary' AND source_id = OLD.meeting_id;
+    INSERT INTO search_documents (source, source_id, meeting_id)
+    SELECT 'summary', NEW.meeting_id, NEW.meeting_id WHERE NEW.result IS NOT NULL;
+    INSERT INTO search_fts (rowid, content)
+    SELECT id,
+           CASE WHEN json_valid(NEW.result)
+                THEN COALESCE(json_extract(NEW.result, '$.markdown'), NEW.result)
+                ELSE NEW.result END
+    FROM search_documents
+    WHERE source = 'summary' AND source_id = NEW.meeting_id AND NEW.result IS NOT NULL;
+END;
+
+CREATE TRIGGER IF NOT EXISTS summaries_search_delete AFTER DELETE ON summary_processes
+BEGIN
+    DELETE FROM search_fts WHERE rowid IN
+        (SELECT id FROM search_documents WHERE source = 'summary' AND source_id = OLD.meeting_id);
+    DELETE FROM search_documents WHERE source = 'summary' AND source_id = OLD.meeting_id;
+END;
+
+-- Meeting notes
+CREATE TRIGGER IF NOT EXISTS notes_search_insert AFTER INSERT ON meeting_notes
+WHEN NEW.notes_markdown IS NOT NULL
+BEGIN
+    DELETE FROM search_fts WHERE rowid IN
+        (SELECT id FROM search_documents WHERE source = 'notes' AND source_id = NEW.meeting_id);
+    DELETE FROM search_documents WHERE source = 'notes' AND source_id = NEW.meeting_id;
+    INSERT INTO search_documents (source, source_id, meeting_id)
+    VALUES ('notes', NEW.meeting_id, NEW.meeting_id);
+    INSERT INTO search_fts (rowid, content)
+    VALUES ((SELECT id FROM search_documents WHERE source = 'notes' AND source_id = NEW.meeting_id),
+            NEW.notes_markdown);
+END;
+
+CREATE TRIGGER IF NOT EXISTS notes_search_update AFTER UPDATE OF notes_markdown ON meeting_notes
+BEGIN
+    DELETE FROM search_fts WHERE rowid IN
+        (SELECT id FROM search_documents WHERE source = 'notes' AND source_id = OLD.meeting_id);
+    DELETE FROM search_documents WHERE source = 'notes' AND source_id = OLD.meeting_id;
+    INSERT INTO search_documents (source, source_id, meeting_id)
+    SELECT 'notes', NEW.meeting_id, NEW.meeting_id WHERE NEW.notes_markdown IS NOT NULL;
+    INSERT INTO search_fts (rowid, content)
+    SELECT id, NEW.notes_markdown
+    FROM search_documents
+    WHERE source = 'notes' AND source_id = NEW.meeting_id AND NEW.notes_markdown IS NOT NULL;
+END;
+
+CREATE TRIGGER IF NOT EXISTS notes_search_delete AFTER DELETE ON meeting_notes
+BEGIN
+    DELETE FROM search_fts WHERE rowid IN
+        (SELECT id FROM search_documents WHERE source = 'notes' AND source_id = OLD.meeting_id);
+    DELETE FROM search_documents WHERE source = 'notes' AND source_id = OLD.meeting_id;
+END;
@@ -0,0 +1,38 @@
+-- Sentence embeddings for semantic search over transcripts and summaries.
+-- Each embedding model gets its own rows so switching models never mixes vector
+-- spaces. Vectors are L2-normalised little-endian f32 arrays.
+CREATE TABLE IF NOT EXISTS embeddings (
+    id INTEGER PRIMARY KEY,
+    source TEXT NOT NULL,           -- 'transcript' or 'summary'
+    source_id TEXT NOT NULL,        -- transcripts.id, or the meeting_id for summaries
+    passage INTEGER NOT NULL DEFAULT 0, -- passage index within a summary
+    model TEXT NOT NULL,            -- e.g. 'local:all-MiniLM-L6-v2', 'ollama:nomic-embed-text'
+    meeting_id TEXT NOT NULL,
+    content TEXT NOT NULL,
+    vector BLOB NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE (source, source_id, passage, model)
+);
+
+CREATE INDEX IF NOT EXISTS idx_embeddings_model_meeting ON embeddings(model, meeting_id);
+
+-- Stale embeddings are dropped here and recomputed on the next index sync
+CREATE TRIGGER IF NOT EXISTS transcripts_embeddings_update AFTER UPDATE OF transcript ON transcripts
+BEGIN
+    DELETE FROM embeddings WHERE source = 'transcript' AND source_id = OLD.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcripts_embeddings_delete AFTER DELETE ON transcripts
+BEGIN
+    DELETE FROM embeddings WHERE source = 'transcript' AND source_id = OLD.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS summaries_embeddings_update AFTER UPDATE OF result ON summary_processes
+BEGIN
+    DELETE FROM embeddings WHERE source = 'summary' AND source_id = OLD.meeting_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS summaries_embeddings_delete AFTER DELETE ON summary_processes
+BEGIN
+    DELETE FROM embeddings WHERE source = 'summary' AND source_id = OLD.meeting_id;
+END;
@@ -0,0 +1,20 @@
+-- Action items extracted from meeting summaries, tracked across meetings.
+-- Supersedes the unused transcripts.action_items column.
+CREATE TABLE IF NOT EXISTS action_items (
+    id TEXT PRIMARY KEY,
+    meeting_id TEXT NOT NULL,
+    task TEXT NOT NULL,
+    owner TEXT,
+    due_date TEXT,                      -- as written in the summary ("Friday", "2026-10-24")
+    source_time REAL,                   -- seconds from recording start where it was discussed
+    source_segment_id TEXT,             -- transcripts.id of that moment, when found
+    completed INTEGER NOT NULL DEFAULT 0,
+    completed_at TEXT,
+    edited INTEGER NOT NULL DEFAULT 0,  -- changed by the user; kept when the summary is regenerated
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_action_items_meeting_id ON action_items(meeting_id);
+CREATE INDEX IF NOT EXISTS idx_action_items_open ON action_items(completed, owner);
@@ -0,0 +1,31 @@
+-- Every generated, edited or restored summary of a meeting.
+-- summary_processes.result stays the current summary, and result_backup only
+-- covers a regeneration in progress; history lives here.
+CREATE TABLE IF NOT EXISTS summary_versions (
+    id TEXT PRIMARY KEY,
+    meeting_id TEXT NOT NULL,
+    version INTEGER NOT NULL,           -- 1, 2, ... per meeting
+    source TEXT NOT NULL,               -- 'generated', 'edited' or 'restored'
+    result TEXT NOT NULL,               -- summary_processes.result JSON
+    provider TEXT,
+    model TEXT,
+    template_id TEXT,
+    custom_prompt TEXT,
+    input_tokens INTEGER,               -- estimated transcript tokens sent
+    output_tokens INTEGER,              -- estimated tokens of the final summary
+    chunk_count INTEGER,
+    processing_time REAL,
+    restored_from INTEGER,              -- version copied by a 'restored' version
+    created_at TEXT NOT NULL,
+    UNIQUE (meeting_id, version),
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+-- Keep the summaries that already exist as their meeting's first version
+INSERT OR IGNORE INTO summary_versions
+    (id, meeting_id, version, source, result, provider, model, chunk_count, processing_time, created_at)
+SELECT 'sv-' || lower(hex(randomblob(16))), p.meeting_id, 1, 'generated', p.result,
+       t.model, t.model_name, p.chunk_count, p.processing_time, COALESCE(p.end_time, p.updated_at)
+FROM summary_processes p
+LEFT JOIN transcript_chunks t ON t.meeting_id = p.meeting_id
+WHERE p.result IS NOT NULL;
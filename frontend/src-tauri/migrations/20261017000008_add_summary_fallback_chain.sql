@@ -0,0 +1,3 @@
+-- Ordered providers to fall back to when summary generation fails
+-- This column stores: [{provider, model}, ...]
+ALTER TABLE settings ADD COLUMN summaryFallbackChain TEXT;
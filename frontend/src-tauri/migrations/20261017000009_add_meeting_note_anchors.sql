@@ -0,0 +1,2 @@
+-- Timestamped anchors tying note lines to recording-relative time
+ALTER TABLE meeting_notes ADD COLUMN anchors TEXT;  -- JSON array of NoteAnchor
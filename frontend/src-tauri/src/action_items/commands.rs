@@ -0,0 +1,123 @@
+use super::tracker::sync_from_summary;
+use crate::database::models::ActionItem;
+use crate::database::repositories::action_item::{ActionItemFilters, ActionItemsRepository};
+use crate::database::repositories::summary::SummaryProcessesRepository;
+use crate::export::commands::summary_markdown_from_result;
+use crate::state::AppState;
+use crate::summary::structured::StructuredSummary;
+use tauri::command;
+
+/// Optional text field from the UI: trimmed, with blanks meaning "none"
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+async fn load_action_item(
+    state: &tauri::State<'_, AppState>,
+    id: &str,
+) -> Result<ActionItem, String> {
+    ActionItemsRepository::get(state.db_manager.pool(), id)
+        .await
+        .map_err(|e| format!("Failed to load action item: {}", e))?
+        .ok_or_else(|| format!("Action item not found: {}", id))
+}
+
+/// Action items across meetings, open ones first
+#[command]
+pub async fn api_list_action_items(
+    state: tauri::State<'_, AppState>,
+    filters: Option<ActionItemFilters>,
+) -> Result<Vec<ActionItem>, String> {
+    ActionItemsRepository::list(state.db_manager.pool(), &filters.unwrap_or_default())
+        .await
+        .map_err(|e| format!("Failed to load action items: {}", e))
+}
+
+#[command]
+pub async fn api_set_action_item_completed(
+    state: tauri::State<'_, AppState>,
+    id: String,
+    completed: bool,
+) -> Result<ActionItem, String> {
+    match ActionItemsRepository::set_completed(state.db_manager.pool(), &id, completed).await {
+        Ok(true) => load_action_item(&state, &id).await,
+        Ok(false) => Err(format!("Action item not found: {}", id)),
+        Err(e) => Err(format!("Failed to update action item: {}", e)),
+    }
+}
+
+/// Edit an item; edited items survive regenerating the meeting's summary
+#[command]
+pub async fn api_update_action_item(
+    state: tauri::State<'_, AppState>,
+    id: String,
+    task: String,
+    owner: Option<String>,
+    due_date: Option<String>,
+) -> Result<ActionItem, String> {
+    let task = task.trim();
+    if task.is_empty() {
+        return Err("Task is empty".to_string());
+    }
+    let owner = non_empty(owner);
+    let due_date = non_empty(due_date);
+
+    match ActionItemsRepository::update(
+        state.db_manager.pool(),
+        &id,
+        task,
+        owner.as_deref(),
+        due_date.as_deref(),
+    )
+    .await
+    {
+        Ok(true) => load_action_item(&state, &id).await,
+        Ok(false) => Err(format!("Action item not found: {}", id)),
+        Err(e) => Err(format!("Failed to update action item: {}", e)),
+    }
+}
+
+#[command]
+pub async fn api_delete_action_item(
+    state: tauri::State<'_, AppState>,
+    id: String,
+) -> Result<(), String> {
+    match ActionItemsRepository::delete(state.db_manager.pool(), &id).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(format!("Action item not found: {}", id)),
+        Err(e) => Err(format!("Failed to delete action item: {}", e)),
+    }
+}
+
+/// Re-run extraction on a meeting's saved summary (e.g. one made before
+/// action items were tracked) and return the meeting's items
+#[command]
+pub async fn api_extract_action_items(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<ActionItem>, String> {
+    let pool = state.db_manager.pool();
+    let result = SummaryProcessesRepository::get_summary_data(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load summary: {}", e))?
+        .and_then(|process| process.result)
+        .ok_or_else(|| format!("Meeting {} has no summary", meeting_id))?;
+
+    let markdown = summary_markdown_from_result(&result).unwrap_or_default();
+    let structured: Option<StructuredSummary> = serde_json::from_str::<serde_json::Value>(&result)
+        .ok()
+        .and_then(|value| value.get("structured").cloned())
+        .and_then(|value| serde_json::from_value(value).ok());
+
+    sync_from_summary(pool, &meeting_id, structured.as_ref(), &markdown).await?;
+
+    let filters = ActionItemFilters {
+        meeting_id: Some(meeting_id),
+        ..Default::default()
+    };
+    ActionItemsRepository::list(pool, &filters)
+        .await
+        .map_err(|e| format!("Failed to load action items: {}", e))
+}
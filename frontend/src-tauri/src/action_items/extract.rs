@@ -0,0 +1,525 @@
+// action_items/extract.rs
+//
+// Parses action items out of a finished summary: from the typed sections of a
+// structured run, or from the "Action Items" table/list of markdown output.
+
+use crate::chat::context::parse_timestamps;
+use crate::summary::structured::{SectionContent, StructuredSummary, EMPTY_SECTION_TEXT};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Section titles (lowercased) that hold action items
+const ACTION_SECTION_TITLES: [&str; 5] = ["action item", "next step", "follow-up", "to-do", "todo"];
+
+/// Titles too generic to link recurring meetings
+const GENERIC_TITLES: [&str; 6] = [
+    "meeting",
+    "call",
+    "new call",
+    "sync",
+    "untitled",
+    "untitled meeting",
+];
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+// "Ana: send the deck", "Ana Silva - send the deck"
+static OWNER_PREFIX_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^([A-Z][\w.'-]*(?: [A-Z][\w.'-]*){0,2})\s*(?::|\s[-–—])\s*(.+)$").unwrap()
+});
+
+// "Ana to send the deck", "Ana will send the deck"
+static OWNER_VERB_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^([A-Z][\w.'-]*(?: [A-Z][\w.'-]*)?) (?:to|will) (.+)$").unwrap());
+
+// "... (due Friday)", "... due: 2026-10-24"
+static DUE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)[\s,;]*\(?\bdue(?: date)?:?\s+([^()]+?)\)?\s*\.?$").unwrap());
+
+// "... by Friday", "... by end of week" (only date-like phrases, not "by the team")
+static BY_DATE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)[\s,;]*\(?\b(?:by|before)\s+((?:next |this |the |end of (?:the )?)?(?:mon|tue|wed|thu|fri|sat|sun|jan|feb|mar|apr|may|jun|jul|aug|sep|oct|nov|dec|tomorrow|today|tonight|week|month|quarter|eod|eow|q[1-4]\b|\d)[^()]*?)\)?\s*\.?$",
+    )
+    .unwrap()
+});
+
+/// An action item parsed from a summary
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedActionItem {
+    pub task: String,
+    pub owner: Option<String>,
+    /// Due date as written ("Friday", "2026-10-24")
+    pub due_date: Option<String>,
+    /// Seconds from recording start, when the summary cites a time
+    pub source_time: Option<f64>,
+    /// Quoted transcript text, when the template asks for one
+    pub source_quote: Option<String>,
+}
+
+/// Action items of a summary, preferring the typed sections of structured output
+pub fn extract_action_items(
+    structured: Option<&StructuredSummary>,
+    markdown: &str,
+) -> Vec<ExtractedActionItem> {
+    if let Some(summary) = structured {
+        let sections: Vec<_> = summary
+            .sections
+            .iter()
+            .filter(|s| is_action_section(&s.title))
+            .collect();
+        if !sections.is_empty() {
+            return sections
+                .into_iter()
+                .flat_map(|section| match &section.content {
+                    SectionContent::Table { columns, rows } => items_from_table(columns, rows),
+                    SectionContent::List { items } => {
+                        items.iter().filter_map(|i| item_from_text(i)).collect()
+                    }
+                    SectionContent::Text { .. } => Vec::new(),
+                })
+                .collect();
+        }
+    }
+    items_from_markdown(markdown)
+}
+
+fn is_action_section(title: &str) -> bool {
+    let title = title.to_lowercase();
+    ACTION_SECTION_TITLES.iter().any(|t| title.contains(t))
+}
+
+/// Columns of an action item table, matched by header name
+#[derive(Debug, Default)]
+struct ColumnMap {
+    task: Option<usize>,
+    owner: Option<usize>,
+    due: Option<usize>,
+    time: Option<usize>,
+    quote: Option<usize>,
+}
+
+impl ColumnMap {
+    fn new(columns: &[String]) -> Self {
+        let mut map = ColumnMap::default();
+        for (i, column) in columns.iter().enumerate() {
+            let name = column.trim_matches('*').trim().to_lowercase();
+            let slot = if name.contains("time") {
+                &mut map.time
+            } else if ["owner", "assignee", "responsible", "who"]
+                .iter()
+                .any(|k| name.contains(k))
+            {
+                &mut map.owner
+            } else if ["due", "deadline", "eta", "date"]
+                .iter()
+                .any(|k| name.contains(k))
+            {
+                &mut map.due
+            } else if ["reference", "segment", "quote"]
+                .iter()
+                .any(|k| name.contains(k))
+            {
+                &mut map.quote
+            } else if ["task", "action", "deliverable", "item"]
+                .iter()
+                .any(|k| name.contains(k))
+            {
+                &mut map.task
+            } else {
+                continue;
+            };
+            slot.get_or_insert(i);
+        }
+        // Tables without a recognisable task column describe the task first
+        if map.task.is_none() {
+            let used = [map.owner, map.due, map.time, map.quote];
+            map.task = (0..columns.len()).find(|i| !used.contains(&Some(*i)));
+        }
+        map
+    }
+}
+
+fn items_from_table(columns: &[String], rows: &[Vec<String>]) -> Vec<ExtractedActionItem> {
+    let map = ColumnMap::new(columns);
+    let cell = |row: &Vec<String>, index: Option<usize>| {
+        index
+            .and_then(|i| row.get(i))
+            .map(|c| c.replace("**", "").trim().to_string())
+            .filter(|c| !is_placeholder(c))
+    };
+
+    rows.iter()
+        .filter_map(|row| {
+            let task = cell(row, map.task)?;
+            let cited = cell(row, map.time)
+                .and_then(|time| first_timestamp(&format!("[{}]", time.trim_matches(['[', ']']))))
+                .or_else(|| first_timestamp(&task));
+            Some(ExtractedActionItem {
+                task: strip_timestamps(&task),
+                owner: cell(row, map.owner),
+                due_date: cell(row, map.due),
+                source_time: cited,
+                source_quote: cell(row, map.quote),
+            })
+        })
+        .collect()
+}
+
+/// Parse a free-text item such as "Ana to send the deck by Friday [12:34]"
+fn item_from_text(text: &str) -> Option<ExtractedActionItem> {
+    let source_time = first_timestamp(text);
+    let mut task = strip_timestamps(&text.replace("**", ""));
+    if is_placeholder(&task) {
+        return None;
+    }
+
+    let mut owner = None;
+    for regex in [&*OWNER_PREFIX_REGEX, &*OWNER_VERB_REGEX] {
+        if let Some(captures) = regex.captures(&task) {
+            owner = Some(captures[1].trim().to_string());
+            task = captures[2].trim().to_string();
+            break;
+        }
+    }
+
+    let mut due_date = None;
+    for regex in [&*DUE_REGEX, &*BY_DATE_REGEX] {
+        if let Some(captures) = regex.captures(&task) {
+            due_date = Some(captures[1].trim().to_string());
+            let start = captures.get(0).map_or(task.len(), |m| m.start());
+            task.truncate(start);
+            break;
+        }
+    }
+
+    let task = task
+        .trim()
+        .trim_end_matches(['.', ',', ';'])
+        .trim()
+        .to_string();
+    if task.is_empty() {
+        return None;
+    }
+    Some(ExtractedActionItem {
+        task,
+        owner,
+        due_date,
+        source_time,
+        source_quote: None,
+    })
+}
+
+/// Items from the action sections of markdown output (`## Title` or `**Title**` headings)
+fn items_from_markdown(markdown: &str) -> Vec<ExtractedActionItem> {
+    let mut items = Vec::new();
+    let mut in_section = false;
+    let mut table_columns: Option<Vec<String>> = None;
+    let mut table_rows: Vec<Vec<String>> = Vec::new();
+
+    for line in markdown.lines().map(str::trim) {
+        if let Some(title) = heading_title(line) {
+            if let Some(columns) = table_columns.take() {
+                items.extend(items_from_table(&columns, &table_rows));
+                table_rows.clear();
+            }
+            in_section = is_action_section(title);
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+
+        if line.starts_with('|') {
+            let cells = split_table_row(line);
+            if table_columns.is_none() {
+                table_columns = Some(cells);
+            } else if !cells
+                .iter()
+                .all(|c| !c.is_empty() && c.chars().all(|ch| matches!(ch, '-' | ':' | ' ')))
+            {
+                table_rows.push(cells);
+            }
+        } else if let Some(text) = list_item_text(line) {
+            items.extend(item_from_text(text));
+        }
+    }
+    if let Some(columns) = table_columns {
+        items.extend(items_from_table(&columns, &table_rows));
+    }
+    items
+}
+
+fn heading_title(line: &str) -> Option<&str> {
+    if line.starts_with('#') {
+        return Some(line.trim_start_matches('#').trim());
+    }
+    let inner = line.strip_prefix("**")?.strip_suffix("**")?;
+    (!inner.contains("**")).then(|| inner.trim().trim_end_matches(':'))
+}
+
+fn list_item_text(line: &str) -> Option<&str> {
+    let text = if let Some(rest) = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))
+    {
+        rest
+    } else {
+        let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+        line.get(digits..)
+            .filter(|_| digits > 0)
+            .and_then(|rest| rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") ")))?
+    };
+    let text = text
+        .strip_prefix("[ ] ")
+        .or_else(|| text.strip_prefix("[x] "))
+        .unwrap_or(text);
+    Some(text.trim())
+}
+
+/// Cells of a markdown table row, honouring `\|` escapes
+fn split_table_row(line: &str) -> Vec<String> {
+    let inner = line.trim().trim_start_matches('|');
+    let inner = inner.strip_suffix('|').unwrap_or(inner);
+
+    let mut cells = Vec::new();
+    let mut current = String::new();
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                current.push('|');
+                chars.next();
+            }
+            '|' => cells.push(std::mem::take(&mut current).trim().to_string()),
+            _ => current.push(c),
+        }
+    }
+    cells.push(current.trim().to_string());
+    cells
+        .into_iter()
+        .map(|c| c.trim_matches('*').trim().to_string())
+        .collect()
+}
+
+fn is_placeholder(text: &str) -> bool {
+    let text = text.trim().trim_end_matches('.').to_lowercase();
+    text.is_empty()
+        || matches!(
+            text.as_str(),
+            "-" | "—" | "n/a" | "na" | "none" | "tbd" | "unassigned"
+        )
+        || text == EMPTY_SECTION_TEXT.trim_end_matches('.').to_lowercase()
+}
+
+fn first_timestamp(text: &str) -> Option<f64> {
+    parse_timestamps(text).first().map(|(_, seconds)| *seconds)
+}
+
+/// Remove `[mm:ss]` markers from item text
+fn strip_timestamps(text: &str) -> String {
+    let mut out = text.to_string();
+    for (label, _) in parse_timestamps(text) {
+        out = out.replace(&format!("[{}]", label), "");
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Index of the transcript segment that best matches an item's wording.
+///
+/// `segments` are `(start seconds, text)`; needs at least two shared words of 4+ letters.
+pub fn locate_source(item: &ExtractedActionItem, segments: &[(f64, &str)]) -> Option<usize> {
+    let text = format!(
+        "{} {}",
+        item.task,
+        item.source_quote.as_deref().unwrap_or("")
+    );
+    let mut terms: Vec<String> = words(&text).filter(|w| w.chars().count() >= 4).collect();
+    terms.sort();
+    terms.dedup();
+
+    segments
+        .iter()
+        .enumerate()
+        .map(|(i, (_, segment))| {
+            let segment_words: Vec<String> = words(segment).collect();
+            let shared = terms.iter().filter(|t| segment_words.contains(t)).count();
+            (i, shared)
+        })
+        .filter(|(_, shared)| *shared >= 2)
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+        .map(|(i, _)| i)
+}
+
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Recurring-meeting prefix of a title ("Weekly Sync - Oct 10" -> "weekly sync").
+///
+/// Stops at separators, dates and numbers; None when nothing distinctive is left.
+pub fn title_prefix(title: &str) -> Option<String> {
+    let head = title
+        .split([':', '|', '(', '[', '—', '–'])
+        .next()
+        .unwrap_or("")
+        .split(" - ")
+        .next()
+        .unwrap_or("");
+    let prefix = head
+        .split_whitespace()
+        .take_while(|word| !word.chars().any(|c| c.is_ascii_digit()) && !is_month(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    let prefix = prefix.trim_matches(|c: char| !c.is_alphanumeric());
+
+    if prefix.chars().count() < 4 || GENERIC_TITLES.contains(&prefix) {
+        None
+    } else {
+        Some(prefix.to_string())
+    }
+}
+
+/// "Oct", "oct." or "October"
+fn is_month(word: &str) -> bool {
+    let word = word.trim_end_matches(['.', ',']).to_lowercase();
+    word.len() >= 3 && MONTHS.iter().any(|m| m.starts_with(&word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::structured::StructuredSection;
+
+    #[test]
+    fn test_extract_from_structured_table() {
+        let summary = StructuredSummary {
+            title: "Weekly Sync".to_string(),
+            sections: vec![StructuredSection {
+                key: "action_items".to_string(),
+                title: "Action Items".to_string(),
+                content: SectionContent::Table {
+                    columns: vec![
+                        "Owner".to_string(),
+                        "Task".to_string(),
+                        "Due".to_string(),
+                        "Reference Transcript Segment".to_string(),
+                        "Segment Time stamp".to_string(),
+                    ],
+                    rows: vec![
+                        vec![
+                            "Ana".to_string(),
+                            "Send the pricing deck".to_string(),
+                            "Friday".to_string(),
+                            "I'll send the deck".to_string(),
+                            "12:34".to_string(),
+                        ],
+                        vec![
+                            "N/A".to_string(),
+                            "".to_string(),
+                            "".to_string(),
+                            "".to_string(),
+                            "".to_string(),
+                        ],
+                    ],
+                },
+            }],
+            unresolved_sections: Vec::new(),
+        };
+
+        let items = extract_action_items(Some(&summary), "");
+        assert_eq!(
+            items,
+            vec![ExtractedActionItem {
+                task: "Send the pricing deck".to_string(),
+                owner: Some("Ana".to_string()),
+                due_date: Some("Friday".to_string()),
+                source_time: Some(754.0),
+                source_quote: Some("I'll send the deck".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_extract_from_markdown_list_and_table() {
+        let markdown = "**Summary**\n\n- Ana to review this later\n\n\
+                        ## Action Items\n\n\
+                        - Ana to send the deck by Friday [01:05]\n\
+                        - **Ben Ortiz**: update the roadmap (due 2026-10-24)\n\
+                        - Book the venue\n\n\
+                        **Next Steps**\n\n\
+                        | **Owner** | **Action** | **Due Date** |\n| --- | --- | --- |\n\
+                        | Cleo | Draft a\\|b plan | EOW |\n\n\
+                        **Notes**\n\n- Not an action";
+
+        let items = extract_action_items(None, markdown);
+        let summary: Vec<_> = items
+            .iter()
+            .map(|i| {
+                (
+                    i.owner.as_deref(),
+                    i.task.as_str(),
+                    i.due_date.as_deref(),
+                    i.source_time,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Some("Ana"), "send the deck", Some("Friday"), Some(65.0)),
+                (
+                    Some("Ben Ortiz"),
+                    "update the roadmap",
+                    Some("2026-10-24"),
+                    None
+                ),
+                (None, "Book the venue", None, None),
+                (Some("Cleo"), "Draft a|b plan", Some("EOW"), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_locate_source_and_title_prefix() {
+        let item = item_from_text("Ana to send the pricing deck").unwrap();
+        let segments = [
+            (1.0, "Hello everyone, thanks for joining"),
+            (42.0, "I can send over the pricing deck tomorrow"),
+            (90.0, "The deck looked good"),
+        ];
+        assert_eq!(locate_source(&item, &segments), Some(1));
+
+        assert_eq!(
+            title_prefix("Weekly Sync - Oct 10"),
+            Some("weekly sync".to_string())
+        );
+        assert_eq!(
+            title_prefix("Design Review: onboarding"),
+            Some("design review".to_string())
+        );
+        assert_eq!(title_prefix("Meeting 21_10_26_10_00_00"), None);
+        assert_eq!(title_prefix("+ New Call"), None);
+        assert_eq!(
+            title_prefix("Marketing Standup"),
+            Some("marketing standup".to_string())
+        );
+    }
+}
@@ -0,0 +1,19 @@
+//! Action items tracked across meetings.
+//!
+//! After a summary is generated, its action items (owner, task, due date and
+//! the transcript time they were discussed) are parsed into the `action_items`
+//! table, where they can be listed, filtered, edited and completed. Open items
+//! from earlier meetings with the same named participants or title prefix are
+//! passed to the next summary as context.
+//!
+//! # Module Structure
+//!
+//! - `extract`: Parsing action items from structured or markdown summaries
+//! - `tracker`: Storing extracted items and building the carry-over context
+//! - `commands`: Tauri commands for listing and editing items
+
+pub mod commands;
+pub mod extract;
+pub mod tracker;
+
+pub use tracker::{carried_over_context, sync_from_summary, ACTION_ITEMS_UPDATED_EVENT};
@@ -0,0 +1,135 @@
+use super::extract::{extract_action_items, locate_source, title_prefix};
+use crate::chat::commands::segment_at;
+use crate::database::repositories::action_item::{ActionItemsRepository, NewActionItem};
+use crate::database::repositories::{meeting::MeetingsRepository, speaker::SpeakersRepository};
+use crate::diarization::default_speaker_label;
+use crate::summary::structured::StructuredSummary;
+use sqlx::SqlitePool;
+use tracing::{info, warn};
+
+/// Emitted with `{ meetingId }` after a summary's action items are stored
+pub const ACTION_ITEMS_UPDATED_EVENT: &str = "action-items-updated";
+
+/// Most open items carried into a new summary's context
+const MAX_CARRIED_ITEMS: i64 = 20;
+
+/// Extract a finished summary's action items into the `action_items` table.
+///
+/// Each item is linked to the transcript segment it cites, or else to the
+/// segment that best matches its wording. Returns the number of new items.
+pub async fn sync_from_summary(
+    pool: &SqlitePool,
+    meeting_id: &str,
+    structured: Option<&StructuredSummary>,
+    markdown: &str,
+) -> Result<usize, String> {
+    let extracted = extract_action_items(structured, markdown);
+    let transcripts = MeetingsRepository::get_meeting(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting: {}", e))?
+        .map(|meeting| meeting.transcripts)
+        .unwrap_or_default();
+
+    let timed: Vec<_> = transcripts
+        .iter()
+        .filter_map(|t| t.audio_start_time.map(|start| (t, start)))
+        .collect();
+    let segments: Vec<(f64, &str)> = timed
+        .iter()
+        .map(|(t, start)| (*start, t.text.as_str()))
+        .collect();
+
+    let items: Vec<NewActionItem> = extracted
+        .into_iter()
+        .map(|item| {
+            let (source_time, source_segment_id) = match item.source_time {
+                Some(seconds) => (
+                    Some(seconds),
+                    segment_at(&transcripts, seconds).map(|t| t.id.clone()),
+                ),
+                None => match locate_source(&item, &segments) {
+                    Some(index) => (Some(timed[index].1), Some(timed[index].0.id.clone())),
+                    None => (None, None),
+                },
+            };
+            NewActionItem {
+                task: item.task,
+                owner: item.owner,
+                due_date: item.due_date,
+                source_time,
+                source_segment_id,
+            }
+        })
+        .collect();
+
+    let inserted = ActionItemsRepository::replace_extracted(pool, meeting_id, &items)
+        .await
+        .map_err(|e| format!("Failed to save action items: {}", e))?;
+    info!(
+        "Extracted {} action items ({} new) for meeting_id: {}",
+        items.len(),
+        inserted,
+        meeting_id
+    );
+    Ok(inserted)
+}
+
+/// Summary context listing open items from earlier meetings with the same
+/// named participants or title prefix, or None when there are none
+pub async fn carried_over_context(pool: &SqlitePool, meeting_id: &str) -> Option<String> {
+    let meeting = match MeetingsRepository::get_meeting_metadata(pool, meeting_id).await {
+        Ok(Some(meeting)) => meeting,
+        Ok(None) => return None,
+        Err(e) => {
+            warn!(
+                "Failed to load meeting {} for action items: {}",
+                meeting_id, e
+            );
+            return None;
+        }
+    };
+
+    // Only renamed speakers identify people across meetings
+    let participants: Vec<String> = SpeakersRepository::get_speakers(pool, meeting_id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|s| s.label != default_speaker_label(&s.speaker_id))
+        .map(|s| s.label.trim().to_lowercase())
+        .collect();
+    let prefix = title_prefix(&meeting.title);
+
+    let items = match ActionItemsRepository::open_items_from_related(
+        pool,
+        meeting_id,
+        &participants,
+        prefix.as_deref(),
+        MAX_CARRIED_ITEMS,
+    )
+    .await
+    {
+        Ok(items) if !items.is_empty() => items,
+        Ok(_) => return None,
+        Err(e) => {
+            warn!("Failed to load open action items for {}: {}", meeting_id, e);
+            return None;
+        }
+    };
+
+    let mut context = String::from(
+        "Open action items from earlier related meetings. Note any that were discussed, \
+         completed or changed in this meeting; don't repeat the others as new action items.\n",
+    );
+    for item in items {
+        let owner = item.owner.map(|o| format!("{}: ", o)).unwrap_or_default();
+        let due = item
+            .due_date
+            .map(|d| format!(" (due {})", d))
+            .unwrap_or_default();
+        context.push_str(&format!(
+            "- [{}] {}{}{}\n",
+            item.meeting_title, owner, item.task, due
+        ));
+    }
+    Some(context.trim_end().to_string())
+}
@@ -1,14 +1,18 @@
 use log::{debug as log_debug, error as log_error, info as log_info, warn as log_warn};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tauri::{AppHandle, Runtime};
+use tauri::{AppHandle, Manager, Runtime};
 use tauri_plugin_store::StoreExt;
 
 use crate::{
+    audio::transcription::WordTimestamp,
     database::{
-        models::MeetingModel,
+        models::{MeetingModel, Speaker},
         repositories::{
-            meeting::MeetingsRepository, setting::SettingsRepository,
+            meeting::MeetingsRepository,
+            search::{SearchFilters, SearchRepository},
+            setting::SettingsRepository,
+            speaker::SpeakersRepository,
             transcript::TranscriptsRepository,
         },
     },
@@ -45,6 +49,17 @@ pub struct TranscriptSearchResult {
     #[serde(rename = "matchContext")]
     pub match_context: String,
     pub timestamp: String,
+    // Where the match was found: "transcript", "summary" or "notes"
+    pub source: String,
+    // Snippet with matched terms wrapped in <mark></mark>
+    pub highlight: String,
+    // Matching transcript segment (transcript matches only)
+    #[serde(rename = "segmentId")]
+    pub segment_id: Option<String>,
+    #[serde(rename = "audioStartTime")]
+    pub audio_start_time: Option<f64>,
+    // BM25 score; lower is a better match
+    pub rank: f64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -102,6 +117,9 @@ pub struct TranscriptConfig {
     pub model: String,
     #[serde(rename = "apiKey")]
     pub api_key: Option<String>,
+    /// API root for the openaiCompatible provider
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -138,6 +156,16 @@ pub struct MeetingTranscript {
     pub audio_end_time: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<f64>,
+    // Word-level timing, seconds from recording start
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub words: Option<Vec<WordTimestamp>>,
+    // Capture stream ("mic"/"system") and diarized speaker
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker_label: Option<String>,
 }
 
 /// Meeting metadata without transcripts (for pagination)
@@ -189,6 +217,14 @@ pub struct TranscriptSegment {
     pub audio_end_time: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<f64>,
+    // Word-level timing, seconds from recording start
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub words: Option<Vec<WordTimestamp>>,
+    // Capture stream ("mic"/"system") and diarized speaker ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -358,17 +394,19 @@ pub async fn api_search_transcripts<R: Runtime>(
     _app: AppHandle<R>,
     state: tauri::State<'_, AppState>,
     query: String,
+    filters: Option<SearchFilters>,
     auth_token: Option<String>,
 ) -> Result<Vec<TranscriptSearchResult>, String> {
     log_info!(
-        "api_search_transcripts called with query: '{}', auth_token: {}",
+        "api_search_transcripts called with query: '{}', filters: {:?}, auth_token: {}",
         query,
+        filters,
         auth_token.is_some()
     );
 
     let pool = state.db_manager.pool();
 
-    match TranscriptsRepository::search_transcripts(pool, &query).await {
+    match SearchRepository::search(pool, &query, &filters.unwrap_or_default()).await {
         Ok(results) => {
             log_info!(
                 "Search completed successfully with {} results.",
@@ -620,6 +658,7 @@ pub async fn api_get_transcript_config<R: Runtime>(
                         provider: config.provider,
                         model: config.model,
                         api_key,
+                        endpoint: config.transcript_endpoint,
                     }))
                 }
                 Err(e) => {
@@ -638,6 +677,7 @@ pub async fn api_get_transcript_config<R: Runtime>(
                 provider: "parakeet".to_string(),
                 model: "parakeet-tdt-0.6b-v3-int8".to_string(),
                 api_key: None,
+                endpoint: None,
             }))
         }
         Err(e) => {
@@ -654,6 +694,7 @@ pub async fn api_save_transcript_config<R: Runtime>(
     provider: String,
     model: String,
     api_key: Option<String>,
+    endpoint: Option<String>,
     _auth_token: Option<String>,
 ) -> Result<serde_json::Value, String> {
     log_info!(
@@ -678,6 +719,16 @@ pub async fn api_save_transcript_config<R: Runtime>(
         }
     }
 
+    // Only sent by the self-hosted (openaiCompatible) settings; an empty string clears it
+    if let Some(endpoint) = endpoint {
+        let endpoint = endpoint.trim();
+        let endpoint = (!endpoint.is_empty()).then_some(endpoint);
+        if let Err(e) = SettingsRepository::save_transcript_endpoint(pool, endpoint).await {
+            log_error!("Failed to save transcript endpoint: {}", e);
+            return Err(e.to_string());
+        }
+    }
+
     log_info!("Successfully saved transcript configuration.");
     Ok(
         serde_json::json!({ "status": "success", "message": "Transcript configuration saved successfully" }),
@@ -759,6 +810,20 @@ pub async fn api_delete_meeting<R: Runtime>(
     match MeetingsRepository::delete_meeting(pool, &meeting_id).await {
         Ok(true) => {
             log_info!("Successfully deleted meeting {}", meeting_id);
+
+            // The built-in model's prompt cache may still hold this meeting's transcript
+            match _app.path().app_data_dir() {
+                Ok(app_data_dir) => {
+                    if let Err(e) =
+                        crate::summary::summary_engine::client::forget_prompt_cache(&app_data_dir)
+                            .await
+                    {
+                        log_warn!("Failed to clear the summary prompt cache: {}", e);
+                    }
+                }
+                Err(e) => log_warn!("Failed to get app data dir: {}", e),
+            }
+
             Ok(serde_json::json!({
                 "status": "success",
                 "message": "Meeting deleted successfully"
@@ -862,6 +927,10 @@ pub async fn api_get_meeting_transcripts<R: Runtime>(
 
     match MeetingsRepository::get_meeting_transcripts_paginated(pool, &meeting_id, limit, offset).await {
         Ok((transcripts, total_count)) => {
+            let speaker_labels = SpeakersRepository::get_labels(pool, &meeting_id)
+                .await
+                .unwrap_or_default();
+
             log_info!(
                 "Successfully retrieved {} transcripts for meeting {} (total: {})",
                 transcripts.len(),
@@ -873,12 +942,19 @@ pub async fn api_get_meeting_transcripts<R: Runtime>(
             let meeting_transcripts = transcripts
                 .into_iter()
                 .map(|t| MeetingTranscript {
+                    words: t.word_timestamps(),
+                    speaker_label: t
+                        .speaker_id
+                        .as_deref()
+                        .map(|id| SpeakersRepository::label_for(&speaker_labels, id)),
                     id: t.id,
                     text: t.transcript,
                     timestamp: t.timestamp,
                     audio_start_time: t.audio_start_time,
                     audio_end_time: t.audio_end_time,
                     duration: t.duration,
+                    speaker: t.speaker,
+                    speaker_id: t.speaker_id,
                 })
                 .collect::<Vec<_>>();
 
@@ -928,9 +1004,102 @@ pub async fn api_save_meeting_title<R: Runtime>(
 }
 
 #[tauri::command]
-pub async fn api_save_transcript<R: Runtime>(
+pub async fn api_get_meeting_speakers<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<Speaker>, String> {
+    log_info!("api_get_meeting_speakers called for meeting_id: {}", meeting_id);
+    let pool = state.db_manager.pool();
+    SpeakersRepository::get_speakers(pool, &meeting_id)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to get speakers for meeting {}: {}", meeting_id, e);
+            format!("Failed to get speakers: {}", e)
+        })
+}
+
+#[tauri::command]
+pub async fn api_rename_speaker<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    speaker_id: String,
+    label: String,
+) -> Result<serde_json::Value, String> {
+    log_info!(
+        "api_rename_speaker called for meeting_id: {}, speaker_id: {}",
+        meeting_id,
+        speaker_id
+    );
+    let label = label.trim();
+    if label.is_empty() {
+        return Err("Speaker name cannot be empty".to_string());
+    }
+
+    let pool = state.db_manager.pool();
+    match SpeakersRepository::rename_speaker(pool, &meeting_id, &speaker_id, label).await {
+        Ok(true) => {
+            log_info!("Successfully renamed speaker {}", speaker_id);
+            Ok(serde_json::json!({"message": "Speaker renamed successfully"}))
+        }
+        Ok(false) => {
+            log_error!(
+                "No speaker {} found for meeting {}",
+                speaker_id,
+                meeting_id
+            );
+            Err(format!("No speaker {} found for meeting {}", speaker_id, meeting_id))
+        }
+        Err(e) => {
+            log_error!("Failed to rename speaker {}", e);
+            Err(format!("Failed to rename speaker: {}", e))
+        }
+    }
+}
+
+/// Reassigns every transcript of `from_speaker_id` to `into_speaker_id`,
+/// e.g. when diarization split one person into two clusters.
+#[tauri::command]
+pub async fn api_merge_speakers<R: Runtime>(
     _app: AppHandle<R>,
     state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    from_speaker_id: String,
+    into_speaker_id: String,
+) -> Result<serde_json::Value, String> {
+    log_info!(
+        "api_merge_speakers called for meeting_id: {}, {} -> {}",
+        meeting_id,
+        from_speaker_id,
+        into_speaker_id
+    );
+    if from_speaker_id == into_speaker_id {
+        return Err("Cannot merge a speaker into itself".to_string());
+    }
+
+    let pool = state.db_manager.pool();
+    match SpeakersRepository::merge_speakers(pool, &meeting_id, &from_speaker_id, &into_speaker_id)
+        .await
+    {
+        Ok(updated) => {
+            log_info!("Merged speakers, {} transcripts reassigned", updated);
+            Ok(serde_json::json!({
+                "message": "Speakers merged successfully",
+                "transcripts_updated": updated
+            }))
+        }
+        Err(e) => {
+            log_error!("Failed to merge speakers {}", e);
+            Err(format!("Failed to merge speakers: {}", e))
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn api_save_transcript<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
     meeting_title: String,
     transcripts: Vec<serde_json::Value>,
     folder_path: Option<String>,
@@ -978,7 +1147,7 @@ pub async fn api_save_transcript<R: Runtime>(
         pool,
         &meeting_title,
         &transcripts_to_save,
-        folder_path,
+        folder_path.clone(),
     )
     .await
     {
@@ -987,6 +1156,18 @@ pub async fn api_save_transcript<R: Runtime>(
                 "Successfully saved transcript and created meeting with id: {}",
                 meeting_id
             );
+
+            // Attach the notes taken during the recording to the new meeting
+            if let Err(e) = crate::notes::autosave::adopt_recording_draft(
+                pool,
+                &meeting_id,
+                folder_path.as_deref(),
+            )
+            .await
+            {
+                log_error!("Failed to save recording notes for {}: {}", meeting_id, e);
+            }
+            crate::semantic::commands::index_in_background(&app);
             Ok(serde_json::json!({
                 "status": "success",
                 "message": "Transcript saved successfully",
@@ -0,0 +1,537 @@
+// audio/import.rs
+//
+// Offline import of existing audio/video files (Zoom/Teams exports, voice memos, ...).
+// The file is decoded to 16kHz mono with FFmpeg, split with VAD, transcribed with the
+// currently selected engine and saved as a regular meeting with its own folder.
+
+use super::audio_processing::create_meeting_folder;
+use super::ffmpeg::find_ffmpeg_path;
+use super::recording_preferences::load_recording_preferences;
+use super::recording_state::{AudioChunk, DeviceType};
+use super::transcription::{self, TranscriptionError};
+use super::vad::{ContinuousVadProcessor, SpeechSegment};
+use crate::api::TranscriptSegment;
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::state::AppState;
+use anyhow::{anyhow, Result};
+use log::{error, info, warn};
+use serde::Serialize;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+const IMPORT_SAMPLE_RATE: u32 = 16000;
+
+/// Same redemption time the live pipeline uses for its VAD
+const VAD_REDEMPTION_TIME_MS: u32 = 400;
+
+/// VAD segments longer than this are split before transcription
+const MAX_SEGMENT_SECS: usize = 30;
+
+/// Decoded audio is read from FFmpeg one second at a time
+const DECODE_FRAME_BYTES: usize = IMPORT_SAMPLE_RATE as usize * 4;
+
+/// Decoding progress is reported every this many seconds of audio
+const DECODE_PROGRESS_SECS: f64 = 60.0;
+
+static IMPORT_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+static IMPORT_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// Payload of the `import-progress` event
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportProgress {
+    /// "decoding", "detecting_speech", "transcribing" or "saving"
+    pub stage: String,
+    /// Overall progress, 0-100
+    pub progress: f32,
+    pub message: String,
+}
+
+/// Result of a successful import (also sent as the `import-complete` event)
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportResult {
+    pub meeting_id: String,
+    pub title: String,
+    pub folder_path: String,
+    pub segment_count: usize,
+    pub duration_seconds: f64,
+}
+
+/// Clears the in-progress flag when the import finishes, fails or is cancelled
+struct ImportGuard;
+
+impl Drop for ImportGuard {
+    fn drop(&mut self) {
+        IMPORT_IN_PROGRESS.store(false, Ordering::SeqCst);
+    }
+}
+
+fn emit_progress<R: Runtime>(app: &AppHandle<R>, stage: &str, progress: f32, message: String) {
+    let _ = app.emit(
+        "import-progress",
+        ImportProgress {
+            stage: stage.to_string(),
+            progress,
+            message,
+        },
+    );
+}
+
+fn ffmpeg_command() -> Result<Command> {
+    let ffmpeg_path = find_ffmpeg_path()
+        .ok_or_else(|| anyhow!("FFmpeg not found. Please install FFmpeg to import files."))?;
+
+    #[allow(unused_mut)]
+    let mut command = Command::new(ffmpeg_path);
+
+    // Hide console window on Windows
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    Ok(command)
+}
+
+/// Speech found in an imported file
+struct DecodedSpeech {
+    segments: Vec<SpeechSegment>,
+    duration_seconds: f64,
+}
+
+/// Decode the first audio stream of any FFmpeg-readable file to 16kHz mono and run
+/// VAD on it while it streams in, so only the speech is kept in memory.
+/// Stops early when the import is cancelled
+fn decode_speech(input: &Path, on_decoded: impl FnMut(f64)) -> Result<DecodedSpeech> {
+    let mut child = ffmpeg_command()?
+        .args(["-hide_banner", "-loglevel", "error", "-i"])
+        .arg(input)
+        .args([
+            "-vn",
+            "-ac",
+            "1",
+            "-ar",
+            &IMPORT_SAMPLE_RATE.to_string(),
+            "-f",
+            "f32le",
+            "pipe:1",
+        ])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Drain stderr on its own thread so FFmpeg never blocks on a full pipe
+    let stderr = child.stderr.take();
+    let stderr_reader = std::thread::spawn(move || {
+        let mut text = String::new();
+        if let Some(mut stderr) = stderr {
+            let _ = stderr.read_to_string(&mut text);
+        }
+        text
+    });
+
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow!("FFmpeg output is not available"))?;
+    let result = stream_speech(stdout, on_decoded);
+    if result.is_err() {
+        let _ = child.kill();
+    }
+    let status = child.wait()?;
+    let stderr = stderr_reader.join().unwrap_or_default();
+    let speech = result?;
+
+    if !status.success() {
+        let reason = stderr.lines().last().unwrap_or("unknown error");
+        return Err(anyhow!(
+            "FFmpeg could not decode {}: {}",
+            input.display(),
+            reason
+        ));
+    }
+    Ok(speech)
+}
+
+/// Read f32le samples frame by frame and collect the speech segments VAD finds
+fn stream_speech(stdout: impl Read, mut on_decoded: impl FnMut(f64)) -> Result<DecodedSpeech> {
+    let mut reader = BufReader::with_capacity(DECODE_FRAME_BYTES, stdout);
+    let mut vad = ContinuousVadProcessor::new(IMPORT_SAMPLE_RATE, VAD_REDEMPTION_TIME_MS)?;
+    let mut frame = vec![0u8; DECODE_FRAME_BYTES];
+    let mut samples = Vec::with_capacity(DECODE_FRAME_BYTES / 4);
+    let mut segments = Vec::new();
+    let mut total_samples = 0usize;
+
+    loop {
+        if IMPORT_CANCELLED.load(Ordering::SeqCst) {
+            return Err(anyhow!("Import cancelled"));
+        }
+
+        let len = read_frame(&mut reader, &mut frame)?;
+        samples.clear();
+        samples.extend(
+            frame[..len]
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+        );
+        if !samples.is_empty() {
+            total_samples += samples.len();
+            segments.extend(vad.process_audio(&samples)?);
+            on_decoded(total_samples as f64 / IMPORT_SAMPLE_RATE as f64);
+        }
+        if len < frame.len() {
+            break;
+        }
+    }
+    segments.extend(vad.flush()?);
+
+    Ok(DecodedSpeech {
+        segments,
+        duration_seconds: total_samples as f64 / IMPORT_SAMPLE_RATE as f64,
+    })
+}
+
+/// Fill `frame` from `reader`; fewer bytes are returned only at the end of the stream
+fn read_frame(reader: &mut impl Read, frame: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < frame.len() {
+        match reader.read(&mut frame[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// Store the imported file's audio as `audio.mp4`, like a live recording
+fn transcode_to_meeting_audio(input: &Path, output_path: &Path) -> Result<()> {
+    let output = ffmpeg_command()?
+        .arg("-y")
+        .arg("-i")
+        .arg(input)
+        .args([
+            "-vn",
+            "-c:a",
+            "aac",
+            "-b:a",
+            "192k",
+            "-movflags",
+            "+faststart",
+        ])
+        .arg(output_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!(
+            "FFmpeg failed to write {}: {}",
+            output_path.display(),
+            stderr.lines().last().unwrap_or("unknown error")
+        ));
+    }
+    Ok(())
+}
+
+/// Split `samples` (starting at `start_secs`) into pieces of at most MAX_SEGMENT_SECS.
+/// Takes the samples by value so a segment that needs no split is moved, not copied
+fn split_segment(mut samples: Vec<f32>, start_secs: f64) -> Vec<(f64, Vec<f32>)> {
+    let max_len = MAX_SEGMENT_SECS * IMPORT_SAMPLE_RATE as usize;
+    let piece_start = |i: usize| start_secs + (i * max_len) as f64 / IMPORT_SAMPLE_RATE as f64;
+
+    // Cut pieces off the end so every sample is copied at most once
+    let mut pieces = Vec::new();
+    while samples.len() > max_len {
+        let index = (samples.len() - 1) / max_len;
+        pieces.push((piece_start(index), samples.split_off(index * max_len)));
+    }
+    if !samples.is_empty() {
+        pieces.push((start_secs, samples));
+    }
+    pieces.reverse();
+    pieces
+}
+
+/// Import an audio or video file and transcribe it into a new meeting
+#[tauri::command]
+pub async fn import_audio_file<R: Runtime>(
+    app: AppHandle<R>,
+    file_path: String,
+    title: Option<String>,
+) -> Result<ImportResult, String> {
+    let input = PathBuf::from(&file_path);
+    if !input.is_file() {
+        return Err(format!("File not found: {}", file_path));
+    }
+
+    if super::recording_commands::is_recording().await {
+        return Err("Stop the current recording before importing a file".to_string());
+    }
+
+    if IMPORT_IN_PROGRESS.swap(true, Ordering::SeqCst) {
+        return Err("Another file is already being imported".to_string());
+    }
+    let _guard = ImportGuard;
+    IMPORT_CANCELLED.store(false, Ordering::SeqCst);
+
+    let title = title
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .or_else(|| {
+            input
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| "Imported meeting".to_string());
+
+    info!("Importing '{}' as meeting '{}'", input.display(), title);
+
+    let result = run_import(&app, &input, &title).await;
+    match &result {
+        Ok(import) => {
+            info!(
+                "✅ Import complete: meeting {} with {} segments",
+                import.meeting_id, import.segment_count
+            );
+            let _ = app.emit("import-complete", import);
+        }
+        Err(e) => {
+            error!("❌ Import of '{}' failed: {}", input.display(), e);
+            let _ = app.emit("import-error", serde_json::json!({ "error": e }));
+        }
+    }
+    result
+}
+
+/// Cancel the running import; it stops while decoding or before the next segment is transcribed
+#[tauri::command]
+pub async fn cancel_audio_import() -> Result<(), String> {
+    if IMPORT_IN_PROGRESS.load(Ordering::SeqCst) {
+        info!("Cancelling file import");
+        IMPORT_CANCELLED.store(true, Ordering::SeqCst);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn is_importing_audio() -> bool {
+    IMPORT_IN_PROGRESS.load(Ordering::SeqCst)
+}
+
+async fn run_import<R: Runtime>(
+    app: &AppHandle<R>,
+    input: &Path,
+    title: &str,
+) -> Result<ImportResult, String> {
+    // 1. Make sure the selected engine has a model loaded
+    transcription::validate_transcription_model_ready(app).await?;
+    let engine = transcription::get_or_init_transcription_engine(app).await?;
+    info!("Using {} for import", engine.provider_name());
+
+    // 2. Decode to 16kHz mono and find speech as the audio streams in
+    emit_progress(
+        app,
+        "decoding",
+        0.0,
+        format!("Decoding {}", input.display()),
+    );
+    let (decode_input, decode_app) = (input.to_path_buf(), app.clone());
+    let speech = tokio::task::spawn_blocking(move || {
+        let mut next_report = DECODE_PROGRESS_SECS;
+        decode_speech(&decode_input, |seconds| {
+            if seconds >= next_report {
+                next_report += DECODE_PROGRESS_SECS;
+                emit_progress(
+                    &decode_app,
+                    "detecting_speech",
+                    0.0,
+                    format!("Detecting speech ({:.0} min decoded)", seconds / 60.0),
+                );
+            }
+        })
+    })
+    .await
+    .map_err(|e| format!("Decoding task failed: {}", e))?
+    .map_err(|e| e.to_string())?;
+
+    if speech.duration_seconds == 0.0 {
+        return Err("The file does not contain any audio".to_string());
+    }
+    let duration_seconds = speech.duration_seconds;
+    info!("Decoded {:.1}s of audio", duration_seconds);
+
+    // 3. Create the meeting folder and keep a copy of the audio in it
+    let preferences = load_recording_preferences(app)
+        .await
+        .map_err(|e| format!("Failed to load recording preferences: {}", e))?;
+    let folder = create_meeting_folder(&preferences.save_folder, title, false)
+        .map_err(|e| format!("Failed to create meeting folder: {}", e))?;
+
+    let (audio_input, audio_output) = (input.to_path_buf(), folder.join("audio.mp4"));
+    match tokio::task::spawn_blocking(move || {
+        transcode_to_meeting_audio(&audio_input, &audio_output)
+    })
+    .await
+    {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => warn!(
+            "Imported audio could not be saved to the meeting folder: {}",
+            e
+        ),
+        Err(e) => warn!("Audio copy task failed: {}", e),
+    }
+
+    // Don't leave an orphaned folder (and the audio copy in it) behind on cancel or failure
+    let result =
+        transcribe_into_meeting(app, &engine, speech.segments, &folder, title, duration_seconds)
+            .await;
+    if result.is_err() {
+        if let Err(e) = std::fs::remove_dir_all(&folder) {
+            warn!("Failed to remove {}: {}", folder.display(), e);
+        }
+    }
+    result
+}
+
+/// Transcribe the speech into a new meeting saved with `folder` as its folder
+async fn transcribe_into_meeting<R: Runtime>(
+    app: &AppHandle<R>,
+    engine: &transcription::TranscriptionEngine,
+    speech_segments: Vec<SpeechSegment>,
+    folder: &Path,
+    title: &str,
+    duration_seconds: f64,
+) -> Result<ImportResult, String> {
+    emit_progress(app, "detecting_speech", 5.0, "Speech detected".to_string());
+    let pieces: Vec<(f64, Vec<f32>)> = speech_segments
+        .into_iter()
+        .flat_map(|segment| split_segment(segment.samples, segment.start_timestamp_ms / 1000.0))
+        .collect();
+    info!("VAD found {} speech chunks to transcribe", pieces.len());
+
+    // 4. Transcribe
+    let total = pieces.len();
+    let mut transcripts = Vec::new();
+    for (index, (start_secs, data)) in pieces.into_iter().enumerate() {
+        if IMPORT_CANCELLED.load(Ordering::SeqCst) {
+            return Err("Import cancelled".to_string());
+        }
+
+        let duration = data.len() as f64 / IMPORT_SAMPLE_RATE as f64;
+        let chunk = AudioChunk {
+            data,
+            sample_rate: IMPORT_SAMPLE_RATE,
+            timestamp: start_secs,
+            chunk_id: index as u64,
+            device_type: DeviceType::Microphone,
+            is_partial: false,
+        };
+
+        match transcription::worker::transcribe_chunk_with_provider(engine, chunk, app).await {
+            Ok((text, _confidence, _is_partial, words)) if !text.is_empty() => {
+                transcripts.push(TranscriptSegment {
+                    id: format!("import-{}", index),
+                    text,
+                    timestamp: transcription::worker::format_recording_time(start_secs),
+                    audio_start_time: Some(start_secs),
+                    audio_end_time: Some(start_secs + duration),
+                    duration: Some(duration),
+                    words: Some(words.into_iter().map(|w| w.offset_by(start_secs)).collect()),
+                    speaker: None,
+                    speaker_id: None,
+                });
+            }
+            Ok(_) => {}
+            Err(TranscriptionError::AudioTooShort { .. }) => {}
+            Err(e) => warn!("Skipping chunk {} at {:.1}s: {}", index, start_secs, e),
+        }
+
+        let done = index + 1;
+        emit_progress(
+            app,
+            "transcribing",
+            5.0 + 90.0 * done as f32 / total as f32,
+            format!("Transcribed {} of {} speech segments", done, total),
+        );
+    }
+
+    if transcripts.is_empty() {
+        return Err("No speech could be transcribed from this file".to_string());
+    }
+
+    // 5. Save as a regular meeting
+    emit_progress(app, "saving", 95.0, "Saving meeting".to_string());
+    let folder_path = folder.to_string_lossy().to_string();
+    let state = app.state::<AppState>();
+    let meeting_id = TranscriptsRepository::save_transcript(
+        state.db_manager.pool(),
+        title,
+        &transcripts,
+        Some(folder_path.clone()),
+    )
+    .await
+    .map_err(|e| format!("Failed to save imported meeting: {}", e))?;
+    crate::semantic::commands::index_in_background(app);
+
+    emit_progress(app, "saving", 100.0, "Import complete".to_string());
+
+    Ok(ImportResult {
+        meeting_id,
+        title: title.to_string(),
+        folder_path,
+        segment_count: transcripts.len(),
+        duration_seconds,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_segment_caps_length_and_offsets_starts() {
+        let max_len = MAX_SEGMENT_SECS * IMPORT_SAMPLE_RATE as usize;
+        let samples = vec![0.0f32; max_len * 2 + 16000];
+
+        let pieces = split_segment(samples, 10.0);
+        assert_eq!(pieces.len(), 3);
+        assert_eq!(pieces[0].0, 10.0);
+        assert_eq!(pieces[1].0, 10.0 + MAX_SEGMENT_SECS as f64);
+        assert_eq!(pieces[2].1.len(), 16000);
+    }
+
+    /// Hands out at most three bytes per read, like a slow pipe
+    struct TrickleReader<'a>(&'a [u8]);
+
+    impl Read for TrickleReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = buf.len().min(3).min(self.0.len());
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_read_frame_fills_frames_across_short_reads() {
+        let data: Vec<u8> = (0..20).collect();
+        let mut reader = TrickleReader(&data);
+        let mut frame = [0u8; 8];
+
+        assert_eq!(read_frame(&mut reader, &mut frame).unwrap(), 8);
+        assert_eq!(frame, [0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(read_frame(&mut reader, &mut frame).unwrap(), 8);
+        assert_eq!(read_frame(&mut reader, &mut frame).unwrap(), 4);
+        assert_eq!(&frame[..4], &[16, 17, 18, 19]);
+        assert_eq!(read_frame(&mut reader, &mut frame).unwrap(), 0);
+    }
+}
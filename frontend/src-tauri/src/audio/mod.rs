@@ -34,6 +34,7 @@ pub mod system_detector;
 pub mod system_audio_commands;
 pub mod device_monitor;  // NEW: Device disconnect/reconnect monitoring
 pub mod playback_monitor; // NEW: Playback device detection for BT warnings
+pub mod import; // Offline import of existing audio/video files
 
 // Transcription module (provider abstraction, engine management, worker pool)
 pub mod transcription;
@@ -12,6 +12,14 @@ use super::devices::AudioDevice;
 use super::recording_state::{AudioChunk, AudioError, RecordingState, DeviceType};
 use super::audio_processing::{audio_to_mono, LoudnessNormalizer, NoiseSuppressionProcessor, HighPassFilter};
 use super::vad::{ContinuousVadProcessor};
+use crate::diarization::{self, SourceHistory};
+
+/// Cadence of interim transcripts while an utterance is in progress
+const INTERIM_INTERVAL: std::time::Duration = std::time::Duration::from_millis(1500);
+/// Minimum in-progress speech (1s at 16kHz) before the first interim transcript
+const INTERIM_MIN_SAMPLES: usize = 16000;
+/// Interim transcripts of long turns only cover the most recent 30s
+const INTERIM_MAX_SAMPLES: usize = 16000 * 30;
 
 /// Ring buffer for synchronized audio mixing
 /// Accumulates samples from mic and system streams until we have aligned windows
@@ -613,6 +621,7 @@ impl AudioCapture {
             timestamp,
             chunk_id,
             device_type: self.device_type.clone(),
+            is_partial: false,
         };
 
         // NOTE: Raw audio is NOT sent to recording saver to prevent echo
@@ -694,6 +703,11 @@ pub struct AudioPipeline {
     mixer: ProfessionalAudioMixer,
     // Recording sender for pre-mixed audio
     recording_sender_for_mixed: Option<mpsc::UnboundedSender<AudioChunk>>,
+    // Per-stream history behind the mixed audio, for speaker diarization
+    source_history: SourceHistory,
+    // Live partial transcripts of in-progress speech
+    interim_transcripts: bool,
+    last_interim_sent: std::time::Instant,
 }
 
 impl AudioPipeline {
@@ -760,6 +774,9 @@ impl AudioPipeline {
             ring_buffer,
             mixer,
             recording_sender_for_mixed: None,  // Will be set by manager
+            source_history: SourceHistory::new(sample_rate),
+            interim_transcripts: false,  // Will be set by manager
+            last_interim_sent: std::time::Instant::now(),
         }
     }
 
@@ -824,6 +841,7 @@ impl AudioPipeline {
                         if let Some((mic_window, sys_window)) = self.ring_buffer.extract_window() {
                             // Simple mixing without aggressive ducking
                             let mixed_clean = self.mixer.mix_window(&mic_window, &sys_window);
+                            self.source_history.push_window(&mic_window, &sys_window);
 
                             // NO POST-GAIN NEEDED: Microphone already normalized by EBU R128 to -23 LUFS
                             // This is broadcast-standard loudness (Netflix/YouTube/Spotify level)
@@ -841,12 +859,20 @@ impl AudioPipeline {
                                             info!("📤 Sending VAD segment: {:.1}ms, {} samples",
                                                   duration_ms, segment.samples.len());
 
+                                            if let Some(source) = self.source_history.segment(
+                                                segment.start_timestamp_ms,
+                                                segment.end_timestamp_ms,
+                                            ) {
+                                                diarization::submit_segment(self.chunk_id_counter, source);
+                                            }
+
                                             let transcription_chunk = AudioChunk {
                                                 data: segment.samples,
                                                 sample_rate: 16000,
                                                 timestamp: segment.start_timestamp_ms / 1000.0,
                                                 chunk_id: self.chunk_id_counter,
                                                 device_type: DeviceType::Microphone,  // Mixed audio
+                                                is_partial: false,
                                             };
 
                                             if let Err(e) = self.transcription_sender.send(transcription_chunk) {
@@ -865,6 +891,9 @@ impl AudioPipeline {
                                 }
                             }
 
+                            // STEP 3b: Interim transcript of speech that hasn't ended yet
+                            self.send_interim_speech();
+
                             // STEP 4: Send mixed audio for recording (WAV file)
                             if let Some(ref sender) = self.recording_sender_for_mixed {
                                 let recording_chunk = AudioChunk {
@@ -873,6 +902,7 @@ impl AudioPipeline {
                                     timestamp: chunk.timestamp,
                                     chunk_id: self.chunk_id_counter,
                                     device_type: DeviceType::Microphone,  // Mixed audio
+                                    is_partial: false,
                                 };
                                 let _ = sender.send(recording_chunk);
                             }
@@ -897,6 +927,35 @@ impl AudioPipeline {
         Ok(())
     }
 
+    /// Send the in-progress utterance for a partial transcript at a fixed cadence.
+    /// Partial chunks reuse the ID the final segment will get and are never saved.
+    fn send_interim_speech(&mut self) {
+        if !self.interim_transcripts || self.last_interim_sent.elapsed() < INTERIM_INTERVAL {
+            return;
+        }
+
+        let Some(segment) = self.vad_processor.in_progress_speech(INTERIM_MAX_SAMPLES) else {
+            return;
+        };
+        if segment.samples.len() < INTERIM_MIN_SAMPLES {
+            return;
+        }
+        self.last_interim_sent = std::time::Instant::now();
+
+        let interim_chunk = AudioChunk {
+            data: segment.samples,
+            sample_rate: 16000,
+            timestamp: segment.start_timestamp_ms / 1000.0,
+            chunk_id: self.chunk_id_counter,
+            device_type: DeviceType::Microphone,  // Mixed audio
+            is_partial: true,
+        };
+
+        if let Err(e) = self.transcription_sender.send(interim_chunk) {
+            debug!("Failed to send interim speech: {}", e);
+        }
+    }
+
     fn flush_remaining_audio(&mut self) -> Result<()> {
         info!("Flushing remaining audio from pipeline (processed {} chunks)", self.processed_chunks);
 
@@ -911,12 +970,20 @@ impl AudioPipeline {
                         info!("📤 Sending final VAD segment to Whisper: {:.1}ms duration, {} samples",
                               duration_ms, segment.samples.len());
 
+                        if let Some(source) = self.source_history.segment(
+                            segment.start_timestamp_ms,
+                            segment.end_timestamp_ms,
+                        ) {
+                            diarization::submit_segment(self.chunk_id_counter, source);
+                        }
+
                         let transcription_chunk = AudioChunk {
                             data: segment.samples,
                             sample_rate: 16000,
                             timestamp: segment.start_timestamp_ms / 1000.0,
                             chunk_id: self.chunk_id_counter,
                             device_type: DeviceType::Microphone,
+                            is_partial: false,
                         };
 
                         if let Err(e) = self.transcription_sender.send(transcription_chunk) {
@@ -966,6 +1033,7 @@ impl AudioPipelineManager {
         mic_device_kind: super::device_detection::InputDeviceKind,
         system_device_name: String,
         system_device_kind: super::device_detection::InputDeviceKind,
+        interim_transcripts: bool,
     ) -> Result<()> {
         // Log device information for adaptive buffering
         info!("🎙️ Starting pipeline with device info:");
@@ -994,6 +1062,7 @@ impl AudioPipelineManager {
         // CRITICAL FIX: Connect recording sender to receive pre-mixed audio
         // This ensures both mic AND system audio are captured in recordings
         pipeline.recording_sender_for_mixed = recording_sender;
+        pipeline.interim_transcripts = interim_transcripts;
 
         let handle = tokio::spawn(async move {
             pipeline.run().await
@@ -1039,6 +1108,7 @@ impl AudioPipelineManager {
                 timestamp: 0.0,
                 chunk_id: u64::MAX, // Special ID to indicate flush
                 device_type: super::recording_state::DeviceType::Microphone,
+                is_partial: false,
             };
 
             if let Err(e) = sender.send(flush_chunk) {
@@ -1059,6 +1129,7 @@ impl AudioPipelineManager {
                         timestamp: 0.0,
                         chunk_id: u64::MAX - (i as u64),
                         device_type: super::recording_state::DeviceType::Microphone,
+                        is_partial: false,
                     };
                     let _ = sender.send(additional_flush);
                 }
@@ -111,18 +111,19 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
     let mut manager = RecordingManager::new();
 
     // Load recording preferences to get auto_save AND device preferences
-    let (auto_save, preferred_mic_name, preferred_system_name) =
+    let (auto_save, preferred_mic_name, preferred_system_name, interim_transcripts) =
         match super::recording_preferences::load_recording_preferences(&app).await {
             Ok(prefs) => {
-                info!("📋 Loaded recording preferences: auto_save={}, preferred_mic={:?}, preferred_system={:?}",
-                      prefs.auto_save, prefs.preferred_mic_device, prefs.preferred_system_device);
-                (prefs.auto_save, prefs.preferred_mic_device, prefs.preferred_system_device)
+                info!("📋 Loaded recording preferences: auto_save={}, preferred_mic={:?}, preferred_system={:?}, interim_transcripts={}",
+                      prefs.auto_save, prefs.preferred_mic_device, prefs.preferred_system_device, prefs.interim_transcripts);
+                (prefs.auto_save, prefs.preferred_mic_device, prefs.preferred_system_device, prefs.interim_transcripts)
             }
             Err(e) => {
                 warn!("Failed to load recording preferences, using defaults: {}", e);
-                (true, None, None)
+                (true, None, None, true)
             }
         };
+    manager.set_interim_transcripts(interim_transcripts);
 
     // ============================================================================
     // MICROPHONE DEVICE RESOLUTION: Preference → Default → Error
@@ -246,6 +247,8 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
     info!("🔍 Setting IS_RECORDING to true and resetting SPEECH_DETECTED_EMITTED");
     IS_RECORDING.store(true, Ordering::SeqCst);
     reset_speech_detected_flag(); // Reset for new recording session
+    crate::diarization::start_session(); // New meeting, new speakers
+    crate::notes::autosave::start_session(); // Fresh notes draft
 
     // Start optimized parallel transcription task and store handle
     let task_handle = transcription::start_transcription_task(app.clone(), transcription_receiver);
@@ -262,6 +265,10 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
         let listener_id = app.listen("transcript-update", move |event: tauri::Event| {
             // Parse the transcript update from the event payload
             if let Ok(update) = serde_json::from_str::<TranscriptUpdate>(event.payload()) {
+                // Partials are superseded by the final segment; only finals are saved
+                if update.is_partial {
+                    return;
+                }
                 // Create structured transcript segment
                 let segment = crate::audio::recording_saver::TranscriptSegment {
                     id: format!("seg_{}", update.sequence_id),
@@ -272,6 +279,9 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
                     display_time: update.timestamp.clone(), // Use wall-clock timestamp for display
                     confidence: update.confidence,
                     sequence_id: update.sequence_id,
+                    words: update.words.clone(),
+                    speaker: update.speaker.clone(),
+                    speaker_id: update.speaker_id.clone(),
                 };
 
                 // Save to recording manager
@@ -370,17 +380,18 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
     // Create new recording manager
     let mut manager = RecordingManager::new();
 
-    // Load recording preferences to check auto_save setting
-    let auto_save = match super::recording_preferences::load_recording_preferences(&app).await {
+    // Load recording preferences to check auto_save and interim transcript settings
+    let (auto_save, interim_transcripts) = match super::recording_preferences::load_recording_preferences(&app).await {
         Ok(prefs) => {
-            info!("📋 Loaded recording preferences: auto_save={}", prefs.auto_save);
-            prefs.auto_save
+            info!("📋 Loaded recording preferences: auto_save={}, interim_transcripts={}", prefs.auto_save, prefs.interim_transcripts);
+            (prefs.auto_save, prefs.interim_transcripts)
         }
         Err(e) => {
             warn!("Failed to load recording preferences, defaulting to auto_save=true: {}", e);
-            true // Default to saving if preferences can't be loaded
+            (true, true) // Default to saving if preferences can't be loaded
         }
     };
+    manager.set_interim_transcripts(interim_transcripts);
 
     // Always ensure a meeting name is set so incremental saver initializes
     let effective_meeting_name = meeting_name.clone().unwrap_or_else(|| {
@@ -414,6 +425,8 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
     info!("🔍 Setting IS_RECORDING to true and resetting SPEECH_DETECTED_EMITTED");
     IS_RECORDING.store(true, Ordering::SeqCst);
     reset_speech_detected_flag(); // Reset for new recording session
+    crate::diarization::start_session(); // New meeting, new speakers
+    crate::notes::autosave::start_session(); // Fresh notes draft
 
     // Start optimized parallel transcription task and store handle
     let task_handle = transcription::start_transcription_task(app.clone(), transcription_receiver);
@@ -430,6 +443,10 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
         let listener_id = app.listen("transcript-update", move |event: tauri::Event| {
             // Parse the transcript update from the event payload
             if let Ok(update) = serde_json::from_str::<TranscriptUpdate>(event.payload()) {
+                // Partials are superseded by the final segment; only finals are saved
+                if update.is_partial {
+                    return;
+                }
                 // Create structured transcript segment
                 let segment = crate::audio::recording_saver::TranscriptSegment {
                     id: format!("seg_{}", update.sequence_id),
@@ -440,6 +457,9 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
                     display_time: update.timestamp.clone(), // Use wall-clock timestamp for display
                     confidence: update.confidence,
                     sequence_id: update.sequence_id,
+                    words: update.words.clone(),
+                    speaker: update.speaker.clone(),
+                    speaker_id: update.speaker_id.clone(),
                 };
 
                 // Save to recording manager
@@ -615,6 +635,7 @@ pub async fn stop_recording<R: Runtime>(
     );
 
     info!("🧠 All transcript chunks processed. Now safely unloading transcription model...");
+    crate::diarization::end_session();
 
     // Determine which provider was used and unload the appropriate model (with timeout)
     let config = match tokio::time::timeout(
@@ -1024,6 +1045,12 @@ pub async fn get_meeting_folder_path() -> Result<Option<String>, String> {
     }
 }
 
+/// Seconds since the current recording started, or `None` when not recording
+pub fn current_recording_duration() -> Option<f64> {
+    let manager_guard = RECORDING_MANAGER.lock().unwrap();
+    manager_guard.as_ref()?.get_recording_duration()
+}
+
 /// Get accumulated transcript segments from current recording session
 /// Used for syncing frontend state after page reload during active recording
 #[tauri::command]
@@ -0,0 +1,206 @@
+// audio/transcription/deepgram_provider.rs
+//
+// Transcription via Deepgram's prerecorded `/v1/listen` API.
+
+use super::provider::{TranscriptResult, TranscriptionError, TranscriptionProvider, WordTimestamp};
+use super::remote::{
+    check_audio_length, encode_wav, language_hint, send_with_retry, RetryPolicy, REMOTE_SAMPLE_RATE,
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+pub const DEEPGRAM_BASE_URL: &str = "https://api.deepgram.com";
+pub const DEEPGRAM_DEFAULT_MODEL: &str = "nova-2";
+
+#[derive(Debug, Deserialize)]
+struct ListenResponse {
+    results: ListenResults,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListenResults {
+    channels: Vec<Channel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Channel {
+    alternatives: Vec<Alternative>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Alternative {
+    transcript: String,
+    #[serde(default)]
+    confidence: Option<f32>,
+    #[serde(default)]
+    words: Vec<ApiWord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiWord {
+    word: String,
+    #[serde(default)]
+    punctuated_word: Option<String>,
+    start: f64,
+    end: f64,
+    #[serde(default)]
+    confidence: Option<f32>,
+}
+
+/// Deepgram prerecorded transcription provider
+pub struct DeepgramProvider {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: String,
+    model: String,
+    retry: RetryPolicy,
+}
+
+impl DeepgramProvider {
+    pub fn new(base_url: &str, api_key: String, model: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key,
+            model: model.to_string(),
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    fn build_request(&self, wav: &[u8], language: Option<&str>) -> reqwest::RequestBuilder {
+        let mut query = vec![
+            ("model", self.model.as_str()),
+            ("smart_format", "true"),
+            ("punctuate", "true"),
+        ];
+        match language {
+            Some(language) => query.push(("language", language)),
+            None => query.push(("detect_language", "true")),
+        }
+
+        self.client
+            .post(format!("{}/v1/listen", self.base_url))
+            .query(&query)
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("Token {}", self.api_key),
+            )
+            .header(reqwest::header::CONTENT_TYPE, "audio/wav")
+            .body(wav.to_vec())
+    }
+}
+
+fn parse_response(body: &str) -> Result<TranscriptResult, serde_json::Error> {
+    let response: ListenResponse = serde_json::from_str(body)?;
+    let alternative = response
+        .results
+        .channels
+        .into_iter()
+        .next()
+        .and_then(|channel| channel.alternatives.into_iter().next());
+
+    let Some(alternative) = alternative else {
+        return Ok(TranscriptResult {
+            text: String::new(),
+            confidence: None,
+            is_partial: false,
+            words: Vec::new(),
+        });
+    };
+
+    Ok(TranscriptResult {
+        text: alternative.transcript.trim().to_string(),
+        confidence: alternative.confidence,
+        is_partial: false,
+        words: alternative
+            .words
+            .into_iter()
+            .map(|w| WordTimestamp {
+                text: w.punctuated_word.unwrap_or(w.word),
+                start: w.start,
+                end: w.end,
+                confidence: w.confidence,
+            })
+            .collect(),
+    })
+}
+
+#[async_trait]
+impl TranscriptionProvider for DeepgramProvider {
+    async fn transcribe(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+    ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        check_audio_length(&audio)?;
+
+        let language = language_hint(language.as_deref());
+        let wav = encode_wav(&audio, REMOTE_SAMPLE_RATE);
+
+        let body = send_with_retry("Deepgram", &self.retry, language, || {
+            self.build_request(&wav, language)
+        })
+        .await?;
+
+        parse_response(&body).map_err(|e| {
+            TranscriptionError::EngineFailed(format!("Invalid Deepgram response: {}", e))
+        })
+    }
+
+    async fn is_model_loaded(&self) -> bool {
+        true
+    }
+
+    async fn get_current_model(&self) -> Option<String> {
+        Some(self.model.clone())
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "Deepgram"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::CannedServer;
+
+    #[tokio::test]
+    async fn test_transcribes_against_mock_server() {
+        let server = CannedServer::start(vec![(
+            200,
+            r#"{"results":{"channels":[{"alternatives":[{"transcript":"hello there","confidence":0.97,"words":[{"word":"hello","punctuated_word":"Hello","start":0.0,"end":0.3,"confidence":0.99},{"word":"there","punctuated_word":"there.","start":0.4,"end":0.8,"confidence":0.95}]}]}]}}"#,
+        )])
+        .await;
+
+        let provider = DeepgramProvider::new(&server.base_url, "dg-key".to_string(), "nova-2");
+        let result = provider.transcribe(vec![0.0; 16000], None).await.unwrap();
+
+        assert_eq!(result.text, "hello there");
+        assert_eq!(result.confidence, Some(0.97));
+        assert_eq!(result.words[0].text, "Hello");
+        assert_eq!(result.words[1].confidence, Some(0.95));
+
+        let requests = server.requests();
+        assert!(requests[0].starts_with("POST /v1/listen?model=nova-2"));
+        assert!(requests[0].contains("detect_language=true"));
+        assert!(requests[0]
+            .to_lowercase()
+            .contains("authorization: token dg-key"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_short_audio_without_request() {
+        let provider = DeepgramProvider::new("http://127.0.0.1:9", "key".to_string(), "nova-2");
+        let result = provider.transcribe(vec![0.0; 100], None).await;
+        assert!(matches!(
+            result,
+            Err(TranscriptionError::AudioTooShort { .. })
+        ));
+    }
+}
@@ -2,7 +2,10 @@
 //
 // TranscriptionEngine enum and model initialization/validation logic.
 
+use super::deepgram_provider::{DeepgramProvider, DEEPGRAM_BASE_URL, DEEPGRAM_DEFAULT_MODEL};
+use super::openai_provider::{OpenAICompatibleProvider, GROQ_BASE_URL, OPENAI_BASE_URL};
 use super::provider::TranscriptionProvider;
+use crate::api::api::TranscriptConfig;
 use log::{info, warn};
 use std::sync::Arc;
 use tauri::{AppHandle, Manager, Runtime};
@@ -47,6 +50,69 @@ impl TranscriptionEngine {
     }
 }
 
+// ============================================================================
+// REMOTE (HTTP) PROVIDERS
+// ============================================================================
+
+/// Transcript providers that run over HTTP with a stored API key
+pub const REMOTE_PROVIDERS: &[&str] = &["openai", "groq", "openaiCompatible", "deepgram"];
+
+fn model_or_default<'a>(model: &'a str, default: &'a str) -> &'a str {
+    if model.trim().is_empty() {
+        default
+    } else {
+        model.trim()
+    }
+}
+
+/// Build the HTTP transcription provider for a remote transcript config
+pub fn create_remote_provider(
+    config: &TranscriptConfig,
+) -> Result<Arc<dyn TranscriptionProvider>, String> {
+    let api_key = config.api_key.clone().filter(|key| !key.trim().is_empty());
+    let missing_key = |name: &str| {
+        format!(
+            "{} API key is not set. Please add it in the transcription settings.",
+            name
+        )
+    };
+
+    match config.provider.as_str() {
+        "openai" => Ok(Arc::new(OpenAICompatibleProvider::new(
+            "OpenAI",
+            OPENAI_BASE_URL,
+            Some(api_key.ok_or_else(|| missing_key("OpenAI"))?),
+            model_or_default(&config.model, "whisper-1"),
+        ))),
+        "groq" => Ok(Arc::new(OpenAICompatibleProvider::new(
+            "Groq",
+            GROQ_BASE_URL,
+            Some(api_key.ok_or_else(|| missing_key("Groq"))?),
+            model_or_default(&config.model, "whisper-large-v3-turbo"),
+        ))),
+        "openaiCompatible" => {
+            let endpoint = config
+                .endpoint
+                .as_deref()
+                .filter(|endpoint| !endpoint.trim().is_empty())
+                .ok_or("Transcription server URL is not set. Please add it in the transcription settings.")?;
+            // Self-hosted servers often run without authentication
+            Ok(Arc::new(OpenAICompatibleProvider::new(
+                "OpenAI-compatible server",
+                endpoint,
+                api_key,
+                model_or_default(&config.model, "whisper-1"),
+            )))
+        }
+        "deepgram" => Ok(Arc::new(DeepgramProvider::new(
+            DEEPGRAM_BASE_URL,
+            api_key.ok_or_else(|| missing_key("Deepgram"))?,
+            model_or_default(&config.model, DEEPGRAM_DEFAULT_MODEL),
+        ))),
+        other => Err(format!("'{}' is not a remote transcription provider", other)),
+    }
+}
+
 // ============================================================================
 // MODEL VALIDATION AND INITIALIZATION
 // ============================================================================
@@ -70,18 +136,20 @@ pub async fn validate_transcription_model_ready<R: Runtime>(app: &AppHandle<R>)
         }
         Ok(None) => {
             info!("📝 No transcript config found, defaulting to parakeet");
-            crate::api::api::TranscriptConfig {
+            TranscriptConfig {
                 provider: "parakeet".to_string(),
                 model: "parakeet-tdt-0.6b-v3-int8".to_string(),
                 api_key: None,
+                endpoint: None,
             }
         }
         Err(e) => {
             warn!("⚠️ Failed to get transcript config: {}, defaulting to parakeet", e);
-            crate::api::api::TranscriptConfig {
+            TranscriptConfig {
                 provider: "parakeet".to_string(),
                 model: "parakeet-tdt-0.6b-v3-int8".to_string(),
                 api_key: None,
+                endpoint: None,
             }
         }
     };
@@ -135,10 +203,23 @@ pub async fn validate_transcription_model_ready<R: Runtime>(app: &AppHandle<R>)
                 }
             }
         }
+        remote if REMOTE_PROVIDERS.contains(&remote) => {
+            info!("🔍 Validating {} configuration...", remote);
+            match create_remote_provider(&config) {
+                Ok(provider) => {
+                    info!("✅ {} transcription is configured", provider.provider_name());
+                    Ok(())
+                }
+                Err(e) => {
+                    warn!("❌ {} validation failed: {}", remote, e);
+                    Err(e)
+                }
+            }
+        }
         other => {
-            warn!("❌ Unsupported transcription provider for local recording: {}", other);
+            warn!("❌ Unsupported transcription provider: {}", other);
             Err(format!(
-                "Provider '{}' is not supported for local transcription. Please select 'localWhisper' or 'parakeet'.",
+                "Provider '{}' is not supported for transcription. Please select 'parakeet', 'localWhisper', 'openai', 'groq', 'openaiCompatible' or 'deepgram'.",
                 other
             ))
         }
@@ -166,18 +247,20 @@ pub async fn get_or_init_transcription_engine<R: Runtime>(
         }
         Ok(None) => {
             info!("📝 No transcript config found, defaulting to parakeet");
-            crate::api::api::TranscriptConfig {
+            TranscriptConfig {
                 provider: "parakeet".to_string(),
                 model: "parakeet-tdt-0.6b-v3-int8".to_string(),
                 api_key: None,
+                endpoint: None,
             }
         }
         Err(e) => {
             warn!("⚠️ Failed to get transcript config: {}, defaulting to parakeet", e);
-            crate::api::api::TranscriptConfig {
+            TranscriptConfig {
                 provider: "parakeet".to_string(),
                 model: "parakeet-tdt-0.6b-v3-int8".to_string(),
                 api_key: None,
+                endpoint: None,
             }
         }
     };
@@ -212,6 +295,15 @@ pub async fn get_or_init_transcription_engine<R: Runtime>(
                 }
             }
         }
+        remote if REMOTE_PROVIDERS.contains(&remote) => {
+            let provider = create_remote_provider(&config)?;
+            info!(
+                "☁️ Using {} transcription (model: {})",
+                provider.provider_name(),
+                provider.get_current_model().await.unwrap_or_default()
+            );
+            Ok(TranscriptionEngine::Provider(provider))
+        }
         "localWhisper" | _ => {
             info!("🎤 Initializing Whisper transcription engine");
             let whisper_engine = get_or_init_whisper(app).await?;
@@ -5,18 +5,30 @@
 pub mod provider;
 pub mod whisper_provider;
 pub mod parakeet_provider;
+pub mod remote;
+pub mod openai_provider;
+pub mod deepgram_provider;
 pub mod engine;
+pub mod reorder;
 pub mod worker;
 
 // Re-export commonly used types
-pub use provider::{TranscriptionError, TranscriptionProvider, TranscriptResult};
+pub use provider::{
+    align_words_to_text, merge_tokens_into_words, TimedToken, TranscriptionError,
+    TranscriptionProvider, TranscriptResult, WordTimestamp,
+};
 pub use whisper_provider::WhisperProvider;
 pub use parakeet_provider::ParakeetProvider;
+pub use openai_provider::OpenAICompatibleProvider;
+pub use deepgram_provider::DeepgramProvider;
+pub use reorder::ReorderBuffer;
 pub use engine::{
     TranscriptionEngine,
     validate_transcription_model_ready,
     get_or_init_transcription_engine,
-    get_or_init_whisper
+    get_or_init_whisper,
+    create_remote_provider,
+    REMOTE_PROVIDERS
 };
 pub use worker::{
     start_transcription_task,
@@ -0,0 +1,253 @@
+// audio/transcription/openai_provider.rs
+//
+// Transcription via the OpenAI-compatible `/audio/transcriptions` API
+// (OpenAI, Groq, and self-hosted whisper servers).
+
+use super::provider::{TranscriptResult, TranscriptionError, TranscriptionProvider, WordTimestamp};
+use super::remote::{
+    check_audio_length, encode_wav, language_hint, send_with_retry, RetryPolicy, REMOTE_SAMPLE_RATE,
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+pub const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+pub const GROQ_BASE_URL: &str = "https://api.groq.com/openai/v1";
+
+#[derive(Debug, Deserialize)]
+struct VerboseTranscription {
+    text: String,
+    #[serde(default)]
+    words: Vec<ApiWord>,
+    #[serde(default)]
+    segments: Vec<ApiSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiWord {
+    word: String,
+    start: f64,
+    end: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiSegment {
+    #[serde(default)]
+    avg_logprob: Option<f32>,
+}
+
+/// Provider for any server implementing OpenAI's `/audio/transcriptions`
+pub struct OpenAICompatibleProvider {
+    client: reqwest::Client,
+    name: &'static str,
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    retry: RetryPolicy,
+}
+
+impl OpenAICompatibleProvider {
+    /// `base_url` is the API root, e.g. "https://api.openai.com/v1"
+    pub fn new(name: &'static str, base_url: &str, api_key: Option<String>, model: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            name,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.filter(|key| !key.trim().is_empty()),
+            model: model.to_string(),
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    fn build_request(
+        &self,
+        wav: &[u8],
+        language: Option<&str>,
+        translate: bool,
+    ) -> reqwest::RequestBuilder {
+        let endpoint = if translate {
+            "translations"
+        } else {
+            "transcriptions"
+        };
+        let file = reqwest::multipart::Part::bytes(wav.to_vec())
+            .file_name("audio.wav")
+            .mime_str("audio/wav")
+            .expect("static mime type is valid");
+
+        let verbose = supports_verbose_json(&self.model);
+        let response_format = if verbose { "verbose_json" } else { "json" };
+        let mut form = reqwest::multipart::Form::new()
+            .part("file", file)
+            .text("model", self.model.clone())
+            .text("response_format", response_format)
+            .text("temperature", "0");
+        if !translate {
+            if verbose {
+                form = form.text("timestamp_granularities[]", "word");
+            }
+            if let Some(language) = language {
+                form = form.text("language", language.to_string());
+            }
+        }
+
+        let mut request = self
+            .client
+            .post(format!("{}/audio/{}", self.base_url, endpoint))
+            .multipart(form);
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+        request
+    }
+}
+
+/// The gpt-4o transcription models only answer with `json` or `text`
+/// and reject `timestamp_granularities`, so they give no word timings
+fn supports_verbose_json(model: &str) -> bool {
+    !model.starts_with("gpt-4o")
+}
+
+/// Convert a verbose_json response; confidence is exp(mean avg_logprob) of the segments
+fn parse_response(body: &str) -> Result<TranscriptResult, serde_json::Error> {
+    // Some self-hosted servers ignore response_format and return `{ "text": ... }`
+    let response: VerboseTranscription = serde_json::from_str(body)?;
+
+    let logprobs: Vec<f32> = response
+        .segments
+        .iter()
+        .filter_map(|segment| segment.avg_logprob)
+        .collect();
+    let confidence = if logprobs.is_empty() {
+        None
+    } else {
+        let mean = logprobs.iter().sum::<f32>() / logprobs.len() as f32;
+        Some(mean.exp().clamp(0.0, 1.0))
+    };
+
+    Ok(TranscriptResult {
+        text: response.text.trim().to_string(),
+        confidence,
+        is_partial: false,
+        words: response
+            .words
+            .into_iter()
+            .map(|w| WordTimestamp {
+                text: w.word.trim().to_string(),
+                start: w.start,
+                end: w.end,
+                confidence: None,
+            })
+            .collect(),
+    })
+}
+
+#[async_trait]
+impl TranscriptionProvider for OpenAICompatibleProvider {
+    async fn transcribe(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+    ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        check_audio_length(&audio)?;
+
+        let translate = language.as_deref() == Some("auto-translate");
+        let language = language_hint(language.as_deref());
+        let wav = encode_wav(&audio, REMOTE_SAMPLE_RATE);
+
+        let body = send_with_retry(self.name, &self.retry, language, || {
+            self.build_request(&wav, language, translate)
+        })
+        .await?;
+
+        parse_response(&body).map_err(|e| {
+            TranscriptionError::EngineFailed(format!("Invalid {} response: {}", self.name, e))
+        })
+    }
+
+    async fn is_model_loaded(&self) -> bool {
+        true
+    }
+
+    async fn get_current_model(&self) -> Option<String> {
+        Some(self.model.clone())
+    }
+
+    fn provider_name(&self) -> &'static str {
+        self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::CannedServer;
+
+    #[tokio::test]
+    async fn test_transcribes_against_mock_server() {
+        let server = CannedServer::start(vec![(
+            200,
+            r#"{"text":" Hello world.","words":[{"word":"Hello","start":0.1,"end":0.4},{"word":"world.","start":0.5,"end":0.9}],"segments":[{"avg_logprob":-0.1}]}"#,
+        )])
+        .await;
+
+        let provider = OpenAICompatibleProvider::new(
+            "Groq",
+            &server.base_url,
+            Some("test-key".to_string()),
+            "whisper-large-v3-turbo",
+        );
+        let result = provider
+            .transcribe(vec![0.0; 16000], Some("en".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(result.text, "Hello world.");
+        assert_eq!(result.words.len(), 2);
+        assert_eq!(result.words[1].text, "world.");
+        assert!((result.confidence.unwrap() - (-0.1f32).exp()).abs() < 1e-6);
+
+        let requests = server.requests();
+        let request = &requests[0];
+        assert!(request.starts_with("POST /audio/transcriptions"));
+        assert!(request.contains("Bearer test-key"));
+        assert!(request.contains("whisper-large-v3-turbo"));
+        assert!(request.contains("RIFF"));
+    }
+
+    #[tokio::test]
+    async fn test_gpt_4o_models_request_plain_json() {
+        let server = CannedServer::start(vec![(200, r#"{"text":"Hello world."}"#)]).await;
+
+        let provider = OpenAICompatibleProvider::new(
+            "OpenAI",
+            &server.base_url,
+            Some("test-key".to_string()),
+            "gpt-4o-mini-transcribe",
+        );
+        let result = provider
+            .transcribe(vec![0.0; 16000], Some("en".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(result.text, "Hello world.");
+        assert!(result.words.is_empty());
+
+        let request = &server.requests()[0];
+        assert!(request.contains("name=\"response_format\"\r\n\r\njson\r\n"));
+        assert!(!request.contains("verbose_json"));
+        assert!(!request.contains("timestamp_granularities"));
+        assert!(request.contains("name=\"language\"\r\n\r\nen\r\n"));
+    }
+
+    #[test]
+    fn test_parse_plain_text_response() {
+        let result = parse_response(r#"{"text":"Hi"}"#).unwrap();
+        assert_eq!(result.text, "Hi");
+        assert!(result.words.is_empty());
+        assert_eq!(result.confidence, None);
+    }
+}
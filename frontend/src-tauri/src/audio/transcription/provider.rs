@@ -4,6 +4,7 @@
 // transcription engines (Whisper, Parakeet, future providers).
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
 // ============================================================================
 // TRANSCRIPTION PROVIDER TRAIT & ERROR TYPES
@@ -37,12 +38,108 @@ impl std::fmt::Display for TranscriptionError {
 
 impl std::error::Error for TranscriptionError {}
 
+/// Timing of a single word, in seconds from the start of the transcribed audio
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordTimestamp {
+    pub text: String,
+    pub start: f64,
+    pub end: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+}
+
+impl WordTimestamp {
+    /// Shift the word by `seconds` (e.g. chunk-relative -> recording-relative)
+    pub fn offset_by(mut self, seconds: f64) -> Self {
+        self.start += seconds;
+        self.end += seconds;
+        self
+    }
+}
+
+/// A decoded sub-word token with its timing, as produced by an engine
+#[derive(Debug, Clone)]
+pub struct TimedToken<'a> {
+    pub text: &'a str,
+    pub start: f64,
+    pub end: f64,
+    pub confidence: Option<f32>,
+}
+
+/// Merge sub-word tokens into words
+///
+/// Both Whisper and Parakeet (SentencePiece) mark the start of a word with a
+/// leading space, so a token without one continues the previous word.
+/// Word confidence is the lowest confidence of its tokens.
+pub fn merge_tokens_into_words<'a>(
+    tokens: impl IntoIterator<Item = TimedToken<'a>>,
+) -> Vec<WordTimestamp> {
+    let mut words: Vec<WordTimestamp> = Vec::new();
+    let mut word_open = false;
+
+    for token in tokens {
+        let starts_word = token.text.starts_with(char::is_whitespace);
+        let text = token.text.trim();
+        if text.is_empty() {
+            // A bare space token ends the current word
+            word_open = word_open && !starts_word;
+            continue;
+        }
+
+        match words.last_mut() {
+            Some(word) if word_open && !starts_word => {
+                word.text.push_str(text);
+                word.end = word.end.max(token.end);
+                word.confidence = match (word.confidence, token.confidence) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+            }
+            _ => words.push(WordTimestamp {
+                text: text.to_string(),
+                start: token.start,
+                end: token.end.max(token.start),
+                confidence: token.confidence,
+            }),
+        }
+        word_open = true;
+    }
+
+    words
+}
+
+/// Keep only the words that survive post-processing of the transcript text
+///
+/// Engines may drop repeated words or phrases after decoding, so `text` is
+/// matched against `words` in order. A word of `text` with no timed
+/// counterpart (e.g. one changed by post-processing) is skipped, so the
+/// result is never longer than `text`.
+pub fn align_words_to_text(words: Vec<WordTimestamp>, text: &str) -> Vec<WordTimestamp> {
+    let mut keep = vec![false; words.len()];
+    let mut next = 0;
+
+    for expected in text.split_whitespace() {
+        if let Some(offset) = words[next..].iter().position(|w| w.text == expected) {
+            keep[next + offset] = true;
+            next += offset + 1;
+        }
+    }
+
+    words
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(word, keep)| keep.then_some(word))
+        .collect()
+}
+
 /// Unified transcription result across all providers
 #[derive(Debug, Clone)]
 pub struct TranscriptResult {
     pub text: String,
     pub confidence: Option<f32>, // None if provider doesn't support confidence scores
     pub is_partial: bool,
+    /// Word-level timing relative to the start of the audio (empty if unsupported)
+    pub words: Vec<WordTimestamp>,
 }
 
 /// Trait for transcription providers (Whisper, Parakeet, future providers)
@@ -55,7 +152,7 @@ pub trait TranscriptionProvider: Send + Sync {
     /// * `language` - Optional language hint (e.g., "en", "es", "fr")
     ///
     /// # Returns
-    /// * `TranscriptResult` with text, optional confidence, and partial flag
+    /// * `TranscriptResult` with text, optional confidence, partial flag and word timings
     async fn transcribe(
         &self,
         audio: Vec<f32>,
@@ -71,3 +168,62 @@ pub trait TranscriptionProvider: Send + Sync {
     /// Get the provider name (for logging/debugging)
     fn provider_name(&self) -> &'static str;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(text: &str, start: f64, end: f64) -> TimedToken<'_> {
+        TimedToken {
+            text,
+            start,
+            end,
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_tokens_into_words() {
+        let words = merge_tokens_into_words(vec![
+            token(" Hel", 0.0, 0.2),
+            token("lo", 0.2, 0.4),
+            token(" world", 0.5, 0.9),
+            token(".", 0.9, 1.0),
+        ]);
+
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].text, "Hello");
+        assert_eq!((words[0].start, words[0].end), (0.0, 0.4));
+        assert_eq!(words[1].text, "world.");
+        assert_eq!((words[1].start, words[1].end), (0.5, 1.0));
+
+        // The first token of a transcript may lack the leading space
+        let words = merge_tokens_into_words(vec![token("Hi", 0.0, 0.1), token(" there", 0.1, 0.3)]);
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].text, "Hi");
+    }
+
+    #[test]
+    fn test_align_words_to_text() {
+        let words: Vec<WordTimestamp> = ["we", "we", "ship", "today"]
+            .iter()
+            .enumerate()
+            .map(|(i, w)| WordTimestamp {
+                text: w.to_string(),
+                start: i as f64,
+                end: i as f64 + 1.0,
+                confidence: None,
+            })
+            .collect();
+
+        let aligned = align_words_to_text(words.clone(), "we ship today");
+        assert_eq!(aligned.len(), 3);
+        assert_eq!(aligned[1].start, 2.0);
+
+        // An unmatched word is skipped without losing the ones after it
+        let aligned = align_words_to_text(words, "we shipped today");
+        let texts: Vec<&str> = aligned.iter().map(|w| w.text.as_str()).collect();
+        assert_eq!(texts, ["we", "today"]);
+        assert_eq!(aligned[1].start, 3.0);
+    }
+}
@@ -0,0 +1,253 @@
+// audio/transcription/remote.rs
+//
+// Shared plumbing for HTTP transcription providers: WAV encoding of VAD
+// segments, retry/timeout handling and mapping of HTTP failures into
+// TranscriptionError.
+
+use super::provider::TranscriptionError;
+use log::warn;
+use std::time::Duration;
+
+/// Sample rate of the audio handed to providers (see TranscriptionProvider::transcribe)
+pub const REMOTE_SAMPLE_RATE: u32 = 16000;
+
+/// Segments shorter than this (100ms) are rejected by most APIs
+pub const MIN_REMOTE_SAMPLES: usize = 1600;
+
+/// Timeout and retry settings for one transcription request
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for each further retry
+    pub initial_backoff: Duration,
+    /// Per-attempt request timeout
+    pub timeout: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Encode 16kHz mono f32 samples as a 16-bit PCM WAV file
+pub fn encode_wav(samples: &[f32], sample_rate: u32) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+
+    // fmt chunk: PCM, 1 channel, 16 bits
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for &sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        wav.extend_from_slice(&value.to_le_bytes());
+    }
+
+    wav
+}
+
+/// Language hint to send to an API ("auto"/"auto-translate" mean detection)
+pub fn language_hint(language: Option<&str>) -> Option<&str> {
+    match language {
+        None | Some("") | Some("auto") | Some("auto-translate") => None,
+        Some(language) => Some(language),
+    }
+}
+
+/// Reject segments that are too short to transcribe remotely
+pub fn check_audio_length(audio: &[f32]) -> Result<(), TranscriptionError> {
+    if audio.len() < MIN_REMOTE_SAMPLES {
+        return Err(TranscriptionError::AudioTooShort {
+            samples: audio.len(),
+            minimum: MIN_REMOTE_SAMPLES,
+        });
+    }
+    Ok(())
+}
+
+/// Map a non-success HTTP response into a TranscriptionError
+pub fn error_from_status(
+    provider: &str,
+    status: reqwest::StatusCode,
+    body: &str,
+    language: Option<&str>,
+) -> TranscriptionError {
+    let detail = body.chars().take(300).collect::<String>();
+    match status.as_u16() {
+        401 | 403 => TranscriptionError::EngineFailed(format!(
+            "{} rejected the API key ({}). Please check it in the transcription settings.",
+            provider, status
+        )),
+        400 | 422 if language.is_some() && detail.to_lowercase().contains("language") => {
+            TranscriptionError::UnsupportedLanguage(language.unwrap_or_default().to_string())
+        }
+        _ => TranscriptionError::EngineFailed(format!(
+            "{} returned {}: {}",
+            provider, status, detail
+        )),
+    }
+}
+
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+        || status.is_server_error()
+}
+
+/// Delay requested via a `Retry-After: <seconds>` header, capped at 30s
+fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(|secs| Duration::from_secs(secs.min(30)))
+}
+
+/// Send a request, retrying timeouts, connection errors, 408, 429 and 5xx.
+///
+/// `build` is called once per attempt because multipart bodies can't be cloned.
+/// Returns the body of the first successful response.
+pub async fn send_with_retry<F>(
+    provider: &str,
+    policy: &RetryPolicy,
+    language: Option<&str>,
+    build: F,
+) -> Result<String, TranscriptionError>
+where
+    F: Fn() -> reqwest::RequestBuilder,
+{
+    let mut backoff = policy.initial_backoff;
+    let mut last_error =
+        TranscriptionError::EngineFailed(format!("{} request was not sent", provider));
+
+    for attempt in 1..=policy.max_attempts.max(1) {
+        let mut delay = backoff;
+
+        match build().timeout(policy.timeout).send().await {
+            Ok(response) => {
+                let status = response.status();
+                if status.is_success() {
+                    return response.text().await.map_err(|e| {
+                        TranscriptionError::EngineFailed(format!(
+                            "Failed to read {} response: {}",
+                            provider, e
+                        ))
+                    });
+                }
+
+                if let Some(requested) = retry_after(&response) {
+                    delay = requested;
+                }
+                let body = response.text().await.unwrap_or_default();
+                last_error = error_from_status(provider, status, &body, language);
+                if !is_retryable_status(status) {
+                    return Err(last_error);
+                }
+            }
+            Err(e) if e.is_timeout() || e.is_connect() || e.is_request() => {
+                last_error =
+                    TranscriptionError::EngineFailed(format!("{} request failed: {}", provider, e));
+            }
+            Err(e) => {
+                return Err(TranscriptionError::EngineFailed(format!(
+                    "{} request failed: {}",
+                    provider, e
+                )));
+            }
+        }
+
+        if attempt < policy.max_attempts {
+            warn!(
+                "{} attempt {}/{} failed ({}), retrying in {:?}",
+                provider, attempt, policy.max_attempts, last_error, delay
+            );
+            tokio::time::sleep(delay).await;
+            backoff *= 2;
+        }
+    }
+
+    Err(last_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::CannedServer;
+
+    #[test]
+    fn test_encode_wav_header_and_samples() {
+        let wav = encode_wav(&[0.0, 1.0, -1.0], REMOTE_SAMPLE_RATE);
+        assert_eq!(wav.len(), 44 + 6);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[8..12], b"WAVE");
+        assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 16000);
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 6);
+        assert_eq!(i16::from_le_bytes([wav[46], wav[47]]), i16::MAX);
+        assert_eq!(i16::from_le_bytes([wav[48], wav[49]]), -i16::MAX);
+    }
+
+    #[test]
+    fn test_error_mapping() {
+        let auth = error_from_status("Groq", reqwest::StatusCode::UNAUTHORIZED, "", None);
+        assert!(auth.to_string().contains("API key"));
+
+        let language = error_from_status(
+            "OpenAI",
+            reqwest::StatusCode::BAD_REQUEST,
+            r#"{"error":{"message":"Unsupported language xx"}}"#,
+            Some("xx"),
+        );
+        assert!(matches!(language, TranscriptionError::UnsupportedLanguage(l) if l == "xx"));
+    }
+
+    #[tokio::test]
+    async fn test_send_with_retry_retries_server_errors() {
+        let server = CannedServer::start(vec![(503, "busy"), (200, "ok")]).await;
+        let client = reqwest::Client::new();
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(10),
+            ..Default::default()
+        };
+
+        let body = send_with_retry("Mock", &policy, None, || client.get(&server.base_url))
+            .await
+            .unwrap();
+        assert_eq!(body, "ok");
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_send_with_retry_does_not_retry_client_errors() {
+        let server = CannedServer::start(vec![(401, "{}"), (200, "ok")]).await;
+        let client = reqwest::Client::new();
+
+        let result = send_with_retry("Mock", &RetryPolicy::default(), None, || {
+            client.get(&server.base_url)
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(server.requests().len(), 1);
+    }
+}
@@ -0,0 +1,91 @@
+// audio/transcription/reorder.rs
+//
+// Reorder buffer that lets transcription workers finish chunks in any order
+// while results are released strictly in dispatch order.
+
+use std::collections::BTreeMap;
+
+/// Holds out-of-order results until every earlier dispatch index has reported.
+///
+/// Every dispatched index must be pushed exactly once; chunks that produce no
+/// transcript (silence, low confidence, errors) are pushed as `None` so they
+/// don't block later results.
+#[derive(Debug)]
+pub struct ReorderBuffer<T> {
+    next_index: u64,
+    pending: BTreeMap<u64, Option<T>>,
+}
+
+impl<T> Default for ReorderBuffer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> ReorderBuffer<T> {
+    pub fn new() -> Self {
+        Self {
+            next_index: 0,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Record the result for `index` and return all results that are now in order.
+    ///
+    /// Indices below the release point (duplicates) are ignored.
+    pub fn push(&mut self, index: u64, item: Option<T>) -> Vec<T> {
+        if index < self.next_index {
+            return Vec::new();
+        }
+        self.pending.insert(index, item);
+
+        let mut ready = Vec::new();
+        while let Some(item) = self.pending.remove(&self.next_index) {
+            self.next_index += 1;
+            ready.extend(item);
+        }
+        ready
+    }
+
+    /// Index of the next result to be released
+    pub fn next_index(&self) -> u64 {
+        self.next_index
+    }
+
+    /// Number of results waiting on an earlier index
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_releases_in_dispatch_order() {
+        let mut buffer = ReorderBuffer::new();
+
+        assert!(buffer.push(2, Some("c")).is_empty());
+        assert!(buffer.push(1, Some("b")).is_empty());
+        assert_eq!(buffer.pending_len(), 2);
+
+        assert_eq!(buffer.push(0, Some("a")), vec!["a", "b", "c"]);
+        assert_eq!(buffer.next_index(), 3);
+        assert_eq!(buffer.pending_len(), 0);
+    }
+
+    #[test]
+    fn test_empty_results_do_not_block() {
+        let mut buffer = ReorderBuffer::new();
+
+        assert!(buffer.push(1, Some("b")).is_empty());
+        assert_eq!(buffer.push(0, None), vec!["b"]);
+        assert!(buffer.push(3, Some("d")).is_empty());
+        assert_eq!(buffer.push(2, None), vec!["d"]);
+        assert_eq!(buffer.next_index(), 4);
+
+        // Late duplicates are dropped
+        assert!(buffer.push(1, Some("again")).is_empty());
+    }
+}
@@ -3,9 +3,11 @@
 // Parallel transcription worker pool and chunk processing logic.
 
 use super::engine::TranscriptionEngine;
-use super::provider::TranscriptionError;
+use super::provider::{TranscriptionError, WordTimestamp};
+use super::reorder::ReorderBuffer;
 use crate::audio::AudioChunk;
-use log::{error, info, warn};
+use crate::whisper_engine::SystemMonitor;
+use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
@@ -36,11 +38,124 @@ pub struct TranscriptUpdate {
     pub audio_start_time: f64, // Seconds from recording start (e.g., 125.3)
     pub audio_end_time: f64,   // Seconds from recording start (e.g., 128.6)
     pub duration: f64,          // Segment duration in seconds (e.g., 3.3)
+    // Word-level timing, seconds from recording start (empty if the engine has none)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<WordTimestamp>,
+    // Capture stream the speech came from ("mic" or "system")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+    // Stable per-meeting speaker ID from diarization ("me", "speaker_1", ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker_id: Option<String>,
+}
+
+impl TranscriptUpdate {
+    /// Update for a transcribed chunk that starts `chunk_start` seconds into the recording.
+    /// Engines time words from the start of the chunk; they are shifted to recording time here
+    fn for_chunk(
+        text: String,
+        confidence: Option<f32>,
+        words: Vec<WordTimestamp>,
+        chunk_start: f64,
+        duration: f64,
+        is_partial: bool,
+    ) -> Self {
+        Self {
+            text,
+            timestamp: format_current_timestamp(), // Wall-clock for reference
+            source: "Audio".to_string(),
+            sequence_id: 0,
+            chunk_start_time: chunk_start, // Legacy compatibility
+            is_partial,
+            confidence: confidence.unwrap_or(0.85), // Default for providers without confidence
+            audio_start_time: chunk_start,
+            audio_end_time: chunk_start + duration,
+            duration,
+            words: words.into_iter().map(|w| w.offset_by(chunk_start)).collect(),
+            speaker: None,
+            speaker_id: None,
+        }
+    }
 }
 
 // NOTE: get_transcript_history and get_recording_meeting_name functions
 // have been moved to recording_commands.rs where they have access to RECORDING_MANAGER
 
+/// Chunks dispatched but not yet emitted, per worker, before the dispatcher waits
+const IN_FLIGHT_CHUNKS_PER_WORKER: usize = 4;
+
+type OrderedUpdate = (u64, Option<TranscriptUpdate>);
+
+/// A worker's result for one dispatched chunk.
+///
+/// Sent to the emitter when dropped, so every exit path (skips, errors, panics)
+/// reports its dispatch index and later transcripts are never held back.
+struct OrderedResult {
+    index: u64,
+    update: Option<TranscriptUpdate>,
+    results: tokio::sync::mpsc::UnboundedSender<OrderedUpdate>,
+}
+
+impl Drop for OrderedResult {
+    fn drop(&mut self) {
+        let _ = self.results.send((self.index, self.update.take()));
+    }
+}
+
+/// Transcribes in-progress speech (`AudioChunk::is_partial`) for live partial updates.
+///
+/// Partials pile up while the engine is busy; only the newest is transcribed since
+/// older ones cover a prefix of the same utterance. Partials for speech a worker
+/// has already picked up as a final segment are dropped.
+fn start_interim_task<R: Runtime>(
+    app: AppHandle<R>,
+    engine: TranscriptionEngine,
+    mut receiver: tokio::sync::mpsc::UnboundedReceiver<AudioChunk>,
+    finalized_until: Arc<AtomicU64>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let is_finalized =
+            |start: f64| start < f64::from_bits(finalized_until.load(Ordering::SeqCst));
+
+        while let Some(mut chunk) = receiver.recv().await {
+            while let Ok(newer) = receiver.try_recv() {
+                chunk = newer;
+            }
+
+            let audio_start_time = chunk.timestamp;
+            if is_finalized(audio_start_time) {
+                continue;
+            }
+            let duration = chunk.data.len() as f64 / chunk.sample_rate as f64;
+
+            match transcribe_chunk_with_provider(&engine, chunk, &app).await {
+                Ok((text, confidence, _, words)) if !text.trim().is_empty() => {
+                    // The final segment may have been picked up while we were transcribing
+                    if is_finalized(audio_start_time) {
+                        continue;
+                    }
+
+                    let mut update = TranscriptUpdate::for_chunk(
+                        text,
+                        confidence,
+                        words,
+                        audio_start_time,
+                        duration,
+                        true,
+                    );
+                    // Not unique: the ID the next final update will take
+                    update.sequence_id = SEQUENCE_COUNTER.load(Ordering::SeqCst);
+                    if let Err(e) = app.emit("transcript-update", &update) {
+                        error!("Failed to emit partial transcript update: {}", e);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => debug!("Interim transcription skipped: {}", e),
+            }
+        }
+    })
+}
+
 /// Optimized parallel transcription task ensuring ZERO chunk loss
 pub fn start_transcription_task<R: Runtime>(
     app: AppHandle<R>,
@@ -63,21 +178,84 @@ pub fn start_transcription_task<R: Runtime>(
             }
         };
 
-        // Create parallel workers for faster processing while preserving ALL chunks
-        const NUM_WORKERS: usize = 1; // Serial processing ensures transcripts emit in chronological order
-        let (work_sender, work_receiver) = tokio::sync::mpsc::unbounded_channel::<AudioChunk>();
+        // Create parallel workers for faster processing while preserving ALL chunks.
+        // Workers may finish out of order; the emitter below restores dispatch order.
+        let num_workers = match SystemMonitor::new().calculate_safe_worker_count().await {
+            Ok(count) => count,
+            Err(e) => {
+                warn!("Failed to calculate safe worker count, using 1: {}", e);
+                1
+            }
+        };
+        let (work_sender, work_receiver) =
+            tokio::sync::mpsc::unbounded_channel::<(u64, AudioChunk)>();
         let work_receiver = Arc::new(tokio::sync::Mutex::new(work_receiver));
 
+        // Backpressure: cap chunks between dispatch and emission so a slow chunk
+        // can't let the reorder buffer grow without bound
+        let in_flight = Arc::new(tokio::sync::Semaphore::new(
+            num_workers * IN_FLIGHT_CHUNKS_PER_WORKER,
+        ));
+        let (result_sender, mut result_receiver) =
+            tokio::sync::mpsc::unbounded_channel::<OrderedUpdate>();
+
+        // Interim (partial) transcripts bypass the ordered workers and chunk accounting.
+        // `finalized_until` holds the f64 bits of the latest audio end time picked up as a
+        // final segment (bit order matches numeric order for non-negative values).
+        let finalized_until = Arc::new(AtomicU64::new(0f64.to_bits()));
+        // Remote providers bill per audio second; re-sending the growing utterance
+        // every interval would multiply the cost, so partials are local-only
+        let interim_supported = !matches!(transcription_engine, TranscriptionEngine::Provider(_));
+        let (interim_sender, interim_receiver) =
+            tokio::sync::mpsc::unbounded_channel::<AudioChunk>();
+        let interim_handle = start_interim_task(
+            app.clone(),
+            match &transcription_engine {
+                TranscriptionEngine::Whisper(e) => TranscriptionEngine::Whisper(e.clone()),
+                TranscriptionEngine::Parakeet(e) => TranscriptionEngine::Parakeet(e.clone()),
+                TranscriptionEngine::Provider(p) => TranscriptionEngine::Provider(p.clone()),
+            },
+            interim_receiver,
+            finalized_until.clone(),
+        );
+
         // Track completion: AtomicU64 for chunks queued, AtomicU64 for chunks completed
         let chunks_queued = Arc::new(AtomicU64::new(0));
         let chunks_completed = Arc::new(AtomicU64::new(0));
         let input_finished = Arc::new(AtomicBool::new(false));
 
-        info!("📊 Starting {} transcription worker{} (serial mode for ordered emission)", NUM_WORKERS, if NUM_WORKERS == 1 { "" } else { "s" });
+        info!("📊 Starting {} transcription worker{} (ordered emission)", num_workers, if num_workers == 1 { "" } else { "s" });
+
+        // Emitter: releases transcript updates in dispatch order and assigns sequence IDs
+        let emitter_handle = {
+            let app = app.clone();
+            let in_flight = in_flight.clone();
+            tokio::spawn(async move {
+                let mut buffer = ReorderBuffer::new();
+                while let Some((index, update)) = result_receiver.recv().await {
+                    let released_before = buffer.next_index();
+                    for mut update in buffer.push(index, update) {
+                        update.sequence_id = SEQUENCE_COUNTER.fetch_add(1, Ordering::SeqCst);
+                        if let Err(e) = app.emit("transcript-update", &update) {
+                            error!("Failed to emit transcript update: {}", e);
+                        }
+                    }
+                    in_flight.add_permits((buffer.next_index() - released_before) as usize);
+                }
+
+                if buffer.pending_len() > 0 {
+                    error!(
+                        "❌ {} transcript updates never emitted - still waiting on chunk index {}",
+                        buffer.pending_len(),
+                        buffer.next_index()
+                    );
+                }
+            })
+        };
 
         // Spawn worker tasks
         let mut worker_handles = Vec::new();
-        for worker_id in 0..NUM_WORKERS {
+        for worker_id in 0..num_workers {
             let engine_clone = match &transcription_engine {
                 TranscriptionEngine::Whisper(e) => TranscriptionEngine::Whisper(e.clone()),
                 TranscriptionEngine::Parakeet(e) => TranscriptionEngine::Parakeet(e.clone()),
@@ -88,6 +266,8 @@ pub fn start_transcription_task<R: Runtime>(
             let chunks_completed_clone = chunks_completed.clone();
             let input_finished_clone = input_finished.clone();
             let chunks_queued_clone = chunks_queued.clone();
+            let result_sender_clone = result_sender.clone();
+            let finalized_until_clone = finalized_until.clone();
 
             let worker_handle = tokio::spawn(async move {
                 info!("👷 Worker {} started", worker_id);
@@ -118,7 +298,14 @@ pub fn start_transcription_task<R: Runtime>(
                     };
 
                     match chunk {
-                        Some(chunk) => {
+                        Some((index, chunk)) => {
+                            // Reported to the emitter when dropped, on every path below
+                            let mut result = OrderedResult {
+                                index,
+                                update: None,
+                                results: result_sender_clone.clone(),
+                            };
+
                             // PERFORMANCE OPTIMIZATION: Reduce logging in hot path
                             // Only log every 10th chunk per worker to reduce I/O overhead
                             let should_log_this_chunk = chunk.chunk_id % 10 == 0;
@@ -142,6 +329,12 @@ pub fn start_transcription_task<R: Runtime>(
 
                             let chunk_timestamp = chunk.timestamp;
                             let chunk_duration = chunk.data.len() as f64 / chunk.sample_rate as f64;
+                            // Partials of this utterance are now stale
+                            finalized_until_clone.fetch_max(
+                                (chunk_timestamp + chunk_duration).to_bits(),
+                                Ordering::SeqCst,
+                            );
+                            let segment_audio = crate::diarization::take_segment(chunk.chunk_id);
 
                             // Transcribe with provider-agnostic approach
                             match transcribe_chunk_with_provider(
@@ -151,7 +344,7 @@ pub fn start_transcription_task<R: Runtime>(
                             )
                             .await
                             {
-                                Ok((transcript, confidence_opt, is_partial)) => {
+                                Ok((transcript, confidence_opt, is_partial, words)) => {
                                     // Provider-aware confidence threshold
                                     let confidence_threshold = match &engine_clone {
                                         TranscriptionEngine::Whisper(_) | TranscriptionEngine::Provider(_) => 0.3,
@@ -191,42 +384,32 @@ pub fn start_transcription_task<R: Runtime>(
                                             info!("🔍 Speech already detected in this session, not re-emitting");
                                         }
 
-                                        // Generate sequence ID and calculate timestamps FIRST
-                                        let sequence_id = SEQUENCE_COUNTER.fetch_add(1, Ordering::SeqCst);
-                                        let audio_start_time = chunk_timestamp; // Already in seconds from recording start
-                                        let audio_end_time = chunk_timestamp + chunk_duration;
-
-                                        // Save structured transcript segment to recording manager (only final results)
-                                        // Save ALL segments (partial and final) to ensure complete JSON
-                                        // Create structured segment with full timestamp data
-                                        // NOTE: This is now handled via the transcript-update event emission below
-                                        // The recording_commands module listens to these events and saves them
-                                        // This decouples the transcription worker from direct RECORDING_MANAGER access
-
-                                        // Emit transcript update with NEW recording-relative timestamps
-
-                                        let update = TranscriptUpdate {
-                                            text: transcript,
-                                            timestamp: format_current_timestamp(), // Wall-clock for reference
-                                            source: "Audio".to_string(),
-                                            sequence_id,
-                                            chunk_start_time: chunk_timestamp, // Legacy compatibility
-                                            is_partial,
-                                            confidence: confidence_opt.unwrap_or(0.85), // Default for providers without confidence
-                                            // NEW: Recording-relative timestamps for sync
-                                            audio_start_time,
-                                            audio_end_time,
-                                            duration: chunk_duration,
+                                        // Attribute the segment to a speaker (embedding runs off the async runtime)
+                                        let speaker = match segment_audio {
+                                            Some(segment) => tokio::task::spawn_blocking(move || {
+                                                crate::diarization::assign_speaker(segment)
+                                            })
+                                            .await
+                                            .ok(),
+                                            None => None,
                                         };
 
-                                        if let Err(e) = app_clone.emit("transcript-update", &update)
-                                        {
-                                            error!(
-                                                "Worker {}: Failed to emit transcript update: {}",
-                                                worker_id, e
-                                            );
+                                        // Recording-relative timestamps; the sequence ID is assigned by the emitter.
+                                        // The recording_commands module listens to the emitted updates and saves them
+                                        let mut update = TranscriptUpdate::for_chunk(
+                                            transcript,
+                                            confidence_opt,
+                                            words,
+                                            chunk_timestamp,
+                                            chunk_duration,
+                                            false, // Complete VAD utterance; partials come from the interim task
+                                        );
+                                        if let Some(speaker) = speaker {
+                                            update.speaker = Some(speaker.source.as_str().to_string());
+                                            update.speaker_id = speaker.speaker_id;
                                         }
-                                        // PERFORMANCE: Removed verbose logging of every emission
+
+                                        result.update = Some(update);
                                     } else if !transcript.trim().is_empty() && should_log_this_chunk
                                     {
                                         // PERFORMANCE: Only log low-confidence results occasionally
@@ -257,6 +440,9 @@ pub fn start_transcription_task<R: Runtime>(
                                 }
                             }
 
+                            // Hand the result to the in-order emitter
+                            drop(result);
+
                             // Mark chunk as completed
                             let completed =
                                 chunks_completed_clone.fetch_add(1, Ordering::SeqCst) + 1;
@@ -320,28 +506,49 @@ pub fn start_transcription_task<R: Runtime>(
             worker_handles.push(worker_handle);
         }
 
+        // Workers hold the remaining senders; the emitter stops once they all exit
+        drop(result_sender);
+
         // Main dispatcher: receive chunks and distribute to workers
         let mut receiver = transcription_receiver;
         while let Some(chunk) = receiver.recv().await {
-            let queued = chunks_queued.fetch_add(1, Ordering::SeqCst) + 1;
+            if chunk.is_partial {
+                if interim_supported {
+                    let _ = interim_sender.send(chunk);
+                }
+                continue;
+            }
+
+            // Released by the emitter once this chunk's result is in order
+            match in_flight.acquire().await {
+                Ok(permit) => permit.forget(),
+                Err(e) => error!("❌ In-flight limiter closed: {}", e),
+            }
+
+            let index = chunks_queued.fetch_add(1, Ordering::SeqCst);
             info!(
                 "📥 Dispatching chunk {} to workers (total queued: {})",
-                chunk.chunk_id, queued
+                chunk.chunk_id,
+                index + 1
             );
 
-            if let Err(_) = work_sender.send(chunk) {
+            if let Err(_) = work_sender.send((index, chunk)) {
                 error!("❌ Failed to send chunk to workers - this should not happen!");
                 break;
             }
         }
 
+        // Partials are pointless once input has finished
+        drop(interim_sender);
+        interim_handle.abort();
+
         // Signal that input is finished
         input_finished.store(true, Ordering::SeqCst);
         drop(work_sender); // Close the channel to signal workers
 
         let total_chunks_queued = chunks_queued.load(Ordering::SeqCst);
         info!("📭 Input finished with {} total chunks queued. Waiting for all {} workers to complete...",
-              total_chunks_queued, NUM_WORKERS);
+              total_chunks_queued, num_workers);
 
         // Emit final chunk count to frontend
         let _ = app.emit("transcription-queue-complete", serde_json::json!({
@@ -358,6 +565,11 @@ pub fn start_transcription_task<R: Runtime>(
             }
         }
 
+        // Wait for the emitter to flush the remaining in-order updates
+        if let Err(e) = emitter_handle.await {
+            error!("❌ Transcript emitter panicked: {:?}", e);
+        }
+
         // Final verification with retry logic to catch any stragglers
         let mut verification_attempts = 0;
         const MAX_VERIFICATION_ATTEMPTS: u32 = 10;
@@ -404,12 +616,12 @@ pub fn start_transcription_task<R: Runtime>(
 }
 
 /// Transcribe audio chunk using the appropriate provider (Whisper, Parakeet, or trait-based)
-/// Returns: (text, confidence Option, is_partial)
-async fn transcribe_chunk_with_provider<R: Runtime>(
+/// Returns: (text, confidence Option, is_partial, chunk-relative word timings)
+pub(crate) async fn transcribe_chunk_with_provider<R: Runtime>(
     engine: &TranscriptionEngine,
     chunk: AudioChunk,
     app: &AppHandle<R>,
-) -> std::result::Result<(String, Option<f32>, bool), TranscriptionError> {
+) -> std::result::Result<(String, Option<f32>, bool, Vec<WordTimestamp>), TranscriptionError> {
     // Convert to 16kHz mono for transcription
     let transcription_data = if chunk.sample_rate != 16000 {
         crate::audio::audio_processing::resample_audio(&chunk.data, chunk.sample_rate, 16000)
@@ -452,10 +664,10 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                 .transcribe_audio_with_confidence(speech_samples, language)
                 .await
             {
-                Ok((text, confidence, is_partial)) => {
+                Ok((text, confidence, is_partial, words)) => {
                     let cleaned_text = text.trim().to_string();
                     if cleaned_text.is_empty() {
-                        return Ok((String::new(), Some(confidence), is_partial));
+                        return Ok((String::new(), Some(confidence), is_partial, Vec::new()));
                     }
 
                     info!(
@@ -463,7 +675,7 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                         chunk.chunk_id, cleaned_text, confidence, is_partial
                     );
 
-                    Ok((cleaned_text, Some(confidence), is_partial))
+                    Ok((cleaned_text, Some(confidence), is_partial, words))
                 }
                 Err(e) => {
                     error!(
@@ -486,11 +698,11 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
             }
         }
         TranscriptionEngine::Parakeet(parakeet_engine) => {
-            match parakeet_engine.transcribe_audio(speech_samples).await {
-                Ok(text) => {
+            match parakeet_engine.transcribe_audio_with_words(speech_samples).await {
+                Ok((text, words)) => {
                     let cleaned_text = text.trim().to_string();
                     if cleaned_text.is_empty() {
-                        return Ok((String::new(), None, false));
+                        return Ok((String::new(), None, false, Vec::new()));
                     }
 
                     info!(
@@ -499,7 +711,7 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                     );
 
                     // Parakeet doesn't provide confidence or partial results
-                    Ok((cleaned_text, None, false))
+                    Ok((cleaned_text, None, false, words))
                 }
                 Err(e) => {
                     error!(
@@ -529,7 +741,7 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                 Ok(result) => {
                     let cleaned_text = result.text.trim().to_string();
                     if cleaned_text.is_empty() {
-                        return Ok((String::new(), result.confidence, result.is_partial));
+                        return Ok((String::new(), result.confidence, result.is_partial, Vec::new()));
                     }
 
                     let confidence_str = match result.confidence {
@@ -546,7 +758,7 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                         result.is_partial
                     );
 
-                    Ok((cleaned_text, result.confidence, result.is_partial))
+                    Ok((cleaned_text, result.confidence, result.is_partial, result.words))
                 }
                 Err(e) => {
                     error!(
@@ -586,11 +798,45 @@ fn format_current_timestamp() -> String {
 }
 
 /// Format recording-relative time as [MM:SS]
-#[allow(dead_code)]
-fn format_recording_time(seconds: f64) -> String {
+pub(crate) fn format_recording_time(seconds: f64) -> String {
     let total_seconds = seconds.floor() as u64;
     let minutes = total_seconds / 60;
     let secs = total_seconds % 60;
 
     format!("[{:02}:{:02}]", minutes, secs)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_update_shifts_words_by_chunk_start() {
+        let words = vec![
+            WordTimestamp {
+                text: "hello".to_string(),
+                start: 0.2,
+                end: 0.6,
+                confidence: Some(0.9),
+            },
+            WordTimestamp {
+                text: "world".to_string(),
+                start: 0.7,
+                end: 1.1,
+                confidence: None,
+            },
+        ];
+
+        let update =
+            TranscriptUpdate::for_chunk("hello world".to_string(), None, words, 125.0, 1.5, false);
+
+        assert_eq!(update.audio_start_time, 125.0);
+        assert_eq!(update.audio_end_time, 126.5);
+        let times: Vec<(f64, f64)> = update.words.iter().map(|w| (w.start, w.end)).collect();
+        assert_eq!(times, [(125.2, 125.6), (125.7, 126.1)]);
+        assert!(update
+            .words
+            .iter()
+            .all(|w| w.start >= update.audio_start_time && w.end <= update.audio_end_time));
+    }
+}
@@ -24,6 +24,8 @@ pub struct ContinuousVadProcessor {
     in_speech: bool,
     processed_samples: usize,
     speech_start_sample: usize,
+    // VAD timestamp of the current utterance's start (for interim transcripts)
+    speech_start_ms: f64,
     // State tracking for smart logging
     last_logged_state: bool,
 }
@@ -77,6 +79,7 @@ impl ContinuousVadProcessor {
             in_speech: false,
             processed_samples: 0,
             speech_start_sample: 0,
+            speech_start_ms: 0.0,
             // Initialize state tracking
             last_logged_state: false,
         })
@@ -159,6 +162,27 @@ impl ContinuousVadProcessor {
         Ok(resampled)
     }
 
+    /// Speech accumulated so far in an utterance that hasn't ended yet (16kHz),
+    /// limited to the most recent `max_samples`. Used for interim transcripts;
+    /// the complete segment is still returned once the utterance ends.
+    pub fn in_progress_speech(&self, max_samples: usize) -> Option<SpeechSegment> {
+        if !self.in_speech || self.current_speech.is_empty() {
+            return None;
+        }
+
+        let skipped = self.current_speech.len().saturating_sub(max_samples);
+        let samples = self.current_speech[skipped..].to_vec();
+        let start_ms = self.speech_start_ms + skipped as f64 / 16.0;
+        let end_ms = start_ms + samples.len() as f64 / 16.0;
+
+        Some(SpeechSegment {
+            samples,
+            start_timestamp_ms: start_ms,
+            end_timestamp_ms: end_ms,
+            confidence: 0.5, // Utterance may still change
+        })
+    }
+
     /// Flush any remaining audio and return final speech segments
     pub fn flush(&mut self) -> Result<Vec<SpeechSegment>> {
         let mut completed_segments = Vec::new();
@@ -217,6 +241,7 @@ impl ContinuousVadProcessor {
                     }
                     self.in_speech = true;
                     self.speech_start_sample = self.processed_samples + (timestamp_ms * self.sample_rate as usize / 1000);
+                    self.speech_start_ms = timestamp_ms as f64;
                     self.current_speech.clear();
                 }
                 VadTransition::SpeechEnd { start_timestamp_ms, end_timestamp_ms, samples } => {
@@ -0,0 +1,298 @@
+use super::context::{
+    build_system_prompt, parse_timestamps, select_context, trim_history, TimestampCitation,
+    ANSWER_RESERVE_TOKENS,
+};
+use crate::api::MeetingTranscript;
+use crate::database::models::{ChatThread, ChatThreadMessage};
+use crate::database::repositories::{
+    chat::ChatRepository, meeting::MeetingsRepository, setting::SettingsRepository,
+};
+use crate::export::{render, ExportDocument, ExportFormat, ExportOptions, ExportSegment};
+use crate::state::AppState;
+use crate::summary::llm_client::{generate_chat, ChatMessage};
+use crate::summary::processor::rough_token_count;
+use crate::summary::SummaryService;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Manager, Runtime};
+
+const DEFAULT_THREAD_TITLE: &str = "New chat";
+
+/// A chat message with its citations decoded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatMessageResponse {
+    pub id: String,
+    pub thread_id: String,
+    pub role: String,
+    pub content: String,
+    pub citations: Vec<TimestampCitation>,
+    pub model_provider: Option<String>,
+    pub model_name: Option<String>,
+    pub created_at: String,
+}
+
+impl From<ChatThreadMessage> for ChatMessageResponse {
+    fn from(message: ChatThreadMessage) -> Self {
+        let citations = message
+            .citations
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default();
+        Self {
+            id: message.id,
+            thread_id: message.thread_id,
+            role: message.role,
+            content: message.content,
+            citations,
+            model_provider: message.model_provider,
+            model_name: message.model_name,
+            created_at: message.created_at,
+        }
+    }
+}
+
+/// The transcript segment playing at `seconds` (markers are floored to the second)
+pub(crate) fn segment_at(transcripts: &[MeetingTranscript], seconds: f64) -> Option<&MeetingTranscript> {
+    transcripts
+        .iter()
+        .filter(|t| {
+            t.audio_start_time
+                .is_some_and(|start| start < seconds + 1.0)
+        })
+        .max_by(|a, b| {
+            a.audio_start_time
+                .partial_cmp(&b.audio_start_time)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+}
+
+#[command]
+pub async fn api_chat_create_thread(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    title: Option<String>,
+) -> Result<ChatThread, String> {
+    log::info!(
+        "api_chat_create_thread called for meeting_id: {}",
+        meeting_id
+    );
+    let pool = state.db_manager.pool();
+
+    MeetingsRepository::get_meeting(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting: {}", e))?
+        .ok_or_else(|| format!("Meeting not found: {}", meeting_id))?;
+
+    let title = title
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| DEFAULT_THREAD_TITLE.to_string());
+    ChatRepository::create_thread(pool, &meeting_id, &title)
+        .await
+        .map_err(|e| format!("Failed to create chat thread: {}", e))
+}
+
+#[command]
+pub async fn api_chat_list_threads(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<ChatThread>, String> {
+    ChatRepository::list_threads(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load chat threads: {}", e))
+}
+
+#[command]
+pub async fn api_chat_get_messages(
+    state: tauri::State<'_, AppState>,
+    thread_id: String,
+) -> Result<Vec<ChatMessageResponse>, String> {
+    let messages = ChatRepository::get_messages(state.db_manager.pool(), &thread_id)
+        .await
+        .map_err(|e| format!("Failed to load chat messages: {}", e))?;
+    Ok(messages
+        .into_iter()
+        .map(ChatMessageResponse::from)
+        .collect())
+}
+
+#[command]
+pub async fn api_chat_rename_thread(
+    state: tauri::State<'_, AppState>,
+    thread_id: String,
+    title: String,
+) -> Result<(), String> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err("Title is empty".to_string());
+    }
+    match ChatRepository::rename_thread(state.db_manager.pool(), &thread_id, title).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(format!("Chat thread not found: {}", thread_id)),
+        Err(e) => Err(format!("Failed to rename chat thread: {}", e)),
+    }
+}
+
+#[command]
+pub async fn api_chat_delete_thread(
+    state: tauri::State<'_, AppState>,
+    thread_id: String,
+) -> Result<(), String> {
+    match ChatRepository::delete_thread(state.db_manager.pool(), &thread_id).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(format!("Chat thread not found: {}", thread_id)),
+        Err(e) => Err(format!("Failed to delete chat thread: {}", e)),
+    }
+}
+
+/// Ask a follow-up question in a thread and return the assistant's reply.
+///
+/// The question is only saved together with the reply, so retrying a failed
+/// request doesn't leave the question in the thread twice.
+/// `model_provider` and `model_name` override the configured model; a
+/// provider other than the configured one needs a model name as well.
+#[command]
+pub async fn api_chat_send_message<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    thread_id: String,
+    message: String,
+    model_provider: Option<String>,
+    model_name: Option<String>,
+) -> Result<ChatMessageResponse, String> {
+    log::info!("api_chat_send_message called for thread_id: {}", thread_id);
+    let message = message.trim();
+    if message.is_empty() {
+        return Err("Message is empty".to_string());
+    }
+
+    let pool = state.db_manager.pool();
+    let thread = ChatRepository::get_thread(pool, &thread_id)
+        .await
+        .map_err(|e| format!("Failed to load chat thread: {}", e))?
+        .ok_or_else(|| format!("Chat thread not found: {}", thread_id))?;
+    let meeting = MeetingsRepository::get_meeting(pool, &thread.meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting: {}", e))?
+        .ok_or_else(|| format!("Meeting not found: {}", thread.meeting_id))?;
+    if meeting.transcripts.is_empty() {
+        return Err("This meeting has no transcript to chat about".to_string());
+    }
+
+    let settings = SettingsRepository::get_model_config(pool)
+        .await
+        .map_err(|e| format!("Failed to load model settings: {}", e))?;
+    let (provider_name, model_name) = match (model_provider, model_name, settings) {
+        (Some(provider), Some(model), _) => (provider, model),
+        (Some(provider), None, Some(settings)) if provider != settings.provider => {
+            return Err(format!("No model given for provider {}", provider))
+        }
+        (provider, model, Some(settings)) => (
+            provider.unwrap_or(settings.provider),
+            model.unwrap_or(settings.model),
+        ),
+        _ => return Err("No summary model configured".to_string()),
+    };
+    let connection = SummaryService::resolve_provider(pool, &provider_name).await?;
+
+    let mut history: Vec<ChatMessage> = ChatRepository::get_messages(pool, &thread_id)
+        .await
+        .map_err(|e| format!("Failed to load chat messages: {}", e))?
+        .into_iter()
+        .map(|m| ChatMessage {
+            role: m.role,
+            content: m.content,
+        })
+        .collect();
+    history.push(ChatMessage::user(message));
+
+    // Split the window: a quarter for history, the rest (less the answer) for transcript
+    let context_limit = SummaryService::context_token_limit(
+        &connection.provider,
+        &model_name,
+        connection.ollama_endpoint.as_deref(),
+    )
+    .await;
+    let answer_reserve = ANSWER_RESERVE_TOKENS.min(context_limit / 4);
+    let history = trim_history(&history, context_limit / 4);
+    let history_tokens: usize = history.iter().map(|m| rough_token_count(&m.content)).sum();
+    let transcript_budget = context_limit.saturating_sub(answer_reserve + history_tokens + 200);
+
+    let document = ExportDocument {
+        meeting_id: meeting.id.clone(),
+        title: meeting.title.clone(),
+        created_at: meeting.created_at.clone(),
+        segments: meeting
+            .transcripts
+            .iter()
+            .map(|t| ExportSegment {
+                start: t.audio_start_time,
+                end: t.audio_end_time,
+                text: t.text.clone(),
+                speaker: t.speaker_label.clone(),
+            })
+            .collect(),
+        summary_markdown: None,
+    };
+    let transcript = render(&document, ExportFormat::Text, &ExportOptions::default());
+    // Recent user turns steer excerpt selection, so follow-ups keep their context
+    let query = history
+        .iter()
+        .filter(|m| m.role == "user")
+        .map(|m| m.content.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let (context, is_excerpt) = select_context(&transcript, &query, transcript_budget);
+    log::info!(
+        "Chat context for meeting {}: {} tokens (limit {}, excerpt: {})",
+        meeting.id,
+        rough_token_count(&context),
+        context_limit,
+        is_excerpt
+    );
+
+    let answer = generate_chat(
+        &reqwest::Client::new(),
+        &connection.provider,
+        &model_name,
+        &connection.api_key,
+        &build_system_prompt(&meeting.title, &context, is_excerpt),
+        history,
+        connection.ollama_endpoint.as_deref(),
+        connection.custom_openai_endpoint.as_deref(),
+        connection.max_tokens,
+        connection.temperature,
+        connection.top_p,
+        app.path().app_data_dir().ok().as_ref(),
+        None,
+    )
+    .await?;
+    let answer = answer.trim();
+
+    let citations: Vec<TimestampCitation> = parse_timestamps(answer)
+        .into_iter()
+        .map(|(label, seconds)| TimestampCitation {
+            segment_id: segment_at(&meeting.transcripts, seconds).map(|t| t.id.clone()),
+            label,
+            seconds,
+        })
+        .collect();
+    let citations_json = serde_json::to_string(&citations)
+        .map_err(|e| format!("Failed to serialize citations: {}", e))?;
+
+    ChatRepository::add_message(pool, &thread_id, "user", message, None, None)
+        .await
+        .map_err(|e| format!("Failed to save message: {}", e))?;
+
+    let reply = ChatRepository::add_message(
+        pool,
+        &thread_id,
+        "assistant",
+        answer,
+        Some(&citations_json),
+        Some((&provider_name, &model_name)),
+    )
+    .await
+    .map_err(|e| format!("Failed to save reply: {}", e))?;
+    Ok(reply.into())
+}
@@ -0,0 +1,228 @@
+// chat/context.rs
+//
+// Builds the transcript context and conversation history sent with each chat
+// turn, and extracts the timestamps an answer cites.
+
+use crate::summary::llm_client::ChatMessage;
+use crate::summary::processor::{chunk_text, rough_token_count};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Tokens kept free for the model's answer
+pub const ANSWER_RESERVE_TOKENS: usize = 1024;
+
+/// Excerpts are never cut below this many tokens, even for tiny models
+const MIN_CONTEXT_TOKENS: usize = 256;
+
+/// A `[mm:ss]` timestamp cited in an answer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimestampCitation {
+    /// The marker as written, e.g. "12:34"
+    pub label: String,
+    /// Seconds from the start of the recording
+    pub seconds: f64,
+    /// Transcript segment playing at that time, if found
+    pub segment_id: Option<String>,
+}
+
+/// System prompt carrying the meeting transcript (or its relevant excerpts)
+pub fn build_system_prompt(meeting_title: &str, transcript: &str, is_excerpt: bool) -> String {
+    let scope = if is_excerpt {
+        "The transcript is long, so only the excerpts most relevant to the conversation are included; gaps are marked [...]."
+    } else {
+        "The full transcript is included."
+    };
+    format!(
+        "You answer follow-up questions about the meeting \"{}\" using its transcript. \
+         Each transcript line starts with its [mm:ss] time from the start of the recording. {} \
+         Cite the time of every line you rely on, e.g. [12:34]. If the transcript doesn't \
+         answer the question, say so instead of guessing.\n\n<transcript>\n{}\n</transcript>",
+        meeting_title,
+        scope,
+        transcript.trim()
+    )
+}
+
+/// The transcript if it fits `token_budget`, otherwise the chunks (from
+/// `chunk_text`) that best match `query`, in transcript order.
+///
+/// Returns the context and whether it was cut down to excerpts.
+pub fn select_context(transcript: &str, query: &str, token_budget: usize) -> (String, bool) {
+    let token_budget = token_budget.max(MIN_CONTEXT_TOKENS);
+    if rough_token_count(transcript) <= token_budget {
+        return (transcript.to_string(), false);
+    }
+
+    let chunk_size = (token_budget / 3).clamp(MIN_CONTEXT_TOKENS / 2, 1500);
+    let chunks = chunk_text(transcript, chunk_size, chunk_size / 10);
+    let terms = query_terms(query);
+
+    let mut ranked: Vec<(usize, usize)> = chunks
+        .iter()
+        .enumerate()
+        .map(|(i, chunk)| (i, relevance(chunk, &terms)))
+        .collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let mut selected = Vec::new();
+    let mut used = 0;
+    for (index, _) in ranked {
+        let tokens = rough_token_count(&chunks[index]);
+        if used + tokens <= token_budget {
+            used += tokens;
+            selected.push(index);
+        }
+    }
+    selected.sort_unstable();
+
+    let excerpts = selected
+        .iter()
+        .map(|&i| chunks[i].trim())
+        .collect::<Vec<_>>()
+        .join("\n[...]\n");
+    (excerpts, true)
+}
+
+/// Lowercased words of 3+ characters, the crude vocabulary used to rank chunks
+fn query_terms(query: &str) -> HashSet<String> {
+    query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn relevance(chunk: &str, terms: &HashSet<String>) -> usize {
+    chunk
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| terms.contains(&word.to_lowercase()))
+        .count()
+}
+
+/// The most recent messages that fit `token_budget`, starting with a user turn.
+///
+/// The latest message is always kept.
+pub fn trim_history(messages: &[ChatMessage], token_budget: usize) -> &[ChatMessage] {
+    let mut start = messages.len().saturating_sub(1);
+    let mut used = messages.last().map_or(0, |m| rough_token_count(&m.content));
+
+    while start > 0 {
+        let tokens = rough_token_count(&messages[start - 1].content);
+        if used + tokens > token_budget {
+            break;
+        }
+        used += tokens;
+        start -= 1;
+    }
+    while start + 1 < messages.len() && messages[start].role != "user" {
+        start += 1;
+    }
+    &messages[start..]
+}
+
+/// `[mm:ss]` / `[h:mm:ss]` markers in an answer, in order of first use.
+///
+/// Brackets may hold several times separated by commas or dashes ("[01:05, 02:10]").
+pub fn parse_timestamps(answer: &str) -> Vec<(String, f64)> {
+    let mut found: Vec<(String, f64)> = Vec::new();
+    let mut rest = answer;
+
+    while let Some(open) = rest.find('[') {
+        rest = &rest[open + 1..];
+        let Some(close) = rest.find(']') else {
+            break;
+        };
+        for part in rest[..close].split([',', ';', '-', '–']) {
+            let label = part.trim();
+            if let Some(seconds) = parse_clock(label) {
+                if !found.iter().any(|(existing, _)| existing == label) {
+                    found.push((label.to_string(), seconds));
+                }
+            }
+        }
+        rest = &rest[close + 1..];
+    }
+    found
+}
+
+/// Seconds from "mm:ss" or "h:mm:ss"
+fn parse_clock(label: &str) -> Option<f64> {
+    let parts: Vec<&str> = label.split(':').collect();
+    if !(2..=3).contains(&parts.len())
+        || parts
+            .iter()
+            .any(|p| p.is_empty() || !p.chars().all(|c| c.is_ascii_digit()))
+    {
+        return None;
+    }
+
+    let values: Vec<u64> = parts.iter().filter_map(|p| p.parse().ok()).collect();
+    let seconds = values.iter().fold(0, |total, v| total * 60 + v);
+    if *values.last()? >= 60 {
+        return None;
+    }
+    Some(seconds as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timestamps() {
+        let answer = "Priya will send the deck [12:34] and review the budget [01:02, 1:05:09]. \
+                      Again at [12:34]; not a time: [1], [99:99], [ab:cd].";
+        assert_eq!(
+            parse_timestamps(answer),
+            vec![
+                ("12:34".to_string(), 754.0),
+                ("01:02".to_string(), 62.0),
+                ("1:05:09".to_string(), 3909.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_select_context_prefers_relevant_chunks() {
+        let short = "[00:01] Me: hello";
+        assert_eq!(
+            select_context(short, "hello", 1000),
+            (short.to_string(), false)
+        );
+
+        let mut lines: Vec<String> = (0..400)
+            .map(|i| {
+                format!(
+                    "[{:02}:{:02}] Speaker 1: small talk about the weather",
+                    i / 60,
+                    i % 60
+                )
+            })
+            .collect();
+        lines[250] = "[04:10] Priya: I commit to shipping the migration guide".to_string();
+        let transcript = lines.join("\n");
+
+        let (context, is_excerpt) = select_context(&transcript, "What did Priya commit to?", 600);
+        assert!(is_excerpt);
+        assert!(context.contains("Priya: I commit to shipping"));
+        assert!(rough_token_count(&context) <= 600 + 10);
+    }
+
+    #[test]
+    fn test_trim_history_keeps_recent_turns_from_a_user_message() {
+        let messages = vec![
+            ChatMessage::user("first question"),
+            ChatMessage::assistant(&"long answer ".repeat(200)),
+            ChatMessage::user("follow up"),
+            ChatMessage::assistant("short answer"),
+            ChatMessage::user("latest"),
+        ];
+
+        let kept = trim_history(&messages, 50);
+        assert_eq!(kept.len(), 3);
+        assert_eq!(kept[0].content, "follow up");
+
+        assert_eq!(trim_history(&messages, 0).len(), 1);
+    }
+}
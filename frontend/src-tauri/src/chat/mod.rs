@@ -0,0 +1,17 @@
+//! Follow-up chat about a single meeting.
+//!
+//! Each meeting can hold several chat threads. Every turn sends the meeting's
+//! transcript (or, when it exceeds the model's context window, the chunks most
+//! relevant to the conversation) together with the thread history to the
+//! configured summary LLM, and the `[mm:ss]` times cited in the answer are
+//! resolved back to transcript segments.
+//!
+//! # Module Structure
+//!
+//! - `context`: Transcript context selection, history trimming and citation parsing
+//! - `commands`: Tauri commands for threads and messages
+
+pub mod commands;
+pub mod context;
+
+pub use context::TimestampCitation;
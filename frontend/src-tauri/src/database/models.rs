@@ -2,6 +2,8 @@ use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::audio::transcription::WordTimestamp;
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct MeetingModel {
     pub id: String,
@@ -35,6 +37,26 @@ pub struct Transcript {
     pub audio_start_time: Option<f64>,
     pub audio_end_time: Option<f64>,
     pub duration: Option<f64>,
+    // JSON array of word timings (see `WordTimestamp`)
+    pub words: Option<String>,
+    // Capture stream ("mic"/"system") and diarized speaker ID
+    pub speaker: Option<String>,
+    pub speaker_id: Option<String>,
+}
+
+impl Transcript {
+    /// Word-level timestamps, if the segment has any
+    pub fn word_timestamps(&self) -> Option<Vec<WordTimestamp>> {
+        serde_json::from_str(self.words.as_deref()?).ok()
+    }
+}
+
+/// Display name for a diarized speaker within one meeting
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Speaker {
+    pub meeting_id: String,
+    pub speaker_id: String,
+    pub label: String,
 }
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
@@ -89,6 +111,9 @@ pub struct Setting {
     #[sqlx(rename = "openRouterApiKey")]
     #[serde(rename = "openRouterApiKey")]
     pub open_router_api_key: Option<String>,
+    #[sqlx(rename = "geminiApiKey")]
+    #[serde(rename = "geminiApiKey")]
+    pub gemini_api_key: Option<String>,
     #[sqlx(rename = "ollamaEndpoint")]
     #[serde(rename = "ollamaEndpoint")]
     pub ollama_endpoint: Option<String>,
@@ -127,4 +152,88 @@ pub struct TranscriptSetting {
     #[sqlx(rename = "openaiApiKey")]
     #[serde(rename = "openaiApiKey")]
     pub openai_api_key: Option<String>,
+    #[sqlx(rename = "openaiCompatibleApiKey")]
+    #[serde(rename = "openaiCompatibleApiKey")]
+    pub openai_compatible_api_key: Option<String>,
+    #[sqlx(rename = "transcriptEndpoint")]
+    #[serde(rename = "transcriptEndpoint")]
+    pub transcript_endpoint: Option<String>,
+}
+
+/// A follow-up conversation about one meeting
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ChatThread {
+    pub id: String,
+    pub meeting_id: String,
+    pub title: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// One turn of a chat thread
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ChatThreadMessage {
+    pub id: String,
+    pub thread_id: String,
+    pub role: String,
+    pub content: String,
+    // JSON array of `TimestampCitation` (assistant messages only)
+    pub citations: Option<String>,
+    pub model_provider: Option<String>,
+    pub model_name: Option<String>,
+    pub created_at: String,
+}
+
+/// A tracked action item, with the title of the meeting it came from
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ActionItem {
+    pub id: String,
+    pub meeting_id: String,
+    pub meeting_title: String,
+    pub task: String,
+    pub owner: Option<String>,
+    pub due_date: Option<String>,
+    // Seconds from recording start where the item was discussed
+    pub source_time: Option<f64>,
+    pub source_segment_id: Option<String>,
+    pub completed: bool,
+    pub completed_at: Option<String>,
+    // Set once the user edits the item, so regenerating the summary keeps it
+    pub edited: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// One recorded version of a meeting's summary
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SummaryVersion {
+    pub id: String,
+    pub meeting_id: String,
+    pub version: i64,
+    // "generated", "edited" or "restored"
+    pub source: String,
+    pub result: String, // JSON, as in summary_processes.result
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub template_id: Option<String>,
+    pub custom_prompt: Option<String>,
+    pub input_tokens: Option<i64>,
+    pub output_tokens: Option<i64>,
+    pub chunk_count: Option<i64>,
+    pub processing_time: Option<f64>,
+    pub restored_from: Option<i64>,
+    pub created_at: String,
+}
+
+/// The user's own notes for a meeting
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct MeetingNotes {
+    pub meeting_id: String,
+    pub notes_markdown: Option<String>,
+    // Editor document, stored as-is for the UI
+    pub notes_json: Option<String>,
+    // JSON array of `NoteAnchor`
+    pub anchors: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
 }
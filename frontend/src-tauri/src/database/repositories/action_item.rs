@@ -0,0 +1,252 @@
+use crate::database::models::ActionItem;
+use chrono::Utc;
+use serde::Deserialize;
+use sqlx::{Connection, Error as SqlxError, SqlitePool};
+use uuid::Uuid;
+
+const DEFAULT_LIMIT: i64 = 200;
+const MAX_LIMIT: i64 = 1000;
+
+const SELECT_ACTION_ITEMS: &str =
+    "SELECT a.id, a.meeting_id, m.title AS meeting_title, a.task, a.owner,
+        a.due_date, a.source_time, a.source_segment_id, a.completed, a.completed_at, a.edited,
+        a.created_at, a.updated_at
+ FROM action_items a
+ JOIN meetings m ON m.id = a.meeting_id";
+
+/// An action item extracted from a summary, ready to be stored
+#[derive(Debug, Clone)]
+pub struct NewActionItem {
+    pub task: String,
+    pub owner: Option<String>,
+    pub due_date: Option<String>,
+    pub source_time: Option<f64>,
+    pub source_segment_id: Option<String>,
+}
+
+/// Optional filters for listing action items across meetings
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionItemFilters {
+    pub meeting_id: Option<String>,
+    /// Case-insensitive substring of the owner
+    pub owner: Option<String>,
+    /// Only open (false) or only completed (true) items
+    pub completed: Option<bool>,
+    /// Case-insensitive substring of the task
+    pub query: Option<String>,
+    /// Earliest meeting date, inclusive ("YYYY-MM-DD", UTC)
+    pub from_date: Option<String>,
+    /// Latest meeting date, inclusive ("YYYY-MM-DD", UTC)
+    pub to_date: Option<String>,
+    pub limit: Option<i64>,
+}
+
+pub struct ActionItemsRepository;
+
+impl ActionItemsRepository {
+    /// Action items matching the filters, open ones first, newest meetings first
+    pub async fn list(
+        pool: &SqlitePool,
+        filters: &ActionItemFilters,
+    ) -> Result<Vec<ActionItem>, SqlxError> {
+        let day = |date: &Option<String>| {
+            date.as_ref()
+                .map(|d| d.chars().take(10).collect::<String>())
+        };
+        let contains = |text: &Option<String>| {
+            text.as_ref()
+                .map(|t| t.trim())
+                .filter(|t| !t.is_empty())
+                .map(|t| format!("%{}%", escape_like(&t.to_lowercase())))
+        };
+        let limit = filters.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+        sqlx::query_as::<_, ActionItem>(&format!(
+            "{}
+             WHERE (? IS NULL OR a.meeting_id = ?)
+               AND (? IS NULL OR lower(a.owner) LIKE ? ESCAPE '\\')
+               AND (? IS NULL OR a.completed = ?)
+               AND (? IS NULL OR lower(a.task) LIKE ? ESCAPE '\\')
+               AND (? IS NULL OR substr(m.created_at, 1, 10) >= ?)
+               AND (? IS NULL OR substr(m.created_at, 1, 10) <= ?)
+             ORDER BY a.completed ASC, m.created_at DESC, a.rowid ASC
+             LIMIT ?",
+            SELECT_ACTION_ITEMS
+        ))
+        .bind(&filters.meeting_id)
+        .bind(&filters.meeting_id)
+        .bind(contains(&filters.owner))
+        .bind(contains(&filters.owner))
+        .bind(filters.completed)
+        .bind(filters.completed)
+        .bind(contains(&filters.query))
+        .bind(contains(&filters.query))
+        .bind(day(&filters.from_date))
+        .bind(day(&filters.from_date))
+        .bind(day(&filters.to_date))
+        .bind(day(&filters.to_date))
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get(pool: &SqlitePool, id: &str) -> Result<Option<ActionItem>, SqlxError> {
+        sqlx::query_as::<_, ActionItem>(&format!("{} WHERE a.id = ?", SELECT_ACTION_ITEMS))
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Replace a meeting's extracted items after (re)summarization.
+    ///
+    /// Items the user completed or edited are kept, and new items whose task
+    /// matches a kept one are skipped. Returns the number of items inserted.
+    pub async fn replace_extracted(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        items: &[NewActionItem],
+    ) -> Result<usize, SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        sqlx::query(
+            "DELETE FROM action_items WHERE meeting_id = ? AND completed = 0 AND edited = 0",
+        )
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+        let mut known: Vec<String> = sqlx::query_scalar::<_, String>(
+            "SELECT lower(task) FROM action_items WHERE meeting_id = ?",
+        )
+        .bind(meeting_id)
+        .fetch_all(&mut *transaction)
+        .await?;
+
+        let now = Utc::now().to_rfc3339();
+        let mut inserted = 0;
+        for item in items {
+            let key = item.task.to_lowercase();
+            if known.contains(&key) {
+                continue;
+            }
+            sqlx::query(
+                "INSERT INTO action_items
+                     (id, meeting_id, task, owner, due_date, source_time, source_segment_id,
+                      completed, edited, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, 0, 0, ?, ?)",
+            )
+            .bind(format!("action-{}", Uuid::new_v4()))
+            .bind(meeting_id)
+            .bind(&item.task)
+            .bind(&item.owner)
+            .bind(&item.due_date)
+            .bind(item.source_time)
+            .bind(&item.source_segment_id)
+            .bind(&now)
+            .bind(&now)
+            .execute(&mut *transaction)
+            .await?;
+            known.push(key);
+            inserted += 1;
+        }
+
+        transaction.commit().await?;
+        Ok(inserted)
+    }
+
+    /// Mark an item done or reopen it; returns false if it doesn't exist
+    pub async fn set_completed(
+        pool: &SqlitePool,
+        id: &str,
+        completed: bool,
+    ) -> Result<bool, SqlxError> {
+        let now = Utc::now().to_rfc3339();
+        let result = sqlx::query(
+            "UPDATE action_items SET completed = ?, completed_at = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(completed)
+        .bind(completed.then_some(&now))
+        .bind(&now)
+        .bind(id)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Overwrite an item's task, owner and due date; returns false if it doesn't exist
+    pub async fn update(
+        pool: &SqlitePool,
+        id: &str,
+        task: &str,
+        owner: Option<&str>,
+        due_date: Option<&str>,
+    ) -> Result<bool, SqlxError> {
+        let result = sqlx::query(
+            "UPDATE action_items SET task = ?, owner = ?, due_date = ?, edited = 1, updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(task)
+        .bind(owner)
+        .bind(due_date)
+        .bind(Utc::now().to_rfc3339())
+        .bind(id)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, SqlxError> {
+        let result = sqlx::query("DELETE FROM action_items WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Open items from meetings held before `meeting_id` that share a named
+    /// participant (`participants`, lowercased) or start with `title_prefix`
+    pub async fn open_items_from_related(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        participants: &[String],
+        title_prefix: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<ActionItem>, SqlxError> {
+        if participants.is_empty() && title_prefix.is_none() {
+            return Ok(Vec::new());
+        }
+        let participants_json =
+            serde_json::to_string(participants).unwrap_or_else(|_| "[]".to_string());
+        let title_pattern = title_prefix.map(|p| format!("{}%", escape_like(p)));
+
+        sqlx::query_as::<_, ActionItem>(&format!(
+            "{}
+             WHERE a.completed = 0
+               AND a.meeting_id != ?
+               AND m.created_at < (SELECT created_at FROM meetings WHERE id = ?)
+               AND ((? IS NOT NULL AND lower(m.title) LIKE ? ESCAPE '\\')
+                    OR EXISTS (SELECT 1 FROM speakers s
+                               WHERE s.meeting_id = a.meeting_id
+                                 AND lower(s.label) IN (SELECT value FROM json_each(?))))
+             ORDER BY m.created_at DESC, a.rowid ASC
+             LIMIT ?",
+            SELECT_ACTION_ITEMS
+        ))
+        .bind(meeting_id)
+        .bind(meeting_id)
+        .bind(&title_pattern)
+        .bind(&title_pattern)
+        .bind(participants_json)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+    }
+}
+
+/// Escape `%`, `_` and `\` for a LIKE pattern using `ESCAPE '\'`
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
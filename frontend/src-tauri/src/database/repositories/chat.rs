@@ -0,0 +1,161 @@
+use crate::database::models::{ChatThread, ChatThreadMessage};
+use chrono::Utc;
+use sqlx::{Connection, Error as SqlxError, SqlitePool};
+use uuid::Uuid;
+
+pub struct ChatRepository;
+
+impl ChatRepository {
+    pub async fn create_thread(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        title: &str,
+    ) -> Result<ChatThread, SqlxError> {
+        let now = Utc::now().to_rfc3339();
+        let thread = ChatThread {
+            id: format!("chat-{}", Uuid::new_v4()),
+            meeting_id: meeting_id.to_string(),
+            title: title.to_string(),
+            created_at: now.clone(),
+            updated_at: now,
+        };
+
+        sqlx::query(
+            "INSERT INTO chat_threads (id, meeting_id, title, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(&thread.id)
+        .bind(&thread.meeting_id)
+        .bind(&thread.title)
+        .bind(&thread.created_at)
+        .bind(&thread.updated_at)
+        .execute(pool)
+        .await?;
+
+        Ok(thread)
+    }
+
+    /// Threads of a meeting, most recently active first
+    pub async fn list_threads(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<ChatThread>, SqlxError> {
+        sqlx::query_as::<_, ChatThread>(
+            "SELECT id, meeting_id, title, created_at, updated_at FROM chat_threads
+             WHERE meeting_id = ?
+             ORDER BY updated_at DESC",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get_thread(
+        pool: &SqlitePool,
+        thread_id: &str,
+    ) -> Result<Option<ChatThread>, SqlxError> {
+        sqlx::query_as::<_, ChatThread>(
+            "SELECT id, meeting_id, title, created_at, updated_at FROM chat_threads WHERE id = ?",
+        )
+        .bind(thread_id)
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Messages of a thread in conversation order
+    pub async fn get_messages(
+        pool: &SqlitePool,
+        thread_id: &str,
+    ) -> Result<Vec<ChatThreadMessage>, SqlxError> {
+        sqlx::query_as::<_, ChatThreadMessage>(
+            "SELECT id, thread_id, role, content, citations, model_provider, model_name, created_at
+             FROM chat_messages
+             WHERE thread_id = ?
+             ORDER BY created_at ASC, rowid ASC",
+        )
+        .bind(thread_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Append a message and mark the thread as updated
+    pub async fn add_message(
+        pool: &SqlitePool,
+        thread_id: &str,
+        role: &str,
+        content: &str,
+        citations: Option<&str>,
+        model: Option<(&str, &str)>,
+    ) -> Result<ChatThreadMessage, SqlxError> {
+        let message = ChatThreadMessage {
+            id: format!("msg-{}", Uuid::new_v4()),
+            thread_id: thread_id.to_string(),
+            role: role.to_string(),
+            content: content.to_string(),
+            citations: citations.map(str::to_string),
+            model_provider: model.map(|(provider, _)| provider.to_string()),
+            model_name: model.map(|(_, name)| name.to_string()),
+            created_at: Utc::now().to_rfc3339(),
+        };
+
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        sqlx::query(
+            "INSERT INTO chat_messages
+                 (id, thread_id, role, content, citations, model_provider, model_name, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&message.id)
+        .bind(&message.thread_id)
+        .bind(&message.role)
+        .bind(&message.content)
+        .bind(&message.citations)
+        .bind(&message.model_provider)
+        .bind(&message.model_name)
+        .bind(&message.created_at)
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query("UPDATE chat_threads SET updated_at = ? WHERE id = ?")
+            .bind(&message.created_at)
+            .bind(thread_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        Ok(message)
+    }
+
+    /// Rename a thread; returns false if it doesn't exist
+    pub async fn rename_thread(
+        pool: &SqlitePool,
+        thread_id: &str,
+        title: &str,
+    ) -> Result<bool, SqlxError> {
+        let result = sqlx::query("UPDATE chat_threads SET title = ? WHERE id = ?")
+            .bind(title)
+            .bind(thread_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete a thread and its messages; returns false if it doesn't exist
+    pub async fn delete_thread(pool: &SqlitePool, thread_id: &str) -> Result<bool, SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        sqlx::query("DELETE FROM chat_messages WHERE thread_id = ?")
+            .bind(thread_id)
+            .execute(&mut *transaction)
+            .await?;
+        let result = sqlx::query("DELETE FROM chat_threads WHERE id = ?")
+            .bind(thread_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,165 @@
+use chrono::Utc;
+use sqlx::{Connection, Error as SqlxError, SqlitePool};
+
+/// Transcript segment that has no embedding for a model yet
+#[derive(Debug, sqlx::FromRow)]
+pub struct PendingTranscript {
+    pub id: String,
+    pub meeting_id: String,
+    pub transcript: String,
+}
+
+/// Summary result that has no embeddings for a model yet
+#[derive(Debug, sqlx::FromRow)]
+pub struct PendingSummary {
+    pub meeting_id: String,
+    pub result: String,
+}
+
+/// Stored embedding with the meeting and segment details needed for citations
+#[derive(Debug, sqlx::FromRow)]
+pub struct EmbeddingRow {
+    pub source: String,
+    pub source_id: String,
+    pub meeting_id: String,
+    pub title: String,
+    pub created_at: String,
+    pub content: String,
+    /// Little-endian f32 values
+    pub vector: Vec<u8>,
+    pub segment_timestamp: Option<String>,
+    pub audio_start_time: Option<f64>,
+}
+
+pub struct EmbeddingsRepository;
+
+impl EmbeddingsRepository {
+    /// Non-empty transcript segments without an embedding for `model`
+    pub async fn pending_transcripts(
+        pool: &SqlitePool,
+        model: &str,
+        meeting_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<PendingTranscript>, SqlxError> {
+        sqlx::query_as::<_, PendingTranscript>(
+            "SELECT t.id, t.meeting_id, t.transcript
+             FROM transcripts t
+             WHERE length(trim(t.transcript)) > 0
+               AND (? IS NULL OR t.meeting_id = ?)
+               AND NOT EXISTS (
+                   SELECT 1 FROM embeddings e
+                   WHERE e.source = 'transcript' AND e.source_id = t.id AND e.model = ?
+               )
+             LIMIT ?",
+        )
+        .bind(meeting_id)
+        .bind(meeting_id)
+        .bind(model)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Completed summaries without embeddings for `model`
+    pub async fn pending_summaries(
+        pool: &SqlitePool,
+        model: &str,
+        meeting_id: Option<&str>,
+    ) -> Result<Vec<PendingSummary>, SqlxError> {
+        sqlx::query_as::<_, PendingSummary>(
+            "SELECT s.meeting_id, s.result
+             FROM summary_processes s
+             WHERE s.result IS NOT NULL
+               AND (? IS NULL OR s.meeting_id = ?)
+               AND NOT EXISTS (
+                   SELECT 1 FROM embeddings e
+                   WHERE e.source = 'summary' AND e.source_id = s.meeting_id AND e.model = ?
+               )",
+        )
+        .bind(meeting_id)
+        .bind(meeting_id)
+        .bind(model)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Store the embedded passages of one source, replacing any previous ones
+    pub async fn save_embeddings(
+        pool: &SqlitePool,
+        model: &str,
+        source: &str,
+        source_id: &str,
+        meeting_id: &str,
+        passages: &[(String, Vec<u8>)],
+    ) -> Result<(), SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query("DELETE FROM embeddings WHERE source = ? AND source_id = ? AND model = ?")
+            .bind(source)
+            .bind(source_id)
+            .bind(model)
+            .execute(&mut *transaction)
+            .await?;
+
+        for (passage, (content, vector)) in passages.iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO embeddings
+                     (source, source_id, passage, model, meeting_id, content, vector, created_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(source)
+            .bind(source_id)
+            .bind(passage as i64)
+            .bind(model)
+            .bind(meeting_id)
+            .bind(content)
+            .bind(vector)
+            .bind(&now)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// All embeddings for `model`, optionally limited to one meeting
+    pub async fn load_embeddings(
+        pool: &SqlitePool,
+        model: &str,
+        meeting_id: Option<&str>,
+    ) -> Result<Vec<EmbeddingRow>, SqlxError> {
+        sqlx::query_as::<_, EmbeddingRow>(
+            "SELECT e.source, e.source_id, e.meeting_id, m.title, m.created_at, e.content, e.vector,
+                    t.timestamp AS segment_timestamp, t.audio_start_time
+             FROM embeddings e
+             JOIN meetings m ON m.id = e.meeting_id
+             LEFT JOIN transcripts t ON e.source = 'transcript' AND t.id = e.source_id
+             WHERE e.model = ? AND (? IS NULL OR e.meeting_id = ?)",
+        )
+        .bind(model)
+        .bind(meeting_id)
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Number of stored embeddings for `model`
+    pub async fn count(pool: &SqlitePool, model: &str) -> Result<i64, SqlxError> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM embeddings WHERE model = ?")
+            .bind(model)
+            .fetch_one(pool)
+            .await
+    }
+
+    /// Drop every embedding for `model` so the next sync rebuilds it
+    pub async fn clear_model(pool: &SqlitePool, model: &str) -> Result<u64, SqlxError> {
+        let result = sqlx::query("DELETE FROM embeddings WHERE model = ?")
+            .bind(model)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
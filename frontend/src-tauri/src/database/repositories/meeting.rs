@@ -1,5 +1,6 @@
 use crate::api::{MeetingDetails, MeetingTranscript};
 use crate::database::models::{MeetingModel, Transcript};
+use crate::database::repositories::speaker::SpeakersRepository;
 use chrono::Utc;
 use sqlx::{Connection, Error as SqlxError, SqliteConnection, SqlitePool};
 use tracing::{error, info};
@@ -79,6 +80,8 @@ impl MeetingsRepository {
                     .bind(meeting_id)
                     .fetch_all(&mut *transaction)
                     .await?;
+            let speaker_labels =
+                SpeakersRepository::get_labels(&mut *transaction, meeting_id).await?;
 
             transaction.commit().await?;
 
@@ -86,12 +89,19 @@ impl MeetingsRepository {
             let meeting_transcripts = transcripts
                 .into_iter()
                 .map(|t| MeetingTranscript {
+                    words: t.word_timestamps(),
+                    speaker_label: t
+                        .speaker_id
+                        .as_deref()
+                        .map(|id| SpeakersRepository::label_for(&speaker_labels, id)),
                     id: t.id,
                     text: t.transcript,
                     timestamp: t.timestamp,
                     audio_start_time: t.audio_start_time,
                     audio_end_time: t.audio_end_time,
                     duration: t.duration,
+                    speaker: t.speaker,
+                    speaker_id: t.speaker_id,
                 })
                 .collect::<Vec<_>>();
 
@@ -264,7 +274,43 @@ async fn delete_meeting_with_transaction(
         .execute(&mut *transaction)
         .await?;
 
-    // 4. Finally, delete the meeting
+    // 4. Delete speaker names
+    sqlx::query("DELETE FROM speakers WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 5. Delete chat threads and their messages
+    sqlx::query(
+        "DELETE FROM chat_messages WHERE thread_id IN (SELECT id FROM chat_threads WHERE meeting_id = ?)",
+    )
+    .bind(meeting_id)
+    .execute(&mut *transaction)
+    .await?;
+    sqlx::query("DELETE FROM chat_threads WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 6. Delete action items
+    sqlx::query("DELETE FROM action_items WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 7. Delete summary history
+    sqlx::query("DELETE FROM summary_versions WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 8. Delete the user's notes
+    sqlx::query("DELETE FROM meeting_notes WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 9. Finally, delete the meeting
     let result = sqlx::query("DELETE FROM meetings WHERE id = ?")
         .bind(meeting_id)
         .execute(&mut *transaction)
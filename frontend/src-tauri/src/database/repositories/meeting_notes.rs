@@ -0,0 +1,61 @@
+use crate::database::models::MeetingNotes;
+use chrono::Utc;
+use sqlx::{Error as SqlxError, SqlitePool};
+
+pub struct MeetingNotesRepository;
+
+impl MeetingNotesRepository {
+    pub async fn get(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Option<MeetingNotes>, SqlxError> {
+        sqlx::query_as::<_, MeetingNotes>(
+            "SELECT meeting_id, notes_markdown, notes_json, anchors, created_at, updated_at
+             FROM meeting_notes WHERE meeting_id = ?",
+        )
+        .bind(meeting_id)
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Insert or replace a meeting's notes, keeping the original creation time
+    pub async fn upsert(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        notes_markdown: Option<&str>,
+        notes_json: Option<&str>,
+        anchors: Option<&str>,
+    ) -> Result<MeetingNotes, SqlxError> {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(
+            "INSERT INTO meeting_notes
+                 (meeting_id, notes_markdown, notes_json, anchors, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?)
+             ON CONFLICT(meeting_id) DO UPDATE SET
+                 notes_markdown = excluded.notes_markdown,
+                 notes_json = excluded.notes_json,
+                 anchors = excluded.anchors,
+                 updated_at = excluded.updated_at",
+        )
+        .bind(meeting_id)
+        .bind(notes_markdown)
+        .bind(notes_json)
+        .bind(anchors)
+        .bind(&now)
+        .bind(&now)
+        .execute(pool)
+        .await?;
+
+        Self::get(pool, meeting_id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)
+    }
+
+    pub async fn delete(pool: &SqlitePool, meeting_id: &str) -> Result<bool, SqlxError> {
+        let result = sqlx::query("DELETE FROM meeting_notes WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
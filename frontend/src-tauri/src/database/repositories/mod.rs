@@ -1,5 +1,12 @@
+pub mod action_item;
+pub mod chat;
+pub mod embedding;
 pub mod meeting;
+pub mod meeting_notes;
+pub mod search;
 pub mod setting;
+pub mod speaker;
 pub mod summary;
+pub mod summary_version;
 pub mod transcript;
 pub mod transcript_chunk;
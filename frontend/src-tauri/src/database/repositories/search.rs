@@ -0,0 +1,376 @@
+use crate::api::TranscriptSearchResult;
+use serde::Deserialize;
+use sqlx::{Error as SqlxError, SqlitePool};
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 500;
+
+/// Optional filters for full-text search
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchFilters {
+    /// Only search within this meeting
+    pub meeting_id: Option<String>,
+    /// Earliest meeting date, inclusive ("YYYY-MM-DD", UTC)
+    pub from_date: Option<String>,
+    /// Latest meeting date, inclusive ("YYYY-MM-DD", UTC)
+    pub to_date: Option<String>,
+    /// Limit to "transcript", "summary" or "notes"
+    pub source: Option<String>,
+    /// Return only the best match of each meeting (e.g. to filter a meeting list)
+    #[serde(default)]
+    pub per_meeting: bool,
+    /// Defaults to 50 results, or to the maximum with `per_meeting`
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct SearchRow {
+    source: String,
+    source_id: String,
+    meeting_id: String,
+    title: String,
+    created_at: String,
+    match_context: String,
+    highlight: String,
+    score: f64,
+    segment_timestamp: Option<String>,
+    audio_start_time: Option<f64>,
+}
+
+pub struct SearchRepository;
+
+impl SearchRepository {
+    /// Search transcripts, summaries and notes, best (BM25) matches first.
+    ///
+    /// Supports "quoted phrases", prefix* terms, and AND / OR / NOT (or -term).
+    pub async fn search(
+        pool: &SqlitePool,
+        query: &str,
+        filters: &SearchFilters,
+    ) -> Result<Vec<TranscriptSearchResult>, SqlxError> {
+        let Some(match_query) = build_match_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        let day = |date: &Option<String>| {
+            date.as_ref()
+                .map(|d| d.chars().take(10).collect::<String>())
+        };
+        let default_limit = if filters.per_meeting {
+            MAX_LIMIT
+        } else {
+            DEFAULT_LIMIT
+        };
+        let limit = filters.limit.unwrap_or(default_limit).clamp(1, MAX_LIMIT);
+
+        let rows = sqlx::query_as::<_, SearchRow>(
+            "WITH matches AS (
+                 SELECT d.source, d.source_id, d.meeting_id, m.title, m.created_at,
+                        snippet(search_fts, 0, '', '', '...', 24) AS match_context,
+                        snippet(search_fts, 0, '<mark>', '</mark>', '...', 24) AS highlight,
+                        bm25(search_fts) AS score,
+                        t.timestamp AS segment_timestamp,
+                        t.audio_start_time
+                 FROM search_fts
+                 JOIN search_documents d ON d.id = search_fts.rowid
+                 JOIN meetings m ON m.id = d.meeting_id
+                 LEFT JOIN transcripts t ON d.source = 'transcript' AND t.id = d.source_id
+                 WHERE search_fts MATCH ?
+                   AND (? IS NULL OR d.meeting_id = ?)
+                   AND (? IS NULL OR substr(m.created_at, 1, 10) >= ?)
+                   AND (? IS NULL OR substr(m.created_at, 1, 10) <= ?)
+                   AND (? IS NULL OR d.source = ?)
+             )
+             SELECT source, source_id, meeting_id, title, created_at, match_context,
+                    highlight, score, segment_timestamp, audio_start_time
+             FROM (SELECT *, ROW_NUMBER() OVER (PARTITION BY meeting_id ORDER BY score)
+                             AS meeting_rank
+                   FROM matches)
+             WHERE NOT ? OR meeting_rank = 1
+             ORDER BY score
+             LIMIT ?",
+        )
+        .bind(&match_query)
+        .bind(&filters.meeting_id)
+        .bind(&filters.meeting_id)
+        .bind(day(&filters.from_date))
+        .bind(day(&filters.from_date))
+        .bind(day(&filters.to_date))
+        .bind(day(&filters.to_date))
+        .bind(&filters.source)
+        .bind(&filters.source)
+        .bind(filters.per_meeting)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let is_transcript = row.source == "transcript";
+                TranscriptSearchResult {
+                    id: row.meeting_id,
+                    title: row.title,
+                    match_context: row.match_context,
+                    timestamp: row.segment_timestamp.unwrap_or(row.created_at),
+                    source: row.source,
+                    highlight: row.highlight,
+                    segment_id: is_transcript.then_some(row.source_id),
+                    audio_start_time: row.audio_start_time,
+                    rank: row.score,
+                }
+            })
+            .collect())
+    }
+}
+
+/// Convert user input into a safe FTS5 MATCH expression.
+///
+/// Terms are quoted so punctuation can't cause syntax errors; `"phrases"`,
+/// `term*` prefixes and uppercase AND / OR / NOT operators are kept, and
+/// `-term` means NOT. Returns None when nothing searchable is left.
+pub fn build_match_query(input: &str) -> Option<String> {
+    let mut parts: Vec<String> = Vec::new();
+    let mut pending_operator: Option<&str> = None;
+
+    for token in tokenize(input) {
+        match token {
+            Token::Operator(operator) => {
+                if !parts.is_empty() {
+                    pending_operator = Some(operator);
+                }
+            }
+            Token::Phrase(text) => {
+                if !is_searchable(&text) {
+                    continue;
+                }
+                push_term(&mut parts, pending_operator.take(), quote(&text));
+            }
+            Token::Word(word) => {
+                let (negated, word) = match word.strip_prefix('-') {
+                    Some(rest) => (true, rest),
+                    None => (false, word.as_str()),
+                };
+                let (prefix, word) = match word.strip_suffix('*') {
+                    Some(rest) => (true, rest),
+                    None => (false, word),
+                };
+                if !is_searchable(word) {
+                    continue;
+                }
+
+                let term = if prefix {
+                    format!("{}*", quote(word))
+                } else {
+                    quote(word)
+                };
+                if negated {
+                    // A leading NOT has nothing to subtract from
+                    pending_operator = None;
+                    if !parts.is_empty() {
+                        push_term(&mut parts, Some("NOT"), term);
+                    }
+                } else {
+                    push_term(&mut parts, pending_operator.take(), term);
+                }
+            }
+        }
+    }
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" "))
+    }
+}
+
+fn push_term(parts: &mut Vec<String>, operator: Option<&str>, term: String) {
+    if !parts.is_empty() {
+        if let Some(operator) = operator {
+            parts.push(operator.to_string());
+        }
+    }
+    parts.push(term);
+}
+
+enum Token {
+    Word(String),
+    Phrase(String),
+    Operator(&'static str),
+}
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() || c == '(' || c == ')' {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let phrase: String = chars.by_ref().take_while(|&c| c != '"').collect();
+            tokens.push(Token::Phrase(phrase));
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' || c == '(' || c == ')' {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(match word.as_str() {
+                "AND" => Token::Operator("AND"),
+                "OR" => Token::Operator("OR"),
+                "NOT" => Token::Operator("NOT"),
+                _ => Token::Word(word),
+            });
+        }
+    }
+
+    tokens
+}
+
+fn is_searchable(text: &str) -> bool {
+    text.chars().any(char::is_alphanumeric)
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.trim().replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn test_pool() -> SqlitePool {
+        // One connection: every connection to :memory: is a separate database
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        pool
+    }
+
+    async fn insert_transcript(pool: &SqlitePool, id: &str, meeting_id: &str, text: &str) {
+        sqlx::query("INSERT OR IGNORE INTO meetings (id, title, created_at, updated_at) VALUES (?, ?, '2026-01-01', '2026-01-01')")
+            .bind(meeting_id)
+            .bind(format!("Meeting {}", meeting_id))
+            .execute(pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO transcripts (id, meeting_id, transcript, timestamp) VALUES (?, ?, ?, '00:00')")
+            .bind(id)
+            .bind(meeting_id)
+            .bind(text)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    async fn search_ids(pool: &SqlitePool, query: &str) -> Vec<Option<String>> {
+        SearchRepository::search(pool, query, &SearchFilters::default())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|result| result.segment_id)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_triggers_keep_transcripts_indexed() {
+        let pool = test_pool().await;
+        insert_transcript(&pool, "t1", "m1", "Budget review for the quarter").await;
+        assert_eq!(
+            search_ids(&pool, "budget").await,
+            vec![Some("t1".to_string())]
+        );
+
+        sqlx::query("UPDATE transcripts SET transcript = 'Hiring plan' WHERE id = 't1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(search_ids(&pool, "budget").await.is_empty());
+        assert_eq!(
+            search_ids(&pool, "hiring").await,
+            vec![Some("t1".to_string())]
+        );
+
+        sqlx::query("DELETE FROM transcripts WHERE id = 't1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(search_ids(&pool, "hiring").await.is_empty());
+        let documents: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM search_documents")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(documents, 0);
+    }
+
+    #[tokio::test]
+    async fn test_per_meeting_search_returns_every_meeting_once() {
+        let pool = test_pool().await;
+        for meeting in 0..3 {
+            for segment in 0..(DEFAULT_LIMIT as usize) {
+                let id = format!("t{}-{}", meeting, segment);
+                insert_transcript(&pool, &id, &format!("m{}", meeting), "budget review").await;
+            }
+        }
+
+        let all = SearchRepository::search(&pool, "budget", &SearchFilters::default())
+            .await
+            .unwrap();
+        assert_eq!(all.len(), DEFAULT_LIMIT as usize);
+
+        let filters = SearchFilters {
+            per_meeting: true,
+            ..Default::default()
+        };
+        let mut meetings: Vec<String> = SearchRepository::search(&pool, "budget", &filters)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|result| result.id)
+            .collect();
+        meetings.sort();
+        assert_eq!(meetings, vec!["m0", "m1", "m2"]);
+    }
+
+    #[test]
+    fn test_build_match_query() {
+        assert_eq!(
+            build_match_query("budget review").as_deref(),
+            Some(r#""budget" "review""#)
+        );
+        assert_eq!(
+            build_match_query(r#""quarterly budget" OR forecast*"#).as_deref(),
+            Some(r#""quarterly budget" OR "forecast"*"#)
+        );
+        assert_eq!(
+            build_match_query("hiring -intern").as_deref(),
+            Some(r#""hiring" NOT "intern""#)
+        );
+        assert_eq!(
+            build_match_query("don't (c++)").as_deref(),
+            Some(r#""don't" "c++""#)
+        );
+    }
+
+    #[test]
+    fn test_build_match_query_drops_dangling_operators() {
+        assert_eq!(
+            build_match_query("AND budget OR").as_deref(),
+            Some(r#""budget""#)
+        );
+        assert_eq!(
+            build_match_query("-intern hiring").as_deref(),
+            Some(r#""hiring""#)
+        );
+        assert_eq!(build_match_query("  ?? \"\" "), None);
+    }
+}
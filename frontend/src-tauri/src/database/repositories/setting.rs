@@ -1,4 +1,5 @@
 use crate::database::models::{Setting, TranscriptSetting};
+use crate::summary::fallback::FallbackTarget;
 use crate::summary::CustomOpenAIConfig;
 use sqlx::SqlitePool;
 
@@ -24,8 +25,8 @@ pub struct SaveTranscriptConfigRequest {
 
 pub struct SettingsRepository;
 
-// Transcript providers: localWhisper, deepgram, elevenLabs, groq, openai
-// Summary providers: openai, claude, ollama, groq, added openrouter
+// Transcript providers: localWhisper, parakeet, deepgram, elevenLabs, groq, openai, openaiCompatible
+// Summary providers: openai, claude, ollama, groq, added openrouter, gemini
 // NOTE: Handle data exclusion in the higher layer as this is database abstraction layer(using SELECT *)
 
 impl SettingsRepository {
@@ -85,6 +86,7 @@ impl SettingsRepository {
             "ollama" => "ollamaApiKey",
             "groq" => "groqApiKey",
             "openrouter" => "openRouterApiKey",
+            "gemini" => "geminiApiKey",
             "builtin-ai" => return Ok(()), // No API key needed
             _ => {
                 return Err(sqlx::Error::Protocol(
@@ -123,6 +125,7 @@ impl SettingsRepository {
             "groq" => "groqApiKey",
             "claude" => "anthropicApiKey",
             "openrouter" => "openRouterApiKey",
+            "gemini" => "geminiApiKey",
             "builtin-ai" => return Ok(None), // No API key needed
             _ => {
                 return Err(sqlx::Error::Protocol(
@@ -172,6 +175,26 @@ impl SettingsRepository {
         Ok(())
     }
 
+    /// API root for the `openaiCompatible` transcript provider (e.g. "http://localhost:8000/v1")
+    pub async fn save_transcript_endpoint(
+        pool: &SqlitePool,
+        endpoint: Option<&str>,
+    ) -> std::result::Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO transcript_settings (id, provider, model, transcriptEndpoint)
+            VALUES ('1', 'parakeet', 'parakeet-tdt-0.6b-v3-int8', $1)
+            ON CONFLICT(id) DO UPDATE SET
+                transcriptEndpoint = $1
+            "#,
+        )
+        .bind(endpoint)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn save_transcript_api_key(
         pool: &SqlitePool,
         provider: &str,
@@ -184,6 +207,7 @@ impl SettingsRepository {
             "elevenLabs" => "elevenLabsApiKey",
             "groq" => "groqApiKey",
             "openai" => "openaiApiKey",
+            "openaiCompatible" => "openaiCompatibleApiKey",
             _ => {
                 return Err(sqlx::Error::Protocol(
                     format!("Invalid provider: {}", provider).into(),
@@ -216,6 +240,7 @@ impl SettingsRepository {
             "elevenLabs" => "elevenLabsApiKey",
             "groq" => "groqApiKey",
             "openai" => "openaiApiKey",
+            "openaiCompatible" => "openaiCompatibleApiKey",
             _ => {
                 return Err(sqlx::Error::Protocol(
                     format!("Invalid provider: {}", provider).into(),
@@ -249,6 +274,7 @@ impl SettingsRepository {
             "groq" => "groqApiKey",
             "claude" => "anthropicApiKey",
             "openrouter" => "openRouterApiKey",
+            "gemini" => "geminiApiKey",
             "builtin-ai" => return Ok(()), // No API key needed
             _ => {
                 return Err(sqlx::Error::Protocol(
@@ -345,4 +371,47 @@ impl SettingsRepository {
 
         Ok(())
     }
+
+    // ===== SUMMARY FALLBACK CHAIN METHODS =====
+
+    /// Gets the ordered providers to fall back to when summarization fails
+    ///
+    /// Returns an empty chain when none is configured.
+    pub async fn get_summary_fallback_chain(
+        pool: &SqlitePool,
+    ) -> std::result::Result<Vec<FallbackTarget>, sqlx::Error> {
+        let chain_json: Option<String> = sqlx::query_scalar(
+            "SELECT summaryFallbackChain FROM settings WHERE id = '1' LIMIT 1",
+        )
+        .fetch_optional(pool)
+        .await?
+        .flatten();
+
+        match chain_json {
+            Some(json) => serde_json::from_str(&json).map_err(|e| {
+                sqlx::Error::Protocol(format!("Invalid JSON in summaryFallbackChain: {}", e).into())
+            }),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Saves the fallback chain as JSON
+    ///
+    /// # Returns
+    /// * `Ok(true)` - Chain saved
+    /// * `Ok(false)` - No settings row yet (no summary model configured)
+    pub async fn save_summary_fallback_chain(
+        pool: &SqlitePool,
+        chain: &[FallbackTarget],
+    ) -> std::result::Result<bool, sqlx::Error> {
+        let chain_json = serde_json::to_string(chain).map_err(|e| {
+            sqlx::Error::Protocol(format!("Failed to serialize fallback chain: {}", e).into())
+        })?;
+
+        let result = sqlx::query("UPDATE settings SET summaryFallbackChain = ? WHERE id = '1'")
+            .bind(chain_json)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
 }
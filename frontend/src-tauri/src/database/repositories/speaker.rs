@@ -0,0 +1,137 @@
+use crate::database::models::Speaker;
+use crate::diarization::default_speaker_label;
+use chrono::Utc;
+use sqlx::{Connection, Error as SqlxError, Executor, Sqlite, SqliteConnection, SqlitePool};
+use std::collections::HashMap;
+use tracing::info;
+
+pub struct SpeakersRepository;
+
+impl SpeakersRepository {
+    /// All speakers of a meeting, including ones that were never renamed
+    pub async fn get_speakers<'c, E>(
+        executor: E,
+        meeting_id: &str,
+    ) -> Result<Vec<Speaker>, SqlxError>
+    where
+        E: Executor<'c, Database = Sqlite>,
+    {
+        sqlx::query_as::<_, Speaker>(
+            "SELECT meeting_id, speaker_id, label FROM speakers
+             WHERE meeting_id = ?
+             ORDER BY speaker_id ASC",
+        )
+        .bind(meeting_id)
+        .fetch_all(executor)
+        .await
+    }
+
+    /// Speaker ID -> display label for a meeting
+    pub async fn get_labels<'c, E>(
+        executor: E,
+        meeting_id: &str,
+    ) -> Result<HashMap<String, String>, SqlxError>
+    where
+        E: Executor<'c, Database = Sqlite>,
+    {
+        Ok(Self::get_speakers(executor, meeting_id)
+            .await?
+            .into_iter()
+            .map(|s| (s.speaker_id, s.label))
+            .collect())
+    }
+
+    /// Resolve a speaker's label, falling back to the default ("Speaker 2")
+    pub fn label_for(labels: &HashMap<String, String>, speaker_id: &str) -> String {
+        labels
+            .get(speaker_id)
+            .cloned()
+            .unwrap_or_else(|| default_speaker_label(speaker_id))
+    }
+
+    /// Create rows with default labels for speakers that don't have one yet
+    pub async fn ensure_speakers(
+        conn: &mut SqliteConnection,
+        meeting_id: &str,
+        speaker_ids: &[String],
+    ) -> Result<(), SqlxError> {
+        let now = Utc::now();
+        for speaker_id in speaker_ids {
+            sqlx::query(
+                "INSERT OR IGNORE INTO speakers (meeting_id, speaker_id, label, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(meeting_id)
+            .bind(speaker_id)
+            .bind(default_speaker_label(speaker_id))
+            .bind(now)
+            .bind(now)
+            .execute(&mut *conn)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Rename a speaker; returns false if the meeting has no such speaker
+    pub async fn rename_speaker(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        speaker_id: &str,
+        label: &str,
+    ) -> Result<bool, SqlxError> {
+        let result = sqlx::query(
+            "UPDATE speakers SET label = ?, updated_at = ? WHERE meeting_id = ? AND speaker_id = ?",
+        )
+        .bind(label)
+        .bind(Utc::now())
+        .bind(meeting_id)
+        .bind(speaker_id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Reassign every transcript of `from_speaker_id` to `into_speaker_id` and
+    /// remove `from_speaker_id`. Returns the number of transcripts moved.
+    pub async fn merge_speakers(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        from_speaker_id: &str,
+        into_speaker_id: &str,
+    ) -> Result<u64, SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        Self::ensure_speakers(
+            &mut *transaction,
+            meeting_id,
+            &[into_speaker_id.to_string()],
+        )
+        .await?;
+
+        let moved = sqlx::query(
+            "UPDATE transcripts SET speaker_id = ? WHERE meeting_id = ? AND speaker_id = ?",
+        )
+        .bind(into_speaker_id)
+        .bind(meeting_id)
+        .bind(from_speaker_id)
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected();
+
+        sqlx::query("DELETE FROM speakers WHERE meeting_id = ? AND speaker_id = ?")
+            .bind(meeting_id)
+            .bind(from_speaker_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+
+        info!(
+            "Merged speaker {} into {} for meeting {} ({} transcripts)",
+            from_speaker_id, into_speaker_id, meeting_id, moved
+        );
+        Ok(moved)
+    }
+}
@@ -102,7 +102,8 @@ impl SummaryProcessesRepository {
                 result_backup = result,
                 result_backup_timestamp = excluded.updated_at,
                 result = result,
-                error = NULL
+                error = NULL,
+                metadata = NULL
             "#
         )
         .bind(meeting_id)
@@ -151,6 +152,28 @@ impl SummaryProcessesRepository {
         Ok(())
     }
 
+    /// Merges `metadata` (a JSON object) into the process's metadata column
+    ///
+    /// Keys already present are overwritten; other keys are kept.
+    pub async fn merge_process_metadata(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        metadata: &Value,
+    ) -> Result<(), sqlx::Error> {
+        let metadata_str = serde_json::to_string(metadata)
+            .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize metadata: {}", e)))?;
+
+        sqlx::query(
+            "UPDATE summary_processes SET metadata = json_patch(COALESCE(metadata, '{}'), ?), updated_at = ? WHERE meeting_id = ?",
+        )
+        .bind(metadata_str)
+        .bind(Utc::now())
+        .bind(meeting_id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn update_process_failed(
         pool: &SqlitePool,
         meeting_id: &str,
@@ -0,0 +1,134 @@
+use crate::database::models::SummaryVersion;
+use chrono::Utc;
+use sqlx::{Connection, Error as SqlxError, SqlitePool};
+use uuid::Uuid;
+
+const SELECT_VERSIONS: &str = "SELECT id, meeting_id, version, source, result, provider, model,
+        template_id, custom_prompt, input_tokens, output_tokens, chunk_count, processing_time,
+        restored_from, created_at
+ FROM summary_versions";
+
+/// Details of a summary version about to be recorded
+#[derive(Debug, Clone, Default)]
+pub struct NewSummaryVersion {
+    pub source: String,
+    pub result: String,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub template_id: Option<String>,
+    pub custom_prompt: Option<String>,
+    pub input_tokens: Option<i64>,
+    pub output_tokens: Option<i64>,
+    pub chunk_count: Option<i64>,
+    pub processing_time: Option<f64>,
+    pub restored_from: Option<i64>,
+}
+
+pub struct SummaryVersionsRepository;
+
+impl SummaryVersionsRepository {
+    /// Append a version, numbered after the meeting's latest one
+    pub async fn record(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        version: &NewSummaryVersion,
+    ) -> Result<SummaryVersion, SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        let number: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(MAX(version), 0) + 1 FROM summary_versions WHERE meeting_id = ?",
+        )
+        .bind(meeting_id)
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        let record = SummaryVersion {
+            id: format!("sv-{}", Uuid::new_v4()),
+            meeting_id: meeting_id.to_string(),
+            version: number,
+            source: version.source.clone(),
+            result: version.result.clone(),
+            provider: version.provider.clone(),
+            model: version.model.clone(),
+            template_id: version.template_id.clone(),
+            custom_prompt: version.custom_prompt.clone(),
+            input_tokens: version.input_tokens,
+            output_tokens: version.output_tokens,
+            chunk_count: version.chunk_count,
+            processing_time: version.processing_time,
+            restored_from: version.restored_from,
+            created_at: Utc::now().to_rfc3339(),
+        };
+
+        sqlx::query(
+            "INSERT INTO summary_versions
+                 (id, meeting_id, version, source, result, provider, model, template_id,
+                  custom_prompt, input_tokens, output_tokens, chunk_count, processing_time,
+                  restored_from, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&record.id)
+        .bind(&record.meeting_id)
+        .bind(record.version)
+        .bind(&record.source)
+        .bind(&record.result)
+        .bind(&record.provider)
+        .bind(&record.model)
+        .bind(&record.template_id)
+        .bind(&record.custom_prompt)
+        .bind(record.input_tokens)
+        .bind(record.output_tokens)
+        .bind(record.chunk_count)
+        .bind(record.processing_time)
+        .bind(record.restored_from)
+        .bind(&record.created_at)
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+        Ok(record)
+    }
+
+    /// All versions of a meeting's summary, newest first
+    pub async fn list(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<SummaryVersion>, SqlxError> {
+        sqlx::query_as::<_, SummaryVersion>(&format!(
+            "{} WHERE meeting_id = ? ORDER BY version DESC",
+            SELECT_VERSIONS
+        ))
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        version: i64,
+    ) -> Result<Option<SummaryVersion>, SqlxError> {
+        sqlx::query_as::<_, SummaryVersion>(&format!(
+            "{} WHERE meeting_id = ? AND version = ?",
+            SELECT_VERSIONS
+        ))
+        .bind(meeting_id)
+        .bind(version)
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn latest(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Option<SummaryVersion>, SqlxError> {
+        sqlx::query_as::<_, SummaryVersion>(&format!(
+            "{} WHERE meeting_id = ? ORDER BY version DESC LIMIT 1",
+            SELECT_VERSIONS
+        ))
+        .bind(meeting_id)
+        .fetch_optional(pool)
+        .await
+    }
+}
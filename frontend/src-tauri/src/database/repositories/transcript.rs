@@ -1,4 +1,5 @@
-use crate::api::{TranscriptSearchResult, TranscriptSegment};
+use crate::api::TranscriptSegment;
+use crate::database::repositories::speaker::SpeakersRepository;
 use chrono::Utc;
 use sqlx::{Connection, Error as SqlxError, SqlitePool};
 use tracing::{error, info};
@@ -47,8 +48,8 @@ impl TranscriptsRepository {
         for segment in transcripts {
             let transcript_id = format!("transcript-{}", Uuid::new_v4());
             let result = sqlx::query(
-                "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration)
-                 VALUES (?, ?, ?, ?, ?, ?, ?)"
+                "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration, words, speaker, speaker_id)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
             )
             .bind(&transcript_id)
             .bind(&meeting_id)
@@ -57,6 +58,15 @@ impl TranscriptsRepository {
             .bind(segment.audio_start_time)
             .bind(segment.audio_end_time)
             .bind(segment.duration)
+            .bind(
+                segment
+                    .words
+                    .as_ref()
+                    .filter(|words| !words.is_empty())
+                    .and_then(|words| serde_json::to_string(words).ok()),
+            )
+            .bind(&segment.speaker)
+            .bind(&segment.speaker_id)
             .execute(&mut *transaction)
             .await;
 
@@ -70,6 +80,21 @@ impl TranscriptsRepository {
             }
         }
 
+        // 3. Register diarized speakers with default labels
+        let mut speaker_ids: Vec<String> = transcripts
+            .iter()
+            .filter_map(|segment| segment.speaker_id.clone())
+            .collect();
+        speaker_ids.sort();
+        speaker_ids.dedup();
+        if let Err(e) =
+            SpeakersRepository::ensure_speakers(&mut *transaction, &meeting_id, &speaker_ids).await
+        {
+            error!("Failed to save speakers for meeting {}: {}", meeting_id, e);
+            transaction.rollback().await?;
+            return Err(e);
+        }
+
         info!(
             "Successfully saved {} transcript segments for meeting {}",
             transcripts.len(),
@@ -81,66 +106,4 @@ impl TranscriptsRepository {
 
         Ok(meeting_id)
     }
-
-    /// Searches for a query string within the transcripts.
-    /// It returns a list of matching transcripts with context.
-    pub async fn search_transcripts(
-        pool: &SqlitePool,
-        query: &str,
-    ) -> Result<Vec<TranscriptSearchResult>, SqlxError> {
-        if query.trim().is_empty() {
-            return Ok(Vec::new());
-        }
-
-        let search_query = format!("%{}%", query.to_lowercase());
-
-        let rows = sqlx::query_as::<_, (String, String, String, String)>(
-            "SELECT m.id, m.title, t.transcript, t.timestamp
-             FROM meetings m
-             JOIN transcripts t ON m.id = t.meeting_id
-             WHERE LOWER(t.transcript) LIKE ?",
-        )
-        .bind(&search_query)
-        .fetch_all(pool)
-        .await?;
-
-        let results = rows
-            .into_iter()
-            .map(|(id, title, transcript, timestamp)| {
-                let match_context = Self::get_match_context(&transcript, query);
-                TranscriptSearchResult {
-                    id,
-                    title,
-                    match_context,
-                    timestamp,
-                }
-            })
-            .collect();
-
-        Ok(results)
-    }
-
-    /// Helper function to extract a snippet of text around the first match of a query.
-    fn get_match_context(transcript: &str, query: &str) -> String {
-        let transcript_lower = transcript.to_lowercase();
-        let query_lower = query.to_lowercase();
-
-        match transcript_lower.find(&query_lower) {
-            Some(match_index) => {
-                let start_index = match_index.saturating_sub(100);
-                let end_index = (match_index + query.len() + 100).min(transcript.len());
-
-                let mut context = String::new();
-                if start_index > 0 {
-                    context.push_str("...");
-                }
-                context.push_str(&transcript[start_index..end_index]);
-                if end_index < transcript.len() {
-                    context.push_str("...");
-                }
-                context
-            }
-            None => transcript.chars().take(200).collect(), // Fallback to the start of the transcript
-        }
-    }
 }
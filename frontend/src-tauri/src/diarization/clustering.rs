@@ -0,0 +1,148 @@
+// diarization/clustering.rs
+//
+// Online agglomeration of speaker embeddings into Speaker 1..N.
+
+/// Cosine similarity above which an embedding joins an existing speaker
+pub const DEFAULT_SIMILARITY_THRESHOLD: f32 = 0.5;
+
+/// Upper bound on distinct speakers per session (further voices join the closest one)
+pub const DEFAULT_MAX_SPEAKERS: usize = 12;
+
+/// Running centroid of one speaker's (L2-normalized) embeddings
+#[derive(Debug, Clone)]
+struct Cluster {
+    sum: Vec<f32>,
+    count: usize,
+}
+
+impl Cluster {
+    fn similarity(&self, embedding: &[f32]) -> f32 {
+        let norm = l2_norm(&self.sum);
+        if norm == 0.0 {
+            return 0.0;
+        }
+        dot(&self.sum, embedding) / norm
+    }
+
+    fn add(&mut self, embedding: &[f32]) {
+        for (s, e) in self.sum.iter_mut().zip(embedding) {
+            *s += e;
+        }
+        self.count += 1;
+    }
+}
+
+/// Assigns each embedding to the most similar speaker seen so far, or opens a
+/// new speaker when nobody is similar enough
+///
+/// Speaker indices are stable for the lifetime of the clusterer: once an
+/// embedding is assigned to speaker 2 it is never renumbered.
+#[derive(Debug, Clone)]
+pub struct OnlineClusterer {
+    clusters: Vec<Cluster>,
+    threshold: f32,
+    max_speakers: usize,
+}
+
+impl Default for OnlineClusterer {
+    fn default() -> Self {
+        Self::new(DEFAULT_SIMILARITY_THRESHOLD, DEFAULT_MAX_SPEAKERS)
+    }
+}
+
+impl OnlineClusterer {
+    pub fn new(threshold: f32, max_speakers: usize) -> Self {
+        Self {
+            clusters: Vec::new(),
+            threshold,
+            max_speakers: max_speakers.max(1),
+        }
+    }
+
+    pub fn speaker_count(&self) -> usize {
+        self.clusters.len()
+    }
+
+    /// Assign an embedding to a speaker and return the speaker's 0-based index
+    ///
+    /// `reliable` should be false for very short segments; their embeddings are
+    /// matched against existing speakers but don't move centroids or open new
+    /// speakers (unless there are none yet).
+    pub fn assign(&mut self, embedding: &[f32], reliable: bool) -> usize {
+        let embedding = normalized(embedding);
+
+        let best = self
+            .clusters
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (i, c.similarity(&embedding)))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        match best {
+            Some((index, similarity))
+                if similarity >= self.threshold
+                    || !reliable
+                    || self.clusters.len() >= self.max_speakers =>
+            {
+                if reliable {
+                    self.clusters[index].add(&embedding);
+                }
+                index
+            }
+            _ => {
+                self.clusters.push(Cluster {
+                    sum: embedding,
+                    count: 1,
+                });
+                self.clusters.len() - 1
+            }
+        }
+    }
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn l2_norm(v: &[f32]) -> f32 {
+    dot(v, v).sqrt()
+}
+
+fn normalized(v: &[f32]) -> Vec<f32> {
+    let norm = l2_norm(v);
+    if norm == 0.0 {
+        return v.to_vec();
+    }
+    v.iter().map(|x| x / norm).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assigns_similar_embeddings_to_same_speaker() {
+        let mut clusterer = OnlineClusterer::default();
+
+        assert_eq!(clusterer.assign(&[1.0, 0.0, 0.0], true), 0);
+        assert_eq!(clusterer.assign(&[0.0, 1.0, 0.0], true), 1);
+        // Scale doesn't matter, direction does
+        assert_eq!(clusterer.assign(&[2.0, 0.2, 0.0], true), 0);
+        assert_eq!(clusterer.assign(&[0.1, 0.9, 0.1], true), 1);
+        assert_eq!(clusterer.speaker_count(), 2);
+    }
+
+    #[test]
+    fn test_unreliable_and_capped_embeddings_reuse_speakers() {
+        let mut clusterer = OnlineClusterer::new(0.9, 2);
+
+        // First embedding always opens a speaker, even if unreliable
+        assert_eq!(clusterer.assign(&[1.0, 0.0, 0.0], false), 0);
+        // Dissimilar but unreliable: matched to the closest speaker
+        assert_eq!(clusterer.assign(&[0.0, 1.0, 0.0], false), 0);
+        assert_eq!(clusterer.assign(&[0.0, 1.0, 0.0], true), 1);
+        // At the speaker cap, new voices join the closest speaker
+        assert_eq!(clusterer.assign(&[0.1, 0.0, 1.0], true), 0);
+        assert_eq!(clusterer.speaker_count(), 2);
+    }
+}
@@ -0,0 +1,165 @@
+use async_trait::async_trait;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{command, AppHandle, Emitter, Manager, Runtime};
+
+use super::SpeakerEmbeddingModel;
+use crate::model_integrity::{self, ModelVerification};
+use crate::model_manager::{self, CatalogEntry, DownloadProgress, EngineKind, ModelEngine};
+
+/// Model name in the shared model manager
+pub const MODEL_NAME: &str = "voxceleb_resnet34_LM";
+/// WeSpeaker ResNet34 (VoxCeleb, large-margin fine-tuned), ~26 MB
+pub const MODEL_FILE_NAME: &str = "voxceleb_resnet34_LM.onnx";
+// Approximate; progress switches to the server-reported length
+const MODEL_SIZE_BYTES: u64 = 26 * 1024 * 1024;
+const MODEL_URL: &str =
+    "https://huggingface.co/Wespeaker/wespeaker-voxceleb-resnet34-LM/resolve/main/voxceleb_resnet34_LM.onnx";
+
+// Global diarization models directory (set during app initialization)
+static MODELS_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Initialize the diarization models directory (`<app_data>/models/diarization`)
+pub fn set_models_directory<R: Runtime>(app: &AppHandle<R>) {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data dir");
+
+    let models_dir = app_data_dir.join("models").join("diarization");
+
+    if !models_dir.exists() {
+        if let Err(e) = std::fs::create_dir_all(&models_dir) {
+            log::error!("Failed to create diarization models directory: {}", e);
+            return;
+        }
+    }
+
+    log::info!(
+        "Diarization models directory set to: {}",
+        models_dir.display()
+    );
+
+    model_manager::registry().register(Arc::new(DiarizationModels {
+        models_dir: models_dir.clone(),
+    }));
+
+    let mut guard = MODELS_DIR.lock().unwrap();
+    *guard = Some(models_dir);
+}
+
+/// Path of the speaker embedding model (may not exist yet)
+pub fn get_model_path() -> Option<PathBuf> {
+    MODELS_DIR
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|dir| dir.join(MODEL_FILE_NAME))
+}
+
+fn get_models_directory() -> Option<PathBuf> {
+    MODELS_DIR.lock().unwrap().clone()
+}
+
+/// Re-check the speaker embedding model's SHA-256, if it is downloaded
+pub async fn verify_model() -> Option<ModelVerification> {
+    let models_dir = get_models_directory()?;
+    if !models_dir.join(MODEL_FILE_NAME).exists() {
+        return None;
+    }
+    let check =
+        model_integrity::verify_installed(&models_dir, MODEL_FILE_NAME, Some(MODEL_URL)).await;
+    Some(ModelVerification::new(
+        "diarization",
+        MODEL_FILE_NAME,
+        vec![check],
+    ))
+}
+
+#[derive(Debug, Serialize)]
+pub struct DiarizationModelStatus {
+    pub downloaded: bool,
+    pub path: Option<String>,
+    pub size_mb: Option<f64>,
+}
+
+#[command]
+pub async fn diarization_get_model_status() -> Result<DiarizationModelStatus, String> {
+    let path = get_model_path();
+    let size = path
+        .as_ref()
+        .and_then(|p| std::fs::metadata(p).ok())
+        .map(|m| m.len());
+
+    Ok(DiarizationModelStatus {
+        downloaded: size.is_some(),
+        path: path.map(|p| p.display().to_string()),
+        size_mb: size.map(|s| s as f64 / (1024.0 * 1024.0)),
+    })
+}
+
+/// Download (or resume) the speaker embedding model through the shared model
+/// manager, emitting `diarization-model-download-progress`
+#[command]
+pub async fn diarization_download_model<R: Runtime>(
+    app_handle: AppHandle<R>,
+) -> Result<(), String> {
+    let on_progress = |progress: &DownloadProgress| {
+        let _ = app_handle.emit(
+            "diarization-model-download-progress",
+            serde_json::json!({
+                "progress": progress.percent,
+                "downloaded_bytes": progress.downloaded_bytes,
+                "total_bytes": progress.total_bytes,
+            }),
+        );
+    };
+    model_manager::registry()
+        .download(EngineKind::Diarization, MODEL_NAME, Some(&on_progress))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let _ = app_handle.emit("diarization-model-download-complete", serde_json::json!({}));
+    Ok(())
+}
+
+/// Cancel the model download; the partial file is kept for resuming
+/// Returns false if the model wasn't downloading
+#[command]
+pub async fn diarization_cancel_download() -> Result<bool, String> {
+    Ok(model_manager::registry().cancel(EngineKind::Diarization, MODEL_NAME))
+}
+
+/// The speaker embedding model as seen by the shared model manager
+struct DiarizationModels {
+    models_dir: PathBuf,
+}
+
+#[async_trait]
+impl ModelEngine for DiarizationModels {
+    fn kind(&self) -> EngineKind {
+        EngineKind::Diarization
+    }
+
+    fn models_dir(&self) -> PathBuf {
+        self.models_dir.clone()
+    }
+
+    fn catalog(&self) -> Vec<CatalogEntry> {
+        vec![CatalogEntry::new(
+            EngineKind::Diarization,
+            MODEL_NAME,
+            "WeSpeaker ResNet34",
+            "Speaker embedding model for telling remote speakers apart",
+        )
+        .with_file(MODEL_FILE_NAME, MODEL_URL, MODEL_SIZE_BYTES)]
+    }
+
+    /// The model is usable if ONNX Runtime can load it
+    async fn validate(&self, _entry: &CatalogEntry) -> anyhow::Result<()> {
+        let path = self.models_dir.join(MODEL_FILE_NAME);
+        tokio::task::spawn_blocking(move || SpeakerEmbeddingModel::load(&path).map(drop)).await??;
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+// diarization/embedding.rs
+//
+// Speaker embedding extraction with a WeSpeaker ResNet34 ONNX model.
+// The model expects Kaldi-compatible log-mel filterbank features.
+
+use ndarray::Array3;
+use ort::execution_providers::CPUExecutionProvider;
+use ort::session::builder::GraphOptimizationLevel;
+use ort::session::Session;
+use ort::value::TensorRef;
+use realfft::RealFftPlanner;
+use std::path::Path;
+
+pub const SAMPLE_RATE: u32 = 16000;
+
+/// Segments shorter than this produce unreliable embeddings (seconds)
+pub const MIN_RELIABLE_SECS: f32 = 1.0;
+
+/// Segments shorter than this are not embedded at all (seconds)
+pub const MIN_EMBEDDING_SECS: f32 = 0.4;
+
+const NUM_MEL_BINS: usize = 80;
+const FRAME_LENGTH: usize = 400; // 25ms at 16kHz
+const FRAME_SHIFT: usize = 160; // 10ms at 16kHz
+const FFT_SIZE: usize = 512;
+const PREEMPHASIS: f32 = 0.97;
+const LOW_FREQ: f32 = 20.0;
+
+#[derive(thiserror::Error, Debug)]
+pub enum EmbeddingError {
+    #[error("ORT error: {0}")]
+    Ort(#[from] ort::Error),
+    #[error("ndarray shape error")]
+    Shape(#[from] ndarray::ShapeError),
+    #[error("Segment too short for a speaker embedding ({0} samples)")]
+    TooShort(usize),
+    #[error("Model output not found: {0}")]
+    OutputNotFound(String),
+}
+
+/// ONNX speaker embedding model (input: fbank `[1, frames, 80]`, output: `[1, dim]`)
+pub struct SpeakerEmbeddingModel {
+    session: Session,
+    input_name: String,
+    output_name: String,
+    fbank: FbankExtractor,
+}
+
+impl SpeakerEmbeddingModel {
+    pub fn load(model_path: &Path) -> Result<Self, EmbeddingError> {
+        let session = Session::builder()?
+            .with_optimization_level(GraphOptimizationLevel::Level3)?
+            .with_execution_providers(vec![CPUExecutionProvider::default().build()])?
+            .with_intra_threads(1)?
+            .commit_from_file(model_path)?;
+
+        let input_name = session
+            .inputs
+            .first()
+            .map(|input| input.name.clone())
+            .unwrap_or_else(|| "feats".to_string());
+        let output_name = session
+            .outputs
+            .first()
+            .map(|output| output.name.clone())
+            .unwrap_or_else(|| "embs".to_string());
+
+        log::info!(
+            "Loaded speaker embedding model from {} (input: {})",
+            model_path.display(),
+            input_name
+        );
+
+        Ok(Self {
+            session,
+            input_name,
+            output_name,
+            fbank: FbankExtractor::new(),
+        })
+    }
+
+    /// Embed 16kHz mono audio
+    pub fn embed(&mut self, samples: &[f32]) -> Result<Vec<f32>, EmbeddingError> {
+        if (samples.len() as f32) < MIN_EMBEDDING_SECS * SAMPLE_RATE as f32 {
+            return Err(EmbeddingError::TooShort(samples.len()));
+        }
+
+        let features = self.fbank.compute(samples);
+        let frames = features.len() / NUM_MEL_BINS;
+        let features = Array3::from_shape_vec((1, frames, NUM_MEL_BINS), features)?;
+
+        let outputs = self.session.run(ort::inputs![
+            self.input_name.as_str() => TensorRef::from_array_view(features.view())?,
+        ])?;
+        let embedding = outputs
+            .get(self.output_name.as_str())
+            .ok_or_else(|| EmbeddingError::OutputNotFound(self.output_name.clone()))?
+            .try_extract_array::<f32>()?;
+
+        Ok(embedding.iter().copied().collect())
+    }
+}
+
+/// Kaldi-compatible log-mel filterbank (povey window, pre-emphasis, no dither)
+/// with per-utterance mean normalization
+pub struct FbankExtractor {
+    window: Vec<f32>,
+    mel_banks: Vec<(usize, Vec<f32>)>,
+    planner: RealFftPlanner<f32>,
+}
+
+impl FbankExtractor {
+    pub fn new() -> Self {
+        let window = (0..FRAME_LENGTH)
+            .map(|i| {
+                let hann = 0.5
+                    - 0.5
+                        * (2.0 * std::f32::consts::PI * i as f32 / (FRAME_LENGTH - 1) as f32).cos();
+                hann.powf(0.85)
+            })
+            .collect();
+
+        Self {
+            window,
+            mel_banks: mel_banks(NUM_MEL_BINS, FFT_SIZE, SAMPLE_RATE as f32, LOW_FREQ),
+            planner: RealFftPlanner::new(),
+        }
+    }
+
+    /// Row-major `[frames, NUM_MEL_BINS]` features
+    pub fn compute(&mut self, samples: &[f32]) -> Vec<f32> {
+        if samples.len() < FRAME_LENGTH {
+            return Vec::new();
+        }
+        let frames = 1 + (samples.len() - FRAME_LENGTH) / FRAME_SHIFT;
+        let fft = self.planner.plan_fft_forward(FFT_SIZE);
+        let mut input = fft.make_input_vec();
+        let mut spectrum = fft.make_output_vec();
+        let mut power = vec![0.0f32; FFT_SIZE / 2 + 1];
+        let mut features = Vec::with_capacity(frames * NUM_MEL_BINS);
+
+        for frame in 0..frames {
+            // WeSpeaker models are trained on int16-scaled audio
+            let start = frame * FRAME_SHIFT;
+            let mut frame_samples: Vec<f32> = samples[start..start + FRAME_LENGTH]
+                .iter()
+                .map(|s| s * 32768.0)
+                .collect();
+
+            let mean = frame_samples.iter().sum::<f32>() / FRAME_LENGTH as f32;
+            frame_samples.iter_mut().for_each(|s| *s -= mean);
+            for i in (1..FRAME_LENGTH).rev() {
+                frame_samples[i] -= PREEMPHASIS * frame_samples[i - 1];
+            }
+            frame_samples[0] -= PREEMPHASIS * frame_samples[0];
+
+            input.iter_mut().for_each(|x| *x = 0.0);
+            for (i, (s, w)) in frame_samples.iter().zip(&self.window).enumerate() {
+                input[i] = s * w;
+            }
+            if fft.process(&mut input, &mut spectrum).is_err() {
+                features.extend(std::iter::repeat(f32::EPSILON.ln()).take(NUM_MEL_BINS));
+                continue;
+            }
+            for (p, c) in power.iter_mut().zip(&spectrum) {
+                *p = c.norm_sqr();
+            }
+
+            for (offset, weights) in &self.mel_banks {
+                let energy: f32 = weights
+                    .iter()
+                    .zip(&power[*offset..])
+                    .map(|(w, p)| w * p)
+                    .sum();
+                features.push(energy.max(f32::EPSILON).ln());
+            }
+        }
+
+        // Cepstral mean normalization over the utterance
+        for bin in 0..NUM_MEL_BINS {
+            let mean = (0..frames)
+                .map(|f| features[f * NUM_MEL_BINS + bin])
+                .sum::<f32>()
+                / frames as f32;
+            for f in 0..frames {
+                features[f * NUM_MEL_BINS + bin] -= mean;
+            }
+        }
+
+        features
+    }
+}
+
+impl Default for FbankExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn mel_scale(freq: f32) -> f32 {
+    1127.0 * (1.0 + freq / 700.0).ln()
+}
+
+/// Triangular mel filters over the FFT bins, as (first bin, weights)
+fn mel_banks(
+    num_bins: usize,
+    fft_size: usize,
+    sample_rate: f32,
+    low_freq: f32,
+) -> Vec<(usize, Vec<f32>)> {
+    let nyquist = sample_rate / 2.0;
+    let fft_bin_width = sample_rate / fft_size as f32;
+    let mel_low = mel_scale(low_freq);
+    let mel_high = mel_scale(nyquist);
+    let mel_delta = (mel_high - mel_low) / (num_bins + 1) as f32;
+
+    (0..num_bins)
+        .map(|bin| {
+            let left = mel_low + bin as f32 * mel_delta;
+            let center = left + mel_delta;
+            let right = center + mel_delta;
+
+            let mut first = None;
+            let mut weights = Vec::new();
+            for i in 0..fft_size / 2 {
+                let mel = mel_scale(fft_bin_width * i as f32);
+                if mel > left && mel < right {
+                    let weight = if mel <= center {
+                        (mel - left) / (center - left)
+                    } else {
+                        (right - mel) / (right - center)
+                    };
+                    first.get_or_insert(i);
+                    weights.push(weight);
+                }
+            }
+            (first.unwrap_or(0), weights)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mel_banks_cover_spectrum_in_order() {
+        let banks = mel_banks(NUM_MEL_BINS, FFT_SIZE, SAMPLE_RATE as f32, LOW_FREQ);
+        assert_eq!(banks.len(), NUM_MEL_BINS);
+
+        let mut previous_first = 0;
+        for (first, weights) in &banks {
+            assert!(!weights.is_empty());
+            assert!(*first >= previous_first);
+            assert!(first + weights.len() <= FFT_SIZE / 2);
+            assert!(weights.iter().all(|w| (0.0..=1.0).contains(w)));
+            previous_first = *first;
+        }
+    }
+}
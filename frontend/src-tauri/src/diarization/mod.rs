@@ -0,0 +1,197 @@
+//! On-device speaker diarization for mixed recordings.
+//!
+//! Speech segments found by VAD on the mixed stream are attributed to the mic or
+//! the system-audio stream. System-audio segments are embedded with an ONNX
+//! speaker model and clustered online into stable "Speaker 1..N" IDs, which are
+//! stored per transcript row and can be renamed or merged afterwards.
+//!
+//! # Module Structure
+//!
+//! - `source`: Mic/system attribution and system-audio extraction per segment
+//! - `embedding`: Fbank features and the ONNX speaker embedding model
+//! - `clustering`: Online clustering of embeddings into speakers
+//! - `commands`: Tauri commands for the embedding model
+
+pub mod clustering;
+pub mod commands;
+pub mod embedding;
+pub mod source;
+
+pub use clustering::OnlineClusterer;
+pub use commands::*;
+pub use embedding::{EmbeddingError, SpeakerEmbeddingModel};
+pub use source::{AudioSource, SegmentAudio, SourceHistory};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Speaker ID used for speech from the local microphone
+pub const LOCAL_SPEAKER_ID: &str = "me";
+
+/// Pending segments older than this many chunks are dropped (never transcribed)
+const MAX_PENDING_SEGMENTS: u64 = 256;
+
+/// Speaker attribution attached to a transcript segment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeakerAssignment {
+    pub source: AudioSource,
+    /// Stable per-meeting ID ("me", "speaker_1", ...); None if no embedding was possible
+    pub speaker_id: Option<String>,
+}
+
+/// Stable ID for the clusterer's 0-based speaker index
+pub fn speaker_id_for_index(index: usize) -> String {
+    format!("speaker_{}", index + 1)
+}
+
+/// Display label for a speaker that hasn't been renamed
+pub fn default_speaker_label(speaker_id: &str) -> String {
+    if speaker_id == LOCAL_SPEAKER_ID {
+        return "Me".to_string();
+    }
+    match speaker_id.strip_prefix("speaker_") {
+        Some(n) => format!("Speaker {}", n),
+        None => speaker_id.to_string(),
+    }
+}
+
+struct Diarizer {
+    // Shared so inference runs without holding the DIARIZER lock
+    model: Option<Arc<Mutex<SpeakerEmbeddingModel>>>,
+    model_load_attempted: bool,
+    clusterer: OnlineClusterer,
+}
+
+// Per-recording diarization state (None when no recording is active)
+static DIARIZER: Lazy<Mutex<Option<Diarizer>>> = Lazy::new(|| Mutex::new(None));
+
+// Held while the embedding model loads
+static MODEL_LOAD: Mutex<()> = Mutex::new(());
+
+// Segment audio submitted by the pipeline, keyed by transcription chunk ID
+static PENDING_SEGMENTS: Lazy<Mutex<HashMap<u64, SegmentAudio>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Reset speakers for a new recording session
+pub fn start_session() {
+    *DIARIZER.lock().unwrap() = Some(Diarizer {
+        model: None,
+        model_load_attempted: false,
+        clusterer: OnlineClusterer::default(),
+    });
+    PENDING_SEGMENTS.lock().unwrap().clear();
+}
+
+/// Release the embedding model and pending audio after a recording
+pub fn end_session() {
+    *DIARIZER.lock().unwrap() = None;
+    PENDING_SEGMENTS.lock().unwrap().clear();
+}
+
+/// Called by the pipeline for every VAD segment sent to transcription
+pub fn submit_segment(chunk_id: u64, segment: SegmentAudio) {
+    let mut pending = PENDING_SEGMENTS.lock().unwrap();
+    pending.retain(|&id, _| id + MAX_PENDING_SEGMENTS > chunk_id);
+    pending.insert(chunk_id, segment);
+}
+
+/// Take the segment audio for a transcription chunk, if the pipeline recorded it
+pub fn take_segment(chunk_id: u64) -> Option<SegmentAudio> {
+    PENDING_SEGMENTS.lock().unwrap().remove(&chunk_id)
+}
+
+/// Attribute a transcribed segment to a speaker
+pub fn assign_speaker(segment: SegmentAudio) -> SpeakerAssignment {
+    if segment.source == AudioSource::Mic {
+        return SpeakerAssignment {
+            source: AudioSource::Mic,
+            speaker_id: Some(LOCAL_SPEAKER_ID.to_string()),
+        };
+    }
+
+    let speaker_id = identify_system_speaker(&segment);
+    SpeakerAssignment {
+        source: AudioSource::System,
+        speaker_id,
+    }
+}
+
+fn identify_system_speaker(segment: &SegmentAudio) -> Option<String> {
+    let model = embedding_model()?;
+
+    let samples = if segment.sample_rate == embedding::SAMPLE_RATE {
+        segment.system_samples.clone()
+    } else {
+        crate::audio::audio_processing::resample_audio(
+            &segment.system_samples,
+            segment.sample_rate,
+            embedding::SAMPLE_RATE,
+        )
+    };
+
+    let result = model.lock().unwrap().embed(&samples);
+    match result {
+        Ok(embedding) => {
+            let duration = samples.len() as f32 / embedding::SAMPLE_RATE as f32;
+            let reliable = duration >= embedding::MIN_RELIABLE_SECS;
+            // The session may have ended while the model was running
+            let mut guard = DIARIZER.lock().unwrap();
+            let index = guard.as_mut()?.clusterer.assign(&embedding, reliable);
+            Some(speaker_id_for_index(index))
+        }
+        Err(EmbeddingError::TooShort(_)) => None,
+        Err(e) => {
+            log::warn!("Speaker embedding failed: {}", e);
+            None
+        }
+    }
+}
+
+/// The session's embedding model, loaded on first use (None if unavailable)
+fn embedding_model() -> Option<Arc<Mutex<SpeakerEmbeddingModel>>> {
+    // Loading holds MODEL_LOAD rather than DIARIZER: concurrent segments wait
+    // for the model while clustering stays available
+    let _loading = MODEL_LOAD.lock().unwrap();
+    {
+        let guard = DIARIZER.lock().unwrap();
+        let diarizer = guard.as_ref()?;
+        if diarizer.model_load_attempted {
+            return diarizer.model.clone();
+        }
+    }
+
+    let model = match commands::get_model_path().filter(|p| p.exists()) {
+        Some(path) => match SpeakerEmbeddingModel::load(&path) {
+            Ok(model) => Some(Arc::new(Mutex::new(model))),
+            Err(e) => {
+                log::warn!("Failed to load speaker embedding model: {}", e);
+                None
+            }
+        },
+        None => {
+            log::info!("Speaker embedding model not downloaded - diarization disabled");
+            None
+        }
+    };
+
+    let mut guard = DIARIZER.lock().unwrap();
+    let diarizer = guard.as_mut()?;
+    diarizer.model_load_attempted = true;
+    diarizer.model = model.clone();
+    model
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_speaker_labels() {
+        assert_eq!(default_speaker_label(LOCAL_SPEAKER_ID), "Me");
+        assert_eq!(default_speaker_label(&speaker_id_for_index(0)), "Speaker 1");
+        assert_eq!(default_speaker_label("speaker_12"), "Speaker 12");
+        assert_eq!(default_speaker_label("custom"), "custom");
+    }
+}
@@ -0,0 +1,199 @@
+// diarization/source.rs
+//
+// Tracks the separate mic/system streams behind the mixed audio sent to VAD, so a
+// speech segment can be attributed to a stream and its system audio extracted.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// How much system audio is kept for embedding extraction (seconds)
+const MAX_HISTORY_SECS: usize = 30;
+
+/// Capture stream a speech segment came from (stored in `transcripts.speaker`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioSource {
+    Mic,
+    System,
+}
+
+impl AudioSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AudioSource::Mic => "mic",
+            AudioSource::System => "system",
+        }
+    }
+}
+
+/// Stream attribution for one VAD segment, plus its system-only audio
+#[derive(Debug, Clone)]
+pub struct SegmentAudio {
+    pub source: AudioSource,
+    /// System audio for the segment (empty for mic segments)
+    pub system_samples: Vec<f32>,
+    pub sample_rate: u32,
+}
+
+/// Energy of one mixing window, keyed by its first sample on the mixed timeline
+#[derive(Debug, Clone, Copy)]
+struct WindowEnergy {
+    start: usize,
+    len: usize,
+    mic: f32,
+    system: f32,
+}
+
+/// Recent mic/system audio aligned with the mixed timeline fed to VAD
+///
+/// VAD timestamps count from the first mixed window, so sample `n` pushed here
+/// is at `n / sample_rate` seconds on the same clock.
+pub struct SourceHistory {
+    sample_rate: u32,
+    max_samples: usize,
+    /// Mixed-timeline index of `system[0]`
+    system_start: usize,
+    system: VecDeque<f32>,
+    energies: VecDeque<WindowEnergy>,
+    total_samples: usize,
+}
+
+impl SourceHistory {
+    pub fn new(sample_rate: u32) -> Self {
+        let max_samples = sample_rate as usize * MAX_HISTORY_SECS;
+        Self {
+            sample_rate,
+            max_samples,
+            system_start: 0,
+            system: VecDeque::with_capacity(max_samples),
+            energies: VecDeque::new(),
+            total_samples: 0,
+        }
+    }
+
+    /// Record one mixing window (both slices cover the same span of time)
+    pub fn push_window(&mut self, mic: &[f32], system: &[f32]) {
+        let len = mic.len().max(system.len());
+        if len == 0 {
+            return;
+        }
+
+        self.energies.push_back(WindowEnergy {
+            start: self.total_samples,
+            len,
+            mic: sum_squares(mic),
+            system: sum_squares(system),
+        });
+        self.system.extend(system.iter().copied());
+        // Keep the system buffer aligned when the windows differ in length
+        self.system
+            .resize(self.system.len() + len - system.len(), 0.0);
+        self.total_samples += len;
+
+        while self.system.len() > self.max_samples {
+            self.system.pop_front();
+            self.system_start += 1;
+        }
+        while self
+            .energies
+            .front()
+            .is_some_and(|w| w.start + w.len <= self.system_start)
+        {
+            self.energies.pop_front();
+        }
+    }
+
+    /// Attribute the segment `[start_ms, end_ms)` of the mixed timeline
+    ///
+    /// Returns None if no audio is available for that span.
+    pub fn segment(&self, start_ms: f64, end_ms: f64) -> Option<SegmentAudio> {
+        let to_sample = |ms: f64| (ms.max(0.0) * self.sample_rate as f64 / 1000.0) as usize;
+        let start = to_sample(start_ms).max(self.system_start);
+        let end = to_sample(end_ms).min(self.total_samples);
+        if start >= end {
+            return None;
+        }
+
+        let (mut mic, mut system) = (0.0f32, 0.0f32);
+        for window in &self.energies {
+            let overlap_start = window.start.max(start);
+            let overlap_end = (window.start + window.len).min(end);
+            if overlap_start >= overlap_end {
+                continue;
+            }
+            // Windows are short, so scale their energy by the overlapping fraction
+            let fraction = (overlap_end - overlap_start) as f32 / window.len as f32;
+            mic += window.mic * fraction;
+            system += window.system * fraction;
+        }
+        if mic == 0.0 && system == 0.0 {
+            return None;
+        }
+
+        let source = if system > mic {
+            AudioSource::System
+        } else {
+            AudioSource::Mic
+        };
+        let system_samples = match source {
+            AudioSource::System => self
+                .system
+                .range(start - self.system_start..end - self.system_start)
+                .copied()
+                .collect(),
+            AudioSource::Mic => Vec::new(),
+        };
+
+        Some(SegmentAudio {
+            source,
+            system_samples,
+            sample_rate: self.sample_rate,
+        })
+    }
+}
+
+fn sum_squares(samples: &[f32]) -> f32 {
+    samples.iter().map(|s| s * s).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_attribution() {
+        // 1kHz keeps the numbers readable: 1 sample == 1ms
+        let mut history = SourceHistory::new(1000);
+        let quiet = vec![0.01; 100];
+        let loud = vec![0.5; 100];
+
+        history.push_window(&loud, &quiet); // 0-100ms: local user talking
+        history.push_window(&quiet, &loud); // 100-200ms: remote participant
+        history.push_window(&quiet, &loud); // 200-300ms
+
+        let mic = history.segment(0.0, 100.0).unwrap();
+        assert_eq!(mic.source, AudioSource::Mic);
+        assert!(mic.system_samples.is_empty());
+
+        let system = history.segment(120.0, 300.0).unwrap();
+        assert_eq!(system.source, AudioSource::System);
+        assert_eq!(system.system_samples.len(), 180);
+        assert!(system.system_samples.iter().all(|&s| s == 0.5));
+
+        assert!(history.segment(400.0, 500.0).is_none());
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut history = SourceHistory::new(10);
+        let window = vec![0.1; 10];
+        for _ in 0..(MAX_HISTORY_SECS + 5) {
+            history.push_window(&window, &window);
+        }
+
+        assert_eq!(history.system.len(), 10 * MAX_HISTORY_SECS);
+        // The oldest seconds are gone, recent ones are still available
+        assert!(history.segment(0.0, 1000.0).is_none());
+        assert!(history.segment(30_000.0, 31_000.0).is_some());
+    }
+}
@@ -0,0 +1,220 @@
+// export/commands.rs
+//
+// Tauri commands that load a meeting from the database and export it.
+
+use super::formats::{render, ExportDocument, ExportFormat, ExportOptions, ExportSegment};
+use crate::database::repositories::{
+    meeting::MeetingsRepository, summary::SummaryProcessesRepository,
+};
+use crate::state::AppState;
+use log::{error, info, warn};
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::path::PathBuf;
+use tauri::{AppHandle, Runtime};
+
+/// Extract the summary Markdown from a `summary_processes.result` JSON string.
+///
+/// Current results are `{ "markdown": "...", "summary_json": [...] }`. Older ones
+/// are either a map of sections (`{ "title": "...", "blocks": [{ "content": "..." }] }`,
+/// ordered by `_section_order` when present) or, when saved from the editor,
+/// `{ "MeetingName": "...", "MeetingNotes": { "sections": [...] } }`.
+pub(crate) fn summary_markdown_from_result(result: &str) -> Option<String> {
+    let value: Value = serde_json::from_str(result).ok()?;
+
+    if let Some(markdown) = value.get("markdown").and_then(Value::as_str) {
+        return Some(markdown.to_string());
+    }
+
+    let mut out = String::new();
+    if let Some(sections) = value
+        .pointer("/MeetingNotes/sections")
+        .and_then(Value::as_array)
+    {
+        for section in sections {
+            push_legacy_section(&mut out, section);
+        }
+    } else {
+        let sections = value.as_object()?;
+        let order: Vec<&str> = match value.get("_section_order").and_then(Value::as_array) {
+            Some(order) => order.iter().filter_map(Value::as_str).collect(),
+            None => sections.keys().map(String::as_str).collect(),
+        };
+        for key in order {
+            if let Some(section) = sections.get(key) {
+                push_legacy_section(&mut out, section);
+            }
+        }
+    }
+
+    if out.is_empty() {
+        None
+    } else {
+        Some(out.trim_end().to_string())
+    }
+}
+
+/// Append a legacy `{ "title": "...", "blocks": [...] }` section as Markdown
+fn push_legacy_section(out: &mut String, section: &Value) {
+    let (Some(title), Some(blocks)) = (
+        section.get("title").and_then(Value::as_str),
+        section.get("blocks").and_then(Value::as_array),
+    ) else {
+        return;
+    };
+    out.push_str(&format!("## {}\n\n", title));
+    for block in blocks {
+        if let Some(content) = block.get("content").and_then(Value::as_str) {
+            out.push_str(&format!("- {}\n", content));
+        }
+    }
+    out.push('\n');
+}
+
+async fn load_document(pool: &SqlitePool, meeting_id: &str) -> Result<ExportDocument, String> {
+    let meeting = MeetingsRepository::get_meeting(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting: {}", e))?
+        .ok_or_else(|| format!("Meeting not found: {}", meeting_id))?;
+
+    let summary_markdown =
+        match SummaryProcessesRepository::get_summary_data(pool, meeting_id).await {
+            Ok(process) => process
+                .and_then(|p| p.result)
+                .and_then(|result| summary_markdown_from_result(&result)),
+            Err(e) => {
+                warn!("Failed to load summary for export of {}: {}", meeting_id, e);
+                None
+            }
+        };
+
+    let segments = meeting
+        .transcripts
+        .into_iter()
+        .map(|t| ExportSegment {
+            start: t.audio_start_time,
+            end: t.audio_end_time,
+            text: t.text,
+            speaker: t.speaker_label,
+        })
+        .collect();
+
+    Ok(ExportDocument {
+        meeting_id: meeting.id,
+        title: meeting.title,
+        created_at: meeting.created_at,
+        segments,
+        summary_markdown,
+    })
+}
+
+/// Export a meeting's transcript (and summary, for Markdown/JSON) as a string
+#[tauri::command]
+pub async fn export_meeting_transcript<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    format: ExportFormat,
+    options: Option<ExportOptions>,
+) -> Result<String, String> {
+    info!(
+        "export_meeting_transcript called for meeting_id: {}, format: {:?}",
+        meeting_id, format
+    );
+    let document = load_document(state.db_manager.pool(), &meeting_id).await?;
+    Ok(render(&document, format, &options.unwrap_or_default()))
+}
+
+/// Export a meeting to a file and return its path.
+///
+/// Without `path`, the file is written to the meeting's recording folder as
+/// `transcript.<ext>`.
+#[tauri::command]
+pub async fn export_meeting_to_file<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    format: ExportFormat,
+    options: Option<ExportOptions>,
+    path: Option<String>,
+) -> Result<String, String> {
+    info!(
+        "export_meeting_to_file called for meeting_id: {}, format: {:?}, path: {:?}",
+        meeting_id, format, path
+    );
+    let pool = state.db_manager.pool();
+
+    let output_path = match path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let meeting = MeetingsRepository::get_meeting_metadata(pool, &meeting_id)
+                .await
+                .map_err(|e| format!("Failed to load meeting: {}", e))?;
+
+            let folder = meeting
+                .and_then(|m| m.folder_path)
+                .ok_or_else(|| "Meeting has no recording folder; choose a file path".to_string())?;
+            PathBuf::from(folder).join(format!("transcript.{}", format.extension()))
+        }
+    };
+
+    let document = load_document(pool, &meeting_id).await?;
+    let content = render(&document, format, &options.unwrap_or_default());
+
+    std::fs::write(&output_path, content).map_err(|e| {
+        error!("Failed to write export to {}: {}", output_path.display(), e);
+        format!("Failed to write {}: {}", output_path.display(), e)
+    })?;
+
+    info!(
+        "Exported meeting {} to {}",
+        meeting_id,
+        output_path.display()
+    );
+    Ok(output_path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_markdown_from_result() {
+        let current = r###"{"markdown": "## Notes\n- a", "summary_json": []}"###;
+        assert_eq!(
+            summary_markdown_from_result(current).as_deref(),
+            Some("## Notes\n- a")
+        );
+
+        let legacy =
+            r#"{"ActionItems": {"title": "Action Items", "blocks": [{"content": "Ship it"}]}}"#;
+        assert_eq!(
+            summary_markdown_from_result(legacy).as_deref(),
+            Some("## Action Items\n\n- Ship it")
+        );
+
+        let ordered = r#"{
+            "_section_order": ["Summary", "ActionItems"],
+            "ActionItems": {"title": "Action Items", "blocks": [{"content": "Ship it"}]},
+            "Summary": {"title": "Summary", "blocks": [{"content": "Went well"}]}
+        }"#;
+        assert_eq!(
+            summary_markdown_from_result(ordered).as_deref(),
+            Some("## Summary\n\n- Went well\n\n## Action Items\n\n- Ship it")
+        );
+
+        let edited = r#"{
+            "MeetingName": "Weekly sync",
+            "MeetingNotes": {"sections": [
+                {"title": "Decisions", "blocks": [{"content": "Use SQLite"}]},
+                {"title": "Next Steps", "blocks": [{"content": "Write docs"}, {"content": "Release"}]}
+            ]}
+        }"#;
+        assert_eq!(
+            summary_markdown_from_result(edited).as_deref(),
+            Some("## Decisions\n\n- Use SQLite\n\n## Next Steps\n\n- Write docs\n- Release")
+        );
+
+        assert_eq!(summary_markdown_from_result("{}"), None);
+    }
+}
@@ -0,0 +1,405 @@
+// export/formats.rs
+//
+// Pure builders for each export format. Everything here works on an in-memory
+// `ExportDocument`, so the formats can be tested without a database.
+
+use serde::{Deserialize, Serialize};
+
+/// Version of the JSON export layout (bump on breaking changes)
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// Supported export formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Srt,
+    Vtt,
+    Text,
+    Markdown,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Srt => "srt",
+            Self::Vtt => "vtt",
+            Self::Text => "txt",
+            Self::Markdown => "md",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Export options, all optional on the frontend side
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    /// Prefix lines/cues with the speaker label when one is known
+    pub include_speakers: bool,
+    /// Include `[mm:ss]` markers in text and Markdown exports
+    pub include_timestamps: bool,
+    /// Join consecutive short segments of the same speaker
+    pub merge_short_segments: bool,
+    /// Segments shorter than this (seconds) are merged into a neighbour
+    pub min_segment_secs: f64,
+    /// Segments further apart than this (seconds) are never merged
+    pub max_merge_gap_secs: f64,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            include_speakers: true,
+            include_timestamps: true,
+            merge_short_segments: false,
+            min_segment_secs: 3.0,
+            max_merge_gap_secs: 1.5,
+        }
+    }
+}
+
+/// One transcript row, timings in seconds from recording start
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportSegment {
+    pub start: Option<f64>,
+    pub end: Option<f64>,
+    pub text: String,
+    pub speaker: Option<String>,
+}
+
+/// Everything needed to render any export format
+#[derive(Debug, Clone)]
+pub struct ExportDocument {
+    pub meeting_id: String,
+    pub title: String,
+    pub created_at: String,
+    pub segments: Vec<ExportSegment>,
+    /// Summary as Markdown, if one has been generated
+    pub summary_markdown: Option<String>,
+}
+
+/// JSON export layout (`schema_version` 1):
+///
+/// ```json
+/// {
+///   "schema_version": 1,
+///   "meeting": { "id": "meeting-…", "title": "Weekly sync", "created_at": "2025-01-01T10:00:00Z" },
+///   "summary": { "markdown": "## Key points …" },       // null if no summary exists
+///   "segments": [
+///     { "index": 0, "start": 12.4, "end": 15.1, "speaker": "Speaker 1", "text": "…" }
+///   ]
+/// }
+/// ```
+///
+/// `start`/`end` are seconds from recording start and are null for transcripts
+/// recorded before timings were stored; `speaker` is null when unknown or when
+/// speaker labels are disabled.
+#[derive(Debug, Serialize)]
+struct JsonExport<'a> {
+    schema_version: u32,
+    meeting: JsonMeeting<'a>,
+    summary: Option<JsonSummary<'a>>,
+    segments: Vec<JsonSegment<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonMeeting<'a> {
+    id: &'a str,
+    title: &'a str,
+    created_at: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonSummary<'a> {
+    markdown: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonSegment<'a> {
+    index: usize,
+    start: Option<f64>,
+    end: Option<f64>,
+    speaker: Option<&'a str>,
+    text: &'a str,
+}
+
+/// Render `document` in the requested format
+pub fn render(document: &ExportDocument, format: ExportFormat, options: &ExportOptions) -> String {
+    let merged;
+    let segments = if options.merge_short_segments {
+        merged = merge_short_segments(
+            &document.segments,
+            options.min_segment_secs,
+            options.max_merge_gap_secs,
+        );
+        &merged
+    } else {
+        &document.segments
+    };
+
+    match format {
+        ExportFormat::Srt => to_srt(segments, options),
+        ExportFormat::Vtt => to_vtt(segments, options),
+        ExportFormat::Text => to_text(segments, options),
+        ExportFormat::Markdown => to_markdown(document, segments, options),
+        ExportFormat::Json => to_json(document, segments, options),
+    }
+}
+
+/// Join consecutive segments of the same speaker when either is shorter than
+/// `min_secs` and the gap between them is at most `max_gap_secs`
+pub fn merge_short_segments(
+    segments: &[ExportSegment],
+    min_secs: f64,
+    max_gap_secs: f64,
+) -> Vec<ExportSegment> {
+    let mut merged: Vec<ExportSegment> = Vec::with_capacity(segments.len());
+
+    for segment in segments {
+        if let Some(last) = merged.last_mut() {
+            let timed = (last.start, last.end, segment.start, segment.end);
+            if let (Some(last_start), Some(last_end), Some(start), Some(end)) = timed {
+                let short = last_end - last_start < min_secs || end - start < min_secs;
+                if last.speaker == segment.speaker && short && start - last_end <= max_gap_secs {
+                    last.text = format!("{} {}", last.text.trim_end(), segment.text.trim_start());
+                    last.end = Some(end.max(last_end));
+                    continue;
+                }
+            }
+        }
+        merged.push(segment.clone());
+    }
+
+    merged
+}
+
+/// Cue timings, filling gaps for segments without stored timings
+fn cue_times(segments: &[ExportSegment]) -> Vec<(f64, f64)> {
+    let mut previous_end = 0.0;
+    segments
+        .iter()
+        .map(|segment| {
+            let start = segment.start.unwrap_or(previous_end);
+            let end = segment.end.unwrap_or(start).max(start);
+            previous_end = end;
+            (start, end)
+        })
+        .collect()
+}
+
+fn format_cue_time(seconds: f64, decimal_separator: char) -> String {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        total_ms / 3_600_000,
+        (total_ms / 60_000) % 60,
+        (total_ms / 1000) % 60,
+        decimal_separator,
+        total_ms % 1000
+    )
+}
+
+/// `[mm:ss]` marker used by text and Markdown exports
+fn format_marker(seconds: f64) -> String {
+    let total = seconds.max(0.0).floor() as u64;
+    format!("[{:02}:{:02}]", total / 60, total % 60)
+}
+
+fn speaker_prefix(segment: &ExportSegment, options: &ExportOptions) -> String {
+    match (&segment.speaker, options.include_speakers) {
+        (Some(speaker), true) => format!("{}: ", speaker),
+        _ => String::new(),
+    }
+}
+
+fn to_srt(segments: &[ExportSegment], options: &ExportOptions) -> String {
+    let mut out = String::new();
+    for (index, (segment, (start, end))) in segments.iter().zip(cue_times(segments)).enumerate() {
+        out.push_str(&format!(
+            "{}\n{} --> {}\n{}{}\n\n",
+            index + 1,
+            format_cue_time(start, ','),
+            format_cue_time(end, ','),
+            speaker_prefix(segment, options),
+            segment.text.trim()
+        ));
+    }
+    out
+}
+
+fn to_vtt(segments: &[ExportSegment], options: &ExportOptions) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for (segment, (start, end)) in segments.iter().zip(cue_times(segments)) {
+        let text = segment.text.trim();
+        let cue_text = match (&segment.speaker, options.include_speakers) {
+            (Some(speaker), true) => format!("<v {}>{}", speaker, text),
+            _ => text.to_string(),
+        };
+        out.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format_cue_time(start, '.'),
+            format_cue_time(end, '.'),
+            cue_text
+        ));
+    }
+    out
+}
+
+fn transcript_lines(segments: &[ExportSegment], options: &ExportOptions) -> String {
+    segments
+        .iter()
+        .map(|segment| {
+            let marker = match (segment.start, options.include_timestamps) {
+                (Some(start), true) => format!("{} ", format_marker(start)),
+                _ => String::new(),
+            };
+            format!(
+                "{}{}{}",
+                marker,
+                speaker_prefix(segment, options),
+                segment.text.trim()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn to_text(segments: &[ExportSegment], options: &ExportOptions) -> String {
+    let mut out = transcript_lines(segments, options);
+    out.push('\n');
+    out
+}
+
+fn to_markdown(
+    document: &ExportDocument,
+    segments: &[ExportSegment],
+    options: &ExportOptions,
+) -> String {
+    let mut out = format!("# {}\n\n", document.title);
+
+    if let Some(summary) = document.summary_markdown.as_deref() {
+        if !summary.trim().is_empty() {
+            out.push_str("## Summary\n\n");
+            out.push_str(summary.trim());
+            out.push_str("\n\n");
+        }
+    }
+
+    out.push_str("## Transcript\n\n");
+    for line in transcript_lines(segments, options).lines() {
+        // Two trailing spaces keep one line per segment when rendered
+        out.push_str(line);
+        out.push_str("  \n");
+    }
+    out
+}
+
+fn to_json(
+    document: &ExportDocument,
+    segments: &[ExportSegment],
+    options: &ExportOptions,
+) -> String {
+    let export = JsonExport {
+        schema_version: JSON_SCHEMA_VERSION,
+        meeting: JsonMeeting {
+            id: &document.meeting_id,
+            title: &document.title,
+            created_at: &document.created_at,
+        },
+        summary: document
+            .summary_markdown
+            .as_deref()
+            .map(|markdown| JsonSummary { markdown }),
+        segments: segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| JsonSegment {
+                index,
+                start: segment.start,
+                end: segment.end,
+                speaker: segment
+                    .speaker
+                    .as_deref()
+                    .filter(|_| options.include_speakers),
+                text: segment.text.trim(),
+            })
+            .collect(),
+    };
+
+    serde_json::to_string_pretty(&export).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start: f64, end: f64, speaker: &str, text: &str) -> ExportSegment {
+        ExportSegment {
+            start: Some(start),
+            end: Some(end),
+            text: text.to_string(),
+            speaker: Some(speaker.to_string()),
+        }
+    }
+
+    fn document() -> ExportDocument {
+        ExportDocument {
+            meeting_id: "meeting-1".to_string(),
+            title: "Weekly sync".to_string(),
+            created_at: "2025-01-01T10:00:00Z".to_string(),
+            segments: vec![
+                segment(0.0, 1.2, "Me", "Hi"),
+                segment(1.5, 65.25, "Me", "everyone."),
+                segment(66.0, 70.0, "Speaker 1", "Hello!"),
+            ],
+            summary_markdown: Some("## Key points\n- Shipped".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_srt_and_vtt_cues() {
+        let options = ExportOptions::default();
+        let srt = render(&document(), ExportFormat::Srt, &options);
+        assert!(srt.starts_with("1\n00:00:00,000 --> 00:00:01,200\nMe: Hi\n\n"));
+        assert!(srt.contains("2\n00:00:01,500 --> 00:01:05,250\nMe: everyone.\n"));
+
+        let vtt = render(&document(), ExportFormat::Vtt, &options);
+        assert!(vtt.starts_with("WEBVTT\n\n"));
+        assert!(vtt.contains("00:01:06.000 --> 00:01:10.000\n<v Speaker 1>Hello!\n"));
+    }
+
+    #[test]
+    fn test_text_markdown_and_json() {
+        let options = ExportOptions {
+            include_speakers: false,
+            ..Default::default()
+        };
+        let text = render(&document(), ExportFormat::Text, &options);
+        assert_eq!(text, "[00:00] Hi\n[00:01] everyone.\n[01:06] Hello!\n");
+
+        let markdown = render(
+            &document(),
+            ExportFormat::Markdown,
+            &ExportOptions::default(),
+        );
+        assert!(markdown.starts_with("# Weekly sync\n\n## Summary\n\n## Key points\n- Shipped\n\n"));
+        assert!(markdown.contains("[01:06] Speaker 1: Hello!  \n"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(&document(), ExportFormat::Json, &options)).unwrap();
+        assert_eq!(json["schema_version"], JSON_SCHEMA_VERSION);
+        assert_eq!(json["segments"][2]["start"], 66.0);
+        assert!(json["segments"][2]["speaker"].is_null());
+    }
+
+    #[test]
+    fn test_merge_short_segments() {
+        let merged = merge_short_segments(&document().segments, 3.0, 1.5);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].text, "Hi everyone.");
+        assert_eq!(merged[0].end, Some(65.25));
+        // Different speaker is never merged
+        assert_eq!(merged[1].speaker.as_deref(), Some("Speaker 1"));
+    }
+}
@@ -0,0 +1,14 @@
+//! Transcript and summary export.
+//!
+//! Builds SRT, WebVTT, plain text, Markdown and JSON exports of a meeting from the
+//! `transcripts` table and the summary stored in `summary_processes.result`.
+//!
+//! # Module Structure
+//!
+//! - `formats`: Pure renderers for each format, the options and the JSON schema
+//! - `commands`: Tauri commands that load a meeting and export it
+
+pub mod commands;
+pub mod formats;
+
+pub use formats::{render, ExportDocument, ExportFormat, ExportOptions, ExportSegment};
@@ -35,22 +35,33 @@ pub(crate) use perf_trace;
 // Re-export async logging macros for external use (removed due to macro conflicts)
 
 // Declare audio module
+pub mod action_items;
 pub mod analytics;
 pub mod api;
 pub mod audio;
+pub mod chat;
 pub mod console_utils;
 pub mod database;
+pub mod diarization;
+pub mod export;
+pub mod model_integrity;
+pub mod model_manager;
+pub mod notes;
 pub mod notifications;
 pub mod ollama;
 pub mod onboarding;
 pub mod openrouter;
 pub mod parakeet_engine;
+pub mod semantic;
 pub mod state;
 pub mod summary;
 pub mod tray;
 pub mod utils;
 pub mod whisper_engine;
 
+#[cfg(test)]
+mod test_http;
+
 use audio::{list_audio_devices, AudioDevice, trigger_audio_permission};
 use log::{error as log_error, info as log_info};
 use notifications::commands::NotificationManagerState;
@@ -442,6 +453,9 @@ pub fn run() {
                 }
             });
 
+            // Forward shared model manager download events to the frontend
+            model_manager::commands::init(&_app.handle());
+
             // Set models directory to use app_data_dir (unified storage location)
             whisper_engine::commands::set_models_directory(&_app.handle());
 
@@ -455,6 +469,12 @@ pub fn run() {
             // Set Parakeet models directory
             parakeet_engine::commands::set_models_directory(&_app.handle());
 
+            // Set speaker diarization models directory
+            diarization::commands::set_models_directory(&_app.handle());
+
+            // Set semantic search embedding models directory
+            semantic::commands::set_models_directory(&_app.handle());
+
             // Initialize Parakeet engine on startup
             tauri::async_runtime::spawn(async {
                 if let Err(e) = parakeet_engine::commands::parakeet_init().await {
@@ -561,6 +581,47 @@ pub fn run() {
             parakeet_engine::commands::parakeet_cancel_download,
             parakeet_engine::commands::parakeet_delete_corrupted_model,
             parakeet_engine::commands::open_parakeet_models_folder,
+            // Model integrity commands
+            model_integrity::commands::verify_models,
+            // Model manager commands
+            model_manager::commands::models_list,
+            model_manager::commands::models_download,
+            model_manager::commands::models_cancel_download,
+            model_manager::commands::models_delete,
+            model_manager::commands::models_import,
+            model_manager::commands::models_export,
+            // Speaker diarization commands
+            diarization::commands::diarization_get_model_status,
+            diarization::commands::diarization_download_model,
+            diarization::commands::diarization_cancel_download,
+            // Semantic search commands
+            semantic::commands::semantic_get_model_status,
+            semantic::commands::semantic_download_model,
+            semantic::commands::semantic_cancel_download,
+            semantic::commands::api_semantic_index,
+            semantic::commands::api_semantic_search,
+            semantic::commands::api_ask_meetings,
+            // Meeting chat commands
+            chat::commands::api_chat_create_thread,
+            chat::commands::api_chat_list_threads,
+            chat::commands::api_chat_get_messages,
+            chat::commands::api_chat_rename_thread,
+            chat::commands::api_chat_delete_thread,
+            chat::commands::api_chat_send_message,
+            // Action item commands
+            action_items::commands::api_list_action_items,
+            action_items::commands::api_set_action_item_completed,
+            action_items::commands::api_update_action_item,
+            action_items::commands::api_delete_action_item,
+            action_items::commands::api_extract_action_items,
+            // Meeting notes commands
+            notes::commands::api_get_meeting_notes,
+            notes::commands::api_save_meeting_notes,
+            notes::commands::api_delete_meeting_notes,
+            notes::commands::api_autosave_meeting_notes,
+            notes::commands::api_flush_meeting_notes,
+            notes::commands::api_get_recording_notes,
+            notes::commands::api_create_note_anchor,
             // Parallel processing commands
             whisper_engine::parallel_commands::initialize_parallel_processor,
             whisper_engine::parallel_commands::start_parallel_processing,
@@ -595,6 +656,10 @@ pub fn run() {
             audio::recording_commands::attempt_device_reconnect,
             // Playback device detection (Bluetooth warning)
             audio::recording_commands::get_active_audio_output,
+            // Offline file import
+            audio::import::import_audio_file,
+            audio::import::cancel_audio_import,
+            audio::import::is_importing_audio,
             // Audio recovery commands (for transcript recovery feature)
             audio::incremental_saver::recover_audio_from_checkpoints,
             audio::incremental_saver::cleanup_checkpoints,
@@ -624,6 +689,12 @@ pub fn run() {
             api::api_get_meeting_metadata,
             api::api_get_meeting_transcripts,
             api::api_save_meeting_title,
+            api::api_get_meeting_speakers,
+            api::api_rename_speaker,
+            api::api_merge_speakers,
+            // Transcript export commands
+            export::commands::export_meeting_transcript,
+            export::commands::export_meeting_to_file,
             api::api_save_transcript,
             api::open_meeting_folder,
             api::test_backend_connection,
@@ -642,16 +713,27 @@ pub fn run() {
             summary::api_list_templates,
             summary::api_get_template_details,
             summary::api_validate_template,
+            // Summary version commands
+            summary::api_list_summary_versions,
+            summary::api_restore_summary_version,
+            summary::api_diff_summary_versions,
+            // Summary fallback chain commands
+            summary::api_get_summary_fallback_chain,
+            summary::api_save_summary_fallback_chain,
             // Built-in AI commands
             summary::summary_engine::builtin_ai_list_models,
             summary::summary_engine::builtin_ai_get_model_info,
             summary::summary_engine::builtin_ai_download_model,
             summary::summary_engine::builtin_ai_cancel_download,
             summary::summary_engine::builtin_ai_delete_model,
+            summary::summary_engine::builtin_ai_inspect_gguf,
+            summary::summary_engine::builtin_ai_register_model,
+            summary::summary_engine::builtin_ai_unregister_model,
             summary::summary_engine::builtin_ai_is_model_ready,
             summary::summary_engine::builtin_ai_get_available_summary_model,
             summary::summary_engine::builtin_ai_get_recommended_model,
             openrouter::get_openrouter_models,
+            summary::api_get_gemini_models,
             audio::recording_preferences::get_recording_preferences,
             audio::recording_preferences::set_recording_preferences,
             audio::recording_preferences::get_default_recordings_folder_path,
@@ -0,0 +1,59 @@
+// Tauri command for re-checking installed models
+
+use tauri::{command, AppHandle, Manager, Runtime};
+
+use super::ModelVerification;
+use crate::parakeet_engine::commands::PARAKEET_ENGINE;
+use crate::summary::summary_engine::ModelManagerState;
+use crate::whisper_engine::commands::WHISPER_ENGINE;
+
+/// Re-hash every installed model file and compare it with its expected SHA-256
+///
+/// Files that don't match are quarantined, so their models show up as not
+/// downloaded and can be downloaded again. Engines that aren't initialized
+/// are skipped.
+#[command]
+pub async fn verify_models<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Vec<ModelVerification>, String> {
+    let mut results = Vec::new();
+
+    let whisper = WHISPER_ENGINE.lock().unwrap().as_ref().cloned();
+    if let Some(engine) = whisper {
+        results.extend(engine.verify_models().await);
+    }
+
+    let parakeet = PARAKEET_ENGINE.lock().unwrap().as_ref().cloned();
+    if let Some(engine) = parakeet {
+        results.extend(engine.verify_models().await);
+    }
+
+    let manager = {
+        let state = app.state::<ModelManagerState>();
+        let manager = state.0.lock().await;
+        manager.as_ref().cloned()
+    };
+    if let Some(manager) = manager {
+        results.extend(manager.verify_models().await);
+    }
+
+    results.extend(crate::diarization::commands::verify_model().await);
+    results.extend(crate::semantic::commands::verify_model().await);
+
+    let damaged: Vec<&str> = results
+        .iter()
+        .filter(|model| !model.is_intact())
+        .map(|model| model.model.as_str())
+        .collect();
+    if damaged.is_empty() {
+        log::info!("Verified {} installed models", results.len());
+    } else {
+        log::warn!(
+            "Verified {} installed models, quarantined files of: {}",
+            results.len(),
+            damaged.join(", ")
+        );
+    }
+
+    Ok(results)
+}
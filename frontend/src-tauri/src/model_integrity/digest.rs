@@ -0,0 +1,139 @@
+// Streaming SHA-256 for model downloads and installed files
+
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use sha2::{Digest, Sha256};
+
+/// Read size when hashing files already on disk
+const READ_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// SHA-256 of a download, fed chunk by chunk as it is written
+#[derive(Clone, Default)]
+pub struct DownloadHasher {
+    hasher: Sha256,
+    bytes: u64,
+}
+
+impl DownloadHasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from the first `existing_size` bytes of a partial file, so a
+    /// resumed download ends up with the hash of the whole file
+    pub async fn resume(path: &Path, existing_size: u64) -> Result<Self> {
+        let path = path.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            let file = File::open(&path)
+                .with_context(|| format!("Failed to open {} for hashing", path.display()))?;
+            let mut hasher = Self::new();
+            hasher.absorb(file.take(existing_size))?;
+            if hasher.bytes != existing_size {
+                return Err(anyhow!(
+                    "{} is shorter than expected ({} of {} bytes)",
+                    path.display(),
+                    hasher.bytes,
+                    existing_size
+                ));
+            }
+            Ok(hasher)
+        })
+        .await
+        .map_err(|e| anyhow!("Hashing task failed: {}", e))?
+    }
+
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+        self.bytes += chunk.len() as u64;
+    }
+
+    /// Bytes hashed so far
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Lowercase hex digest
+    pub fn finalize(self) -> String {
+        format!("{:x}", self.hasher.finalize())
+    }
+
+    fn absorb(&mut self, reader: impl Read) -> Result<()> {
+        let mut reader = BufReader::with_capacity(READ_BUFFER_SIZE, reader);
+        let mut buffer = vec![0u8; READ_BUFFER_SIZE];
+        loop {
+            let read = reader.read(&mut buffer)?;
+            if read == 0 {
+                return Ok(());
+            }
+            self.update(&buffer[..read]);
+        }
+    }
+}
+
+/// SHA-256 and size of a whole file, hashed off the async runtime
+pub async fn sha256_file(path: &Path) -> Result<(String, u64)> {
+    let path: PathBuf = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let file = File::open(&path)
+            .with_context(|| format!("Failed to open {} for hashing", path.display()))?;
+        let mut hasher = DownloadHasher::new();
+        hasher
+            .absorb(file)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let bytes = hasher.bytes();
+        Ok((hasher.finalize(), bytes))
+    })
+    .await
+    .map_err(|e| anyhow!("Hashing task failed: {}", e))?
+}
+
+/// A SHA-256 hex digest as published by a server (64 hex digits), normalized to lowercase
+pub fn normalize_sha256(value: &str) -> Option<String> {
+    let value = value.trim();
+    (value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| value.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // SHA-256 of "hello world"
+    const HELLO_WORLD: &str = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+    #[tokio::test]
+    async fn test_resumed_hash_matches_whole_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("model.bin");
+        std::fs::write(&path, b"hello world").unwrap();
+
+        // Six bytes were on disk before the download resumed
+        let mut resumed = DownloadHasher::resume(&path, 6).await.unwrap();
+        resumed.update(b"world");
+        assert_eq!(resumed.bytes(), 11);
+        assert_eq!(resumed.finalize(), HELLO_WORLD);
+
+        assert_eq!(
+            sha256_file(&path).await.unwrap(),
+            (HELLO_WORLD.to_string(), 11)
+        );
+        assert!(DownloadHasher::resume(&path, 20).await.is_err());
+    }
+
+    #[test]
+    fn test_normalize_sha256() {
+        assert_eq!(
+            normalize_sha256(&HELLO_WORLD.to_uppercase()).as_deref(),
+            Some(HELLO_WORLD)
+        );
+        // Git blob ids (SHA-1) of non-LFS files are not SHA-256
+        assert_eq!(
+            normalize_sha256("2aae6c35c94fcfb415dbe95f408b9ce91ee846ed"),
+            None
+        );
+        assert_eq!(normalize_sha256(""), None);
+    }
+}
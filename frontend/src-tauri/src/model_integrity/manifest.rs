@@ -0,0 +1,243 @@
+// Expected SHA-256 hashes per model file
+// Catalog files are pinned in the checked-in `known_checksums.json`, keyed by
+// download URL. Each models directory also keeps a `checksums.json` keyed by
+// file path relative to that directory. Pinned hashes and hashes published by
+// the download server are authoritative; other files get the hash of their
+// first complete download recorded, so later checks still catch changes on
+// disk, but they are never reported as verified
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use super::digest::normalize_sha256;
+
+/// Manifest file in each models directory
+const MANIFEST_FILE_NAME: &str = "checksums.json";
+
+/// Serializes read-modify-write of manifests (engines can share a directory)
+static MANIFEST_LOCK: Mutex<()> = Mutex::new(());
+
+/// Pinned hash and size per catalog download URL, filled in by
+/// `scripts/update-model-checksums.js`
+static KNOWN_CHECKSUMS: Lazy<BTreeMap<String, KnownFile>> = Lazy::new(|| {
+    serde_json::from_str(include_str!("known_checksums.json"))
+        .expect("known_checksums.json is invalid")
+});
+
+#[derive(Debug, Deserialize)]
+struct KnownFile {
+    sha256: Option<String>,
+    size: Option<u64>,
+}
+
+/// Where an expected hash came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestSource {
+    /// Pinned in the app or published by the download server
+    Published,
+    /// Computed from the first complete download (no published hash)
+    Recorded,
+}
+
+/// Expected hash of one model file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileDigest {
+    pub sha256: String,
+    pub size: u64,
+    pub source: DigestSource,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    files: BTreeMap<String, FileDigest>,
+}
+
+/// Expected hash of `file` (relative to `models_dir`), if known
+pub fn lookup(models_dir: &Path, file: &str) -> Option<FileDigest> {
+    let _guard = MANIFEST_LOCK.lock().unwrap();
+    match read_manifest(models_dir) {
+        Ok(mut manifest) => manifest.files.remove(file),
+        Err(e) => {
+            log::warn!("{}", e);
+            None
+        }
+    }
+}
+
+/// Store the hash of a verified (or first-downloaded) file
+pub fn record(models_dir: &Path, file: &str, digest: FileDigest) -> Result<()> {
+    let _guard = MANIFEST_LOCK.lock().unwrap();
+    let mut manifest = read_manifest(models_dir).unwrap_or_default();
+    manifest.files.insert(file.to_string(), digest);
+    write_manifest(models_dir, &manifest)
+}
+
+/// Drop a recorded hash once its file is gone; published hashes stay, as
+/// they still describe the file a new download must produce
+pub fn forget_recorded(models_dir: &Path, file: &str) -> Result<()> {
+    let _guard = MANIFEST_LOCK.lock().unwrap();
+    let mut manifest = read_manifest(models_dir).unwrap_or_default();
+    let recorded = manifest
+        .files
+        .get(file)
+        .is_some_and(|digest| digest.source == DigestSource::Recorded);
+    if !recorded {
+        return Ok(());
+    }
+    manifest.files.remove(file);
+    write_manifest(models_dir, &manifest)
+}
+
+/// Pinned hash and size of the file at a catalog download URL, if any
+pub fn known_digest(url: &str) -> Option<FileDigest> {
+    let known = KNOWN_CHECKSUMS.get(url)?;
+    Some(FileDigest {
+        sha256: normalize_sha256(known.sha256.as_deref()?)?,
+        size: known.size?,
+        source: DigestSource::Published,
+    })
+}
+
+/// Hash a new download of `file` must match
+///
+/// A pinned hash wins; otherwise the server's current hash wins over the
+/// manifest, so updated upstream files are accepted, and without one a
+/// previously published hash is used.
+pub async fn expected_sha256(models_dir: &Path, file: &str, url: &str) -> Option<String> {
+    if let Some(digest) = known_digest(url) {
+        return Some(digest.sha256);
+    }
+    if let Some(sha256) = published_sha256(url).await {
+        return Some(sha256);
+    }
+    lookup(models_dir, file)
+        .filter(|digest| digest.source == DigestSource::Published)
+        .map(|digest| digest.sha256)
+}
+
+/// SHA-256 the server publishes for a download URL, if any
+///
+/// Hugging Face answers `resolve` requests for LFS files with a redirect that
+/// carries the file's SHA-256 in `X-Linked-Etag`; other hosts and small
+/// non-LFS files (whose ETag is a git blob id) yield `None`.
+pub async fn published_sha256(url: &str) -> Option<String> {
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .connect_timeout(Duration::from_secs(10))
+        .timeout(Duration::from_secs(15))
+        .build()
+        .ok()?;
+
+    let response = match client.head(url).send().await {
+        Ok(response) => response,
+        Err(e) => {
+            log::warn!("Failed to look up published checksum for {}: {}", url, e);
+            return None;
+        }
+    };
+
+    ["x-linked-etag", "etag"]
+        .iter()
+        .filter_map(|name| response.headers().get(*name))
+        .filter_map(|value| value.to_str().ok())
+        .find_map(etag_sha256)
+}
+
+fn etag_sha256(etag: &str) -> Option<String> {
+    normalize_sha256(etag.trim().trim_start_matches("W/").trim_matches('"'))
+}
+
+fn read_manifest(models_dir: &Path) -> Result<Manifest> {
+    let path = models_dir.join(MANIFEST_FILE_NAME);
+    if !path.exists() {
+        return Ok(Manifest::default());
+    }
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| anyhow!("Invalid checksum manifest {}: {}", path.display(), e))
+}
+
+fn write_manifest(models_dir: &Path, manifest: &Manifest) -> Result<()> {
+    std::fs::create_dir_all(models_dir)?;
+    let path = models_dir.join(MANIFEST_FILE_NAME);
+    let json = serde_json::to_string_pretty(manifest)?;
+    // Write then rename so a crash can't leave a truncated manifest
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, json)?;
+    std::fs::rename(&tmp_path, &path).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA256: &str = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+    #[test]
+    fn test_etag_sha256() {
+        assert_eq!(
+            etag_sha256(&format!("\"{}\"", SHA256)).as_deref(),
+            Some(SHA256)
+        );
+        assert_eq!(
+            etag_sha256(&format!("W/\"{}\"", SHA256)).as_deref(),
+            Some(SHA256)
+        );
+        assert_eq!(
+            etag_sha256("\"2aae6c35c94fcfb415dbe95f408b9ce91ee846ed\""),
+            None
+        );
+    }
+
+    #[test]
+    fn test_known_checksums_are_valid() {
+        // Every catalog URL must be pinned; run
+        // `scripts/update-model-checksums.js` after adding one
+        for (url, known) in KNOWN_CHECKSUMS.iter() {
+            assert!(url.starts_with("https://"), "{}", url);
+            let sha256 = known
+                .sha256
+                .as_deref()
+                .unwrap_or_else(|| panic!("No pinned hash for {}", url));
+            assert!(normalize_sha256(sha256).is_some(), "{}", url);
+            assert!(known.size.is_some_and(|size| size > 0), "{}", url);
+            assert!(known_digest(url).is_some(), "{}", url);
+        }
+        assert_eq!(known_digest("https://example.com/unknown.bin"), None);
+    }
+
+    #[test]
+    fn test_forget_keeps_published_hashes() {
+        let dir = tempfile::tempdir().unwrap();
+        let digest = |source| FileDigest {
+            sha256: SHA256.to_string(),
+            size: 11,
+            source,
+        };
+
+        record(dir.path(), "ggml-base.bin", digest(DigestSource::Published)).unwrap();
+        record(
+            dir.path(),
+            "model/vocab.txt",
+            digest(DigestSource::Recorded),
+        )
+        .unwrap();
+        forget_recorded(dir.path(), "ggml-base.bin").unwrap();
+        forget_recorded(dir.path(), "model/vocab.txt").unwrap();
+
+        assert_eq!(
+            lookup(dir.path(), "ggml-base.bin"),
+            Some(digest(DigestSource::Published))
+        );
+        assert_eq!(lookup(dir.path(), "model/vocab.txt"), None);
+    }
+}
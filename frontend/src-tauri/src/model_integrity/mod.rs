@@ -0,0 +1,372 @@
+//! SHA-256 integrity checks for downloaded models.
+//!
+//! Every model download is hashed while it streams to disk (resumed downloads
+//! hash the bytes already on disk first) and compared with the expected hash
+//! pinned in the app, published by the download server or kept in the models
+//! directory's checksum manifest. A file that doesn't match is moved to a
+//! quarantine folder and downloaded again; installed models can be re-checked
+//! at any time with `verify_models`. Files whose hash is only known from their
+//! own first download are reported as unverified.
+//!
+//! # Module Structure
+//!
+//! - `digest`: Streaming SHA-256 over downloads and installed files
+//! - `manifest`: Expected hashes per model file (`known_checksums.json`,
+//!   `checksums.json`)
+//! - `commands`: Tauri command re-checking every installed model
+
+pub mod commands;
+pub mod digest;
+pub mod manifest;
+
+pub use digest::{sha256_file, DownloadHasher};
+pub use manifest::{expected_sha256, known_digest, DigestSource, FileDigest};
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Serialize;
+
+/// Attempts per model download when the downloaded file fails verification
+pub const MAX_DOWNLOAD_ATTEMPTS: u32 = 2;
+
+/// Folder (inside a models directory) for files that failed verification
+const QUARANTINE_DIR: &str = ".quarantine";
+
+#[derive(thiserror::Error, Debug)]
+pub enum IntegrityError {
+    #[error("Checksum mismatch for {file}: expected sha256 {expected}, got {actual}")]
+    Mismatch {
+        file: String,
+        expected: String,
+        actual: String,
+        quarantined: PathBuf,
+    },
+}
+
+/// Whether a download failed verification (and is worth another attempt)
+pub fn is_mismatch(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<IntegrityError>().is_some()
+}
+
+/// Result of re-checking one installed file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    /// Matches a pinned or published hash
+    Verified,
+    /// No pinned or published hash is known for the file (it may still match
+    /// the hash recorded when it was downloaded)
+    Unverified,
+    /// Doesn't match; the file was quarantined
+    Mismatch,
+    /// The file couldn't be hashed
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileCheck {
+    /// Path relative to the models directory
+    pub file: String,
+    pub status: CheckStatus,
+    pub sha256: Option<String>,
+    pub expected_sha256: Option<String>,
+    pub size: Option<u64>,
+    pub quarantined_to: Option<PathBuf>,
+    pub error: Option<String>,
+}
+
+/// Checks of all files of one installed model
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelVerification {
+    /// "whisper", "parakeet", "builtin_ai", "diarization" or "semantic"
+    pub engine: String,
+    pub model: String,
+    pub files: Vec<FileCheck>,
+}
+
+impl ModelVerification {
+    pub fn new(engine: &str, model: &str, files: Vec<FileCheck>) -> Self {
+        Self {
+            engine: engine.to_string(),
+            model: model.to_string(),
+            files,
+        }
+    }
+
+    /// False if any file was quarantined, i.e. the model needs downloading again
+    pub fn is_intact(&self) -> bool {
+        self.files
+            .iter()
+            .all(|check| check.status != CheckStatus::Mismatch)
+    }
+}
+
+/// Compare a completed download of `file`, still at `downloaded` (e.g. its
+/// `.part` file), with the hash it must have
+///
+/// A match (or any file, when no hash is expected) is moved into place and
+/// recorded in the manifest. On a mismatch the download is quarantined
+/// without ever replacing `file`, and `IntegrityError::Mismatch` is
+/// returned, so the caller can download again.
+pub fn check_download(
+    models_dir: &Path,
+    file: &str,
+    downloaded: &Path,
+    expected: Option<&str>,
+    hasher: DownloadHasher,
+) -> Result<String> {
+    let size = hasher.bytes();
+    let actual = hasher.finalize();
+
+    if let Some(expected) = expected.filter(|expected| *expected != actual) {
+        let quarantined = quarantine_from(models_dir, file, downloaded)?;
+        log::error!(
+            "Download of {} failed verification (expected sha256 {}, got {}), moved to {}",
+            file,
+            expected,
+            actual,
+            quarantined.display()
+        );
+        return Err(IntegrityError::Mismatch {
+            file: file.to_string(),
+            expected: expected.to_string(),
+            actual,
+            quarantined,
+        }
+        .into());
+    }
+
+    let source = if expected.is_some() {
+        log::info!("Verified {} (sha256 {})", file, actual);
+        DigestSource::Published
+    } else {
+        log::info!(
+            "No published checksum for {}, recorded sha256 {}",
+            file,
+            actual
+        );
+        DigestSource::Recorded
+    };
+    std::fs::rename(downloaded, models_dir.join(file))
+        .map_err(|e| anyhow::anyhow!("Failed to move {} into place: {}", file, e))?;
+    manifest::record(
+        models_dir,
+        file,
+        FileDigest {
+            sha256: actual.clone(),
+            size,
+            source,
+        },
+    )?;
+    discard_quarantined(models_dir, file);
+    Ok(actual)
+}
+
+/// Re-hash an installed file and compare it with its expected hash,
+/// quarantining it on a mismatch
+///
+/// The hash pinned for `url` or published in the manifest is checked first;
+/// failing that, the hash the server publishes for `url` (recorded if it
+/// matches). A file only known by the hash recorded at download is checked
+/// against it but stays unverified.
+pub async fn verify_installed(models_dir: &Path, file: &str, url: Option<&str>) -> FileCheck {
+    let mut check = FileCheck {
+        file: file.to_string(),
+        status: CheckStatus::Unverified,
+        sha256: None,
+        expected_sha256: None,
+        size: None,
+        quarantined_to: None,
+        error: None,
+    };
+
+    let (actual, size) = match sha256_file(&models_dir.join(file)).await {
+        Ok(digest) => digest,
+        Err(e) => {
+            log::warn!("Failed to verify {}: {}", file, e);
+            check.status = CheckStatus::Failed;
+            check.error = Some(e.to_string());
+            return check;
+        }
+    };
+    check.size = Some(size);
+
+    let known = manifest::lookup(models_dir, file);
+    let published = match (url.and_then(known_digest), &known) {
+        (Some(pinned), _) => Some(pinned.sha256),
+        (None, Some(digest)) if digest.source == DigestSource::Published => {
+            Some(digest.sha256.clone())
+        }
+        (None, _) => match url {
+            Some(url) => manifest::published_sha256(url).await,
+            None => None,
+        },
+    };
+    let expected = published
+        .clone()
+        .or_else(|| known.as_ref().map(|digest| digest.sha256.clone()));
+
+    check.status = match expected.as_deref() {
+        None => CheckStatus::Unverified,
+        Some(expected) if expected != actual => CheckStatus::Mismatch,
+        Some(_) if published.is_some() => CheckStatus::Verified,
+        Some(_) => CheckStatus::Unverified,
+    };
+
+    let recorded_published = known
+        .as_ref()
+        .is_some_and(|digest| digest.source == DigestSource::Published && digest.sha256 == actual);
+    if check.status == CheckStatus::Verified && !recorded_published {
+        let digest = FileDigest {
+            sha256: actual.clone(),
+            size,
+            source: DigestSource::Published,
+        };
+        if let Err(e) = manifest::record(models_dir, file, digest) {
+            log::warn!("Failed to record checksum of {}: {}", file, e);
+        }
+    }
+
+    if check.status == CheckStatus::Mismatch {
+        match quarantine(models_dir, file) {
+            Ok(path) => {
+                log::error!(
+                    "{} doesn't match its expected sha256 {} (got {}), moved to {}",
+                    file,
+                    expected.as_deref().unwrap_or_default(),
+                    actual,
+                    path.display()
+                );
+                check.quarantined_to = Some(path);
+            }
+            Err(e) => {
+                log::error!("Failed to quarantine {}: {}", file, e);
+                check.error = Some(e.to_string());
+            }
+        }
+    }
+
+    check.sha256 = Some(actual);
+    check.expected_sha256 = expected;
+    check
+}
+
+/// Move a file that failed verification out of the way, keeping it for inspection
+pub fn quarantine(models_dir: &Path, file: &str) -> Result<PathBuf> {
+    quarantine_from(models_dir, file, &models_dir.join(file))
+}
+
+/// Quarantine `source` as the copy of `file` that failed verification
+fn quarantine_from(models_dir: &Path, file: &str, source: &Path) -> Result<PathBuf> {
+    let quarantine_dir = models_dir.join(QUARANTINE_DIR);
+    std::fs::create_dir_all(&quarantine_dir)?;
+
+    // One slot per file: an older quarantined copy is replaced
+    let target = quarantine_dir.join(quarantine_name(file));
+    if target.exists() {
+        std::fs::remove_file(&target)?;
+    }
+    std::fs::rename(source, &target)?;
+    manifest::forget_recorded(models_dir, file)?;
+    Ok(target)
+}
+
+/// Delete the quarantined copy of a file that has since downloaded correctly
+fn discard_quarantined(models_dir: &Path, file: &str) {
+    let path = models_dir.join(QUARANTINE_DIR).join(quarantine_name(file));
+    if path.exists() {
+        match std::fs::remove_file(&path) {
+            Ok(()) => log::info!("Removed quarantined copy {}", path.display()),
+            Err(e) => log::warn!("Failed to remove {}: {}", path.display(), e),
+        }
+    }
+}
+
+fn quarantine_name(file: &str) -> String {
+    file.replace(['/', '\\'], "__")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HELLO_WORLD: &str = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+    fn hashed(bytes: &[u8]) -> DownloadHasher {
+        let mut hasher = DownloadHasher::new();
+        hasher.update(bytes);
+        hasher
+    }
+
+    #[tokio::test]
+    async fn test_mismatch_is_quarantined_and_redownload_verifies() {
+        let dir = tempfile::tempdir().unwrap();
+        let models_dir = dir.path();
+        std::fs::create_dir_all(models_dir.join("model")).unwrap();
+        let file = "model/encoder.onnx";
+        let part = models_dir.join("model/encoder.onnx.part");
+
+        std::fs::write(&part, b"hello w0rld").unwrap();
+        let error = check_download(
+            models_dir,
+            file,
+            &part,
+            Some(HELLO_WORLD),
+            hashed(b"hello w0rld"),
+        )
+        .unwrap_err();
+        assert!(is_mismatch(&error));
+        assert!(!part.exists());
+        assert!(!models_dir.join(file).exists());
+        let quarantined = models_dir.join(QUARANTINE_DIR).join("model__encoder.onnx");
+        assert!(quarantined.exists());
+
+        std::fs::write(&part, b"hello world").unwrap();
+        check_download(
+            models_dir,
+            file,
+            &part,
+            Some(HELLO_WORLD),
+            hashed(b"hello world"),
+        )
+        .unwrap();
+        assert!(!part.exists());
+        assert!(!quarantined.exists());
+
+        let check = verify_installed(models_dir, file, None).await;
+        assert_eq!(check.status, CheckStatus::Verified);
+
+        // Changed on disk after download
+        std::fs::write(models_dir.join(file), b"hello").unwrap();
+        let check = verify_installed(models_dir, file, None).await;
+        assert_eq!(check.status, CheckStatus::Mismatch);
+        assert_eq!(check.quarantined_to, Some(quarantined));
+        assert!(!models_dir.join(file).exists());
+    }
+
+    #[tokio::test]
+    async fn test_unpublished_download_is_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let part = dir.path().join("vocab.txt.part");
+        std::fs::write(&part, b"hello world").unwrap();
+
+        check_download(dir.path(), "vocab.txt", &part, None, hashed(b"hello world")).unwrap();
+        let digest = manifest::lookup(dir.path(), "vocab.txt").unwrap();
+        assert_eq!(digest.source, DigestSource::Recorded);
+        assert_eq!(digest.size, 11);
+
+        // Matches only its own recorded hash
+        let check = verify_installed(dir.path(), "vocab.txt", None).await;
+        assert_eq!(check.status, CheckStatus::Unverified);
+        assert_eq!(check.expected_sha256.as_deref(), Some(HELLO_WORLD));
+
+        // Still caught when it changes on disk
+        std::fs::write(dir.path().join("vocab.txt"), b"hello").unwrap();
+        let check = verify_installed(dir.path(), "vocab.txt", None).await;
+        assert_eq!(check.status, CheckStatus::Mismatch);
+
+        let missing = verify_installed(dir.path(), "missing.bin", None).await;
+        assert_eq!(missing.status, CheckStatus::Failed);
+    }
+}
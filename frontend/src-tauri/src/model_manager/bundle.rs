@@ -0,0 +1,1300 @@
+// Offline model bundles
+// Models travel between machines as a folder or an archive (tar, tar.gz or
+// zip). Import finds catalog models anywhere inside by their catalog file
+// paths, checks each file against the hash known here (verified) or else the
+// one shipped in the bundle (which only catches damage in transit), installs
+// it into the engine's models directory and lets the engine validate it.
+// Other GGUF files are installed as custom built-in AI models. Export writes
+// installed models to a tar archive laid out as `<engine>/<file>`, with their
+// hashes in a `meetily-models.json` manifest
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use super::download::part_path;
+use super::{
+    disk, remove_files, CatalogEntry, DownloadPhase, EngineKind, ModelEngine, ModelManagerError,
+    ModelRegistry, ModelState,
+};
+use crate::model_integrity::{
+    self, manifest, CheckStatus, DigestSource, DownloadHasher, FileCheck, FileDigest,
+    ModelVerification,
+};
+use crate::summary::summary_engine::custom_models;
+
+/// Manifest at the root of exported bundles
+pub const BUNDLE_MANIFEST: &str = "meetily-models.json";
+
+const BUNDLE_VERSION: u32 = 1;
+
+/// How deep import looks for models below the folder it was given
+const MAX_SCAN_DEPTH: usize = 8;
+
+/// Folder (inside the built-in AI models directory) for imported custom models
+const CUSTOM_MODELS_DIR: &str = "custom";
+
+/// Write buffer for exports (multi-GB files)
+const WRITE_BUFFER_SIZE: usize = 8 * 1024 * 1024;
+
+/// One file of an exported model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleFile {
+    /// Path relative to the engine's models directory
+    pub path: String,
+    pub sha256: String,
+    pub size: u64,
+    /// Whether the hash was published by the download server or only recorded
+    pub source: DigestSource,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleModel {
+    pub engine: EngineKind,
+    pub name: String,
+    pub files: Vec<BundleFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BundleManifest {
+    version: u32,
+    models: Vec<BundleModel>,
+}
+
+/// A model picked for export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelRef {
+    pub engine: EngineKind,
+    pub model: String,
+}
+
+/// Something found in a bundle that wasn't installed
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SkippedItem {
+    /// Model name, or path inside the bundle for unrecognized files
+    pub name: String,
+    pub engine: Option<EngineKind>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportReport {
+    /// Installed models with the checksum result of each file
+    pub installed: Vec<ModelVerification>,
+    pub skipped: Vec<SkippedItem>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportReport {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub models: Vec<BundleModel>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ArchiveFormat {
+    Tar,
+    TarGz,
+    Zip,
+}
+
+impl ArchiveFormat {
+    fn detect(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// A catalog model whose files were all found below `prefix`
+struct FoundModel {
+    engine: Arc<dyn ModelEngine>,
+    entry: CatalogEntry,
+    prefix: String,
+}
+
+/// Unpacked archive contents, removed however the import ends
+struct StagingDir {
+    path: PathBuf,
+}
+
+impl StagingDir {
+    fn create(parent: &Path) -> Result<Self> {
+        let path = parent.join(format!(".import-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        Ok(Self { path })
+    }
+}
+
+impl Drop for StagingDir {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_dir_all(&self.path) {
+            log::warn!("Failed to remove {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// Hashes what is read through it
+struct HashingReader<R> {
+    inner: R,
+    hasher: DownloadHasher,
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hasher.update(&buf[..read]);
+        Ok(read)
+    }
+}
+
+impl ModelRegistry {
+    /// Install the models found in a folder or archive
+    ///
+    /// Archives are unpacked into a folder below `staging_dir` (best on the
+    /// same disk as the models directories) that is removed afterwards; a
+    /// folder's files are copied. Models that are already installed, don't
+    /// match their checksum or are rejected by their engine are skipped.
+    pub async fn import_bundle(&self, source: &Path, staging_dir: &Path) -> Result<ImportReport> {
+        let catalogs: Vec<(Arc<dyn ModelEngine>, CatalogEntry)> = self
+            .engines(None)
+            .into_iter()
+            .flat_map(|engine| {
+                engine
+                    .catalog()
+                    .into_iter()
+                    .map(move |entry| (engine.clone(), entry))
+            })
+            .collect();
+        if catalogs.is_empty() {
+            return Err(anyhow!("No model engines are initialized"));
+        }
+
+        let staging = if source.is_dir() {
+            None
+        } else {
+            let format = ArchiveFormat::detect(source).ok_or_else(|| {
+                anyhow!(
+                    "Unsupported model bundle {}: expected a folder or a .tar, .tar.gz, .tgz or .zip archive",
+                    source.display()
+                )
+            })?;
+            let staging = StagingDir::create(staging_dir)?;
+            let wanted: Vec<String> = catalogs
+                .iter()
+                .flat_map(|(_, entry)| entry.files.iter().map(|file| file.path.clone()))
+                .collect();
+            let (archive, target) = (source.to_path_buf(), staging.path.clone());
+            log::info!("Unpacking model bundle {}", archive.display());
+            tokio::task::spawn_blocking(move || {
+                // Check the whole bundle fits before writing any of it
+                let size = unpacked_size(format, &archive, &wanted)?;
+                disk::ensure_space(&target, size)?;
+                unpack(format, &archive, &target, &wanted)
+            })
+            .await
+            .map_err(|e| anyhow!("Unpacking task failed: {}", e))??;
+            Some(staging)
+        };
+        let root = staging
+            .as_ref()
+            .map_or_else(|| source.to_path_buf(), |staging| staging.path.clone());
+
+        let scan_root = root.clone();
+        let files = tokio::task::spawn_blocking(move || scan(&scan_root))
+            .await
+            .map_err(|e| anyhow!("Scanning task failed: {}", e))??;
+        let shipped = read_bundle_manifests(&root, &files);
+        let (found, unrecognized) = find_models(catalogs, &files);
+
+        let mut report = ImportReport::default();
+        let mut touched = Vec::new();
+        for found in found {
+            let kind = found.engine.kind();
+            let model_root = root.join(&found.prefix);
+            let copy = staging.is_none();
+            match self
+                .import_model(
+                    found.engine.as_ref(),
+                    &found.entry,
+                    &model_root,
+                    &shipped,
+                    copy,
+                )
+                .await
+            {
+                Ok(verification) => {
+                    log::info!("Imported {} model {}", kind, found.entry.name);
+                    report.installed.push(verification);
+                    touch(&mut touched, &found.engine);
+                }
+                Err(e) => {
+                    log::warn!("Skipped {} model {}: {}", kind, found.entry.name, e);
+                    report.skipped.push(SkippedItem {
+                        name: found.entry.name.clone(),
+                        engine: Some(kind),
+                        reason: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        let (custom, unrecognized): (Vec<String>, Vec<String>) =
+            unrecognized.into_iter().partition(|path| is_gguf(path));
+        for path in custom {
+            match self
+                .import_custom_model(&root.join(&path), staging.is_none())
+                .await
+            {
+                Ok((engine, verification)) => {
+                    log::info!("Imported {} as custom model {}", path, verification.model);
+                    report.installed.push(verification);
+                    touch(&mut touched, &engine);
+                }
+                Err(e) => {
+                    log::warn!("Skipped custom model {}: {}", path, e);
+                    report.skipped.push(SkippedItem {
+                        name: path,
+                        engine: Some(EngineKind::BuiltinAi),
+                        reason: e.to_string(),
+                    });
+                }
+            }
+        }
+        report
+            .skipped
+            .extend(unrecognized.into_iter().map(|name| SkippedItem {
+                name,
+                engine: None,
+                reason: "Not a recognized Whisper, Parakeet or built-in AI model".to_string(),
+            }));
+
+        for engine in touched {
+            engine.refresh().await;
+        }
+        Ok(report)
+    }
+
+    async fn import_model(
+        &self,
+        engine: &dyn ModelEngine,
+        entry: &CatalogEntry,
+        source_root: &Path,
+        shipped: &HashMap<(EngineKind, String), BundleFile>,
+        copy: bool,
+    ) -> Result<ModelVerification> {
+        let kind = engine.kind();
+        let models_dir = engine.models_dir();
+        if self.state(engine, &models_dir, entry).await == ModelState::Installed {
+            return Err(anyhow!("Already installed"));
+        }
+        // Keeps downloads of the model out while its files are replaced
+        let (_guard, _) = self.claim(
+            kind,
+            &entry.name,
+            DownloadPhase::Verifying,
+            entry.total_bytes(),
+        )?;
+
+        // Check every file before touching the models directory. Only hashes
+        // known here verify a file; a bundle's own hashes just catch damage
+        // in transit, as whoever made the bundle could have changed both
+        let mut checks = Vec::new();
+        for file in &entry.files {
+            let (actual, size) =
+                model_integrity::sha256_file(&source_root.join(&file.path)).await?;
+            let known = model_integrity::known_digest(&file.url)
+                .or_else(|| {
+                    manifest::lookup(&models_dir, &file.path)
+                        .filter(|digest| digest.source == DigestSource::Published)
+                })
+                .map(|digest| digest.sha256);
+            let expected = known.clone().or_else(|| {
+                shipped
+                    .get(&(kind, file.path.clone()))
+                    .map(|file| file.sha256.clone())
+            });
+            if let Some(expected) = expected.as_ref().filter(|expected| **expected != actual) {
+                return Err(anyhow!(
+                    "Checksum mismatch for {}: expected sha256 {}, got {}",
+                    file.path,
+                    expected,
+                    actual
+                ));
+            }
+
+            let (status, source) = if known.is_some() {
+                (CheckStatus::Verified, DigestSource::Published)
+            } else {
+                (CheckStatus::Unverified, DigestSource::Recorded)
+            };
+            let check = FileCheck {
+                file: file.path.clone(),
+                status,
+                sha256: Some(actual),
+                expected_sha256: expected,
+                size: Some(size),
+                quarantined_to: None,
+                error: None,
+            };
+            checks.push((check, source));
+        }
+        if copy {
+            let needed = checks.iter().filter_map(|(check, _)| check.size).sum();
+            disk::ensure_space(&models_dir, needed)?;
+        }
+
+        for (check, source) in &checks {
+            let installed = install_file(
+                &source_root.join(&check.file),
+                &models_dir.join(&check.file),
+                copy,
+            )
+            .await
+            .and_then(|()| {
+                manifest::record(
+                    &models_dir,
+                    &check.file,
+                    FileDigest {
+                        sha256: check.sha256.clone().unwrap_or_default(),
+                        size: check.size.unwrap_or_default(),
+                        source: *source,
+                    },
+                )
+            });
+            if let Err(e) = installed {
+                remove_files(&models_dir, entry).await;
+                return Err(e);
+            }
+        }
+
+        if let Err(e) = engine.validate(entry).await {
+            remove_files(&models_dir, entry).await;
+            return Err(anyhow!("Not usable by the {} engine: {}", kind, e));
+        }
+
+        let checks = checks.into_iter().map(|(check, _)| check).collect();
+        Ok(ModelVerification::new(kind.as_str(), &entry.name, checks))
+    }
+
+    /// Install a GGUF file that isn't a catalog model as a custom built-in AI model
+    async fn import_custom_model(
+        &self,
+        source: &Path,
+        copy: bool,
+    ) -> Result<(Arc<dyn ModelEngine>, ModelVerification)> {
+        let engine = self.engine(EngineKind::BuiltinAi)?;
+        let file_name = source
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| anyhow!("Invalid file name {}", source.display()))?;
+        let file = format!("{}/{}", CUSTOM_MODELS_DIR, file_name);
+        let models_dir = engine.models_dir();
+        let target = models_dir.join(&file);
+        if target.exists() {
+            return Err(anyhow!("Already installed"));
+        }
+
+        // Reject files without a readable GGUF header before copying gigabytes
+        let header = source.to_path_buf();
+        tokio::task::spawn_blocking(move || custom_models::inspect(&header))
+            .await
+            .map_err(|e| anyhow!("Inspection task failed: {}", e))??;
+        let (actual, size) = model_integrity::sha256_file(source).await?;
+        if copy {
+            disk::ensure_space(&models_dir, size)?;
+        }
+        install_file(source, &target, copy).await?;
+
+        let model = match custom_models::register(&models_dir, &target, None) {
+            Ok(model) => model,
+            Err(e) => {
+                let _ = fs::remove_file(&target).await;
+                return Err(e);
+            }
+        };
+        let check = FileCheck {
+            file,
+            status: CheckStatus::Unverified,
+            sha256: Some(actual),
+            expected_sha256: None,
+            size: Some(size),
+            quarantined_to: None,
+            error: None,
+        };
+        let verification =
+            ModelVerification::new(EngineKind::BuiltinAi.as_str(), &model.name, vec![check]);
+        Ok((engine, verification))
+    }
+
+    /// Write installed models to a tar archive at `destination`
+    ///
+    /// An empty `models` exports every installed model.
+    pub async fn export_bundle(
+        &self,
+        models: &[ModelRef],
+        destination: &Path,
+    ) -> Result<ExportReport> {
+        let mut selected = Vec::new();
+        for engine in self.engines(None) {
+            let models_dir = engine.models_dir();
+            for entry in engine.catalog() {
+                let requested = models
+                    .iter()
+                    .any(|model| model.engine == entry.engine && model.model == entry.name);
+                if !models.is_empty() && !requested {
+                    continue;
+                }
+                let state = self.state(engine.as_ref(), &models_dir, &entry).await;
+                if state == ModelState::Installed {
+                    selected.push((models_dir.clone(), entry));
+                } else if requested {
+                    return Err(anyhow!(
+                        "{} model {} is not installed",
+                        entry.engine,
+                        entry.name
+                    ));
+                }
+            }
+        }
+        for model in models {
+            let known = selected
+                .iter()
+                .any(|(_, entry)| entry.engine == model.engine && entry.name == model.model);
+            if !known {
+                self.engine(model.engine)?;
+                return Err(ModelManagerError::UnknownModel {
+                    engine: model.engine,
+                    model: model.model.clone(),
+                }
+                .into());
+            }
+        }
+        if selected.is_empty() {
+            return Err(anyhow!("No installed models to export"));
+        }
+
+        let destination = destination.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            let part = part_path(&destination);
+            let result = write_bundle(&selected, &part).and_then(|models| {
+                std::fs::rename(&part, &destination).with_context(|| {
+                    format!("Failed to move bundle to {}", destination.display())
+                })?;
+                let size_bytes = std::fs::metadata(&destination)?.len();
+                log::info!(
+                    "Exported {} models to {} ({} MB)",
+                    models.len(),
+                    destination.display(),
+                    size_bytes / (1024 * 1024)
+                );
+                Ok(ExportReport {
+                    path: destination.clone(),
+                    size_bytes,
+                    models,
+                })
+            });
+            if result.is_err() {
+                let _ = std::fs::remove_file(&part);
+            }
+            result
+        })
+        .await
+        .map_err(|e| anyhow!("Export task failed: {}", e))?
+    }
+}
+
+/// Remember an engine whose models changed, to refresh it once
+fn touch(touched: &mut Vec<Arc<dyn ModelEngine>>, engine: &Arc<dyn ModelEngine>) {
+    if !touched
+        .iter()
+        .any(|touched| touched.kind() == engine.kind())
+    {
+        touched.push(engine.clone());
+    }
+}
+
+/// Move (or copy) a file into place through its `.part` path, so a half
+/// copied file never looks installed
+async fn install_file(source: &Path, target: &Path, copy: bool) -> Result<()> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let part = part_path(target);
+    // Moving fails across disks; copy then
+    let moved = !copy && fs::rename(source, &part).await.is_ok();
+    if !moved {
+        fs::copy(source, &part)
+            .await
+            .with_context(|| format!("Failed to copy {}", source.display()))?;
+    }
+    fs::rename(&part, target)
+        .await
+        .with_context(|| format!("Failed to move {} into place", target.display()))
+}
+
+/// Extract the archive entries that can belong to a catalog model, plus bundle manifests
+fn unpack(format: ArchiveFormat, archive: &Path, target: &Path, wanted: &[String]) -> Result<()> {
+    let file =
+        File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+    match format {
+        ArchiveFormat::Tar => unpack_tar(BufReader::new(file), target, wanted),
+        ArchiveFormat::TarGz => unpack_tar(GzDecoder::new(BufReader::new(file)), target, wanted),
+        ArchiveFormat::Zip => unpack_zip(BufReader::new(file), target, wanted),
+    }
+    .with_context(|| format!("Failed to unpack {}", archive.display()))
+}
+
+/// Total size of the archive entries `unpack` extracts
+///
+/// Tar archives have no index, so they are read through once (a .tar.gz is
+/// decompressed twice).
+fn unpacked_size(format: ArchiveFormat, archive: &Path, wanted: &[String]) -> Result<u64> {
+    let file =
+        File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+    match format {
+        ArchiveFormat::Tar => tar_unpacked_size(BufReader::new(file), wanted),
+        ArchiveFormat::TarGz => tar_unpacked_size(GzDecoder::new(BufReader::new(file)), wanted),
+        ArchiveFormat::Zip => zip_unpacked_size(BufReader::new(file), wanted),
+    }
+    .with_context(|| format!("Failed to read {}", archive.display()))
+}
+
+fn tar_unpacked_size(reader: impl Read, wanted: &[String]) -> Result<u64> {
+    let mut archive = tar::Archive::new(reader);
+    let mut total = 0u64;
+    for entry in archive.entries()? {
+        let entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        if archive_path(&entry.path()?).is_some_and(|name| is_wanted(&name, wanted)) {
+            total = total.saturating_add(entry.size());
+        }
+    }
+    Ok(total)
+}
+
+fn zip_unpacked_size(reader: impl Read + io::Seek, wanted: &[String]) -> Result<u64> {
+    let mut archive = zip::ZipArchive::new(reader)?;
+    let mut total = 0u64;
+    for index in 0..archive.len() {
+        let entry = archive.by_index(index)?;
+        if entry.is_dir() || entry.is_symlink() {
+            continue;
+        }
+        let name = entry.enclosed_name().and_then(|path| archive_path(&path));
+        if name.is_some_and(|name| is_wanted(&name, wanted)) {
+            total = total.saturating_add(entry.size());
+        }
+    }
+    Ok(total)
+}
+
+fn unpack_tar(reader: impl Read, target: &Path, wanted: &[String]) -> Result<()> {
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        // Links could point outside the target folder
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let Some(name) = archive_path(&entry.path()?) else {
+            continue;
+        };
+        if is_wanted(&name, wanted) {
+            let size = entry.size();
+            extract(&mut entry, target, &name, size)?;
+        }
+    }
+    Ok(())
+}
+
+fn unpack_zip(reader: impl Read + io::Seek, target: &Path, wanted: &[String]) -> Result<()> {
+    let mut archive = zip::ZipArchive::new(reader)?;
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if entry.is_dir() || entry.is_symlink() {
+            continue;
+        }
+        let Some(name) = entry.enclosed_name().and_then(|path| archive_path(&path)) else {
+            continue;
+        };
+        if is_wanted(&name, wanted) {
+            let size = entry.size();
+            extract(&mut entry, target, &name, size)?;
+        }
+    }
+    Ok(())
+}
+
+fn extract(reader: &mut impl Read, target: &Path, name: &str, size: u64) -> Result<()> {
+    disk::ensure_space(target, size)?;
+    let path = target.join(name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut writer = BufWriter::with_capacity(WRITE_BUFFER_SIZE, File::create(&path)?);
+    io::copy(reader, &mut writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// A relative path inside an archive as `a/b/c`; `None` if it could escape the target folder
+fn archive_path(path: &Path) -> Option<String> {
+    let mut parts = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_str()?.to_string()),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+fn is_wanted(name: &str, wanted: &[String]) -> bool {
+    file_name(name) == BUNDLE_MANIFEST
+        || is_gguf(name)
+        || wanted
+            .iter()
+            .any(|path| strip_path_suffix(name, path).is_some())
+}
+
+fn is_gguf(path: &str) -> bool {
+    path.to_lowercase().ends_with(".gguf")
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// The folder `path` is in if it ends with the relative path `suffix`
+/// ("" when they're equal)
+fn strip_path_suffix<'a>(path: &'a str, suffix: &str) -> Option<&'a str> {
+    if path == suffix {
+        return Some("");
+    }
+    path.strip_suffix(suffix)?.strip_suffix('/')
+}
+
+fn join_path(prefix: &str, path: &str) -> String {
+    if prefix.is_empty() {
+        path.to_string()
+    } else {
+        format!("{}/{}", prefix, path)
+    }
+}
+
+/// Relative paths (`a/b/c`) of the regular files below `root`
+///
+/// Hidden folders (quarantine, unpacked imports) and links are skipped.
+fn scan(root: &Path) -> Result<Vec<String>> {
+    let mut files = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), String::new(), 0)];
+    while let Some((dir, prefix, depth)) = pending.pop() {
+        let entries =
+            std::fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+        for entry in entries {
+            let entry = entry?;
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            let file_type = entry.file_type()?;
+            let path = join_path(&prefix, &name);
+            if file_type.is_file() {
+                files.push(path);
+            } else if file_type.is_dir() && !name.starts_with('.') && depth < MAX_SCAN_DEPTH {
+                pending.push((entry.path(), path, depth + 1));
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Hashes shipped in bundle manifests, by engine and file path
+fn read_bundle_manifests(
+    root: &Path,
+    files: &[String],
+) -> HashMap<(EngineKind, String), BundleFile> {
+    let mut shipped = HashMap::new();
+    for path in files
+        .iter()
+        .filter(|path| file_name(path) == BUNDLE_MANIFEST)
+    {
+        let manifest = std::fs::read_to_string(root.join(path))
+            .map_err(anyhow::Error::from)
+            .and_then(|json| Ok(serde_json::from_str::<BundleManifest>(&json)?));
+        match manifest {
+            Ok(manifest) => {
+                for model in manifest.models {
+                    for file in model.files {
+                        shipped.insert((model.engine, file.path.clone()), file);
+                    }
+                }
+            }
+            Err(e) => log::warn!("Ignoring invalid bundle manifest {}: {}", path, e),
+        }
+    }
+    shipped
+}
+
+/// Catalog models with all files present (the first location found wins),
+/// and the model-like files that belong to none of them
+fn find_models(
+    catalogs: Vec<(Arc<dyn ModelEngine>, CatalogEntry)>,
+    files: &[String],
+) -> (Vec<FoundModel>, Vec<String>) {
+    let present: HashSet<&str> = files.iter().map(String::as_str).collect();
+    let mut claimed = HashSet::new();
+    let mut found = Vec::new();
+    for (engine, entry) in catalogs {
+        let Some(first) = entry.files.first() else {
+            continue;
+        };
+        let prefix = files
+            .iter()
+            .filter_map(|path| strip_path_suffix(path, &first.path))
+            .find(|prefix| {
+                entry
+                    .files
+                    .iter()
+                    .all(|file| present.contains(join_path(prefix, &file.path).as_str()))
+            });
+        if let Some(prefix) = prefix {
+            claimed.extend(entry.files.iter().map(|file| join_path(prefix, &file.path)));
+            found.push(FoundModel {
+                engine,
+                entry,
+                prefix: prefix.to_string(),
+            });
+        }
+    }
+
+    // Report ONNX models by folder, as Parakeet models are folders
+    let unrecognized: BTreeSet<String> = files
+        .iter()
+        .filter(|path| !claimed.contains(*path))
+        .filter_map(|path| {
+            let extension = Path::new(path).extension()?.to_str()?.to_lowercase();
+            match extension.as_str() {
+                "bin" | "gguf" => Some(path.clone()),
+                "onnx" => Some(
+                    path.rsplit_once('/')
+                        .map_or(path.as_str(), |(dir, _)| dir)
+                        .to_string(),
+                ),
+                _ => None,
+            }
+        })
+        .collect();
+    (found, unrecognized.into_iter().collect())
+}
+
+/// Write the models' files and a manifest of their hashes to a tar archive
+fn write_bundle(models: &[(PathBuf, CatalogEntry)], path: &Path) -> Result<Vec<BundleModel>> {
+    let file =
+        File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut builder = tar::Builder::new(BufWriter::with_capacity(WRITE_BUFFER_SIZE, file));
+
+    let mut exported = Vec::new();
+    for (models_dir, entry) in models {
+        let mut files = Vec::new();
+        for file in &entry.files {
+            let source = models_dir.join(&file.path);
+            let reader = File::open(&source)
+                .with_context(|| format!("Failed to open {}", source.display()))?;
+            let size = reader.metadata()?.len();
+            let mut reader = HashingReader {
+                inner: BufReader::new(reader),
+                hasher: DownloadHasher::new(),
+            };
+            builder
+                .append_data(
+                    &mut file_header(size),
+                    format!("{}/{}", entry.engine, file.path),
+                    &mut reader,
+                )
+                .with_context(|| format!("Failed to add {} to the bundle", file.path))?;
+
+            let sha256 = reader.hasher.finalize();
+            let published = manifest::lookup(models_dir, &file.path).is_some_and(|digest| {
+                digest.source == DigestSource::Published && digest.sha256 == sha256
+            });
+            files.push(BundleFile {
+                path: file.path.clone(),
+                sha256,
+                size,
+                source: if published {
+                    DigestSource::Published
+                } else {
+                    DigestSource::Recorded
+                },
+            });
+        }
+        exported.push(BundleModel {
+            engine: entry.engine,
+            name: entry.name.clone(),
+            files,
+        });
+    }
+
+    // Last, as the hashes are computed while the files are written
+    let manifest = serde_json::to_vec_pretty(&BundleManifest {
+        version: BUNDLE_VERSION,
+        models: exported.clone(),
+    })?;
+    builder.append_data(
+        &mut file_header(manifest.len() as u64),
+        BUNDLE_MANIFEST,
+        manifest.as_slice(),
+    )?;
+    builder.into_inner()?.flush()?;
+    Ok(exported)
+}
+
+fn file_header(size: u64) -> tar::Header {
+    let mut header = tar::Header::new_gnu();
+    header.set_entry_type(tar::EntryType::Regular);
+    header.set_size(size);
+    header.set_mode(0o644);
+    header.set_mtime(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default(),
+    );
+    header
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    /// Engine whose model files must start with "ok"
+    struct BundleEngine {
+        kind: EngineKind,
+        models_dir: PathBuf,
+        files: Vec<&'static str>,
+    }
+
+    #[async_trait]
+    impl ModelEngine for BundleEngine {
+        fn kind(&self) -> EngineKind {
+            self.kind
+        }
+
+        fn models_dir(&self) -> PathBuf {
+            self.models_dir.clone()
+        }
+
+        fn catalog(&self) -> Vec<CatalogEntry> {
+            let entry = CatalogEntry::new(self.kind, "model", "Model", "");
+            let files = self.files.iter().fold(entry, |entry, file| {
+                entry.with_file(file, &format!("http://127.0.0.1:9/{}", file), 6)
+            });
+            vec![files]
+        }
+
+        async fn validate(&self, entry: &CatalogEntry) -> Result<()> {
+            for file in &entry.files {
+                let contents = std::fs::read(self.models_dir.join(&file.path))?;
+                if !contents.starts_with(b"ok") {
+                    return Err(anyhow!("{} is not a model", file.path));
+                }
+            }
+            Ok(())
+        }
+    }
+
+    fn registry(dir: &Path) -> ModelRegistry {
+        let registry = ModelRegistry::new(1);
+        registry.register(Arc::new(BundleEngine {
+            kind: EngineKind::Whisper,
+            models_dir: dir.join("whisper"),
+            files: vec!["ggml-model.bin"],
+        }));
+        registry.register(Arc::new(BundleEngine {
+            kind: EngineKind::Parakeet,
+            models_dir: dir.join("parakeet"),
+            files: vec!["model/encoder.onnx", "model/vocab.txt"],
+        }));
+        registry.register(Arc::new(BundleEngine {
+            kind: EngineKind::BuiltinAi,
+            models_dir: dir.join("summary"),
+            files: vec!["gemma.gguf"],
+        }));
+        registry
+    }
+
+    fn write(path: &Path, contents: &[u8]) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_export_then_import_on_another_machine() {
+        let here = tempfile::tempdir().unwrap();
+        write(&here.path().join("whisper/ggml-model.bin"), b"ok ggml");
+        write(&here.path().join("parakeet/model/encoder.onnx"), b"ok onnx");
+        write(&here.path().join("parakeet/model/vocab.txt"), b"ok vocab");
+        let bundle = here.path().join("models.tar");
+
+        let export = registry(here.path())
+            .export_bundle(&[], &bundle)
+            .await
+            .unwrap();
+        assert_eq!(export.models.len(), 2);
+        assert!(!part_path(&bundle).exists());
+
+        let there = tempfile::tempdir().unwrap();
+        // Published for the Whisper model on this machine
+        let ggml_sha256 = export.models[0].files[0].sha256.clone();
+        manifest::record(
+            &there.path().join("whisper"),
+            "ggml-model.bin",
+            FileDigest {
+                sha256: ggml_sha256,
+                size: 7,
+                source: DigestSource::Published,
+            },
+        )
+        .unwrap();
+        let report = registry(there.path())
+            .import_bundle(&bundle, there.path())
+            .await
+            .unwrap();
+        assert_eq!(report.installed.len(), 2);
+        assert!(report.skipped.is_empty());
+        // Only the hash known here verifies; the bundle's own hashes don't
+        let statuses: Vec<(&str, CheckStatus)> = report
+            .installed
+            .iter()
+            .flat_map(|model| &model.files)
+            .map(|check| (check.file.as_str(), check.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("ggml-model.bin", CheckStatus::Verified),
+                ("model/encoder.onnx", CheckStatus::Unverified),
+                ("model/vocab.txt", CheckStatus::Unverified),
+            ]
+        );
+        assert_eq!(
+            manifest::lookup(&there.path().join("parakeet"), "model/vocab.txt")
+                .unwrap()
+                .source,
+            DigestSource::Recorded
+        );
+        assert_eq!(
+            std::fs::read(there.path().join("parakeet/model/vocab.txt")).unwrap(),
+            b"ok vocab"
+        );
+        assert!(manifest::lookup(&there.path().join("whisper"), "ggml-model.bin").is_some());
+
+        // Nothing left of the unpacked archive
+        let leftovers: Vec<_> = std::fs::read_dir(there.path())
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with(".import-"))
+            .collect();
+        assert!(leftovers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_folder_skips_unusable_and_unknown_files() {
+        let source = tempfile::tempdir().unwrap();
+        write(
+            &source.path().join("usb/whisper/ggml-model.bin"),
+            b"ok ggml",
+        );
+        write(
+            &source.path().join("usb/parakeet/model/encoder.onnx"),
+            b"garbage",
+        );
+        write(
+            &source.path().join("usb/parakeet/model/vocab.txt"),
+            b"ok vocab",
+        );
+        write(&source.path().join("usb/other/ggml-large.bin"), b"ok ggml");
+
+        let dir = tempfile::tempdir().unwrap();
+        let report = registry(dir.path())
+            .import_bundle(source.path(), dir.path())
+            .await
+            .unwrap();
+
+        assert_eq!(report.installed.len(), 1);
+        assert_eq!(report.installed[0].engine, "whisper");
+        assert_eq!(report.installed[0].files[0].status, CheckStatus::Unverified);
+        // Copied, not moved
+        assert!(source.path().join("usb/whisper/ggml-model.bin").exists());
+        assert!(dir.path().join("whisper/ggml-model.bin").exists());
+
+        let skipped: Vec<(&str, Option<EngineKind>)> = report
+            .skipped
+            .iter()
+            .map(|item| (item.name.as_str(), item.engine))
+            .collect();
+        assert_eq!(
+            skipped,
+            vec![
+                ("model", Some(EngineKind::Parakeet)),
+                ("usb/other/ggml-large.bin", None)
+            ]
+        );
+        // The rejected model isn't left half installed
+        assert!(!dir.path().join("parakeet/model").exists());
+    }
+
+    #[tokio::test]
+    async fn test_zip_import_rejects_checksum_mismatch() {
+        use zip::write::SimpleFileOptions;
+
+        let source = tempfile::tempdir().unwrap();
+        let archive = source.path().join("models.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&archive).unwrap());
+        let options = SimpleFileOptions::default();
+        zip.start_file("bundle/whisper/ggml-model.bin", options)
+            .unwrap();
+        zip.write_all(b"ok tampered").unwrap();
+        zip.start_file("../ggml-model.bin", options).unwrap();
+        zip.write_all(b"ok outside").unwrap();
+        zip.start_file(format!("bundle/{}", BUNDLE_MANIFEST), options)
+            .unwrap();
+        let manifest = BundleManifest {
+            version: BUNDLE_VERSION,
+            models: vec![BundleModel {
+                engine: EngineKind::Whisper,
+                name: "model".to_string(),
+                files: vec![BundleFile {
+                    path: "ggml-model.bin".to_string(),
+                    sha256: "0".repeat(64),
+                    size: 7,
+                    source: DigestSource::Published,
+                }],
+            }],
+        };
+        zip.write_all(&serde_json::to_vec(&manifest).unwrap())
+            .unwrap();
+        zip.finish().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let report = registry(dir.path())
+            .import_bundle(&archive, dir.path())
+            .await
+            .unwrap();
+
+        assert!(report.installed.is_empty());
+        assert_eq!(report.skipped.len(), 1);
+        assert!(report.skipped[0].reason.starts_with("Checksum mismatch"));
+        assert!(!dir.path().join("whisper/ggml-model.bin").exists());
+        assert!(!dir.path().join("ggml-model.bin").exists());
+    }
+
+    #[tokio::test]
+    async fn test_import_registers_other_gguf_files_as_custom_models() {
+        use crate::summary::summary_engine::gguf::{tests::build_gguf, GgufValue};
+
+        let source = tempfile::tempdir().unwrap();
+        let gguf = build_gguf(&[
+            (
+                "general.architecture",
+                GgufValue::String("llama".to_string()),
+            ),
+            ("llama.block_count", GgufValue::U32(16)),
+        ]);
+        write(&source.path().join("usb/llama-import-test.gguf"), &gguf);
+        write(&source.path().join("usb/broken.gguf"), b"not a model");
+
+        let dir = tempfile::tempdir().unwrap();
+        let report = registry(dir.path())
+            .import_bundle(source.path(), dir.path())
+            .await
+            .unwrap();
+
+        assert_eq!(report.installed.len(), 1);
+        let installed = &report.installed[0];
+        assert_eq!(installed.engine, "builtin_ai");
+        assert_eq!(installed.model, "custom:llama-import-test");
+        assert_eq!(installed.files[0].status, CheckStatus::Unverified);
+        let target = dir.path().join("summary/custom/llama-import-test.gguf");
+        assert_eq!(std::fs::read(&target).unwrap(), gguf);
+        assert!(custom_models::registered()
+            .iter()
+            .any(|model| model.name == installed.model
+                && Path::new(&model.gguf_file) == target.canonicalize().unwrap()));
+
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].name, "usb/broken.gguf");
+        assert_eq!(report.skipped[0].engine, Some(EngineKind::BuiltinAi));
+        assert!(!dir.path().join("summary/custom/broken.gguf").exists());
+    }
+
+    #[test]
+    fn test_archive_path_rejects_escaping_paths() {
+        assert_eq!(
+            archive_path(Path::new("./bundle/whisper/ggml-base.bin")).as_deref(),
+            Some("bundle/whisper/ggml-base.bin")
+        );
+        assert_eq!(archive_path(Path::new("../ggml-base.bin")), None);
+        assert_eq!(archive_path(Path::new("bundle/../../ggml-base.bin")), None);
+        assert_eq!(archive_path(Path::new("/tmp/ggml-base.bin")), None);
+        assert_eq!(archive_path(Path::new(".")), None);
+    }
+
+    /// Names and link targets are written as is, since `tar::Builder`
+    /// refuses to write unsafe paths
+    fn raw_tar_header(
+        name: &str,
+        entry_type: tar::EntryType,
+        link: &str,
+        size: u64,
+    ) -> tar::Header {
+        let mut header = tar::Header::new_gnu();
+        header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+        header.as_old_mut().linkname[..link.len()].copy_from_slice(link.as_bytes());
+        header.set_entry_type(entry_type);
+        header.set_size(size);
+        header.set_mode(0o644);
+        header.set_cksum();
+        header
+    }
+
+    /// Every path below `dir`, including hidden folders and links
+    fn all_paths(dir: &Path) -> Vec<String> {
+        let mut paths = Vec::new();
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let entry = entry.unwrap();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.file_type().unwrap().is_dir() {
+                paths.extend(
+                    all_paths(&entry.path())
+                        .into_iter()
+                        .map(|path| format!("{}/{}", name, path)),
+                );
+            } else {
+                paths.push(name);
+            }
+        }
+        paths.sort();
+        paths
+    }
+
+    /// Only the safe entry ends up in `target`, and nothing next to it
+    fn assert_only_safe_entry_unpacked(dir: &Path) {
+        assert_eq!(
+            all_paths(dir),
+            vec!["target/bundle/whisper/ggml-model.bin".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_unpack_tar_skips_escaping_entries_and_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target");
+        std::fs::create_dir(&target).unwrap();
+        let absolute = dir.path().join("ggml-model.bin");
+        let wanted = vec!["ggml-model.bin".to_string()];
+
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, entry_type, link, data) in [
+            ("../ggml-model.bin", tar::EntryType::Regular, "", &b"ok"[..]),
+            (
+                absolute.to_str().unwrap(),
+                tar::EntryType::Regular,
+                "",
+                &b"ok"[..],
+            ),
+            (
+                "whisper/ggml-model.bin",
+                tar::EntryType::Symlink,
+                "../../ggml-model.bin",
+                &b""[..],
+            ),
+            (
+                "bundle/whisper/ggml-model.bin",
+                tar::EntryType::Regular,
+                "",
+                &b"ok ggml"[..],
+            ),
+        ] {
+            let header = raw_tar_header(name, entry_type, link, data.len() as u64);
+            builder.append(&header, data).unwrap();
+        }
+        let archive = builder.into_inner().unwrap();
+
+        // Counted like they are unpacked, for the disk space check
+        assert_eq!(tar_unpacked_size(archive.as_slice(), &wanted).unwrap(), 7);
+        unpack_tar(archive.as_slice(), &target, &wanted).unwrap();
+        assert_only_safe_entry_unpacked(dir.path());
+    }
+
+    #[test]
+    fn test_unpack_zip_skips_escaping_entries_and_links() {
+        use zip::write::SimpleFileOptions;
+
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target");
+        std::fs::create_dir(&target).unwrap();
+        let absolute = dir.path().join("ggml-model.bin");
+        let wanted = vec!["ggml-model.bin".to_string()];
+
+        let mut zip = zip::ZipWriter::new(io::Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default();
+        for name in ["../ggml-model.bin", absolute.to_str().unwrap()] {
+            zip.start_file(name, options).unwrap();
+            zip.write_all(b"ok").unwrap();
+        }
+        zip.add_symlink("whisper/ggml-model.bin", "../../ggml-model.bin", options)
+            .unwrap();
+        zip.start_file("bundle/whisper/ggml-model.bin", options)
+            .unwrap();
+        zip.write_all(b"ok ggml").unwrap();
+        let archive = zip.finish().unwrap();
+
+        assert_eq!(zip_unpacked_size(archive.clone(), &wanted).unwrap(), 7);
+        unpack_zip(archive, &target, &wanted).unwrap();
+        assert_only_safe_entry_unpacked(dir.path());
+    }
+
+    #[test]
+    fn test_strip_path_suffix() {
+        assert_eq!(
+            strip_path_suffix("ggml-base.bin", "ggml-base.bin"),
+            Some("")
+        );
+        assert_eq!(
+            strip_path_suffix("usb/whisper/ggml-base.bin", "ggml-base.bin"),
+            Some("usb/whisper")
+        );
+        assert_eq!(
+            strip_path_suffix("usb/my-ggml-base.bin", "ggml-base.bin"),
+            None
+        );
+        assert_eq!(
+            strip_path_suffix("a/parakeet-v3/vocab.txt", "parakeet-v3/vocab.txt"),
+            Some("a")
+        );
+    }
+}
@@ -0,0 +1,91 @@
+// Common catalog format for downloadable models
+// Every engine describes its models the same way: a model is one or more
+// files, each with a download URL and a path relative to the engine's models
+// directory (the same path keys the directory's checksum manifest)
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Engines whose models are managed by the shared model manager
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EngineKind {
+    Whisper,
+    Parakeet,
+    BuiltinAi,
+    Diarization,
+    Semantic,
+}
+
+impl EngineKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EngineKind::Whisper => "whisper",
+            EngineKind::Parakeet => "parakeet",
+            EngineKind::BuiltinAi => "builtin_ai",
+            EngineKind::Diarization => "diarization",
+            EngineKind::Semantic => "semantic",
+        }
+    }
+
+    /// Whether the engine's models are speech-to-text models (listed in the tray menu)
+    pub fn is_transcription(&self) -> bool {
+        matches!(self, EngineKind::Whisper | EngineKind::Parakeet)
+    }
+}
+
+impl fmt::Display for EngineKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One file of a model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogFile {
+    /// Path relative to the engine's models directory
+    pub path: String,
+    pub url: String,
+    /// Expected size in bytes; approximate for some models. Used for progress
+    /// until the server reports the real length, and for disk space checks
+    pub size_bytes: u64,
+}
+
+/// A downloadable model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogEntry {
+    pub engine: EngineKind,
+    /// Engine-specific model name (e.g. "base", "parakeet-tdt-0.6b-v3-int8", "gemma3:1b")
+    pub name: String,
+    pub display_name: String,
+    pub description: String,
+    pub files: Vec<CatalogFile>,
+}
+
+impl CatalogEntry {
+    pub fn new(engine: EngineKind, name: &str, display_name: &str, description: &str) -> Self {
+        Self {
+            engine,
+            name: name.to_string(),
+            display_name: display_name.to_string(),
+            description: description.to_string(),
+            files: Vec::new(),
+        }
+    }
+
+    /// Add a file to download into `path` (relative to the models directory)
+    pub fn with_file(mut self, path: &str, url: &str, size_bytes: u64) -> Self {
+        self.files.push(CatalogFile {
+            path: path.to_string(),
+            url: url.to_string(),
+            size_bytes,
+        });
+        self
+    }
+
+    /// Expected size of all files
+    pub fn total_bytes(&self) -> u64 {
+        self.files.iter().map(|file| file.size_bytes).sum()
+    }
+}
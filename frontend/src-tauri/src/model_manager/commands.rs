@@ -0,0 +1,155 @@
+// Tauri commands for the shared model manager
+// One set of commands for every engine; progress arrives as
+// `model-manager-download` events
+
+use std::path::Path;
+use std::sync::Arc;
+
+use tauri::{command, AppHandle, Emitter, Manager, Runtime};
+
+use super::{
+    registry, DownloadEvent, EngineKind, ExportReport, ImportReport, ModelEntry, ModelRef,
+    MODEL_DOWNLOAD_EVENT,
+};
+use crate::summary::summary_engine::ModelManagerState;
+
+/// Forward download events to the frontend; called once during app setup
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    let app = app.clone();
+    registry().set_listener(Arc::new(move |event: &DownloadEvent| {
+        if let Err(e) = app.emit(MODEL_DOWNLOAD_EVENT, event) {
+            log::error!("Failed to emit model download event: {}", e);
+        }
+    }));
+}
+
+/// Initialize an engine that hasn't started yet, so its models are registered
+async fn ensure_engine<R: Runtime>(app: &AppHandle<R>, engine: EngineKind) -> Result<(), String> {
+    match engine {
+        EngineKind::Whisper => crate::whisper_engine::commands::whisper_init().await,
+        EngineKind::Parakeet => crate::parakeet_engine::commands::parakeet_init().await,
+        EngineKind::BuiltinAi => {
+            let initialized = app.state::<ModelManagerState>().0.lock().await.is_some();
+            if initialized {
+                return Ok(());
+            }
+            crate::summary::summary_engine::commands::init_model_manager(app)
+                .await
+                .map_err(|e| format!("Failed to initialize model manager: {}", e))
+        }
+        // Registered during app setup
+        EngineKind::Diarization | EngineKind::Semantic => Ok(()),
+    }
+}
+
+/// Initialize every engine, so bundles can hold models of any of them
+async fn ensure_all_engines<R: Runtime>(app: &AppHandle<R>) {
+    for engine in [
+        EngineKind::Whisper,
+        EngineKind::Parakeet,
+        EngineKind::BuiltinAi,
+        EngineKind::Diarization,
+        EngineKind::Semantic,
+    ] {
+        if let Err(e) = ensure_engine(app, engine).await {
+            log::warn!("{} models unavailable for bundles: {}", engine, e);
+        }
+    }
+}
+
+/// Downloadable models of all engines (or one engine) with their state
+#[command]
+pub async fn models_list<R: Runtime>(
+    app: AppHandle<R>,
+    engine: Option<EngineKind>,
+) -> Result<Vec<ModelEntry>, String> {
+    if let Some(engine) = engine {
+        ensure_engine(&app, engine).await?;
+    }
+    Ok(registry().list(engine).await)
+}
+
+/// Download (or resume) a model; waits for a free download slot
+#[command]
+pub async fn models_download<R: Runtime>(
+    app: AppHandle<R>,
+    engine: EngineKind,
+    model: String,
+) -> Result<(), String> {
+    ensure_engine(&app, engine).await?;
+    registry()
+        .download(engine, &model, None)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if engine.is_transcription() {
+        // Transcription models show up in the tray menu
+        crate::tray::update_tray_menu(&app);
+    }
+    Ok(())
+}
+
+/// Cancel a running or queued download, keeping partial files for resuming
+/// Returns false if the model wasn't downloading
+#[command]
+pub async fn models_cancel_download(engine: EngineKind, model: String) -> Result<bool, String> {
+    Ok(registry().cancel(engine, &model))
+}
+
+/// Delete a model's files, including partial downloads
+#[command]
+pub async fn models_delete<R: Runtime>(
+    app: AppHandle<R>,
+    engine: EngineKind,
+    model: String,
+) -> Result<(), String> {
+    ensure_engine(&app, engine).await?;
+    registry()
+        .delete(engine, &model)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Install models from a folder or a .tar, .tar.gz, .tgz or .zip archive,
+/// for machines without internet access
+#[command]
+pub async fn models_import<R: Runtime>(
+    app: AppHandle<R>,
+    source: String,
+) -> Result<ImportReport, String> {
+    ensure_all_engines(&app).await;
+    // Archives are unpacked next to the models, so installing them is a rename
+    let staging_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("models");
+
+    let report = registry()
+        .import_bundle(Path::new(&source), &staging_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if report.installed.iter().any(|model| {
+        model.engine == EngineKind::Whisper.as_str()
+            || model.engine == EngineKind::Parakeet.as_str()
+    }) {
+        crate::tray::update_tray_menu(&app);
+    }
+    Ok(report)
+}
+
+/// Package installed models (all of them if none are given) as a tar archive
+/// that `models_import` accepts on another machine
+#[command]
+pub async fn models_export<R: Runtime>(
+    app: AppHandle<R>,
+    destination: String,
+    models: Option<Vec<ModelRef>>,
+) -> Result<ExportReport, String> {
+    ensure_all_engines(&app).await;
+    registry()
+        .export_bundle(&models.unwrap_or_default(), Path::new(&destination))
+        .await
+        .map_err(|e| e.to_string())
+}
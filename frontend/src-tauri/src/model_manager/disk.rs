@@ -0,0 +1,73 @@
+// Free disk space checks before model downloads
+
+use std::path::{Path, PathBuf};
+
+use sysinfo::Disks;
+
+use super::ModelManagerError;
+
+/// Space a download must leave free on the disk, for the OS and everything else
+const HEADROOM_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Free space on the disk holding `path` (which may not exist yet)
+pub fn available_space(path: &Path) -> Option<u64> {
+    let path = existing_ancestor(path)?.canonicalize().ok()?;
+    let disks = Disks::new_with_refreshed_list();
+
+    // The deepest mount point containing the path is the disk it lives on
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
+/// Fail if writing `needed` more bytes into `dir` would (nearly) fill its disk
+///
+/// When the free space can't be determined the download goes ahead.
+pub fn ensure_space(dir: &Path, needed: u64) -> Result<(), ModelManagerError> {
+    match available_space(dir) {
+        Some(available) => check_space(needed, available),
+        None => {
+            log::warn!(
+                "Couldn't determine free disk space for {}, downloading anyway",
+                dir.display()
+            );
+            Ok(())
+        }
+    }
+}
+
+fn check_space(needed: u64, available: u64) -> Result<(), ModelManagerError> {
+    if needed.saturating_add(HEADROOM_BYTES) > available {
+        return Err(ModelManagerError::InsufficientSpace { needed, available });
+    }
+    Ok(())
+}
+
+fn existing_ancestor(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|ancestor| ancestor.exists())
+        .map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    #[test]
+    fn test_check_space_keeps_headroom() {
+        assert!(check_space(GB, 2 * GB).is_ok());
+        assert!(check_space(0, 0).is_err());
+
+        let error = check_space(2 * GB, 2 * GB).unwrap_err();
+        assert!(matches!(
+            error,
+            ModelManagerError::InsufficientSpace { needed, available }
+                if needed == 2 * GB && available == 2 * GB
+        ));
+    }
+}
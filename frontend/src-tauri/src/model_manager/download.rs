@@ -0,0 +1,314 @@
+// Resumable download of a single model file
+// Bytes go to `<file>.part` next to the final path, so engines never see a
+// half-written model. An interrupted download resumes from the partial file
+// with a Range request (a partial file the server reports as complete is
+// finished as is); the finished file is renamed into place and checked
+// against its expected SHA-256 (see model_integrity)
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use futures_util::StreamExt;
+use reqwest::{Client, StatusCode};
+use tokio::fs::{self, OpenOptions};
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
+
+use super::catalog::CatalogFile;
+use super::ModelManagerError;
+use crate::model_integrity::{self, DownloadHasher};
+
+/// Suffix of files still being downloaded
+const PART_SUFFIX: &str = ".part";
+
+/// A connection that delivers nothing for this long is given up on
+const STALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Write buffer, to keep disk syscalls down on multi-GB files
+const WRITE_BUFFER_SIZE: usize = 8 * 1024 * 1024;
+
+/// Where the partial download of `path` is kept
+pub fn part_path(path: &Path) -> PathBuf {
+    let mut part = path.as_os_str().to_owned();
+    part.push(PART_SUFFIX);
+    PathBuf::from(part)
+}
+
+/// Size of a file, or 0 if it doesn't exist
+pub async fn file_size(path: &Path) -> u64 {
+    fs::metadata(path).await.map(|m| m.len()).unwrap_or(0)
+}
+
+/// Download `file` into `models_dir`, resuming its partial download if any
+///
+/// `on_progress(received, total)` is called with the bytes of this file on
+/// disk so far. Cancelling keeps the partial file for the next attempt. A
+/// download that fails verification is quarantined and reported as
+/// `IntegrityError::Mismatch`.
+pub async fn fetch_file(
+    client: &Client,
+    models_dir: &Path,
+    file: &CatalogFile,
+    cancel: &CancellationToken,
+    on_progress: &mut (dyn FnMut(u64, u64) + Send),
+) -> Result<()> {
+    let path = models_dir.join(&file.path);
+    let part = part_path(&path);
+    if let Some(parent) = part.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .map_err(|e| anyhow!("Failed to create {}: {}", parent.display(), e))?;
+    }
+
+    let expected_sha256 = model_integrity::expected_sha256(models_dir, &file.path, &file.url).await;
+
+    let mut existing = file_size(&part).await;
+    let mut response = request(client, &file.url, existing).await?;
+    if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        if range_total(&response) == Some(existing) {
+            log::info!("{} was already complete ({} bytes)", file.path, existing);
+            on_progress(existing, existing);
+            let hasher = DownloadHasher::resume(&part, existing).await?;
+            return finish(models_dir, file, expected_sha256.as_deref(), hasher).await;
+        }
+        // The partial file no longer matches the server's file
+        log::warn!(
+            "Server rejected resuming {} at byte {}, starting over",
+            file.path,
+            existing
+        );
+        fs::remove_file(&part).await?;
+        existing = 0;
+        response = request(client, &file.url, 0).await?;
+    }
+    if !response.status().is_success() {
+        return Err(anyhow!(
+            "Download of {} failed with status: {}",
+            file.path,
+            response.status()
+        ));
+    }
+
+    let resuming = existing > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
+    if existing > 0 && !resuming {
+        log::warn!(
+            "Server doesn't support resume for {}, starting over",
+            file.path
+        );
+    }
+    let offset = if resuming { existing } else { 0 };
+    let total = response
+        .content_length()
+        .map(|remaining| offset + remaining)
+        .unwrap_or(file.size_bytes);
+
+    log::info!(
+        "Downloading {} from {} ({:.1} of {:.1} MB on disk)",
+        file.path,
+        file.url,
+        offset as f64 / (1024.0 * 1024.0),
+        total as f64 / (1024.0 * 1024.0)
+    );
+
+    // Hash the bytes already on disk so the checksum covers the whole file
+    let mut hasher = if resuming {
+        DownloadHasher::resume(&part, existing).await?
+    } else {
+        DownloadHasher::new()
+    };
+    let handle = if resuming {
+        OpenOptions::new().append(true).open(&part).await
+    } else {
+        fs::File::create(&part).await
+    }
+    .map_err(|e| anyhow!("Failed to open {}: {}", part.display(), e))?;
+    let mut writer = BufWriter::with_capacity(WRITE_BUFFER_SIZE, handle);
+
+    let mut received = offset;
+    on_progress(received, total);
+
+    let mut stream = response.bytes_stream();
+    loop {
+        let next = tokio::select! {
+            _ = cancel.cancelled() => {
+                // Keep what we have for the next attempt
+                let _ = writer.flush().await;
+                return Err(ModelManagerError::Cancelled.into());
+            }
+            next = timeout(STALL_TIMEOUT, stream.next()) => next,
+        };
+
+        let chunk = match next {
+            Err(_) => {
+                let _ = writer.flush().await;
+                return Err(anyhow!(
+                    "Download timeout - No data received for {} seconds",
+                    STALL_TIMEOUT.as_secs()
+                ));
+            }
+            Ok(None) => break,
+            Ok(Some(Err(e))) => {
+                let _ = writer.flush().await;
+                return Err(anyhow!("{}: {}", describe_error(&e), e));
+            }
+            Ok(Some(Ok(chunk))) => chunk,
+        };
+
+        writer
+            .write_all(&chunk)
+            .await
+            .map_err(|e| anyhow!("Failed to write to {}: {}", part.display(), e))?;
+        hasher.update(&chunk);
+        received += chunk.len() as u64;
+        on_progress(received, total);
+    }
+
+    writer
+        .flush()
+        .await
+        .map_err(|e| anyhow!("Failed to flush {}: {}", part.display(), e))?;
+    drop(writer);
+
+    finish(models_dir, file, expected_sha256.as_deref(), hasher).await
+}
+
+/// Check a complete partial file against its expected hash and move it into place.
+/// A file that doesn't match never replaces the installed one
+async fn finish(
+    models_dir: &Path,
+    file: &CatalogFile,
+    expected_sha256: Option<&str>,
+    hasher: DownloadHasher,
+) -> Result<()> {
+    let part = part_path(&models_dir.join(&file.path));
+    model_integrity::check_download(models_dir, &file.path, &part, expected_sha256, hasher)?;
+    Ok(())
+}
+
+/// Full size from a 416 response's `Content-Range: bytes */<size>`
+fn range_total(response: &reqwest::Response) -> Option<u64> {
+    response
+        .headers()
+        .get(reqwest::header::CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .strip_prefix("bytes */")?
+        .trim()
+        .parse()
+        .ok()
+}
+
+async fn request(client: &Client, url: &str, from: u64) -> Result<reqwest::Response> {
+    let mut request = client.get(url);
+    if from > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", from));
+    }
+    request
+        .send()
+        .await
+        .map_err(|e| anyhow!("{}: {}", describe_error(&e), e))
+}
+
+/// User-facing summary of a network error
+fn describe_error(error: &reqwest::Error) -> &'static str {
+    if error.is_timeout() {
+        "Connection timeout - Check your internet"
+    } else if error.is_connect() {
+        "Connection failed - Check your internet"
+    } else if error.is_body() || error.is_decode() {
+        "Stream interrupted - Network unstable"
+    } else {
+        "Download error"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_manager::test_server::TestServer;
+
+    fn catalog_file(server: &TestServer, path: &str, size_bytes: u64) -> CatalogFile {
+        CatalogFile {
+            path: path.to_string(),
+            url: server.url(path),
+            size_bytes,
+        }
+    }
+
+    async fn fetch(dir: &Path, file: &CatalogFile) -> Result<u64> {
+        let mut last = 0;
+        fetch_file(
+            &Client::new(),
+            dir,
+            file,
+            &CancellationToken::new(),
+            &mut |received, _| last = received,
+        )
+        .await
+        .map(|_| last)
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_download_resumes_with_range() {
+        let server = TestServer::start().await;
+        let body: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        server.serve("model/encoder.onnx", &body);
+        let dir = tempfile::tempdir().unwrap();
+        let file = catalog_file(&server, "model/encoder.onnx", body.len() as u64);
+
+        server.cut_next_after(40_000);
+        assert!(fetch(dir.path(), &file).await.is_err());
+        let part = part_path(&dir.path().join("model/encoder.onnx"));
+        assert_eq!(file_size(&part).await, 40_000);
+
+        assert_eq!(fetch(dir.path(), &file).await.unwrap(), 100_000);
+        assert_eq!(
+            std::fs::read(dir.path().join("model/encoder.onnx")).unwrap(),
+            body
+        );
+        assert!(!part.exists());
+        assert!(server
+            .requests()
+            .contains(&"GET /model/encoder.onnx bytes=40000-".to_string()));
+        // Verified against the hash the server published
+        let digest = model_integrity::manifest::lookup(dir.path(), "model/encoder.onnx").unwrap();
+        assert_eq!(digest.source, model_integrity::DigestSource::Published);
+    }
+
+    #[tokio::test]
+    async fn test_restarts_when_server_ignores_range() {
+        let server = TestServer::start().await;
+        server.serve("ggml-tiny.bin", b"ggml model weights");
+        server.ignore_ranges();
+        let dir = tempfile::tempdir().unwrap();
+        let file = catalog_file(&server, "ggml-tiny.bin", 18);
+
+        std::fs::write(part_path(&dir.path().join("ggml-tiny.bin")), b"ggml").unwrap();
+        fetch(dir.path(), &file).await.unwrap();
+        assert_eq!(
+            std::fs::read(dir.path().join("ggml-tiny.bin")).unwrap(),
+            b"ggml model weights"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_corrupted_download_is_quarantined() {
+        let server = TestServer::start().await;
+        server.serve("model.gguf", b"GGUF weights");
+        server.corrupt_next_get();
+        let dir = tempfile::tempdir().unwrap();
+        let file = catalog_file(&server, "model.gguf", 12);
+
+        let error = fetch(dir.path(), &file).await.unwrap_err();
+        assert!(model_integrity::is_mismatch(&error));
+        assert!(!dir.path().join("model.gguf").exists());
+
+        fetch(dir.path(), &file).await.unwrap();
+        assert_eq!(
+            std::fs::read(dir.path().join("model.gguf")).unwrap(),
+            b"GGUF weights"
+        );
+    }
+}
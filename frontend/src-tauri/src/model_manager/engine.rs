@@ -0,0 +1,30 @@
+// Extension point each engine implements to have its models managed here
+// The model manager owns downloading, resuming, verification and progress;
+// engines only describe their models and decide whether installed files are
+// usable
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use super::catalog::{CatalogEntry, EngineKind};
+
+#[async_trait]
+pub trait ModelEngine: Send + Sync {
+    fn kind(&self) -> EngineKind;
+
+    /// Directory the catalog's file paths are relative to
+    fn models_dir(&self) -> PathBuf;
+
+    /// Models this engine can download
+    fn catalog(&self) -> Vec<CatalogEntry>;
+
+    /// Check that a model whose files are all present can be used by the engine
+    /// (headers, sizes, required companion files)
+    async fn validate(&self, entry: &CatalogEntry) -> Result<()>;
+
+    /// Called after a model was installed or deleted, so the engine can
+    /// refresh its own view of the models directory
+    async fn refresh(&self) {}
+}
@@ -0,0 +1,883 @@
+//! Shared model management for Whisper, Parakeet and built-in AI models.
+//!
+//! Engines describe their downloadable models in a common catalog format and
+//! plug in their own validation through [`ModelEngine`]; everything else lives
+//! here once: resumable downloads, SHA-256 verification, a limit on
+//! concurrent downloads, disk space checks, cancellation and a single progress
+//! event (`model-manager-download`) for every engine. The engines' existing
+//! download commands delegate to the [`registry`] and keep their own status
+//! types and events for the screens that use them.
+//!
+//! # Module Structure
+//!
+//! - `catalog`: Catalog format (engine, model, files with URL and size)
+//! - `engine`: Trait engines implement to plug in
+//! - `download`: Resumable single-file download with Range requests
+//! - `bundle`: Offline import and export of models as a folder or archive
+//! - `disk`: Free disk space checks
+//! - `progress`: Progress type and event payload shared by all engines
+//! - `commands`: Tauri commands for listing, downloading, cancelling, deleting,
+//!   importing and exporting models
+
+pub mod bundle;
+pub mod catalog;
+pub mod commands;
+pub mod disk;
+pub mod download;
+pub mod engine;
+pub mod progress;
+
+#[cfg(test)]
+mod test_server;
+
+pub use bundle::{BundleModel, ExportReport, ImportReport, ModelRef};
+pub use catalog::{CatalogEntry, CatalogFile, EngineKind};
+pub use engine::ModelEngine;
+pub use progress::{DownloadEvent, DownloadPhase, DownloadProgress, MODEL_DOWNLOAD_EVENT};
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde::Serialize;
+use tokio::fs;
+use tokio::sync::Semaphore;
+use tokio_util::sync::CancellationToken;
+
+use crate::model_integrity::{self, manifest, CheckStatus};
+use download::{file_size, part_path};
+use progress::ProgressTracker;
+
+/// Downloads running at once; further downloads wait in a queue
+pub const MAX_CONCURRENT_DOWNLOADS: usize = 2;
+
+static REGISTRY: Lazy<ModelRegistry> = Lazy::new(|| ModelRegistry::new(MAX_CONCURRENT_DOWNLOADS));
+
+/// The process-wide registry engines register with
+pub fn registry() -> &'static ModelRegistry {
+    &REGISTRY
+}
+
+/// Receives every download event (the app forwards them to the frontend)
+pub type DownloadListener = Arc<dyn Fn(&DownloadEvent) + Send + Sync>;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ModelManagerError {
+    #[error("The {0} engine is not initialized")]
+    EngineNotRegistered(EngineKind),
+
+    #[error("Unknown {engine} model: {model}")]
+    UnknownModel { engine: EngineKind, model: String },
+
+    #[error("Download already in progress for {engine} model {model}")]
+    DownloadInProgress { engine: EngineKind, model: String },
+
+    #[error(
+        "Not enough disk space: {} MB needed, {} MB available",
+        .needed / (1024 * 1024),
+        .available / (1024 * 1024)
+    )]
+    InsufficientSpace { needed: u64, available: u64 },
+
+    #[error("Download cancelled by user")]
+    Cancelled,
+}
+
+/// Whether a download ended because it was cancelled
+pub fn is_cancelled(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<ModelManagerError>(),
+        Some(ModelManagerError::Cancelled)
+    )
+}
+
+/// Where a model stands
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum ModelState {
+    NotDownloaded,
+    /// Part of the model is on disk; downloading it again resumes
+    Partial {
+        downloaded_bytes: u64,
+    },
+    /// Waiting for a free download slot
+    Queued,
+    Downloading {
+        progress: DownloadProgress,
+    },
+    Installed,
+    /// All files are present but the engine rejected them
+    Invalid {
+        reason: String,
+    },
+}
+
+/// A catalog entry with its current state, for the model list
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelEntry {
+    #[serde(flatten)]
+    pub model: CatalogEntry,
+    pub state: ModelState,
+}
+
+type DownloadKey = (EngineKind, String);
+
+struct ActiveDownload {
+    cancel: CancellationToken,
+    phase: DownloadPhase,
+    progress: DownloadProgress,
+}
+
+/// Registered engines and the downloads running for them
+pub struct ModelRegistry {
+    engines: RwLock<HashMap<EngineKind, Arc<dyn ModelEngine>>>,
+    active: Mutex<HashMap<DownloadKey, ActiveDownload>>,
+    slots: Semaphore,
+    client: Client,
+    listener: RwLock<Option<DownloadListener>>,
+}
+
+impl ModelRegistry {
+    pub fn new(max_concurrent_downloads: usize) -> Self {
+        // No overall timeout: multi-GB files take long, stalls are detected per chunk
+        let client = Client::builder()
+            .tcp_nodelay(true)
+            .connect_timeout(Duration::from_secs(30))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            engines: RwLock::new(HashMap::new()),
+            active: Mutex::new(HashMap::new()),
+            slots: Semaphore::new(max_concurrent_downloads),
+            client,
+            listener: RwLock::new(None),
+        }
+    }
+
+    /// Make an engine's models available (replaces an earlier instance)
+    pub fn register(&self, engine: Arc<dyn ModelEngine>) {
+        log::info!("Registered {} models with the model manager", engine.kind());
+        self.engines.write().unwrap().insert(engine.kind(), engine);
+    }
+
+    pub fn set_listener(&self, listener: DownloadListener) {
+        *self.listener.write().unwrap() = Some(listener);
+    }
+
+    /// Registered engines (or one engine), in a stable order
+    fn engines(&self, kind: Option<EngineKind>) -> Vec<Arc<dyn ModelEngine>> {
+        let mut engines: Vec<Arc<dyn ModelEngine>> = self
+            .engines
+            .read()
+            .unwrap()
+            .values()
+            .filter(|engine| kind.is_none() || kind == Some(engine.kind()))
+            .cloned()
+            .collect();
+        engines.sort_by_key(|engine| engine.kind());
+        engines
+    }
+
+    fn engine(&self, kind: EngineKind) -> Result<Arc<dyn ModelEngine>, ModelManagerError> {
+        self.engines
+            .read()
+            .unwrap()
+            .get(&kind)
+            .cloned()
+            .ok_or(ModelManagerError::EngineNotRegistered(kind))
+    }
+
+    fn catalog_entry(
+        engine: &dyn ModelEngine,
+        model: &str,
+    ) -> Result<CatalogEntry, ModelManagerError> {
+        engine
+            .catalog()
+            .into_iter()
+            .find(|entry| entry.name == model)
+            .ok_or_else(|| ModelManagerError::UnknownModel {
+                engine: engine.kind(),
+                model: model.to_string(),
+            })
+    }
+
+    /// Catalog of registered engines (or one engine) with each model's state
+    pub async fn list(&self, kind: Option<EngineKind>) -> Vec<ModelEntry> {
+        let mut entries = Vec::new();
+        for engine in self.engines(kind) {
+            let models_dir = engine.models_dir();
+            for model in engine.catalog() {
+                let state = self.state(engine.as_ref(), &models_dir, &model).await;
+                entries.push(ModelEntry { model, state });
+            }
+        }
+        entries
+    }
+
+    async fn state(
+        &self,
+        engine: &dyn ModelEngine,
+        models_dir: &Path,
+        model: &CatalogEntry,
+    ) -> ModelState {
+        if let Some(active) = self.active_state(engine.kind(), &model.name) {
+            return active;
+        }
+
+        let present = model
+            .files
+            .iter()
+            .all(|file| models_dir.join(&file.path).exists());
+        if present {
+            return match engine.validate(model).await {
+                Ok(()) => ModelState::Installed,
+                Err(e) => ModelState::Invalid {
+                    reason: e.to_string(),
+                },
+            };
+        }
+
+        let mut downloaded_bytes = 0;
+        for file in &model.files {
+            let path = models_dir.join(&file.path);
+            downloaded_bytes += file_size(&path).await + file_size(&part_path(&path)).await;
+        }
+        if downloaded_bytes > 0 {
+            ModelState::Partial { downloaded_bytes }
+        } else {
+            ModelState::NotDownloaded
+        }
+    }
+
+    fn active_state(&self, kind: EngineKind, model: &str) -> Option<ModelState> {
+        let active = self.active.lock().unwrap();
+        let download = active.get(&(kind, model.to_string()))?;
+        Some(match download.phase {
+            DownloadPhase::Queued => ModelState::Queued,
+            _ => ModelState::Downloading {
+                progress: download.progress.clone(),
+            },
+        })
+    }
+
+    /// Progress of a running (or queued) download, for engines' own status types
+    pub fn progress(&self, kind: EngineKind, model: &str) -> Option<DownloadProgress> {
+        let active = self.active.lock().unwrap();
+        active
+            .get(&(kind, model.to_string()))
+            .map(|download| download.progress.clone())
+    }
+
+    /// Download a model, resuming whatever is already on disk
+    ///
+    /// Waits for a free download slot first. `on_progress` gets the same
+    /// progress as the download event, for callers that report it their own way.
+    pub async fn download(
+        &self,
+        kind: EngineKind,
+        model: &str,
+        on_progress: Option<&(dyn Fn(&DownloadProgress) + Send + Sync)>,
+    ) -> Result<()> {
+        let engine = self.engine(kind)?;
+        let entry = Self::catalog_entry(engine.as_ref(), model)?;
+        let (guard, cancel) =
+            self.claim(kind, model, DownloadPhase::Queued, entry.total_bytes())?;
+
+        let result = self
+            .run_download(engine.as_ref(), &entry, &cancel, on_progress)
+            .await;
+        let last = self.progress(kind, model).unwrap_or_default();
+        drop(guard);
+
+        match &result {
+            Ok(()) => {
+                log::info!("Downloaded {} model {}", kind, model);
+                let total = last.total_bytes;
+                self.emit(
+                    kind,
+                    model,
+                    DownloadPhase::Completed,
+                    DownloadProgress::new(total, total, 0.0),
+                    None,
+                );
+            }
+            Err(e) if is_cancelled(e) => {
+                log::info!("Download of {} model {} cancelled", kind, model);
+                self.emit(kind, model, DownloadPhase::Cancelled, last, None);
+            }
+            Err(e) => {
+                log::error!("Download of {} model {} failed: {}", kind, model, e);
+                self.emit(
+                    kind,
+                    model,
+                    DownloadPhase::Failed,
+                    last,
+                    Some(e.to_string()),
+                );
+            }
+        }
+        engine.refresh().await;
+        result
+    }
+
+    /// Put a model on the active list, so nothing else downloads or replaces
+    /// its files until the returned guard is dropped
+    fn claim(
+        &self,
+        kind: EngineKind,
+        model: &str,
+        phase: DownloadPhase,
+        total_bytes: u64,
+    ) -> Result<(ActiveGuard<'_>, CancellationToken), ModelManagerError> {
+        let cancel = CancellationToken::new();
+        let key = (kind, model.to_string());
+        let mut active = self.active.lock().unwrap();
+        if active.contains_key(&key) {
+            return Err(ModelManagerError::DownloadInProgress {
+                engine: kind,
+                model: model.to_string(),
+            });
+        }
+        active.insert(
+            key.clone(),
+            ActiveDownload {
+                cancel: cancel.clone(),
+                phase,
+                progress: DownloadProgress::new(0, total_bytes, 0.0),
+            },
+        );
+        let guard = ActiveGuard {
+            registry: self,
+            key: Some(key),
+        };
+        Ok((guard, cancel))
+    }
+
+    async fn run_download(
+        &self,
+        engine: &dyn ModelEngine,
+        entry: &CatalogEntry,
+        cancel: &CancellationToken,
+        on_progress: Option<&(dyn Fn(&DownloadProgress) + Send + Sync)>,
+    ) -> Result<()> {
+        let kind = engine.kind();
+        self.report(
+            kind,
+            &entry.name,
+            DownloadPhase::Queued,
+            DownloadProgress::new(0, entry.total_bytes(), 0.0),
+        );
+
+        let _slot = tokio::select! {
+            slot = self.slots.acquire() => slot.map_err(|e| anyhow!("Download queue closed: {}", e))?,
+            _ = cancel.cancelled() => return Err(ModelManagerError::Cancelled.into()),
+        };
+
+        let models_dir = engine.models_dir();
+        fs::create_dir_all(&models_dir)
+            .await
+            .map_err(|e| anyhow!("Failed to create models directory: {}", e))?;
+
+        // Keep files already downloaded, count what is left to fetch
+        let mut sizes: Vec<u64> = entry.files.iter().map(|file| file.size_bytes).collect();
+        let mut pending = Vec::new();
+        let mut remaining = 0u64;
+        for (index, file) in entry.files.iter().enumerate() {
+            match keep_existing(&models_dir, file).await {
+                Some(size) => sizes[index] = size,
+                None => {
+                    let partial = file_size(&part_path(&models_dir.join(&file.path))).await;
+                    remaining += file.size_bytes.saturating_sub(partial);
+                    pending.push(index);
+                }
+            }
+        }
+        disk::ensure_space(&models_dir, remaining)?;
+
+        let mut finished: u64 = (0..entry.files.len())
+            .filter(|index| !pending.contains(index))
+            .map(|index| sizes[index])
+            .sum();
+        let mut tracker = ProgressTracker::new(finished, sizes.iter().sum());
+        self.report(
+            kind,
+            &entry.name,
+            DownloadPhase::Downloading,
+            tracker.snapshot(0.0),
+        );
+
+        for index in pending {
+            let file = &entry.files[index];
+            let others: u64 = sizes.iter().sum::<u64>() - sizes[index];
+            let mut file_total = sizes[index];
+
+            let mut attempt = 1;
+            loop {
+                let result = download::fetch_file(
+                    &self.client,
+                    &models_dir,
+                    file,
+                    cancel,
+                    &mut |received, total| {
+                        file_total = total;
+                        tracker.set_total(others + total);
+                        if let Some(progress) = tracker.update(finished + received) {
+                            if let Some(callback) = on_progress {
+                                callback(&progress);
+                            }
+                            self.report(kind, &entry.name, DownloadPhase::Downloading, progress);
+                        }
+                    },
+                )
+                .await;
+
+                match result {
+                    Err(e)
+                        if model_integrity::is_mismatch(&e)
+                            && attempt < model_integrity::MAX_DOWNLOAD_ATTEMPTS =>
+                    {
+                        log::warn!("{}, downloading {} again", e, file.path);
+                        attempt += 1;
+                    }
+                    result => break result?,
+                }
+            }
+
+            sizes[index] = file_total;
+            finished += file_total;
+        }
+
+        let total = sizes.iter().sum();
+        let done = DownloadProgress::new(total, total, 0.0);
+        if let Some(callback) = on_progress {
+            callback(&done);
+        }
+        self.report(kind, &entry.name, DownloadPhase::Verifying, done);
+
+        if let Err(e) = engine.validate(entry).await {
+            // The files match their checksums, so downloading again won't help
+            // until the catalog changes; don't leave an unusable model behind
+            remove_files(&models_dir, entry).await;
+            return Err(anyhow!(
+                "Downloaded {} model {} is not usable: {}",
+                kind,
+                entry.name,
+                e
+            ));
+        }
+        Ok(())
+    }
+
+    /// Cancel a running or queued download; the partial files are kept for resuming
+    pub fn cancel(&self, kind: EngineKind, model: &str) -> bool {
+        let active = self.active.lock().unwrap();
+        match active.get(&(kind, model.to_string())) {
+            Some(download) => {
+                download.cancel.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Delete a model's files, including partial downloads
+    pub async fn delete(&self, kind: EngineKind, model: &str) -> Result<()> {
+        if self.progress(kind, model).is_some() {
+            return Err(ModelManagerError::DownloadInProgress {
+                engine: kind,
+                model: model.to_string(),
+            }
+            .into());
+        }
+        let engine = self.engine(kind)?;
+        let entry = Self::catalog_entry(engine.as_ref(), model)?;
+
+        remove_files(&engine.models_dir(), &entry).await;
+        log::info!("Deleted {} model {}", kind, model);
+        engine.refresh().await;
+        Ok(())
+    }
+
+    /// Record a phase change or new progress and tell the listener
+    fn report(
+        &self,
+        kind: EngineKind,
+        model: &str,
+        phase: DownloadPhase,
+        progress: DownloadProgress,
+    ) {
+        {
+            let mut active = self.active.lock().unwrap();
+            if let Some(download) = active.get_mut(&(kind, model.to_string())) {
+                download.phase = phase;
+                download.progress = progress.clone();
+            }
+        }
+        self.emit(kind, model, phase, progress, None);
+    }
+
+    fn emit(
+        &self,
+        kind: EngineKind,
+        model: &str,
+        phase: DownloadPhase,
+        progress: DownloadProgress,
+        error: Option<String>,
+    ) {
+        let listener = self.listener.read().unwrap().clone();
+        if let Some(listener) = listener {
+            listener(&DownloadEvent {
+                engine: kind,
+                model: model.to_string(),
+                phase,
+                progress,
+                error,
+            });
+        }
+    }
+}
+
+/// Takes a download off the active list however it ends
+struct ActiveGuard<'a> {
+    registry: &'a ModelRegistry,
+    key: Option<DownloadKey>,
+}
+
+impl Drop for ActiveGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.registry.active.lock().unwrap().remove(&key);
+        }
+    }
+}
+
+/// Size of a file already in place if it can be kept, `None` if it must be downloaded
+///
+/// Only a file of exactly the size pinned or recorded for it is checked and
+/// kept; files that don't match their expected hash are quarantined. Anything
+/// else may be a partial download written in place by older versions, so it is
+/// moved aside to be resumed. A file that turns out to be complete then costs
+/// one Range request and is verified like any finished download.
+async fn keep_existing(models_dir: &Path, file: &CatalogFile) -> Option<u64> {
+    let path = models_dir.join(&file.path);
+    if !path.exists() {
+        return None;
+    }
+
+    let size = file_size(&path).await;
+    let exact_size = model_integrity::known_digest(&file.url)
+        .or_else(|| manifest::lookup(models_dir, &file.path))
+        .map(|digest| digest.size);
+    if exact_size == Some(size) {
+        let check =
+            model_integrity::verify_installed(models_dir, &file.path, Some(file.url.as_str()))
+                .await;
+        return match check.status {
+            CheckStatus::Verified | CheckStatus::Unverified => Some(size),
+            CheckStatus::Mismatch | CheckStatus::Failed => None,
+        };
+    }
+
+    let part = part_path(&path);
+    if !part.exists() {
+        log::info!("Resuming {} ({} bytes in place)", file.path, size);
+        if let Err(e) = fs::rename(&path, &part).await {
+            log::warn!("Failed to move {} aside: {}", file.path, e);
+        }
+    }
+    None
+}
+
+/// Remove a model's files, partial downloads, recorded hashes and emptied folders
+async fn remove_files(models_dir: &Path, entry: &CatalogEntry) {
+    for file in &entry.files {
+        let path = models_dir.join(&file.path);
+        for path in [part_path(&path), path] {
+            if path.exists() {
+                if let Err(e) = fs::remove_file(&path).await {
+                    log::warn!("Failed to delete {}: {}", path.display(), e);
+                }
+            }
+        }
+        if let Err(e) = manifest::forget_recorded(models_dir, &file.path) {
+            log::warn!("Failed to forget checksum of {}: {}", file.path, e);
+        }
+
+        // Model folders (e.g. Parakeet's) go once empty; never the models directory
+        let mut parent = models_dir.join(&file.path).parent().map(Path::to_path_buf);
+        while let Some(dir) = parent.filter(|dir| dir.starts_with(models_dir) && dir != models_dir)
+        {
+            if fs::remove_dir(&dir).await.is_err() {
+                break;
+            }
+            parent = dir.parent().map(Path::to_path_buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::path::PathBuf;
+    use test_server::TestServer;
+
+    /// Engine with two-file models whose files must start with "ok"
+    struct FakeEngine {
+        models_dir: PathBuf,
+        server_url: String,
+    }
+
+    #[async_trait]
+    impl ModelEngine for FakeEngine {
+        fn kind(&self) -> EngineKind {
+            EngineKind::Parakeet
+        }
+
+        fn models_dir(&self) -> PathBuf {
+            self.models_dir.clone()
+        }
+
+        fn catalog(&self) -> Vec<CatalogEntry> {
+            ["good", "bad", "other"]
+                .iter()
+                .map(|name| {
+                    CatalogEntry::new(EngineKind::Parakeet, name, name, "")
+                        .with_file(
+                            &format!("{}/a.onnx", name),
+                            &format!("{}/{}/a.onnx", self.server_url, name),
+                            2,
+                        )
+                        .with_file(
+                            &format!("{}/b.txt", name),
+                            &format!("{}/{}/b.txt", self.server_url, name),
+                            2,
+                        )
+                })
+                .collect()
+        }
+
+        async fn validate(&self, entry: &CatalogEntry) -> Result<()> {
+            for file in &entry.files {
+                let bytes = std::fs::read(self.models_dir.join(&file.path))?;
+                if !bytes.starts_with(b"ok") {
+                    return Err(anyhow!("{} has no header", file.path));
+                }
+            }
+            Ok(())
+        }
+    }
+
+    async fn setup(
+        max_concurrent: usize,
+    ) -> (
+        TestServer,
+        tempfile::TempDir,
+        Arc<ModelRegistry>,
+        Arc<Mutex<Vec<DownloadEvent>>>,
+    ) {
+        let server = TestServer::start().await;
+        for name in ["good", "other"] {
+            server.serve(&format!("{}/a.onnx", name), b"ok encoder");
+            server.serve(&format!("{}/b.txt", name), b"ok vocab");
+        }
+        server.serve("bad/a.onnx", b"no header");
+        server.serve("bad/b.txt", b"ok vocab");
+
+        let dir = tempfile::tempdir().unwrap();
+        let registry = Arc::new(ModelRegistry::new(max_concurrent));
+        registry.register(Arc::new(FakeEngine {
+            models_dir: dir.path().to_path_buf(),
+            server_url: server.url("").trim_end_matches('/').to_string(),
+        }));
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        registry.set_listener(Arc::new(move |event: &DownloadEvent| {
+            sink.lock().unwrap().push(event.clone())
+        }));
+        (server, dir, registry, events)
+    }
+
+    fn state_of(entries: &[ModelEntry], name: &str) -> ModelState {
+        entries
+            .iter()
+            .find(|entry| entry.model.name == name)
+            .unwrap()
+            .state
+            .clone()
+    }
+
+    #[tokio::test]
+    async fn test_download_validate_and_delete() {
+        let (_server, dir, registry, events) = setup(2).await;
+
+        registry
+            .download(EngineKind::Parakeet, "good", None)
+            .await
+            .unwrap();
+        let phases: Vec<DownloadPhase> = events
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|event| event.phase)
+            .collect();
+        assert_eq!(phases.first(), Some(&DownloadPhase::Queued));
+        assert_eq!(phases.last(), Some(&DownloadPhase::Completed));
+        assert!(phases.contains(&DownloadPhase::Verifying));
+        assert_eq!(
+            state_of(&registry.list(None).await, "good"),
+            ModelState::Installed
+        );
+
+        // Checksums match but the engine rejects the model: nothing is left behind
+        let error = registry
+            .download(EngineKind::Parakeet, "bad", None)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("not usable"));
+        assert!(!dir.path().join("bad").exists());
+        assert_eq!(
+            events.lock().unwrap().last().unwrap().phase,
+            DownloadPhase::Failed
+        );
+
+        registry.delete(EngineKind::Parakeet, "good").await.unwrap();
+        assert!(!dir.path().join("good").exists());
+        assert_eq!(
+            state_of(&registry.list(None).await, "good"),
+            ModelState::NotDownloaded
+        );
+
+        let error = registry
+            .download(EngineKind::Whisper, "base", None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<ModelManagerError>(),
+            Some(ModelManagerError::EngineNotRegistered(EngineKind::Whisper))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_queued_download_can_be_cancelled() {
+        let (server, dir, registry, events) = setup(1).await;
+        server.hold();
+
+        let first = tokio::spawn({
+            let registry = registry.clone();
+            async move { registry.download(EngineKind::Parakeet, "good", None).await }
+        });
+        while !server
+            .requests()
+            .iter()
+            .any(|request| request.starts_with("GET"))
+        {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        // The only slot is taken, so the second download waits
+        let second = tokio::spawn({
+            let registry = registry.clone();
+            async move { registry.download(EngineKind::Parakeet, "other", None).await }
+        });
+        while registry.progress(EngineKind::Parakeet, "other").is_none() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let entries = registry.list(Some(EngineKind::Parakeet)).await;
+        assert_eq!(state_of(&entries, "other"), ModelState::Queued);
+        assert!(matches!(
+            state_of(&entries, "good"),
+            ModelState::Downloading { .. }
+        ));
+        assert!(registry
+            .download(EngineKind::Parakeet, "good", None)
+            .await
+            .is_err());
+
+        assert!(registry.cancel(EngineKind::Parakeet, "other"));
+        assert!(is_cancelled(&second.await.unwrap().unwrap_err()));
+        assert!(!dir.path().join("other").exists());
+        assert!(events
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|event| event.model == "other" && event.phase == DownloadPhase::Cancelled));
+
+        server.release();
+        first.await.unwrap().unwrap();
+        assert!(registry.progress(EngineKind::Parakeet, "good").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_partial_download_in_place_is_resumed() {
+        let (server, dir, registry, _events) = setup(2).await;
+        // Written straight to the final path by an older version
+        std::fs::create_dir_all(dir.path().join("good")).unwrap();
+        std::fs::write(dir.path().join("good/a.onnx"), b"o").unwrap();
+        // Reported as partial rather than installed or invalid
+        let entries = registry.list(None).await;
+        assert_eq!(
+            state_of(&entries, "good"),
+            ModelState::Partial {
+                downloaded_bytes: 1
+            }
+        );
+
+        registry
+            .download(EngineKind::Parakeet, "good", None)
+            .await
+            .unwrap();
+        assert!(server
+            .requests()
+            .contains(&"GET /good/a.onnx bytes=1-".to_string()));
+        assert_eq!(
+            std::fs::read(dir.path().join("good/a.onnx")).unwrap(),
+            b"ok encoder"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_in_place_needs_exact_size() {
+        let (server, dir, registry, _events) = setup(2).await;
+        std::fs::create_dir_all(dir.path().join("good")).unwrap();
+        // Truncated, and bigger than the approximate catalog size
+        std::fs::write(dir.path().join("good/a.onnx"), b"ok enc").unwrap();
+        // Complete, but its exact size isn't known yet
+        std::fs::write(dir.path().join("good/b.txt"), b"ok vocab").unwrap();
+
+        registry
+            .download(EngineKind::Parakeet, "good", None)
+            .await
+            .unwrap();
+        let requests = server.requests();
+        assert!(requests.contains(&"GET /good/a.onnx bytes=6-".to_string()));
+        // The complete file only cost a Range request the server rejected
+        assert!(requests.contains(&"GET /good/b.txt bytes=8-".to_string()));
+        assert!(!requests.contains(&"GET /good/b.txt".to_string()));
+        assert_eq!(
+            std::fs::read(dir.path().join("good/a.onnx")).unwrap(),
+            b"ok encoder"
+        );
+        assert_eq!(
+            std::fs::read(dir.path().join("good/b.txt")).unwrap(),
+            b"ok vocab"
+        );
+
+        // Recorded sizes and hashes now let both files be kept without downloading
+        std::fs::remove_file(dir.path().join("good/a.onnx")).unwrap();
+        std::fs::write(dir.path().join("good/a.onnx"), b"ok encoder").unwrap();
+        let before = server.requests().len();
+        registry
+            .download(EngineKind::Parakeet, "good", None)
+            .await
+            .unwrap();
+        assert!(server.requests()[before..]
+            .iter()
+            .all(|request| !request.starts_with("GET")));
+    }
+}
@@ -0,0 +1,158 @@
+// Download progress shared by all engines, and the event it is emitted as
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::catalog::EngineKind;
+
+/// Event emitted for every model download, whichever engine it belongs to
+pub const MODEL_DOWNLOAD_EVENT: &str = "model-manager-download";
+
+/// Minimum time between progress reports when the percentage doesn't change
+const REPORT_INTERVAL: Duration = Duration::from_millis(500);
+
+const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
+
+/// Detailed download progress info (MB-based with speed)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DownloadProgress {
+    /// Bytes downloaded so far
+    pub downloaded_bytes: u64,
+    /// Total size in bytes
+    pub total_bytes: u64,
+    /// Downloaded in MB (for display)
+    pub downloaded_mb: f64,
+    /// Total size in MB (for display)
+    pub total_mb: f64,
+    /// Download speed in MB/s
+    pub speed_mbps: f64,
+    /// Percentage complete (0-100)
+    pub percent: u8,
+}
+
+impl DownloadProgress {
+    pub fn new(downloaded: u64, total: u64, speed_mbps: f64) -> Self {
+        let percent = if total > 0 {
+            ((downloaded as f64 / total as f64) * 100.0).min(100.0) as u8
+        } else {
+            0
+        };
+        Self {
+            downloaded_bytes: downloaded,
+            total_bytes: total,
+            downloaded_mb: downloaded as f64 / BYTES_PER_MB,
+            total_mb: total as f64 / BYTES_PER_MB,
+            speed_mbps,
+            percent,
+        }
+    }
+}
+
+/// Stage of a model download
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadPhase {
+    /// Waiting for a free download slot
+    Queued,
+    Downloading,
+    /// All files are down; the engine is validating the model
+    Verifying,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+/// Payload of `MODEL_DOWNLOAD_EVENT`
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadEvent {
+    pub engine: EngineKind,
+    pub model: String,
+    pub phase: DownloadPhase,
+    #[serde(flatten)]
+    pub progress: DownloadProgress,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Turns byte counts into throttled progress reports with the current speed
+pub(crate) struct ProgressTracker {
+    downloaded: u64,
+    total: u64,
+    last_report: Instant,
+    bytes_at_last_report: u64,
+    last_percent: u8,
+}
+
+impl ProgressTracker {
+    pub fn new(downloaded: u64, total: u64) -> Self {
+        let mut tracker = Self {
+            downloaded,
+            total,
+            last_report: Instant::now(),
+            bytes_at_last_report: downloaded,
+            last_percent: 0,
+        };
+        tracker.last_percent = tracker.snapshot(0.0).percent;
+        tracker
+    }
+
+    /// Update the total once the server reports a file's real length
+    pub fn set_total(&mut self, total: u64) {
+        self.total = total;
+    }
+
+    /// Record the bytes downloaded so far; returns a report every percent or
+    /// every 500 ms, and when the download is complete
+    pub fn update(&mut self, downloaded: u64) -> Option<DownloadProgress> {
+        self.downloaded = downloaded;
+
+        let elapsed = self.last_report.elapsed();
+        let percent = self.snapshot(0.0).percent;
+        let complete = self.total > 0 && downloaded >= self.total;
+        if percent <= self.last_percent && elapsed < REPORT_INTERVAL && !complete {
+            return None;
+        }
+
+        // A restarted file moves the count backwards; report no speed then
+        let speed_mbps = if elapsed.as_secs_f64() > 0.0 {
+            downloaded.saturating_sub(self.bytes_at_last_report) as f64
+                / BYTES_PER_MB
+                / elapsed.as_secs_f64()
+        } else {
+            0.0
+        };
+
+        self.last_report = Instant::now();
+        self.bytes_at_last_report = downloaded;
+        self.last_percent = percent;
+        Some(self.snapshot(speed_mbps))
+    }
+
+    pub fn snapshot(&self, speed_mbps: f64) -> DownloadProgress {
+        DownloadProgress::new(self.downloaded, self.total, speed_mbps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reports_each_percent_and_completion() {
+        let mut tracker = ProgressTracker::new(0, 1000);
+
+        assert!(tracker.update(5).is_none());
+        assert_eq!(tracker.update(10).unwrap().percent, 1);
+        assert!(tracker.update(15).is_none());
+
+        let done = tracker.update(1000).unwrap();
+        assert_eq!(done.percent, 100);
+        assert_eq!(done.downloaded_bytes, 1000);
+
+        // Servers can send more than the catalog size suggested
+        tracker.set_total(1200);
+        assert!(tracker.update(1100).is_none());
+        assert_eq!(tracker.update(1200).unwrap().percent, 100);
+    }
+}
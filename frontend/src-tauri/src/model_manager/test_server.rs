@@ -0,0 +1,183 @@
+// Local HTTP file server standing in for Hugging Face in tests
+// Serves in-memory files with Range support and publishes each file's
+// SHA-256 in `X-Linked-Etag` on HEAD, like Hugging Face does for LFS files.
+// Tests can cut a transfer short, ignore Range headers, corrupt a response
+// or hold responses back
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use tokio::sync::watch;
+
+use crate::model_integrity::DownloadHasher;
+use crate::test_http::{self, Request};
+
+#[derive(Default)]
+struct ServerState {
+    files: HashMap<String, Vec<u8>>,
+    ignore_ranges: bool,
+    cut_next_after: Option<usize>,
+    corrupt_next_get: bool,
+    /// "METHOD /path range" per request, in arrival order
+    requests: Vec<String>,
+}
+
+pub struct TestServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<ServerState>>,
+    open: watch::Sender<bool>,
+}
+
+impl TestServer {
+    pub async fn start() -> Self {
+        let state = Arc::new(Mutex::new(ServerState::default()));
+        let (open, open_rx) = watch::channel(true);
+
+        let server_state = state.clone();
+        let addr = test_http::serve(move |request, stream| {
+            handle(request, stream, server_state.clone(), open_rx.clone())
+        })
+        .await;
+
+        Self { addr, state, open }
+    }
+
+    pub fn serve(&self, path: &str, body: &[u8]) {
+        let mut state = self.state.lock().unwrap();
+        state.files.insert(path.to_string(), body.to_vec());
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("http://{}/{}", self.addr, path)
+    }
+
+    /// Answer Range requests with the whole file
+    pub fn ignore_ranges(&self) {
+        self.state.lock().unwrap().ignore_ranges = true;
+    }
+
+    /// Drop the connection after `bytes` of the next file body
+    pub fn cut_next_after(&self, bytes: usize) {
+        self.state.lock().unwrap().cut_next_after = Some(bytes);
+    }
+
+    /// Flip a byte of the next file body (the published hash stays right)
+    pub fn corrupt_next_get(&self) {
+        self.state.lock().unwrap().corrupt_next_get = true;
+    }
+
+    /// Hold GET responses back until `release`
+    pub fn hold(&self) {
+        self.open.send_replace(false);
+    }
+
+    pub fn release(&self) {
+        self.open.send_replace(true);
+    }
+
+    pub fn requests(&self) -> Vec<String> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+async fn handle(
+    request: Request,
+    mut stream: TcpStream,
+    state: Arc<Mutex<ServerState>>,
+    mut open: watch::Receiver<bool>,
+) {
+    let method = request.method.as_str();
+    let path = request.path.as_str();
+    let range = request.header("range").map(str::to_string);
+
+    let served = {
+        let mut state = state.lock().unwrap();
+        state.requests.push(
+            format!(
+                "{} /{} {}",
+                method,
+                path,
+                range.as_deref().unwrap_or_default()
+            )
+            .trim_end()
+            .to_string(),
+        );
+        state.files.get(path).cloned().map(|body| {
+            let start = match (&range, state.ignore_ranges) {
+                (Some(range), false) => range
+                    .trim_start_matches("bytes=")
+                    .trim_end_matches('-')
+                    .parse::<usize>()
+                    .ok(),
+                _ => None,
+            };
+            if method == "GET" {
+                (
+                    body,
+                    start,
+                    state.cut_next_after.take(),
+                    std::mem::take(&mut state.corrupt_next_get),
+                )
+            } else {
+                (body, start, None, false)
+            }
+        })
+    };
+    let Some((body, start, cut, corrupt)) = served else {
+        let _ = stream
+            .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+            .await;
+        return;
+    };
+
+    if method == "HEAD" {
+        let mut hasher = DownloadHasher::new();
+        hasher.update(&body);
+        let head = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nX-Linked-Etag: \"{}\"\r\nConnection: close\r\n\r\n",
+            body.len(),
+            hasher.finalize()
+        );
+        let _ = stream.write_all(head.as_bytes()).await;
+        return;
+    }
+
+    if start.is_some_and(|start| start >= body.len()) {
+        let head = format!(
+            "HTTP/1.1 416 Range Not Satisfiable\r\nContent-Range: bytes */{}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            body.len()
+        );
+        let _ = stream.write_all(head.as_bytes()).await;
+        return;
+    }
+
+    let _ = open.wait_for(|open| *open).await;
+
+    let head = match start {
+        Some(start) => format!(
+            "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {}-{}/{}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            start,
+            body.len() - 1,
+            body.len(),
+            body.len() - start
+        ),
+        None => format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            body.len()
+        ),
+    };
+    let mut sent = body[start.unwrap_or(0)..].to_vec();
+    if corrupt {
+        sent[0] ^= 0xff;
+    }
+    if let Some(cut) = cut {
+        sent.truncate(cut);
+    }
+
+    let _ = stream.write_all(head.as_bytes()).await;
+    let _ = stream.write_all(&sent).await;
+    let _ = stream.shutdown().await;
+}
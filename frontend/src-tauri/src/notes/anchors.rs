@@ -0,0 +1,109 @@
+// Timestamped note anchors and the notes context passed to summaries
+
+use crate::audio::transcription::worker::format_recording_time;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A note line tied to a moment of the recording
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoteAnchor {
+    pub id: String,
+    /// Seconds from the start of the recording, as in `transcripts.audio_start_time`
+    pub time: f64,
+    /// Editor block the anchor is attached to, if any
+    #[serde(default)]
+    pub block_id: Option<String>,
+    /// Text of the anchored note, kept in sync by the editor
+    #[serde(default)]
+    pub text: String,
+}
+
+impl NoteAnchor {
+    pub fn new(time: f64, block_id: Option<String>, text: String) -> Self {
+        Self {
+            id: format!("anchor-{}", Uuid::new_v4()),
+            time: time.max(0.0),
+            block_id,
+            text,
+        }
+    }
+}
+
+/// Anchors stored in `meeting_notes.anchors`; unreadable JSON counts as none
+pub fn parse_anchors(json: Option<&str>) -> Vec<NoteAnchor> {
+    json.and_then(|json| serde_json::from_str(json).ok())
+        .unwrap_or_default()
+}
+
+/// The user's notes as summary context: the markdown followed by the anchored
+/// notes in recording order, using the transcript's `[MM:SS]` labels.
+/// `None` when there is nothing written.
+pub fn notes_context(notes_markdown: Option<&str>, anchors: &[NoteAnchor]) -> Option<String> {
+    let mut sections = Vec::new();
+
+    let markdown = notes_markdown.map(str::trim).unwrap_or_default();
+    if !markdown.is_empty() {
+        sections.push(markdown.to_string());
+    }
+
+    let mut anchored: Vec<&NoteAnchor> = anchors
+        .iter()
+        .filter(|anchor| !anchor.text.trim().is_empty())
+        .collect();
+    anchored.sort_by(|a, b| a.time.total_cmp(&b.time));
+    if !anchored.is_empty() {
+        let lines: Vec<String> = anchored
+            .iter()
+            .map(|anchor| {
+                format!(
+                    "- {} {}",
+                    format_recording_time(anchor.time),
+                    anchor.text.trim()
+                )
+            })
+            .collect();
+        sections.push(format!("Timestamped notes:\n{}", lines.join("\n")));
+    }
+
+    (!sections.is_empty()).then(|| sections.join("\n\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notes_context_orders_anchors_and_skips_blanks() {
+        let anchors = vec![
+            NoteAnchor::new(754.2, None, "Budget approved".to_string()),
+            NoteAnchor::new(
+                65.0,
+                Some("block-1".to_string()),
+                " Ask Dana about QA ".to_string(),
+            ),
+            NoteAnchor::new(90.0, None, "  ".to_string()),
+        ];
+
+        assert_eq!(
+            notes_context(Some("## Decisions\n- Ship on Friday\n"), &anchors).as_deref(),
+            Some(
+                "## Decisions\n- Ship on Friday\n\nTimestamped notes:\n\
+                 - [01:05] Ask Dana about QA\n- [12:34] Budget approved"
+            )
+        );
+        assert_eq!(notes_context(Some("  \n"), &anchors[2..]), None);
+        assert_eq!(notes_context(None, &[]), None);
+    }
+
+    #[test]
+    fn test_parse_anchors_tolerates_bad_json() {
+        let json = r#"[{"id": "anchor-1", "time": 12.5, "text": "Intro"}]"#;
+        let anchors = parse_anchors(Some(json));
+        assert_eq!(anchors.len(), 1);
+        assert_eq!(anchors[0].block_id, None);
+        assert_eq!(anchors[0].time, 12.5);
+
+        assert!(parse_anchors(Some("not json")).is_empty());
+        assert!(parse_anchors(None).is_empty());
+    }
+}
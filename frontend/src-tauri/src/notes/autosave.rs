@@ -0,0 +1,306 @@
+// Debounced autosave of meeting notes
+// The editor reports every change; only the last one in a quiet period of
+// `AUTOSAVE_DEBOUNCE` is written. During a recording the meeting row doesn't
+// exist yet, so the draft is held in memory and mirrored to `notes.json` in the
+// recording's folder until the transcript is saved and the draft is adopted
+
+use crate::database::repositories::meeting_notes::MeetingNotesRepository;
+use crate::notes::anchors::NoteAnchor;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Quiet period after the last edit before it is written
+pub const AUTOSAVE_DEBOUNCE: Duration = Duration::from_millis(1500);
+/// Draft file kept in the recording's meeting folder
+const DRAFT_FILE_NAME: &str = "notes.json";
+
+/// Notes as edited in the UI, not yet tied to a stored row
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NotesDraft {
+    pub notes_markdown: Option<String>,
+    pub notes_json: Option<String>,
+    #[serde(default)]
+    pub anchors: Vec<NoteAnchor>,
+}
+
+impl NotesDraft {
+    pub fn is_empty(&self) -> bool {
+        self.notes_markdown
+            .as_deref()
+            .unwrap_or_default()
+            .trim()
+            .is_empty()
+            && self.anchors.is_empty()
+    }
+
+    /// Anchors as stored in `meeting_notes.anchors`
+    pub fn anchors_json(&self) -> Option<String> {
+        if self.anchors.is_empty() {
+            return None;
+        }
+        serde_json::to_string(&self.anchors).ok()
+    }
+}
+
+/// Where a draft is saved
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NotesTarget {
+    Meeting(String),
+    /// The recording in progress, whose meeting isn't saved yet
+    Recording,
+}
+
+impl NotesTarget {
+    pub fn from_meeting_id(meeting_id: Option<String>) -> Self {
+        match meeting_id
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty())
+        {
+            Some(id) => Self::Meeting(id),
+            None => Self::Recording,
+        }
+    }
+}
+
+/// Latest unsaved edit per target, tagged with a sequence number so a
+/// debounce timer can tell whether a newer edit superseded its own
+#[derive(Debug, Default)]
+pub struct PendingEdits {
+    edits: HashMap<NotesTarget, (u64, NotesDraft)>,
+    next_seq: u64,
+}
+
+impl PendingEdits {
+    /// Records an edit, replacing any older one, and returns its sequence number
+    pub fn push(&mut self, target: NotesTarget, draft: NotesDraft) -> u64 {
+        self.next_seq += 1;
+        self.edits.insert(target, (self.next_seq, draft));
+        self.next_seq
+    }
+
+    /// Takes the edit only if it is still the latest for `target`
+    pub fn take_if_latest(&mut self, target: &NotesTarget, seq: u64) -> Option<NotesDraft> {
+        match self.edits.get(target) {
+            Some((latest, _)) if *latest == seq => self.take(target),
+            _ => None,
+        }
+    }
+
+    pub fn take(&mut self, target: &NotesTarget) -> Option<NotesDraft> {
+        self.edits.remove(target).map(|(_, draft)| draft)
+    }
+
+    pub fn get(&self, target: &NotesTarget) -> Option<&NotesDraft> {
+        self.edits.get(target).map(|(_, draft)| draft)
+    }
+}
+
+static PENDING: Lazy<Mutex<PendingEdits>> = Lazy::new(|| Mutex::new(PendingEdits::default()));
+
+/// Latest notes of the recording in progress, saved to disk or not
+static RECORDING_DRAFT: Lazy<Mutex<Option<NotesDraft>>> = Lazy::new(|| Mutex::new(None));
+
+/// Forget the previous recording's notes when a new recording starts
+pub fn start_session() {
+    PENDING.lock().unwrap().take(&NotesTarget::Recording);
+    *RECORDING_DRAFT.lock().unwrap() = None;
+}
+
+/// Queue an edit; it is written once no newer edit arrives within `AUTOSAVE_DEBOUNCE`
+pub fn schedule(pool: SqlitePool, target: NotesTarget, draft: NotesDraft) {
+    if target == NotesTarget::Recording {
+        *RECORDING_DRAFT.lock().unwrap() = Some(draft.clone());
+    }
+    let seq = PENDING.lock().unwrap().push(target.clone(), draft);
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(AUTOSAVE_DEBOUNCE).await;
+        let Some(draft) = PENDING.lock().unwrap().take_if_latest(&target, seq) else {
+            return;
+        };
+        if let Err(e) = write(&pool, &target, &draft).await {
+            warn!("Failed to autosave notes ({:?}): {}", target, e);
+        }
+    });
+}
+
+/// Write the pending edit for `target` now, if there is one
+pub async fn flush(pool: &SqlitePool, target: &NotesTarget) -> Result<bool, String> {
+    let draft = PENDING.lock().unwrap().take(target);
+    match draft {
+        Some(draft) => write(pool, target, &draft).await.map(|_| true),
+        None => Ok(false),
+    }
+}
+
+/// Drop the pending edit for `target` without writing it
+pub fn discard(target: &NotesTarget) {
+    PENDING.lock().unwrap().take(target);
+}
+
+/// The unsaved edit for `target`, newer than what is stored
+pub fn pending(target: &NotesTarget) -> Option<NotesDraft> {
+    PENDING.lock().unwrap().get(target).cloned()
+}
+
+/// Notes of the recording in progress, for restoring the editor after a reload
+pub fn recording_draft() -> Option<NotesDraft> {
+    RECORDING_DRAFT.lock().unwrap().clone()
+}
+
+/// Store the recording's notes under the meeting just saved from it
+///
+/// Uses the in-memory draft, or the draft file in `folder_path` when the app
+/// restarted since. Returns whether any notes were saved.
+pub async fn adopt_recording_draft(
+    pool: &SqlitePool,
+    meeting_id: &str,
+    folder_path: Option<&str>,
+) -> Result<bool, String> {
+    PENDING.lock().unwrap().take(&NotesTarget::Recording);
+    let in_memory = RECORDING_DRAFT.lock().unwrap().take();
+
+    let draft_file = folder_path.map(|folder| Path::new(folder).join(DRAFT_FILE_NAME));
+    let draft = match (in_memory, draft_file.as_deref()) {
+        (Some(draft), _) => Some(draft),
+        (None, Some(path)) => read_draft_file(path).await,
+        (None, None) => None,
+    };
+
+    let saved = match draft.filter(|draft| !draft.is_empty()) {
+        Some(draft) => {
+            write(pool, &NotesTarget::Meeting(meeting_id.to_string()), &draft).await?;
+            info!("Saved recording notes to meeting {}", meeting_id);
+            true
+        }
+        None => false,
+    };
+
+    if let Some(path) = draft_file {
+        if path.exists() {
+            if let Err(e) = tokio::fs::remove_file(&path).await {
+                warn!("Failed to remove notes draft {}: {}", path.display(), e);
+            }
+        }
+    }
+    Ok(saved)
+}
+
+async fn write(pool: &SqlitePool, target: &NotesTarget, draft: &NotesDraft) -> Result<(), String> {
+    match target {
+        NotesTarget::Meeting(meeting_id) => {
+            MeetingNotesRepository::upsert(
+                pool,
+                meeting_id,
+                draft.notes_markdown.as_deref(),
+                draft.notes_json.as_deref(),
+                draft.anchors_json().as_deref(),
+            )
+            .await
+            .map_err(|e| format!("Failed to save notes: {}", e))?;
+            Ok(())
+        }
+        NotesTarget::Recording => {
+            // Without a meeting folder (auto-save off) the draft lives in memory only
+            let Some(folder) = crate::audio::recording_commands::get_meeting_folder_path().await?
+            else {
+                return Ok(());
+            };
+            write_draft_file(&PathBuf::from(folder).join(DRAFT_FILE_NAME), draft).await
+        }
+    }
+}
+
+async fn write_draft_file(path: &Path, draft: &NotesDraft) -> Result<(), String> {
+    let json = serde_json::to_vec_pretty(draft)
+        .map_err(|e| format!("Failed to serialize notes draft: {}", e))?;
+    // Write then rename, so a crash mid-write leaves the previous draft intact
+    let tmp_path = path.with_extension("json.tmp");
+    tokio::fs::write(&tmp_path, json)
+        .await
+        .map_err(|e| format!("Failed to write notes draft: {}", e))?;
+    tokio::fs::rename(&tmp_path, path)
+        .await
+        .map_err(|e| format!("Failed to write notes draft: {}", e))
+}
+
+async fn read_draft_file(path: &Path) -> Option<NotesDraft> {
+    let bytes = tokio::fs::read(path).await.ok()?;
+    match serde_json::from_slice(&bytes) {
+        Ok(draft) => Some(draft),
+        Err(e) => {
+            warn!("Ignoring unreadable notes draft {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draft(markdown: &str) -> NotesDraft {
+        NotesDraft {
+            notes_markdown: Some(markdown.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pending_edits_keep_only_the_latest() {
+        let mut pending = PendingEdits::default();
+        let meeting = NotesTarget::Meeting("meeting-1".to_string());
+
+        let first = pending.push(meeting.clone(), draft("a"));
+        let second = pending.push(meeting.clone(), draft("ab"));
+        let recording = pending.push(NotesTarget::Recording, draft("live"));
+
+        // The first timer fires after a newer edit: nothing to write yet
+        assert_eq!(pending.take_if_latest(&meeting, first), None);
+        assert_eq!(pending.take_if_latest(&meeting, second), Some(draft("ab")));
+        assert_eq!(pending.take_if_latest(&meeting, second), None);
+
+        assert_eq!(pending.get(&NotesTarget::Recording), Some(&draft("live")));
+        assert_eq!(pending.take(&NotesTarget::Recording), Some(draft("live")));
+        assert_eq!(
+            pending.take_if_latest(&NotesTarget::Recording, recording),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_draft_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(DRAFT_FILE_NAME);
+
+        let mut notes = draft("- Follow up with legal");
+        notes
+            .anchors
+            .push(NoteAnchor::new(42.0, None, "Pricing".to_string()));
+        write_draft_file(&path, &notes).await.unwrap();
+        assert_eq!(read_draft_file(&path).await, Some(notes));
+
+        std::fs::write(&path, "{ truncated").unwrap();
+        assert_eq!(read_draft_file(&path).await, None);
+    }
+
+    #[test]
+    fn test_target_from_meeting_id() {
+        assert_eq!(NotesTarget::from_meeting_id(None), NotesTarget::Recording);
+        assert_eq!(
+            NotesTarget::from_meeting_id(Some(" ".to_string())),
+            NotesTarget::Recording
+        );
+        assert_eq!(
+            NotesTarget::from_meeting_id(Some("meeting-1".to_string())),
+            NotesTarget::Meeting("meeting-1".to_string())
+        );
+        assert!(draft(" \n").is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+use super::anchors::NoteAnchor;
+use super::autosave::{self, NotesDraft, NotesTarget};
+use crate::database::models::MeetingNotes;
+use crate::database::repositories::meeting_notes::MeetingNotesRepository;
+use crate::state::AppState;
+use tauri::command;
+
+/// A meeting's saved notes, or `None` if the user hasn't written any
+#[command]
+pub async fn api_get_meeting_notes(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Option<MeetingNotes>, String> {
+    let pool = state.db_manager.pool();
+    // An edit still waiting on the autosave timer is newer than the stored row
+    autosave::flush(pool, &NotesTarget::Meeting(meeting_id.clone())).await?;
+
+    MeetingNotesRepository::get(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load notes: {}", e))
+}
+
+/// Save a meeting's notes immediately, replacing any pending autosave
+#[command]
+pub async fn api_save_meeting_notes(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    notes_markdown: Option<String>,
+    notes_json: Option<String>,
+    anchors: Option<Vec<NoteAnchor>>,
+) -> Result<MeetingNotes, String> {
+    autosave::discard(&NotesTarget::Meeting(meeting_id.clone()));
+
+    let draft = NotesDraft {
+        notes_markdown,
+        notes_json,
+        anchors: anchors.unwrap_or_default(),
+    };
+    MeetingNotesRepository::upsert(
+        state.db_manager.pool(),
+        &meeting_id,
+        draft.notes_markdown.as_deref(),
+        draft.notes_json.as_deref(),
+        draft.anchors_json().as_deref(),
+    )
+    .await
+    .map_err(|e| format!("Failed to save notes: {}", e))
+}
+
+#[command]
+pub async fn api_delete_meeting_notes(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<bool, String> {
+    autosave::discard(&NotesTarget::Meeting(meeting_id.clone()));
+
+    MeetingNotesRepository::delete(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to delete notes: {}", e))
+}
+
+/// Queue notes for a debounced save; call on every editor change
+///
+/// Without a `meeting_id` the notes belong to the recording in progress and
+/// are stored with its meeting once the transcript is saved.
+#[command]
+pub async fn api_autosave_meeting_notes(
+    state: tauri::State<'_, AppState>,
+    meeting_id: Option<String>,
+    notes_markdown: Option<String>,
+    notes_json: Option<String>,
+    anchors: Option<Vec<NoteAnchor>>,
+) -> Result<(), String> {
+    let draft = NotesDraft {
+        notes_markdown,
+        notes_json,
+        anchors: anchors.unwrap_or_default(),
+    };
+    autosave::schedule(
+        state.db_manager.pool().clone(),
+        NotesTarget::from_meeting_id(meeting_id),
+        draft,
+    );
+    Ok(())
+}
+
+/// Write a pending autosave now (e.g. when the editor closes); returns whether
+/// there was one
+#[command]
+pub async fn api_flush_meeting_notes(
+    state: tauri::State<'_, AppState>,
+    meeting_id: Option<String>,
+) -> Result<bool, String> {
+    autosave::flush(
+        state.db_manager.pool(),
+        &NotesTarget::from_meeting_id(meeting_id),
+    )
+    .await
+}
+
+/// Notes of the recording in progress, for restoring the editor after a page reload
+#[command]
+pub async fn api_get_recording_notes() -> Result<Option<NotesDraft>, String> {
+    Ok(autosave::recording_draft())
+}
+
+/// Anchor a note to the current point of the recording
+#[command]
+pub async fn api_create_note_anchor(
+    text: Option<String>,
+    block_id: Option<String>,
+) -> Result<NoteAnchor, String> {
+    let time = crate::audio::recording_commands::current_recording_duration()
+        .ok_or_else(|| "No recording in progress".to_string())?;
+    Ok(NoteAnchor::new(time, block_id, text.unwrap_or_default()))
+}
@@ -0,0 +1,46 @@
+//! The user's own meeting notes.
+//!
+//! Notes are written alongside a meeting, including while it is being
+//! recorded. Edits are saved with a short debounce; during a recording they
+//! are kept as a draft (mirrored to the meeting folder) and attached to the
+//! meeting when its transcript is saved. Note lines can be anchored to the
+//! recording-relative time they were taken at, and the notes are passed to
+//! summary generation as high-priority context.
+//!
+//! # Module Structure
+//!
+//! - `anchors`: Timestamped note anchors and the notes context for summaries
+//! - `autosave`: Debounced saving and the recording draft
+//! - `commands`: Tauri commands for reading, saving and anchoring notes
+
+pub mod anchors;
+pub mod autosave;
+pub mod commands;
+
+pub use anchors::{notes_context, parse_anchors, NoteAnchor};
+
+use crate::database::repositories::meeting_notes::MeetingNotesRepository;
+use sqlx::SqlitePool;
+use tracing::warn;
+
+/// A meeting's notes as summary context, or `None` if it has none
+pub async fn summary_context(pool: &SqlitePool, meeting_id: &str) -> Option<String> {
+    // Include an edit still waiting on the autosave timer
+    let target = autosave::NotesTarget::Meeting(meeting_id.to_string());
+    if let Err(e) = autosave::flush(pool, &target).await {
+        warn!("{}", e);
+    }
+
+    match MeetingNotesRepository::get(pool, meeting_id).await {
+        Ok(notes) => notes.and_then(|notes| {
+            notes_context(
+                notes.notes_markdown.as_deref(),
+                &parse_anchors(notes.anchors.as_deref()),
+            )
+        }),
+        Err(e) => {
+            warn!("Failed to load notes for {}: {}", meeting_id, e);
+            None
+        }
+    }
+}
@@ -47,7 +47,9 @@ pub async fn parakeet_init() -> Result<(), String> {
     let models_dir = get_models_directory();
     let engine = ParakeetEngine::new_with_models_dir(models_dir)
         .map_err(|e| format!("Failed to initialize Parakeet engine: {}", e))?;
-    *guard = Some(Arc::new(engine));
+    let engine = Arc::new(engine);
+    crate::model_manager::registry().register(engine.clone());
+    *guard = Some(engine);
     Ok(())
 }
 
@@ -509,14 +511,12 @@ pub async fn parakeet_retry_download<R: Runtime>(
     };
 
     if let Some(engine) = engine {
-        // DEFENSIVE: Ensure clean state before retry
-        // This handles any edge cases where error handler didn't complete
+        // A retry while the previous attempt is still running would only be rejected
+        if crate::model_manager::registry()
+            .progress(crate::model_manager::EngineKind::Parakeet, &model_name)
+            .is_some()
         {
-            let mut active = engine.active_downloads.write().await;
-            if active.contains(&model_name) {
-                log::warn!("Retry: Model {} was still in active downloads, removing", model_name);
-                active.remove(&model_name);
-            }
+            return Err(format!("Download already in progress for model: {}", model_name));
         }
 
         // DEFENSIVE: Force model status to Missing to allow fresh download
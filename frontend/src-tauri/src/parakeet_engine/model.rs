@@ -7,6 +7,8 @@ use ort::session::Session;
 use ort::value::TensorRef;
 use regex::Regex;
 
+use crate::audio::transcription::{merge_tokens_into_words, TimedToken, WordTimestamp};
+
 use std::fs;
 use std::path::Path;
 
@@ -26,6 +28,33 @@ pub struct TimestampedResult {
     pub tokens: Vec<String>,
 }
 
+impl TimestampedResult {
+    /// Word-level timing in seconds from the start of the audio
+    ///
+    /// Tokens only carry a start time, so each token ends where the next one
+    /// starts; the last one ends at `audio_duration` (capped to avoid
+    /// stretching it over trailing silence).
+    pub fn words(&self, audio_duration: f64) -> Vec<WordTimestamp> {
+        const LAST_TOKEN_MAX_DURATION: f64 = 0.5;
+
+        let starts: Vec<f64> = self.timestamps.iter().map(|&t| t as f64).collect();
+        let tokens = self.tokens.iter().zip(&starts).enumerate().map(|(i, (text, &start))| {
+            let end = starts
+                .get(i + 1)
+                .copied()
+                .unwrap_or_else(|| audio_duration.min(start + LAST_TOKEN_MAX_DURATION));
+            TimedToken {
+                text,
+                start,
+                end,
+                confidence: None,
+            }
+        });
+
+        merge_tokens_into_words(tokens)
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum ParakeetError {
     #[error("ORT error")]
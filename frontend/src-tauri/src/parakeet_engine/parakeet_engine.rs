@@ -1,14 +1,17 @@
 use crate::parakeet_engine::model::ParakeetModel;
+use crate::audio::transcription::WordTimestamp;
+use crate::model_integrity::{self, ModelVerification};
+use crate::model_manager::{self, CatalogEntry, EngineKind, ModelEngine};
 use anyhow::{anyhow, Result};
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::fs;
-use tokio::io::{AsyncWriteExt, BufWriter};
-use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
-use tokio::time::timeout;
+
+pub use crate::model_manager::DownloadProgress;
 
 /// Quantization type for Parakeet models
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -33,40 +36,14 @@ pub enum ModelStatus {
     Corrupted { file_size: u64, expected_min_size: u64 },
 }
 
-/// Detailed download progress info (MB-based with speed)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DownloadProgress {
-    /// Bytes downloaded so far
-    pub downloaded_bytes: u64,
-    /// Total file size in bytes
-    pub total_bytes: u64,
-    /// Downloaded in MB (for display)
-    pub downloaded_mb: f64,
-    /// Total size in MB (for display)
-    pub total_mb: f64,
-    /// Download speed in MB/s
-    pub speed_mbps: f64,
-    /// Percentage complete (0-100)
-    pub percent: u8,
-}
-
-impl DownloadProgress {
-    pub fn new(downloaded: u64, total: u64, speed_mbps: f64) -> Self {
-        let percent = if total > 0 {
-            ((downloaded as f64 / total as f64) * 100.0).min(100.0) as u8
-        } else {
-            0
-        };
-        Self {
-            downloaded_bytes: downloaded,
-            total_bytes: total,
-            downloaded_mb: downloaded as f64 / (1024.0 * 1024.0),
-            total_mb: total as f64 / (1024.0 * 1024.0),
-            speed_mbps,
-            percent,
-        }
-    }
-}
+// Parakeet model configurations
+// Model name format: parakeet-tdt-0.6b-v{version}-{quantization}
+// Sizes match actual download sizes (encoder + decoder + preprocessor + vocab)
+// (name, size_mb, quantization, speed, description)
+const MODEL_CONFIGS: [(&str, u32, QuantizationType, &str, &str); 2] = [
+    ("parakeet-tdt-0.6b-v3-int8", 670, QuantizationType::Int8, "Ultra Fast (v3)", "Real time on M4 Max, latest version with int8 quantization"),
+    ("parakeet-tdt-0.6b-v2-int8", 661, QuantizationType::Int8, "Fast (v2)", "Previous version with int8 quantization, good balance of speed and accuracy"),
+];
 
 /// Information about a Parakeet model
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -116,9 +93,6 @@ pub struct ParakeetEngine {
     current_model: Arc<RwLock<Option<ParakeetModel>>>,
     current_model_name: Arc<RwLock<Option<String>>>,
     pub(crate) available_models: Arc<RwLock<HashMap<String, ModelInfo>>>,
-    cancel_download_flag: Arc<RwLock<Option<String>>>, // Model name being cancelled
-    // Active downloads tracking to prevent concurrent downloads
-    pub(crate) active_downloads: Arc<RwLock<HashSet<String>>>, // Set of models currently being downloaded
 }
 
 impl ParakeetEngine {
@@ -157,9 +131,6 @@ impl ParakeetEngine {
             current_model: Arc::new(RwLock::new(None)),
             current_model_name: Arc::new(RwLock::new(None)),
             available_models: Arc::new(RwLock::new(HashMap::new())),
-            cancel_download_flag: Arc::new(RwLock::new(None)),
-            // Initialize active downloads tracking
-            active_downloads: Arc::new(RwLock::new(HashSet::new())),
         })
     }
 
@@ -168,26 +139,13 @@ impl ParakeetEngine {
         let models_dir = &self.models_dir;
         let mut models = Vec::new();
 
-        // Parakeet model configurations
-        // Model name format: parakeet-tdt-0.6b-v{version}-{quantization}
-        // Sizes match actual download sizes (encoder + decoder + preprocessor + vocab)
-        let model_configs = [
-            ("parakeet-tdt-0.6b-v3-int8", 670, QuantizationType::Int8, "Ultra Fast (v3)", "Real time on M4 Max, latest version with int8 quantization"),
-            ("parakeet-tdt-0.6b-v2-int8", 661, QuantizationType::Int8, "Fast (v2)", "Previous version with int8 quantization, good balance of speed and accuracy"),
-        ];
-
-        // Get active downloads to override status
-        let active_downloads = self.active_downloads.read().await;
-
-        for (name, size_mb, quantization, speed, description) in model_configs {
+        for (name, size_mb, quantization, speed, description) in MODEL_CONFIGS {
             let model_path = models_dir.join(name);
 
             // Check if model is currently downloading
-            let status = if active_downloads.contains(name) {
+            let status = if let Some(progress) = model_manager::registry().progress(EngineKind::Parakeet, name) {
                 // If downloading, preserve that status regardless of file system
-                // We don't know the exact progress here without more state, but 0 is safe fallback
-                // The progress events will update the UI
-                ModelStatus::Downloading { progress: 0 }
+                ModelStatus::Downloading { progress: progress.percent }
             } else if model_path.exists() {
                 // Check for required ONNX files
                 let required_files = match quantization {
@@ -238,8 +196,8 @@ impl ParakeetEngine {
             let model_info = ModelInfo {
                 name: name.to_string(),
                 path: model_path,
-                size_mb: size_mb as u32,
-                quantization: quantization.clone(),
+                size_mb,
+                quantization,
                 speed: speed.to_string(),
                 status,
                 description: description.to_string(),
@@ -325,53 +283,6 @@ impl ParakeetEngine {
         Ok(())
     }
 
-    /// Clean incomplete model directory before download
-    /// Removes all files if directory exists but model is not Available
-    async fn clean_incomplete_model_directory(&self, model_dir: &PathBuf) -> Result<()> {
-        if !model_dir.exists() {
-            return Ok(()); // Nothing to clean
-        }
-
-        // Validate the directory
-        match self.validate_model_directory(model_dir).await {
-            Ok(_) => {
-                log::info!("Model directory is valid, no cleanup needed");
-                return Ok(());
-            }
-            Err(validation_error) => {
-                log::warn!(
-                    "Model directory exists but is invalid: {}. Cleaning up...",
-                    validation_error
-                );
-
-                // List and remove all files in the directory
-                let mut entries = fs::read_dir(model_dir).await
-                    .map_err(|e| anyhow!("Failed to read model directory: {}", e))?;
-
-                let mut removed_count = 0;
-                while let Some(entry) = entries.next_entry().await
-                    .map_err(|e| anyhow!("Failed to read directory entry: {}", e))?
-                {
-                    let path = entry.path();
-                    if path.is_file() {
-                        match fs::remove_file(&path).await {
-                            Ok(_) => {
-                                log::info!("Removed incomplete file: {:?}", path.file_name());
-                                removed_count += 1;
-                            }
-                            Err(e) => {
-                                log::warn!("Failed to remove file {:?}: {}", path, e);
-                            }
-                        }
-                    }
-                }
-
-                log::info!("Cleaned {} incomplete files from model directory", removed_count);
-                Ok(())
-            }
-        }
-    }
-
     /// Load a Parakeet model
     pub async fn load_model(&self, model_name: &str) -> Result<()> {
         let models = self.available_models.read().await;
@@ -452,6 +363,15 @@ impl ParakeetEngine {
 
     /// Transcribe audio samples using the loaded Parakeet model
     pub async fn transcribe_audio(&self, audio_data: Vec<f32>) -> Result<String> {
+        let (text, _) = self.transcribe_audio_with_words(audio_data).await?;
+        Ok(text)
+    }
+
+    /// Transcribe audio and return word-level timings (seconds from the start of `audio_data`)
+    pub async fn transcribe_audio_with_words(
+        &self,
+        audio_data: Vec<f32>,
+    ) -> Result<(String, Vec<WordTimestamp>)> {
         let mut model_guard = self.current_model.write().await;
         let model = model_guard
             .as_mut()
@@ -471,7 +391,8 @@ impl ParakeetEngine {
 
         log::debug!("Parakeet transcription result: '{}'", result.text);
 
-        Ok(result.text)
+        let words = result.words(duration_seconds);
+        Ok((result.text, words))
     }
 
     /// Get the models directory path
@@ -529,77 +450,29 @@ impl ParakeetEngine {
     pub async fn download_model(
         &self,
         model_name: &str,
-        progress_callback: Option<Box<dyn Fn(u8) + Send>>,
+        progress_callback: Option<Box<dyn Fn(u8) + Send + Sync>>,
     ) -> Result<()> {
         // Wrap simple callback to use detailed version
-        let detailed_callback: Option<Box<dyn Fn(DownloadProgress) + Send>> =
+        let detailed_callback: Option<Box<dyn Fn(DownloadProgress) + Send + Sync>> =
             progress_callback.map(|cb| {
-                Box::new(move |p: DownloadProgress| cb(p.percent)) as Box<dyn Fn(DownloadProgress) + Send>
+                Box::new(move |p: DownloadProgress| cb(p.percent)) as Box<dyn Fn(DownloadProgress) + Send + Sync>
             });
         self.download_model_detailed(model_name, detailed_callback).await
     }
 
-    /// Download a Parakeet model with detailed progress (MB/speed/resume support)
-    pub async fn download_model_detailed(
-        &self,
-        model_name: &str,
-        progress_callback: Option<Box<dyn Fn(DownloadProgress) + Send>>,
-    ) -> Result<()> {
-        log::info!("Starting download for Parakeet model: {}", model_name);
-
-        // Check if download is already in progress for this model
-        {
-            let active = self.active_downloads.read().await;
-            if active.contains(model_name) {
-                log::warn!("Download already in progress for Parakeet model: {}", model_name);
-                return Err(anyhow!("Download already in progress for model: {}", model_name));
-            }
-        }
-
-        // Add to active downloads
-        {
-            let mut active = self.active_downloads.write().await;
-            active.insert(model_name.to_string());
-        }
-
-        // Clear any previous cancellation flag for this model
-        {
-            let mut cancel_flag = self.cancel_download_flag.write().await;
-            *cancel_flag = None;
-        }
-
-        // Get model info
-        let model_info = {
-            let models = self.available_models.read().await;
-            match models.get(model_name).cloned() {
-                Some(info) => info,
-                None => {
-                    // Remove from active downloads on error
-                    let mut active = self.active_downloads.write().await;
-                    active.remove(model_name);
-                    return Err(anyhow!("Model {} not found", model_name));
-                }
-            }
-        };
-
-        // Update model status to downloading
-        {
-            let mut models = self.available_models.write().await;
-            if let Some(model) = models.get_mut(model_name) {
-                model.status = ModelStatus::Downloading { progress: 0 };
-            }
-        }
-
-        // HuggingFace base URL for Parakeet models (version-specific)
-        let base_url = if model_name.contains("-v2-") {
+    /// HuggingFace base URL for Parakeet models (version-specific)
+    fn download_base_url(model_name: &str) -> &'static str {
+        if model_name.contains("-v2-") {
             "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v2-onnx/resolve/main"
         } else {
             // Default to v3 for v3 models
             "https://meetily.towardsgeneralintelligence.com/models/parakeet-tdt-0.6b-v3-onnx"
-        };
+        }
+    }
 
-        // Determine which files to download based on quantization
-        let files_to_download = match model_info.quantization {
+    /// Files making up a model of the given quantization
+    fn model_files(quantization: &QuantizationType) -> Vec<&'static str> {
+        match quantization {
             QuantizationType::Int8 => vec![
                 "encoder-model.int8.onnx",
                 "decoder_joint-model.int8.onnx",
@@ -612,40 +485,13 @@ impl ParakeetEngine {
                 "nemo128.onnx",
                 "vocab.txt",
             ],
-        };
-
-        // Create model directory
-        let model_dir = &model_info.path;
-        if !model_dir.exists() {
-            if let Err(e) = fs::create_dir_all(model_dir).await {
-                // Remove from active downloads on error
-                let mut active = self.active_downloads.write().await;
-                active.remove(model_name);
-                return Err(anyhow!("Failed to create model directory: {}", e));
-            }
         }
+    }
 
-        // Clean up incomplete downloads before starting
-        log::info!("Checking for incomplete model files to clean up...");
-        if let Err(e) = self.clean_incomplete_model_directory(model_dir).await {
-            log::warn!("Failed to clean incomplete model directory: {}", e);
-            // Continue anyway - we'll handle errors during download
-        }
-
-        // Optimized HTTP client for large file downloads
-        let client = reqwest::Client::builder()
-            .tcp_nodelay(true)              // Disable Nagle's algorithm for better streaming
-            .pool_max_idle_per_host(1)      // Keep connection alive
-            .timeout(Duration::from_secs(3600))  // 1 hour timeout for large files
-            .connect_timeout(Duration::from_secs(30))
-            .build()
-            .map_err(|e| anyhow!("Failed to create HTTP client: {}", e))?;
-
-        let total_files = files_to_download.len();
-
-        // Calculate total download size for weighted progress
-        // Note: These are approximate sizes based on HuggingFace repo inspection
-        let file_sizes: std::collections::HashMap<&str, u64> = match model_info.quantization {
+    /// Approximate size of each model file, used for progress and disk space checks
+    /// Note: These are approximate sizes based on HuggingFace repo inspection
+    fn file_sizes(model_name: &str, quantization: &QuantizationType) -> HashMap<&'static str, u64> {
+        match quantization {
             QuantizationType::Int8 => {
                 if model_name.contains("-v2-") {
                     // V2 model sizes
@@ -674,415 +520,116 @@ impl ParakeetEngine {
                     ("vocab.txt", 93_900u64),                                  // 93.9 KB
                 ].iter().cloned().collect()
             }
-        };
-
-        // Calculate total expected download size
-        let total_size_bytes: u64 = files_to_download.iter()
-            .filter_map(|f| file_sizes.get(*f))
-            .copied()
-            .sum();
-
-        // Check for existing downloads (complete or partial) to calculate resume offset
-        let mut already_downloaded: u64 = 0;
-        for filename in &files_to_download {
-            let file_path = model_dir.join(filename);
-            if file_path.exists() {
-                if let Ok(metadata) = fs::metadata(&file_path).await {
-                    let file_size = metadata.len();
-                    let expected_size = file_sizes.get(*filename).copied().unwrap_or(0);
-                    // Count all existing bytes (complete files capped at expected size, partial as-is)
-                    // This ensures progress starts from where we left off
-                    already_downloaded += file_size.min(expected_size);
-                }
-            }
         }
+    }
 
-        let mut total_downloaded: u64 = already_downloaded;
-
-        // Timing for speed calculation
-        let download_start_time = Instant::now();
-        let mut last_report_time = Instant::now();
-        let mut bytes_since_last_report: u64 = 0;
-        let mut last_reported_progress: u8 = 0;
-
-        log::info!(
-            "Starting weighted download for {} files, total size: {:.2} MB (already downloaded: {:.2} MB)",
-            total_files,
-            total_size_bytes as f64 / 1_048_576.0,
-            already_downloaded as f64 / 1_048_576.0
-        );
-
-        for (index, filename) in files_to_download.iter().enumerate() {
-            let file_url = format!("{}/{}", base_url, filename);
-            let file_path = model_dir.join(filename);
-
-            // Check for existing partial file to resume
-            let existing_size: u64 = if file_path.exists() {
-                fs::metadata(&file_path).await.map(|m| m.len()).unwrap_or(0)
-            } else {
-                0
-            };
-
-            let expected_size = file_sizes.get(*filename).copied().unwrap_or(0);
+    /// Download a Parakeet model with detailed progress (MB/speed/resume support)
+    /// Goes through the shared model manager: partial files are resumed and a file
+    /// that fails SHA-256 verification is quarantined and the download retried
+    pub async fn download_model_detailed(
+        &self,
+        model_name: &str,
+        progress_callback: Option<Box<dyn Fn(DownloadProgress) + Send + Sync>>,
+    ) -> Result<()> {
+        log::info!("Starting download for Parakeet model: {}", model_name);
 
-            // Skip if file is already complete (with 1% tolerance for size variations)
-            let size_tolerance = (expected_size as f64 * 0.99) as u64;
-            if existing_size >= size_tolerance && expected_size > 0 {
-                log::info!(
-                    "Skipping complete file: {} ({:.2} MB, expected: {:.2} MB)",
-                    filename,
-                    existing_size as f64 / 1_048_576.0,
-                    expected_size as f64 / 1_048_576.0
-                );
-                continue;
+        let on_progress = |progress: &DownloadProgress| {
+            if let Some(ref callback) = progress_callback {
+                callback(progress.clone());
             }
+        };
+        model_manager::registry()
+            .download(EngineKind::Parakeet, model_name, Some(&on_progress))
+            .await?;
 
-            log::info!("Downloading file {}/{}: {} (resuming from {} bytes)", index + 1, total_files, filename, existing_size);
+        log::info!("Download completed for Parakeet model: {}", model_name);
+        Ok(())
+    }
 
-            // Build request with optional Range header for resume
-            let mut request = client.get(&file_url);
-            if existing_size > 0 {
-                request = request.header("Range", format!("bytes={}-", existing_size));
-                log::info!("Resuming download from byte {}", existing_size);
+    /// Re-check the SHA-256 of every installed model file, then rediscover models
+    pub async fn verify_models(&self) -> Vec<ModelVerification> {
+        let models = match self.discover_models().await {
+            Ok(models) => models,
+            Err(e) => {
+                log::error!("Failed to discover Parakeet models for verification: {}", e);
+                return Vec::new();
             }
+        };
 
-            let mut response = request.send().await
-                .map_err(|e| {
-                    anyhow!("Failed to start download for {}: {}", filename, e)
-                })?;
-
-            // Handle response status
-            let (file_total_size, resuming) = if response.status() == reqwest::StatusCode::PARTIAL_CONTENT {
-                // Server supports resume, get remaining size
-                let remaining = response.content_length().unwrap_or(0);
-                log::info!("Server supports resume, remaining: {} bytes", remaining);
-                (existing_size + remaining, true)
-            } else if response.status().is_success() {
-                // Fresh download or server doesn't support resume
-                if existing_size > 0 {
-                    log::warn!("Server doesn't support resume for {}, starting fresh download", filename);
-                }
-                (response.content_length().unwrap_or(0), false)
-            } else if response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
-                // 416: Range not satisfiable - file complete or invalid range
-                log::warn!("Server returned 416 Range Not Satisfiable for {}", filename);
-
-                let size_tolerance = (expected_size as f64 * 0.99) as u64;
-                if existing_size >= size_tolerance && expected_size > 0 {
-                    // File is complete - skip it
-                    log::info!("File {} complete ({} bytes). Skipping.", filename, existing_size);
-                    continue;
-                } else {
-                    // File incomplete but server won't accept range - delete and retry
-                    log::warn!(
-                        "File {} incomplete ({}/{} bytes). Deleting and retrying.",
-                        filename, existing_size, expected_size
-                    );
-
-                    if let Err(e) = fs::remove_file(&file_path).await {
-                        let mut active = self.active_downloads.write().await;
-                        active.remove(model_name);
-                        return Err(anyhow!("Failed to delete incomplete file {}: {}", filename, e));
-                    }
-
-                    // Retry without Range header
-                    log::info!("Retrying {} without resume", filename);
-                    response = client.get(&file_url).send().await
-                        .map_err(|e| anyhow!("Retry failed for {}: {}", filename, e))?;
-
-                    if !response.status().is_success() {
-                        let mut active = self.active_downloads.write().await;
-                        active.remove(model_name);
-                        return Err(anyhow!("Retry failed for {} with status: {}", filename, response.status()));
-                    }
-
-                    (response.content_length().unwrap_or(0), false)
-                }
-            } else {
-                // Other errors
-                let mut active = self.active_downloads.write().await;
-                active.remove(model_name);
-                return Err(anyhow!("Download failed for {} with status: {}", filename, response.status()));
-            };
-
-            // Open file for writing (append if resuming, create new if not)
-            let file = if resuming {
-                fs::OpenOptions::new()
-                    .append(true)
-                    .open(&file_path)
-                    .await
-                    .map_err(|e| anyhow!("Failed to open file for resume {}: {}", filename, e))?
-            } else {
-                fs::File::create(&file_path)
-                    .await
-                    .map_err(|e| anyhow!("Failed to create file {}: {}", filename, e))?
-            };
-
-            // Use buffered writer for better I/O performance (8MB buffer)
-            let mut writer = BufWriter::with_capacity(8 * 1024 * 1024, file);
-
-            // Stream download
-            use futures_util::StreamExt;
-            let mut stream = response.bytes_stream();
-            let mut file_downloaded = if resuming { existing_size } else { 0u64 };
-
-            loop {
-                // Check for cancellation before processing chunk
-                {
-                    let cancel_flag = self.cancel_download_flag.read().await;
-                    if cancel_flag.as_ref() == Some(&model_name.to_string()) {
-                        log::info!("Download cancelled for {}", model_name);
-                        // Flush and keep partial file for resume on next attempt
-                        let _ = writer.flush().await;
-                        drop(writer);
-                        // Remove from active downloads on cancellation
-                        let mut active = self.active_downloads.write().await;
-                        active.remove(model_name);
-                        return Err(anyhow!("Download cancelled by user"));
-                    }
-                }
-
-                // Add per-chunk timeout (30 seconds) to detect stalled connections
-                let next_result = timeout(Duration::from_secs(30), stream.next()).await;
-
-                let chunk = match next_result {
-                    // Timeout - no data received for 30 seconds
-                    Err(_) => {
-                        log::warn!("Download timeout for {}: no data received for 30 seconds", model_name);
-                        let _ = writer.flush().await;
-
-                        // Remove from active downloads
-                        {
-                            let mut active = self.active_downloads.write().await;
-                            active.remove(model_name);
-                        }
-
-                        // Update model status to Missing so retry can work
-                        {
-                            let mut models = self.available_models.write().await;
-                            if let Some(model) = models.get_mut(model_name) {
-                                model.status = ModelStatus::Missing;
-                            }
-                        }
-
-                        return Err(anyhow!("Download timeout - No data received for 30 seconds"));
-                    },
-                    // Stream ended
-                    Ok(None) => break,
-                    // Got chunk result
-                    Ok(Some(chunk_result)) => {
-                        match chunk_result {
-                            Ok(c) => c,
-                            // Detect error type for better user feedback
-                            Err(e) => {
-                                log::error!("Download error for {}: {:?}", model_name, e);
-                                let _ = writer.flush().await;
-
-                                // Remove from active downloads
-                                {
-                                    let mut active = self.active_downloads.write().await;
-                                    active.remove(model_name);
-                                }
-
-                                // Update model status to Missing so retry can work
-                                {
-                                    let mut models = self.available_models.write().await;
-                                    if let Some(model) = models.get_mut(model_name) {
-                                        model.status = ModelStatus::Missing;
-                                    }
-                                }
-
-                                let error_msg = if e.is_timeout() {
-                                    "Connection timeout - Check your internet"
-                                } else if e.is_connect() {
-                                    "Connection failed - Check your internet"
-                                } else if e.is_body() {
-                                    "Stream interrupted - Network unstable"
-                                } else {
-                                    "Download error"
-                                };
-
-                                return Err(anyhow!("{}: {}", error_msg, e));
-                            }
-                        }
-                    }
-                };
-
-                if let Err(e) = writer.write_all(&chunk).await {
-                    // Remove from active downloads on error
-                    {
-                        let mut active = self.active_downloads.write().await;
-                        active.remove(model_name);
-                    }
-
-                    // Update model status to Missing so retry can work
-                    {
-                        let mut models = self.available_models.write().await;
-                        if let Some(model) = models.get_mut(model_name) {
-                            model.status = ModelStatus::Missing;
-                        }
-                    }
-
-                    return Err(anyhow!("Failed to write chunk to file: {}", e));
-                }
-
-                let chunk_len = chunk.len() as u64;
-                file_downloaded += chunk_len;
-                total_downloaded += chunk_len;
-                bytes_since_last_report += chunk_len;
-
-                // Calculate weighted overall progress based on total bytes downloaded
-                let overall_progress = if total_size_bytes > 0 {
-                    ((total_downloaded as f64 / total_size_bytes as f64) * 100.0).min(99.0) as u8
-                } else {
-                    // Fallback to per-file progress if total size unknown
-                    ((index as f64 + (file_downloaded as f64 / file_total_size.max(1) as f64)) / total_files as f64 * 100.0) as u8
-                };
-
-                // Report every 1% progress change OR every 500ms for smooth UI updates
-                let elapsed_since_report = last_report_time.elapsed();
-                let progress_changed = overall_progress > last_reported_progress;
-                let time_threshold = elapsed_since_report >= Duration::from_millis(500);
-                let is_complete = file_downloaded >= file_total_size;
-
-                let should_report = progress_changed || time_threshold || is_complete;
-
-                if should_report {
-                    // Calculate download speed
-                    let speed_mbps = if elapsed_since_report.as_secs_f64() >= 0.1 {
-                        (bytes_since_last_report as f64 / (1024.0 * 1024.0)) / elapsed_since_report.as_secs_f64()
-                    } else {
-                        // Fallback to overall average speed
-                        let total_elapsed = download_start_time.elapsed().as_secs_f64();
-                        if total_elapsed > 0.0 {
-                            ((total_downloaded - already_downloaded) as f64 / (1024.0 * 1024.0)) / total_elapsed
-                        } else {
-                            0.0
-                        }
-                    };
-
-                    last_reported_progress = overall_progress;
-                    last_report_time = Instant::now();
-                    bytes_since_last_report = 0;
-
-                    // Create detailed progress and report
-                    let progress = DownloadProgress::new(total_downloaded, total_size_bytes, speed_mbps);
-                    if let Some(ref callback) = progress_callback {
-                        callback(progress);
-                    }
-
-                    // Update model status
-                    {
-                        let mut models = self.available_models.write().await;
-                        if let Some(model) = models.get_mut(model_name) {
-                            model.status = ModelStatus::Downloading { progress: overall_progress };
-                        }
-                    }
-                }
+        let mut results = Vec::new();
+        for model in models {
+            if matches!(model.status, ModelStatus::Missing | ModelStatus::Downloading { .. }) {
+                continue;
             }
 
-            // Flush the buffered writer
-            if let Err(e) = writer.flush().await {
-                // Remove from active downloads on error
-                {
-                    let mut active = self.active_downloads.write().await;
-                    active.remove(model_name);
-                }
-
-                // Update model status to Missing so retry can work
-                {
-                    let mut models = self.available_models.write().await;
-                    if let Some(model) = models.get_mut(model_name) {
-                        model.status = ModelStatus::Missing;
-                    }
+            let base_url = Self::download_base_url(&model.name);
+            let mut checks = Vec::new();
+            for filename in Self::model_files(&model.quantization) {
+                if !model.path.join(filename).exists() {
+                    continue;
                 }
-
-                return Err(anyhow!("Failed to flush file {}: {}", filename, e));
-            }
-
-            log::info!(
-                "Completed download: {} ({:.2} MB, overall progress: {:.1}%)",
-                filename,
-                file_downloaded as f64 / 1_048_576.0,
-                (total_downloaded as f64 / total_size_bytes as f64) * 100.0
-            );
-        }
-
-        // Report 100% progress with final speed
-        let total_elapsed = download_start_time.elapsed().as_secs_f64();
-        let final_speed = if total_elapsed > 0.0 {
-            ((total_downloaded - already_downloaded) as f64 / (1024.0 * 1024.0)) / total_elapsed
-        } else {
-            0.0
-        };
-        let final_progress = DownloadProgress::new(total_size_bytes, total_size_bytes, final_speed);
-        if let Some(ref callback) = progress_callback {
-            callback(final_progress);
-        }
-
-        // Update model status to available
-        {
-            let mut models = self.available_models.write().await;
-            if let Some(model) = models.get_mut(model_name) {
-                model.status = ModelStatus::Available;
-                model.path = model_dir.clone();
+                let file_url = format!("{}/{}", base_url, filename);
+                let manifest_file = format!("{}/{}", model.name, filename);
+                checks.push(model_integrity::verify_installed(&self.models_dir, &manifest_file, Some(file_url.as_str())).await);
             }
+            results.push(ModelVerification::new("parakeet", &model.name, checks));
         }
 
-        // Remove from active downloads on completion
-        {
-            let mut active = self.active_downloads.write().await;
-            active.remove(model_name);
-        }
-
-        // Clear cancellation flag on successful completion
-        {
-            let mut cancel_flag = self.cancel_download_flag.write().await;
-            if cancel_flag.as_ref() == Some(&model_name.to_string()) {
-                *cancel_flag = None;
-            }
+        if let Err(e) = self.discover_models().await {
+            log::error!("Failed to rediscover Parakeet models after verification: {}", e);
         }
-
-        log::info!("Download completed for Parakeet model: {}", model_name);
-        Ok(())
+        results
     }
 
     /// Cancel an ongoing model download
+    /// Downloaded files are kept so the next download resumes where this one stopped
     pub async fn cancel_download(&self, model_name: &str) -> Result<()> {
         log::info!("Cancelling download for Parakeet model: {}", model_name);
 
-        // Set cancellation flag to interrupt the download loop
-        {
-            let mut cancel_flag = self.cancel_download_flag.write().await;
-            *cancel_flag = Some(model_name.to_string());
+        if !model_manager::registry().cancel(EngineKind::Parakeet, model_name) {
+            log::warn!("No download in progress for Parakeet model: {}", model_name);
         }
+        Ok(())
+    }
+}
 
-        // Remove from active downloads
-        {
-            let mut active = self.active_downloads.write().await;
-            active.remove(model_name);
-        }
+#[async_trait]
+impl ModelEngine for ParakeetEngine {
+    fn kind(&self) -> EngineKind {
+        EngineKind::Parakeet
+    }
 
-        // Update model status to Missing (so it can be retried)
-        {
-            let mut models = self.available_models.write().await;
-            if let Some(model) = models.get_mut(model_name) {
-                model.status = ModelStatus::Missing;
-            }
-        }
+    fn models_dir(&self) -> PathBuf {
+        self.models_dir.clone()
+    }
+
+    /// One entry per model, with a file per ONNX/vocab file in the model's directory
+    fn catalog(&self) -> Vec<CatalogEntry> {
+        MODEL_CONFIGS.iter()
+            .map(|(name, _, quantization, _, description)| {
+                let base_url = Self::download_base_url(name);
+                let sizes = Self::file_sizes(name, quantization);
+                Self::model_files(quantization).into_iter().fold(
+                    CatalogEntry::new(EngineKind::Parakeet, name, name, description),
+                    |entry, filename| {
+                        entry.with_file(
+                            &format!("{}/{}", name, filename),
+                            &format!("{}/{}", base_url, filename),
+                            sizes.get(filename).copied().unwrap_or(0),
+                        )
+                    },
+                )
+            })
+            .collect()
+    }
 
-        // Clean up partially downloaded files
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await; // Brief delay to let download loop exit
+    async fn validate(&self, entry: &CatalogEntry) -> Result<()> {
+        self.validate_model_directory(&self.models_dir.join(&entry.name)).await
+    }
 
-        let model_path = self.models_dir.join(model_name);
-        if model_path.exists() {
-            if let Err(e) = fs::remove_dir_all(&model_path).await {
-                log::warn!("Failed to clean up cancelled download directory: {}", e);
-            } else {
-                log::info!("Cleaned up cancelled download directory: {}", model_path.display());
-            }
+    async fn refresh(&self) {
+        if let Err(e) = self.discover_models().await {
+            log::error!("Failed to rediscover Parakeet models: {}", e);
         }
-
-        Ok(())
     }
 }
@@ -0,0 +1,483 @@
+use super::embedding::{
+    Embedder, EmbeddingBackend, SentenceEmbeddingModel, DEFAULT_OLLAMA_EMBEDDING_MODEL,
+};
+use super::index::{retrieve, sync_index, RetrievedPassage};
+use super::qa::{build_qa_prompt, collect_citations, MeetingAnswer, QA_SYSTEM_PROMPT};
+use crate::database::repositories::{embedding::EmbeddingsRepository, setting::SettingsRepository};
+use crate::model_integrity::{self, ModelVerification};
+use crate::model_manager::{self, CatalogEntry, DownloadProgress, EngineKind, ModelEngine};
+use crate::state::AppState;
+use crate::summary::llm_client::generate_summary;
+use crate::summary::SummaryService;
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{command, AppHandle, Emitter, Manager, Runtime};
+
+/// sentence-transformers/all-MiniLM-L6-v2 (384 dimensions), ~90 MB
+pub const LOCAL_MODEL_NAME: &str = "all-MiniLM-L6-v2";
+/// File name, download URL and approximate size of each model file
+const MODEL_FILES: [(&str, &str, u64); 2] = [
+    (
+        "vocab.txt",
+        "https://huggingface.co/sentence-transformers/all-MiniLM-L6-v2/resolve/main/vocab.txt",
+        232 * 1024,
+    ),
+    (
+        "model.onnx",
+        "https://huggingface.co/sentence-transformers/all-MiniLM-L6-v2/resolve/main/onnx/model.onnx",
+        90 * 1024 * 1024,
+    ),
+];
+
+const DEFAULT_TOP_K: usize = 8;
+const MAX_TOP_K: usize = 50;
+
+// Global embedding models directory (set during app initialization)
+static MODELS_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+// Loaded on first use and kept for the rest of the session
+static LOCAL_MODEL: Lazy<Mutex<Option<Arc<Mutex<SentenceEmbeddingModel>>>>> =
+    Lazy::new(|| Mutex::new(None));
+
+// Backend of the last search, so background indexing embeds for the same model
+static INDEX_CONFIG: Lazy<Mutex<Option<EmbeddingConfig>>> = Lazy::new(|| Mutex::new(None));
+
+// Held while the index is being brought up to date
+static INDEX_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+// Set while a background run is waiting to start
+static INDEX_QUEUED: AtomicBool = AtomicBool::new(false);
+
+/// Initialize the embedding models directory (`<app_data>/models/embeddings`)
+pub fn set_models_directory<R: Runtime>(app: &AppHandle<R>) {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data dir");
+
+    let models_dir = app_data_dir.join("models").join("embeddings");
+
+    if !models_dir.exists() {
+        if let Err(e) = std::fs::create_dir_all(&models_dir) {
+            log::error!("Failed to create embedding models directory: {}", e);
+            return;
+        }
+    }
+
+    log::info!(
+        "Embedding models directory set to: {}",
+        models_dir.display()
+    );
+
+    model_manager::registry().register(Arc::new(SemanticModels {
+        models_dir: models_dir.clone(),
+    }));
+
+    let mut guard = MODELS_DIR.lock().unwrap();
+    *guard = Some(models_dir);
+}
+
+/// Directory of the local sentence embedding model (may not exist yet)
+pub fn get_model_dir() -> Option<PathBuf> {
+    MODELS_DIR
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|dir| dir.join(LOCAL_MODEL_NAME))
+}
+
+/// Path of a model file relative to the embedding models directory
+fn model_file_path(file_name: &str) -> String {
+    format!("{}/{}", LOCAL_MODEL_NAME, file_name)
+}
+
+/// Re-check the SHA-256 of the local embedding model's files, if downloaded
+pub async fn verify_model() -> Option<ModelVerification> {
+    let models_dir = MODELS_DIR.lock().unwrap().clone()?;
+    let mut checks = Vec::new();
+    for (file_name, url, _) in MODEL_FILES {
+        let path = model_file_path(file_name);
+        if models_dir.join(&path).exists() {
+            checks.push(model_integrity::verify_installed(&models_dir, &path, Some(url)).await);
+        }
+    }
+    (!checks.is_empty()).then(|| ModelVerification::new("semantic", LOCAL_MODEL_NAME, checks))
+}
+
+fn is_model_downloaded(model_dir: &Path) -> bool {
+    MODEL_FILES
+        .iter()
+        .all(|(file_name, _, _)| model_dir.join(file_name).exists())
+}
+
+/// Which backend embeds text for semantic search; defaults to the local model
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingConfig {
+    #[serde(default)]
+    pub backend: EmbeddingBackend,
+    /// Ollama embedding model (default "nomic-embed-text")
+    pub ollama_model: Option<String>,
+    /// Ollama server; defaults to the endpoint saved in the model settings
+    pub ollama_endpoint: Option<String>,
+}
+
+async fn load_local_model() -> Result<Arc<Mutex<SentenceEmbeddingModel>>, String> {
+    let cached = LOCAL_MODEL.lock().unwrap().clone();
+    if let Some(model) = cached {
+        return Ok(model);
+    }
+
+    let model_dir = get_model_dir().ok_or("Embedding models directory not initialized")?;
+    if !is_model_downloaded(&model_dir) {
+        return Err("Semantic search model not downloaded".to_string());
+    }
+
+    let model = tokio::task::spawn_blocking(move || SentenceEmbeddingModel::load(&model_dir))
+        .await
+        .map_err(|e| format!("Failed to load embedding model: {}", e))?
+        .map_err(|e| format!("Failed to load embedding model: {}", e))?;
+
+    let model = Arc::new(Mutex::new(model));
+    *LOCAL_MODEL.lock().unwrap() = Some(model.clone());
+    Ok(model)
+}
+
+async fn build_embedder(
+    pool: &SqlitePool,
+    config: Option<EmbeddingConfig>,
+) -> Result<Embedder, String> {
+    let config = config.unwrap_or_default();
+    match config.backend {
+        EmbeddingBackend::Local => Ok(Embedder::Local {
+            model: load_local_model().await?,
+            name: LOCAL_MODEL_NAME.to_string(),
+        }),
+        EmbeddingBackend::Ollama => {
+            let endpoint = match config.ollama_endpoint {
+                Some(endpoint) => Some(endpoint),
+                None => SettingsRepository::get_model_config(pool)
+                    .await
+                    .ok()
+                    .flatten()
+                    .and_then(|settings| settings.ollama_endpoint),
+            };
+            let model = config
+                .ollama_model
+                .filter(|m| !m.trim().is_empty())
+                .unwrap_or_else(|| DEFAULT_OLLAMA_EMBEDDING_MODEL.to_string());
+            Ok(Embedder::ollama(endpoint.as_deref(), &model))
+        }
+    }
+}
+
+/// Bring the index up to date, emitting `semantic-index-progress`
+async fn sync_with_progress<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    embedder: &Embedder,
+) -> Result<usize, String> {
+    let indexed = sync_index(pool, embedder, None, |indexed| {
+        let _ = app.emit(
+            "semantic-index-progress",
+            serde_json::json!({ "indexed": indexed }),
+        );
+    })
+    .await?;
+    if indexed > 0 {
+        log::info!(
+            "Indexed {} new passages with {}",
+            indexed,
+            embedder.model_key()
+        );
+    }
+    Ok(indexed)
+}
+
+#[derive(Debug, Serialize)]
+pub struct SemanticModelStatus {
+    pub downloaded: bool,
+    pub path: Option<String>,
+    pub size_mb: Option<f64>,
+}
+
+#[command]
+pub async fn semantic_get_model_status() -> Result<SemanticModelStatus, String> {
+    let model_dir = get_model_dir();
+    let downloaded = model_dir.as_deref().is_some_and(is_model_downloaded);
+    let size = model_dir.as_ref().filter(|_| downloaded).map(|dir| {
+        MODEL_FILES
+            .iter()
+            .filter_map(|(file_name, _, _)| std::fs::metadata(dir.join(file_name)).ok())
+            .map(|m| m.len())
+            .sum::<u64>()
+    });
+
+    Ok(SemanticModelStatus {
+        downloaded,
+        path: model_dir.map(|p| p.display().to_string()),
+        size_mb: size.map(|s| s as f64 / (1024.0 * 1024.0)),
+    })
+}
+
+/// Download (or resume) the local embedding model through the shared model
+/// manager, emitting `semantic-model-download-progress`
+#[command]
+pub async fn semantic_download_model<R: Runtime>(app_handle: AppHandle<R>) -> Result<(), String> {
+    let on_progress = |progress: &DownloadProgress| {
+        let _ = app_handle.emit(
+            "semantic-model-download-progress",
+            serde_json::json!({
+                "progress": progress.percent,
+                "downloaded_bytes": progress.downloaded_bytes,
+                "total_bytes": progress.total_bytes,
+            }),
+        );
+    };
+    model_manager::registry()
+        .download(EngineKind::Semantic, LOCAL_MODEL_NAME, Some(&on_progress))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let _ = app_handle.emit("semantic-model-download-complete", serde_json::json!({}));
+    index_in_background(&app_handle);
+    Ok(())
+}
+
+/// Cancel the model download; partial files are kept for resuming
+/// Returns false if the model wasn't downloading
+#[command]
+pub async fn semantic_cancel_download() -> Result<bool, String> {
+    Ok(model_manager::registry().cancel(EngineKind::Semantic, LOCAL_MODEL_NAME))
+}
+
+/// The local embedding model as seen by the shared model manager
+struct SemanticModels {
+    models_dir: PathBuf,
+}
+
+#[async_trait]
+impl ModelEngine for SemanticModels {
+    fn kind(&self) -> EngineKind {
+        EngineKind::Semantic
+    }
+
+    fn models_dir(&self) -> PathBuf {
+        self.models_dir.clone()
+    }
+
+    fn catalog(&self) -> Vec<CatalogEntry> {
+        let entry = CatalogEntry::new(
+            EngineKind::Semantic,
+            LOCAL_MODEL_NAME,
+            "MiniLM L6 v2",
+            "Sentence embedding model for semantic search and questions about meetings",
+        );
+        vec![MODEL_FILES
+            .iter()
+            .fold(entry, |entry, (file_name, url, size_bytes)| {
+                entry.with_file(&model_file_path(file_name), url, *size_bytes)
+            })]
+    }
+
+    /// The model is usable if ONNX Runtime and the tokenizer can load it
+    async fn validate(&self, _entry: &CatalogEntry) -> anyhow::Result<()> {
+        let model_dir = self.models_dir.join(LOCAL_MODEL_NAME);
+        tokio::task::spawn_blocking(move || SentenceEmbeddingModel::load(&model_dir).map(drop))
+            .await??;
+        Ok(())
+    }
+
+    /// Drop the loaded model so a re-downloaded or deleted one isn't used
+    async fn refresh(&self) {
+        *LOCAL_MODEL.lock().unwrap() = None;
+    }
+}
+
+/// Index new transcripts and summaries without blocking the caller; called
+/// whenever meeting content is saved. Searches use whatever is indexed so far
+pub fn index_in_background<R: Runtime>(app: &AppHandle<R>) {
+    // A run that hasn't started yet will pick up this content too
+    if INDEX_QUEUED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let _running = INDEX_LOCK.lock().await;
+        INDEX_QUEUED.store(false, Ordering::SeqCst);
+
+        let pool = app.state::<AppState>().db_manager.pool().clone();
+        let config = INDEX_CONFIG.lock().unwrap().clone();
+        let embedder = match build_embedder(&pool, config).await {
+            Ok(embedder) => embedder,
+            Err(e) => {
+                log::debug!("Skipping background semantic indexing: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = sync_with_progress(&app, &pool, &embedder).await {
+            log::warn!("Background semantic indexing failed: {}", e);
+        }
+    });
+}
+
+/// Remember the backend a search used and build its embedder
+async fn search_embedder(
+    pool: &SqlitePool,
+    config: Option<EmbeddingConfig>,
+) -> Result<Embedder, String> {
+    if let Some(config) = &config {
+        *INDEX_CONFIG.lock().unwrap() = Some(config.clone());
+    }
+    build_embedder(pool, config).await
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticIndexStatus {
+    pub model: String,
+    /// Passages embedded by this call
+    pub indexed: usize,
+    /// Passages stored for this model in total
+    pub total: i64,
+}
+
+/// Embed everything not yet indexed; pass `rebuild` to re-embed from scratch
+#[command]
+pub async fn api_semantic_index<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    config: Option<EmbeddingConfig>,
+    rebuild: Option<bool>,
+) -> Result<SemanticIndexStatus, String> {
+    let pool = state.db_manager.pool();
+    let embedder = search_embedder(pool, config).await?;
+    let model = embedder.model_key();
+    let _running = INDEX_LOCK.lock().await;
+
+    if rebuild.unwrap_or(false) {
+        let removed = EmbeddingsRepository::clear_model(pool, &model)
+            .await
+            .map_err(|e| format!("Failed to clear semantic index: {}", e))?;
+        log::info!("Cleared {} embeddings for {}", removed, model);
+    }
+
+    let indexed = sync_with_progress(&app, pool, &embedder).await?;
+    let total = EmbeddingsRepository::count(pool, &model)
+        .await
+        .map_err(|e| format!("Failed to count embeddings: {}", e))?;
+
+    Ok(SemanticIndexStatus {
+        model,
+        indexed,
+        total,
+    })
+}
+
+/// Transcript segments and summary passages most similar in meaning to `query`,
+/// among those indexed so far (new content is indexed in the background)
+#[command]
+pub async fn api_semantic_search<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    query: String,
+    top_k: Option<usize>,
+    meeting_id: Option<String>,
+    config: Option<EmbeddingConfig>,
+) -> Result<Vec<RetrievedPassage>, String> {
+    log::info!(
+        "api_semantic_search called with query: '{}', meeting_id: {:?}",
+        query,
+        meeting_id
+    );
+    if query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let pool = state.db_manager.pool();
+    let embedder = search_embedder(pool, config).await?;
+    let top_k = top_k.unwrap_or(DEFAULT_TOP_K).clamp(1, MAX_TOP_K);
+
+    index_in_background(&app);
+    retrieve(pool, &embedder, &query, top_k, meeting_id.as_deref()).await
+}
+
+/// Answer a question from the most relevant passages with the configured LLM.
+///
+/// Uses the summary provider and model from settings unless overridden. The
+/// answer cites passages as `[n]`; each citation carries its `audioStartTime`.
+#[command]
+pub async fn api_ask_meetings<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    question: String,
+    top_k: Option<usize>,
+    meeting_id: Option<String>,
+    config: Option<EmbeddingConfig>,
+    model_provider: Option<String>,
+    model_name: Option<String>,
+) -> Result<MeetingAnswer, String> {
+    log::info!(
+        "api_ask_meetings called with question: '{}', meeting_id: {:?}",
+        question,
+        meeting_id
+    );
+    if question.trim().is_empty() {
+        return Err("Question is empty".to_string());
+    }
+
+    let pool = state.db_manager.pool();
+    let embedder = search_embedder(pool, config).await?;
+    let top_k = top_k.unwrap_or(DEFAULT_TOP_K).clamp(1, MAX_TOP_K);
+
+    index_in_background(&app);
+    let passages = retrieve(pool, &embedder, &question, top_k, meeting_id.as_deref()).await?;
+    if passages.is_empty() {
+        return Ok(MeetingAnswer {
+            answer: "No meeting content has been indexed yet.".to_string(),
+            citations: Vec::new(),
+        });
+    }
+
+    let settings = SettingsRepository::get_model_config(pool)
+        .await
+        .map_err(|e| format!("Failed to load model settings: {}", e))?;
+    let (provider_name, model_name) = match (model_provider, model_name, settings) {
+        (Some(provider), Some(model), _) => (provider, model),
+        (provider, model, Some(settings)) => (
+            provider.unwrap_or(settings.provider),
+            model.unwrap_or(settings.model),
+        ),
+        _ => return Err("No summary model configured".to_string()),
+    };
+    let connection = SummaryService::resolve_provider(pool, &provider_name).await?;
+
+    let answer = generate_summary(
+        &reqwest::Client::new(),
+        &connection.provider,
+        &model_name,
+        &connection.api_key,
+        QA_SYSTEM_PROMPT,
+        &build_qa_prompt(&question, &passages),
+        connection.ollama_endpoint.as_deref(),
+        connection.custom_openai_endpoint.as_deref(),
+        connection.max_tokens,
+        connection.temperature,
+        connection.top_p,
+        app.path().app_data_dir().ok().as_ref(),
+        None,
+    )
+    .await?;
+
+    let citations = collect_citations(&answer, &passages);
+    Ok(MeetingAnswer {
+        answer: answer.trim().to_string(),
+        citations,
+    })
+}
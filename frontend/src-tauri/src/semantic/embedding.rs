@@ -0,0 +1,212 @@
+// semantic/embedding.rs
+//
+// Sentence embeddings, computed locally with an ONNX MiniLM model through `ort`
+// or by a local Ollama server's `/api/embed` endpoint.
+
+use super::tokenizer::WordPieceTokenizer;
+use ndarray::{Array2, Axis};
+use ort::execution_providers::CPUExecutionProvider;
+use ort::session::builder::GraphOptimizationLevel;
+use ort::session::Session;
+use ort::value::TensorRef;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// all-MiniLM-L6-v2 was trained on sequences of up to 256 tokens
+const MAX_SEQ_LEN: usize = 256;
+
+pub const DEFAULT_OLLAMA_EMBEDDING_MODEL: &str = "nomic-embed-text";
+const DEFAULT_OLLAMA_ENDPOINT: &str = "http://localhost:11434";
+
+#[derive(thiserror::Error, Debug)]
+pub enum SentenceEmbeddingError {
+    #[error("ORT error: {0}")]
+    Ort(#[from] ort::Error),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("ndarray shape error")]
+    Shape(#[from] ndarray::ShapeError),
+    #[error("Model output not found: {0}")]
+    OutputNotFound(String),
+}
+
+/// ONNX sentence-transformer (inputs: `input_ids`, `attention_mask`,
+/// `token_type_ids`; output: token embeddings `[1, tokens, dim]`), mean pooled
+pub struct SentenceEmbeddingModel {
+    session: Session,
+    tokenizer: WordPieceTokenizer,
+    output_name: String,
+}
+
+impl SentenceEmbeddingModel {
+    /// Load `model.onnx` and `vocab.txt` from `model_dir`
+    pub fn load(model_dir: &Path) -> Result<Self, SentenceEmbeddingError> {
+        let tokenizer = WordPieceTokenizer::load(&model_dir.join("vocab.txt"))?;
+        let session = Session::builder()?
+            .with_optimization_level(GraphOptimizationLevel::Level3)?
+            .with_execution_providers(vec![CPUExecutionProvider::default().build()])?
+            .with_intra_threads(2)?
+            .commit_from_file(model_dir.join("model.onnx"))?;
+
+        let output_name = session
+            .outputs
+            .iter()
+            .find(|output| output.name == "last_hidden_state")
+            .or_else(|| session.outputs.first())
+            .map(|output| output.name.clone())
+            .unwrap_or_else(|| "last_hidden_state".to_string());
+
+        log::info!(
+            "Loaded sentence embedding model from {} (output: {})",
+            model_dir.display(),
+            output_name
+        );
+
+        Ok(Self {
+            session,
+            tokenizer,
+            output_name,
+        })
+    }
+
+    /// L2-normalised embedding of `text`
+    pub fn embed(&mut self, text: &str) -> Result<Vec<f32>, SentenceEmbeddingError> {
+        let ids = self.tokenizer.encode(text, MAX_SEQ_LEN);
+        let len = ids.len();
+        let input_ids = Array2::from_shape_vec((1, len), ids)?;
+        let attention_mask = Array2::<i64>::ones((1, len));
+        let token_type_ids = Array2::<i64>::zeros((1, len));
+
+        let outputs = self.session.run(ort::inputs![
+            "input_ids" => TensorRef::from_array_view(input_ids.view())?,
+            "attention_mask" => TensorRef::from_array_view(attention_mask.view())?,
+            "token_type_ids" => TensorRef::from_array_view(token_type_ids.view())?,
+        ])?;
+        let hidden = outputs
+            .get(self.output_name.as_str())
+            .ok_or_else(|| SentenceEmbeddingError::OutputNotFound(self.output_name.clone()))?
+            .try_extract_array::<f32>()?;
+
+        // Token embeddings are mean pooled; some exports already return `[1, dim]`
+        let mut embedding: Vec<f32> = if hidden.ndim() == 3 {
+            hidden
+                .mean_axis(Axis(1))
+                .map(|pooled| pooled.iter().copied().collect())
+                .unwrap_or_default()
+        } else {
+            hidden.iter().copied().collect()
+        };
+        normalize(&mut embedding);
+        Ok(embedding)
+    }
+}
+
+/// Which embedding backend to use
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmbeddingBackend {
+    #[default]
+    Local,
+    Ollama,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaEmbedResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+/// A configured embedding backend
+pub enum Embedder {
+    Local {
+        model: Arc<Mutex<SentenceEmbeddingModel>>,
+        name: String,
+    },
+    Ollama {
+        client: reqwest::Client,
+        endpoint: String,
+        model: String,
+    },
+}
+
+impl Embedder {
+    pub fn ollama(endpoint: Option<&str>, model: &str) -> Self {
+        Embedder::Ollama {
+            client: reqwest::Client::new(),
+            endpoint: endpoint
+                .unwrap_or(DEFAULT_OLLAMA_ENDPOINT)
+                .trim_end_matches('/')
+                .to_string(),
+            model: model.to_string(),
+        }
+    }
+
+    /// Key stored with each vector so different models never get compared
+    pub fn model_key(&self) -> String {
+        match self {
+            Embedder::Local { name, .. } => format!("local:{}", name),
+            Embedder::Ollama { model, .. } => format!("ollama:{}", model),
+        }
+    }
+
+    /// L2-normalised embeddings, one per input text
+    pub async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, String> {
+        match self {
+            Embedder::Local { model, .. } => {
+                let model = model.clone();
+                tokio::task::spawn_blocking(move || {
+                    let mut model = model
+                        .lock()
+                        .map_err(|_| "Embedding model lock poisoned".to_string())?;
+                    texts
+                        .iter()
+                        .map(|text| model.embed(text).map_err(|e| e.to_string()))
+                        .collect()
+                })
+                .await
+                .map_err(|e| format!("Embedding task failed: {}", e))?
+            }
+            Embedder::Ollama {
+                client,
+                endpoint,
+                model,
+            } => {
+                let response = client
+                    .post(format!("{}/api/embed", endpoint))
+                    .json(&serde_json::json!({ "model": model, "input": texts }))
+                    .send()
+                    .await
+                    .map_err(|e| format!("Failed to reach Ollama at {}: {}", endpoint, e))?;
+
+                if !response.status().is_success() {
+                    let status = response.status();
+                    let body = response.text().await.unwrap_or_default();
+                    return Err(format!("Ollama embeddings failed ({}): {}", status, body));
+                }
+
+                let mut embeddings = response
+                    .json::<OllamaEmbedResponse>()
+                    .await
+                    .map_err(|e| format!("Invalid Ollama embeddings response: {}", e))?
+                    .embeddings;
+                if embeddings.len() != texts.len() {
+                    return Err(format!(
+                        "Ollama returned {} embeddings for {} inputs",
+                        embeddings.len(),
+                        texts.len()
+                    ));
+                }
+                embeddings.iter_mut().for_each(|e| normalize(e));
+                Ok(embeddings)
+            }
+        }
+    }
+}
+
+/// Scale `vector` to unit length (left as-is if it is all zeros)
+pub fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > f32::EPSILON {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+}
@@ -0,0 +1,220 @@
+// semantic/index.rs
+//
+// Keeps the `embeddings` table in sync with transcripts and summaries, and
+// ranks stored passages against a query by cosine similarity.
+
+use super::embedding::Embedder;
+use crate::database::repositories::embedding::EmbeddingsRepository;
+use crate::export::commands::summary_markdown_from_result;
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+/// Transcript segments embedded per batch
+const TRANSCRIPT_BATCH_SIZE: i64 = 64;
+
+/// Summaries are split into passages of roughly this many characters
+const SUMMARY_PASSAGE_CHARS: usize = 800;
+
+/// A transcript segment or summary passage matching a query
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetrievedPassage {
+    pub meeting_id: String,
+    pub meeting_title: String,
+    /// "transcript" or "summary"
+    pub source: String,
+    /// Transcript segment ID (transcript passages only)
+    pub segment_id: Option<String>,
+    pub text: String,
+    /// Segment timestamp, or the meeting's creation time for summaries
+    pub timestamp: String,
+    /// Seconds from the start of the recording (transcript passages only)
+    pub audio_start_time: Option<f64>,
+    /// Cosine similarity to the query
+    pub score: f32,
+}
+
+/// Embed every transcript segment and summary that isn't indexed for this
+/// embedder's model yet. Returns the number of passages added.
+pub async fn sync_index(
+    pool: &SqlitePool,
+    embedder: &Embedder,
+    meeting_id: Option<&str>,
+    on_progress: impl Fn(usize),
+) -> Result<usize, String> {
+    let model = embedder.model_key();
+    let mut indexed = 0;
+
+    loop {
+        let pending = EmbeddingsRepository::pending_transcripts(
+            pool,
+            &model,
+            meeting_id,
+            TRANSCRIPT_BATCH_SIZE,
+        )
+        .await
+        .map_err(|e| format!("Failed to load transcripts to index: {}", e))?;
+        if pending.is_empty() {
+            break;
+        }
+
+        let texts = pending.iter().map(|t| t.transcript.clone()).collect();
+        let vectors = embedder.embed(texts).await?;
+
+        for (segment, vector) in pending.into_iter().zip(vectors) {
+            EmbeddingsRepository::save_embeddings(
+                pool,
+                &model,
+                "transcript",
+                &segment.id,
+                &segment.meeting_id,
+                &[(segment.transcript, encode_vector(&vector))],
+            )
+            .await
+            .map_err(|e| format!("Failed to save embedding: {}", e))?;
+            indexed += 1;
+        }
+        on_progress(indexed);
+    }
+
+    let summaries = EmbeddingsRepository::pending_summaries(pool, &model, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load summaries to index: {}", e))?;
+    for summary in summaries {
+        let passages = summary_markdown_from_result(&summary.result)
+            .map(|markdown| split_passages(&markdown, SUMMARY_PASSAGE_CHARS))
+            .unwrap_or_default();
+        if passages.is_empty() {
+            continue;
+        }
+
+        let vectors = embedder.embed(passages.clone()).await?;
+        let rows: Vec<(String, Vec<u8>)> = passages
+            .into_iter()
+            .zip(vectors.iter().map(|v| encode_vector(v)))
+            .collect();
+        EmbeddingsRepository::save_embeddings(
+            pool,
+            &model,
+            "summary",
+            &summary.meeting_id,
+            &summary.meeting_id,
+            &rows,
+        )
+        .await
+        .map_err(|e| format!("Failed to save embedding: {}", e))?;
+        indexed += rows.len();
+        on_progress(indexed);
+    }
+
+    Ok(indexed)
+}
+
+/// The `top_k` stored passages most similar to `query`
+pub async fn retrieve(
+    pool: &SqlitePool,
+    embedder: &Embedder,
+    query: &str,
+    top_k: usize,
+    meeting_id: Option<&str>,
+) -> Result<Vec<RetrievedPassage>, String> {
+    let query_vector = embedder
+        .embed(vec![query.to_string()])
+        .await?
+        .pop()
+        .ok_or("No embedding returned for the query")?;
+
+    let rows = EmbeddingsRepository::load_embeddings(pool, &embedder.model_key(), meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load embeddings: {}", e))?;
+
+    let mut passages: Vec<RetrievedPassage> = rows
+        .into_iter()
+        .filter_map(|row| {
+            let vector = decode_vector(&row.vector);
+            if vector.len() != query_vector.len() {
+                return None;
+            }
+            let is_transcript = row.source == "transcript";
+            Some(RetrievedPassage {
+                score: dot(&vector, &query_vector),
+                meeting_id: row.meeting_id,
+                meeting_title: row.title,
+                segment_id: is_transcript.then_some(row.source_id),
+                source: row.source,
+                text: row.content,
+                timestamp: row.segment_timestamp.unwrap_or(row.created_at),
+                audio_start_time: row.audio_start_time,
+            })
+        })
+        .collect();
+
+    passages.sort_by(|a, b| b.score.total_cmp(&a.score));
+    passages.truncate(top_k);
+    Ok(passages)
+}
+
+pub fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+pub fn decode_vector(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+/// Cosine similarity of two L2-normalised vectors
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Split Markdown into passages at blank lines, merging short blocks up to
+/// `max_chars` so each passage carries enough context to embed well
+pub fn split_passages(markdown: &str, max_chars: usize) -> Vec<String> {
+    let mut passages = Vec::new();
+    let mut current = String::new();
+
+    for block in markdown
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|b| !b.is_empty())
+    {
+        if !current.is_empty() && current.len() + block.len() + 2 > max_chars {
+            passages.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push_str("\n\n");
+        }
+        current.push_str(block);
+    }
+    if !current.is_empty() {
+        passages.push(current);
+    }
+    passages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_round_trip() {
+        let vector = vec![0.25, -1.5, 3.0e-7, 0.0];
+        assert_eq!(decode_vector(&encode_vector(&vector)), vector);
+    }
+
+    #[test]
+    fn test_split_passages_merges_short_blocks() {
+        let markdown = "# Decisions\n\nDrop the v1 API.\n\n\n\n## Action items\n\n- Ship v2";
+        assert_eq!(
+            split_passages(markdown, 40),
+            vec![
+                "# Decisions\n\nDrop the v1 API.",
+                "## Action items\n\n- Ship v2"
+            ]
+        );
+        assert_eq!(split_passages(" \n\n ", 40), Vec::<String>::new());
+    }
+}
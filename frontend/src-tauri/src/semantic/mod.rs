@@ -0,0 +1,24 @@
+//! Semantic search and question answering over past meetings.
+//!
+//! Transcript segments and summary passages are embedded (locally with an ONNX
+//! MiniLM model, or by Ollama) and stored in the `embeddings` table. Queries are
+//! ranked by cosine similarity, and questions are answered by the configured
+//! summary LLM from the top passages, citing each one back to its audio time.
+//!
+//! # Module Structure
+//!
+//! - `tokenizer`: WordPiece tokenizer for the local model
+//! - `embedding`: Local ONNX and Ollama embedding backends
+//! - `index`: Incremental indexing and similarity retrieval
+//! - `qa`: Question-answering prompt and citation parsing
+//! - `commands`: Tauri commands for the model, search and Q&A
+
+pub mod commands;
+pub mod embedding;
+pub mod index;
+pub mod qa;
+pub mod tokenizer;
+
+pub use embedding::{Embedder, EmbeddingBackend, SentenceEmbeddingModel};
+pub use index::RetrievedPassage;
+pub use qa::{Citation, MeetingAnswer};
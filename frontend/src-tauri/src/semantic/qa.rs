@@ -0,0 +1,139 @@
+// semantic/qa.rs
+//
+// Prompt construction and citation parsing for answering questions from
+// retrieved meeting passages.
+
+use super::index::RetrievedPassage;
+use serde::Serialize;
+
+pub const QA_SYSTEM_PROMPT: &str = "You answer questions about the user's past meetings using only the numbered excerpts provided. Cite the excerpts you rely on inline as [n]. If the excerpts do not contain the answer, say so plainly instead of guessing.";
+
+/// A passage the answer cites, numbered as in the prompt
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Citation {
+    /// The `[n]` marker used in the answer
+    pub index: usize,
+    #[serde(flatten)]
+    pub passage: RetrievedPassage,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MeetingAnswer {
+    pub answer: String,
+    pub citations: Vec<Citation>,
+}
+
+/// User prompt listing the passages as numbered excerpts followed by the question
+pub fn build_qa_prompt(question: &str, passages: &[RetrievedPassage]) -> String {
+    let mut prompt = String::from("<excerpts>\n");
+    for (i, passage) in passages.iter().enumerate() {
+        let location = match passage.audio_start_time {
+            Some(seconds) => format!(" at {}", format_offset(seconds)),
+            None if passage.source == "summary" => " (summary)".to_string(),
+            None => String::new(),
+        };
+        prompt.push_str(&format!(
+            "[{}] {} ({}){}:\n{}\n\n",
+            i + 1,
+            passage.meeting_title,
+            passage.timestamp.get(..10).unwrap_or(&passage.timestamp),
+            location,
+            passage.text.trim()
+        ));
+    }
+    prompt.push_str("</excerpts>\n\nQuestion: ");
+    prompt.push_str(question.trim());
+    prompt
+}
+
+/// Pair the answer's `[n]` markers with their passages, in order of first use.
+///
+/// An answer that cites nothing gets no citations, rather than every passage
+/// that was retrieved for it.
+pub fn collect_citations(answer: &str, passages: &[RetrievedPassage]) -> Vec<Citation> {
+    let mut cited: Vec<usize> = Vec::new();
+    let mut rest = answer;
+    while let Some(open) = rest.find('[') {
+        rest = &rest[open + 1..];
+        let Some(close) = rest.find(']') else {
+            break;
+        };
+        // Accept "[2]" as well as "[1, 3]"
+        for part in rest[..close].split(',') {
+            if let Ok(n) = part.trim().parse::<usize>() {
+                if (1..=passages.len()).contains(&n) && !cited.contains(&n) {
+                    cited.push(n);
+                }
+            }
+        }
+        rest = &rest[close + 1..];
+    }
+
+    cited
+        .into_iter()
+        .map(|index| Citation {
+            index,
+            passage: passages[index - 1].clone(),
+        })
+        .collect()
+}
+
+/// "m:ss" or "h:mm:ss" from seconds
+fn format_offset(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    let (h, m, s) = (total / 3600, (total % 3600) / 60, total % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn passage(title: &str, text: &str, audio_start_time: Option<f64>) -> RetrievedPassage {
+        RetrievedPassage {
+            meeting_id: format!("meeting-{}", title),
+            meeting_title: title.to_string(),
+            source: "transcript".to_string(),
+            segment_id: Some("seg".to_string()),
+            text: text.to_string(),
+            timestamp: "2026-03-02T10:00:00Z".to_string(),
+            audio_start_time,
+            score: 0.8,
+        }
+    }
+
+    #[test]
+    fn test_build_qa_prompt() {
+        let prompt = build_qa_prompt(
+            " When did we drop v1? ",
+            &[passage("API sync", "Let's drop the v1 API.", Some(3725.0))],
+        );
+        assert_eq!(
+            prompt,
+            "<excerpts>\n[1] API sync (2026-03-02) at 1:02:05:\nLet's drop the v1 API.\n\n</excerpts>\n\nQuestion: When did we drop v1?"
+        );
+    }
+
+    #[test]
+    fn test_collect_citations() {
+        let passages = vec![
+            passage("a", "one", Some(1.0)),
+            passage("b", "two", Some(2.0)),
+            passage("c", "three", None),
+        ];
+
+        let citations =
+            collect_citations("Decided in March [3], see also [1, 3] and [9].", &passages);
+        let indices: Vec<usize> = citations.iter().map(|c| c.index).collect();
+        assert_eq!(indices, vec![3, 1]);
+        assert_eq!(citations[0].passage.text, "three");
+
+        assert!(collect_citations("No idea.", &passages).is_empty());
+    }
+}
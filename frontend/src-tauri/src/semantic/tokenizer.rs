@@ -0,0 +1,167 @@
+// semantic/tokenizer.rs
+//
+// BERT-style WordPiece tokenizer (uncased) for the sentence embedding model.
+// Reads the model's `vocab.txt`, one token per line, the line number is the ID.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+const CLS_TOKEN: &str = "[CLS]";
+const SEP_TOKEN: &str = "[SEP]";
+const UNK_TOKEN: &str = "[UNK]";
+const CONTINUATION_PREFIX: &str = "##";
+
+/// Words longer than this are mapped to [UNK] without trying to split them
+const MAX_WORD_CHARS: usize = 100;
+
+pub struct WordPieceTokenizer {
+    vocab: HashMap<String, i64>,
+    cls_id: i64,
+    sep_id: i64,
+    unk_id: i64,
+}
+
+impl WordPieceTokenizer {
+    pub fn load(vocab_path: &Path) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(vocab_path)?;
+        Self::from_vocab(content.lines()).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Vocabulary is missing [CLS], [SEP] or [UNK]",
+            )
+        })
+    }
+
+    pub fn from_vocab<'a>(tokens: impl IntoIterator<Item = &'a str>) -> Option<Self> {
+        let vocab: HashMap<String, i64> = tokens
+            .into_iter()
+            .enumerate()
+            .map(|(id, token)| (token.trim_end().to_string(), id as i64))
+            .collect();
+
+        Some(Self {
+            cls_id: *vocab.get(CLS_TOKEN)?,
+            sep_id: *vocab.get(SEP_TOKEN)?,
+            unk_id: *vocab.get(UNK_TOKEN)?,
+            vocab,
+        })
+    }
+
+    /// Token IDs wrapped in [CLS] ... [SEP], truncated to `max_len` in total
+    pub fn encode(&self, text: &str, max_len: usize) -> Vec<i64> {
+        let mut ids = vec![self.cls_id];
+        let budget = max_len.saturating_sub(2);
+
+        'words: for word in split_words(text) {
+            for id in self.word_pieces(&word) {
+                if ids.len() > budget {
+                    break 'words;
+                }
+                ids.push(id);
+            }
+        }
+
+        ids.push(self.sep_id);
+        ids
+    }
+
+    /// Greedy longest-match-first split of one word into vocabulary pieces
+    fn word_pieces(&self, word: &str) -> Vec<i64> {
+        let chars: Vec<char> = word.chars().collect();
+        if chars.len() > MAX_WORD_CHARS {
+            return vec![self.unk_id];
+        }
+
+        let mut pieces = Vec::new();
+        let mut start = 0;
+        while start < chars.len() {
+            let mut end = chars.len();
+            let mut found = None;
+            while start < end {
+                let mut candidate: String = chars[start..end].iter().collect();
+                if start > 0 {
+                    candidate.insert_str(0, CONTINUATION_PREFIX);
+                }
+                if let Some(&id) = self.vocab.get(&candidate) {
+                    found = Some(id);
+                    break;
+                }
+                end -= 1;
+            }
+
+            match found {
+                Some(id) => pieces.push(id),
+                None => return vec![self.unk_id],
+            }
+            start = end;
+        }
+        pieces
+    }
+}
+
+/// Lowercase, then split on whitespace with punctuation and CJK characters as
+/// words of their own
+fn split_words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_whitespace() || c.is_control() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+        } else if is_punctuation(c) || is_cjk(c) {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            words.push(c.to_string());
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn is_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation() || (!c.is_alphanumeric() && !c.is_whitespace() && !c.is_control())
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x4E00..=0x9FFF
+        | 0x3400..=0x4DBF
+        | 0x20000..=0x2A6DF
+        | 0xF900..=0xFAFF
+        | 0x2F800..=0x2FA1F)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokenizer() -> WordPieceTokenizer {
+        WordPieceTokenizer::from_vocab([
+            "[PAD]", "[UNK]", "[CLS]", "[SEP]", "we", "drop", "the", "v", "##1", "api", "?", "un",
+            "##want", "##ed", ",",
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_encode_splits_words_and_punctuation() {
+        let ids = tokenizer().encode("We drop the V1 API?", 64);
+        assert_eq!(ids, vec![2, 4, 5, 6, 7, 8, 9, 10, 3]);
+
+        let ids = tokenizer().encode("unwanted, unknown", 64);
+        assert_eq!(ids, vec![2, 11, 12, 13, 14, 1, 3]);
+    }
+
+    #[test]
+    fn test_encode_truncates_to_max_len() {
+        let ids = tokenizer().encode("we drop the api we drop the api", 5);
+        assert_eq!(ids, vec![2, 4, 5, 6, 3]);
+    }
+}
@@ -1,9 +1,11 @@
 use crate::database::repositories::{
     meeting::MeetingsRepository, summary::SummaryProcessesRepository,
-    transcript_chunk::TranscriptChunksRepository,
+    summary_version::SummaryVersionsRepository, transcript_chunk::TranscriptChunksRepository,
 };
 use crate::state::AppState;
+use crate::summary::processor::SummaryOutputFormat;
 use crate::summary::service::SummaryService;
+use crate::summary::version_commands::record_derived_version;
 use log::{error as log_error, info as log_info, warn as log_warn};
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Runtime};
@@ -46,6 +48,27 @@ pub async fn api_save_meeting_summary<R: Runtime>(
     match SummaryProcessesRepository::update_meeting_summary(pool, &meeting_id, &summary).await {
         Ok(true) => {
             log_info!("Summary saved successfully for meeting_id: {}", meeting_id);
+
+            // Edits keep the generation details of the version they were made from
+            let basis = SummaryVersionsRepository::latest(pool, &meeting_id)
+                .await
+                .unwrap_or_else(|e| {
+                    log_warn!("Failed to load latest summary version for {}: {}", meeting_id, e);
+                    None
+                });
+            if let Err(e) = record_derived_version(
+                pool,
+                &meeting_id,
+                "edited",
+                summary.to_string(),
+                basis.as_ref(),
+                None,
+            )
+            .await
+            {
+                log_warn!("Failed to record edited summary version for {}: {}", meeting_id, e);
+            }
+
             Ok(serde_json::json!({
                 "message": "Meeting summary saved successfully"
             }))
@@ -162,7 +185,9 @@ pub async fn api_get_summary<R: Runtime>(
 
 /// Processes transcript and generates summary (Native SQLx implementation)
 ///
-/// Spawns a background task and returns immediately with process_id
+/// Spawns a background task and returns immediately with process_id.
+/// `output_format` selects free markdown (default) or schema-validated structured output.
+/// `include_notes` (default false) passes the user's meeting notes as priority context.
 #[tauri::command]
 pub async fn api_process_transcript<R: Runtime>(
     app: AppHandle<R>,
@@ -175,6 +200,8 @@ pub async fn api_process_transcript<R: Runtime>(
     _overlap: Option<i32>,
     custom_prompt: Option<String>,
     template_id: Option<String>,
+    output_format: Option<SummaryOutputFormat>,
+    include_notes: Option<bool>,
     _auth_token: Option<String>,
 ) -> Result<ProcessTranscriptResponse, String> {
     use uuid::Uuid;
@@ -227,6 +254,8 @@ pub async fn api_process_transcript<R: Runtime>(
             model_name,
             final_prompt,
             final_template_id,
+            output_format.unwrap_or_default(),
+            include_notes.unwrap_or(false),
         )
         .await;
     });
@@ -0,0 +1,169 @@
+// Provider fallback chain and request retry policy for summarization
+// The chain starts with the meeting's selected provider and continues with the
+// user's configured fallbacks; each request is retried on transient errors, and
+// a provider whose request still fails hands over to the next one
+
+use crate::summary::llm_error::LlmError;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// A provider/model pair to summarize with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FallbackTarget {
+    /// Provider name as accepted by `LLMProvider::from_str` (e.g. "ollama")
+    pub provider: String,
+    pub model: String,
+}
+
+/// Retry settings applied to each summarization request
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Attempts per request, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on every further retry
+    pub initial_backoff: Duration,
+    /// Upper bound for the computed backoff
+    pub max_backoff: Duration,
+    /// Longest `Retry-After` worth waiting for; beyond it the next provider is tried
+    pub max_retry_after: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_secs(2),
+            max_backoff: Duration::from_secs(30),
+            max_retry_after: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retrying after attempt number `attempt` (1-based) failed
+    /// with `error`, or `None` to give up (and fall back to the next provider)
+    pub fn next_delay(&self, attempt: u32, error: &LlmError) -> Option<Duration> {
+        if attempt >= self.max_attempts || !error.is_retryable() {
+            return None;
+        }
+
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_backoff);
+        match error.retry_after() {
+            Some(wait) if wait > self.max_retry_after => None,
+            Some(wait) => Some(wait.max(backoff)),
+            None => Some(backoff),
+        }
+    }
+}
+
+/// Outcome of one provider in the chain, recorded in `SummaryProcess.metadata`
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderAttempt {
+    pub provider: String,
+    pub model: String,
+    pub succeeded: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ProviderAttempt {
+    pub fn succeeded(target: &FallbackTarget) -> Self {
+        Self {
+            provider: target.provider.clone(),
+            model: target.model.clone(),
+            succeeded: true,
+            error_kind: None,
+            error: None,
+        }
+    }
+
+    pub fn failed(target: &FallbackTarget, error: &LlmError) -> Self {
+        Self {
+            provider: target.provider.clone(),
+            model: target.model.clone(),
+            succeeded: false,
+            error_kind: Some(error.kind()),
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+/// The primary target followed by the fallbacks, skipping blanks and repeats
+pub fn build_chain(primary: FallbackTarget, fallbacks: &[FallbackTarget]) -> Vec<FallbackTarget> {
+    let mut chain: Vec<FallbackTarget> = Vec::with_capacity(fallbacks.len() + 1);
+    for target in std::iter::once(primary).chain(fallbacks.iter().cloned()) {
+        let target = FallbackTarget {
+            provider: target.provider.trim().to_lowercase(),
+            model: target.model.trim().to_string(),
+        };
+        if target.provider.is_empty() || target.model.is_empty() || chain.contains(&target) {
+            continue;
+        }
+        chain.push(target);
+    }
+    chain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(provider: &str, model: &str) -> FallbackTarget {
+        FallbackTarget {
+            provider: provider.to_string(),
+            model: model.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_chain_dedups_and_keeps_order() {
+        let chain = build_chain(
+            target("builtin-ai", "gemma3:1b"),
+            &[
+                target("Ollama", "llama3.2:latest"),
+                target("builtin-ai", "gemma3:1b"),
+                target("", "gpt-4o"),
+                target("custom-openai", " mistral-7b "),
+            ],
+        );
+        assert_eq!(
+            chain,
+            vec![
+                target("builtin-ai", "gemma3:1b"),
+                target("ollama", "llama3.2:latest"),
+                target("custom-openai", "mistral-7b"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_next_delay_backs_off_and_respects_retry_after() {
+        let policy = RetryPolicy::default();
+        let server = LlmError::Server {
+            status: 502,
+            message: "Bad gateway".to_string(),
+        };
+        assert_eq!(policy.next_delay(1, &server), Some(Duration::from_secs(2)));
+        assert_eq!(policy.next_delay(2, &server), Some(Duration::from_secs(4)));
+        assert_eq!(policy.next_delay(3, &server), None);
+
+        let rate_limited = |seconds| LlmError::RateLimited {
+            message: "slow down".to_string(),
+            retry_after: Some(Duration::from_secs(seconds)),
+        };
+        assert_eq!(
+            policy.next_delay(1, &rate_limited(20)),
+            Some(Duration::from_secs(20))
+        );
+        // Too long to wait: move on to the next provider
+        assert_eq!(policy.next_delay(1, &rate_limited(600)), None);
+
+        let auth = LlmError::Auth("Invalid API key".to_string());
+        assert_eq!(policy.next_delay(1, &auth), None);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::database::repositories::setting::SettingsRepository;
+use crate::state::AppState;
+use crate::summary::fallback::{build_chain, FallbackTarget};
+use crate::summary::llm_client::LLMProvider;
+use tauri::Runtime;
+use tracing::info;
+
+/// Most fallbacks worth configuring; each one can add minutes to a failing run
+const MAX_FALLBACKS: usize = 5;
+
+/// Returns the providers tried, in order, when the selected one fails
+#[tauri::command]
+pub async fn api_get_summary_fallback_chain<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<FallbackTarget>, String> {
+    info!("api_get_summary_fallback_chain called");
+    SettingsRepository::get_summary_fallback_chain(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to load summary fallback chain: {}", e))
+}
+
+/// Replaces the fallback chain; an empty list turns fallback off
+#[tauri::command]
+pub async fn api_save_summary_fallback_chain<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    chain: Vec<FallbackTarget>,
+) -> Result<Vec<FallbackTarget>, String> {
+    info!(
+        "api_save_summary_fallback_chain called with {} entries",
+        chain.len()
+    );
+
+    for target in &chain {
+        LLMProvider::from_str(target.provider.trim())?;
+        if target.model.trim().is_empty() {
+            return Err(format!("Model name is required for {}", target.provider));
+        }
+    }
+
+    // Normalize the same way the chain is built at run time
+    let chain: Vec<FallbackTarget> = match chain.split_first() {
+        Some((first, rest)) => build_chain(first.clone(), rest),
+        None => Vec::new(),
+    };
+    if chain.len() > MAX_FALLBACKS {
+        return Err(format!(
+            "At most {} fallback providers can be configured",
+            MAX_FALLBACKS
+        ));
+    }
+
+    let saved = SettingsRepository::save_summary_fallback_chain(state.db_manager.pool(), &chain)
+        .await
+        .map_err(|e| format!("Failed to save summary fallback chain: {}", e))?;
+    if !saved {
+        return Err("Configure a summary model before adding fallback providers".to_string());
+    }
+    Ok(chain)
+}
@@ -0,0 +1,389 @@
+// Google Gemini support: generateContent request/response types and model listing
+// Gemini differs from the OpenAI-style providers in three ways: the system
+// prompt is a separate `systemInstruction`, assistant turns use the "model"
+// role, and the API key travels in the `x-goog-api-key` header
+
+use crate::summary::llm_client::ChatMessage;
+use crate::summary::llm_error::LlmError;
+use reqwest::{header, Client};
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_BASE_URL: &str = "https://generativelanguage.googleapis.com";
+/// Overrides the API host, e.g. to point the client at a local stand-in server
+const BASE_URL_ENV: &str = "MEETILY_GEMINI_BASE_URL";
+/// Header carrying the API key
+pub const API_KEY_HEADER: &str = "x-goog-api-key";
+/// Upper bound on model list pages fetched, as a guard against looping tokens
+const MAX_MODEL_PAGES: usize = 10;
+
+/// API host, without a trailing slash
+pub fn base_url() -> String {
+    std::env::var(BASE_URL_ENV)
+        .ok()
+        .map(|url| url.trim().trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+}
+
+/// Endpoint for a completion; streaming uses Server-Sent Events (`alt=sse`)
+pub fn generate_url(base_url: &str, model_name: &str, stream: bool) -> String {
+    // Accept both "gemini-2.5-flash" and the API's own "models/gemini-2.5-flash"
+    let model = model_name.trim().trim_start_matches("models/");
+    if stream {
+        format!(
+            "{}/v1beta/models/{}:streamGenerateContent?alt=sse",
+            base_url, model
+        )
+    } else {
+        format!("{}/v1beta/models/{}:generateContent", base_url, model)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_instruction: Option<GeminiContent>,
+    pub contents: Vec<GeminiContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generation_config: Option<GenerationConfig>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GeminiContent {
+    /// "user" or "model"; omitted for the system instruction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    #[serde(default)]
+    pub parts: Vec<GeminiPart>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GeminiPart {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Set on reasoning summaries from thinking models, which aren't part of the answer
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub thought: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerationConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+}
+
+/// Builds a generateContent request from a system prompt and conversation
+pub fn build_request(
+    system_prompt: &str,
+    messages: &[ChatMessage],
+    generation_config: Option<GenerationConfig>,
+) -> GeminiRequest {
+    let text_content = |role: Option<&str>, text: &str| GeminiContent {
+        role: role.map(str::to_string),
+        parts: vec![GeminiPart {
+            text: Some(text.to_string()),
+            thought: false,
+        }],
+    };
+
+    GeminiRequest {
+        system_instruction: (!system_prompt.trim().is_empty())
+            .then(|| text_content(None, system_prompt)),
+        contents: messages
+            .iter()
+            .map(|message| {
+                let role = if message.role == "assistant" {
+                    "model"
+                } else {
+                    "user"
+                };
+                text_content(Some(role), &message.content)
+            })
+            .collect(),
+        generation_config,
+    }
+}
+
+/// Asks for a JSON response matching `schema` (`generationConfig.responseJsonSchema`)
+pub fn apply_json_schema(request_body: &mut serde_json::Value, schema: &serde_json::Value) {
+    let Some(body) = request_body.as_object_mut() else {
+        return;
+    };
+    let config = body
+        .entry("generationConfig")
+        .or_insert_with(|| serde_json::json!({}));
+    if let Some(config) = config.as_object_mut() {
+        config.insert(
+            "responseMimeType".to_string(),
+            serde_json::json!("application/json"),
+        );
+        config.insert("responseJsonSchema".to_string(), schema.clone());
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiResponse {
+    #[serde(default)]
+    pub candidates: Vec<GeminiCandidate>,
+    pub prompt_feedback: Option<PromptFeedback>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiCandidate {
+    pub content: Option<GeminiContent>,
+    pub finish_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptFeedback {
+    pub block_reason: Option<String>,
+}
+
+impl GeminiResponse {
+    /// Answer text of the first candidate, without thought parts
+    pub fn text(&self) -> String {
+        self.candidates
+            .first()
+            .and_then(|candidate| candidate.content.as_ref())
+            .map(|content| answer_text(&content.parts))
+            .unwrap_or_default()
+    }
+
+    /// The trimmed answer, or why there is none (blocked prompt, empty candidate)
+    pub fn into_text(self) -> Result<String, LlmError> {
+        if let Some(reason) = self
+            .prompt_feedback
+            .as_ref()
+            .and_then(|feedback| feedback.block_reason.as_deref())
+        {
+            return Err(LlmError::Other(format!(
+                "Gemini blocked the prompt ({})",
+                reason
+            )));
+        }
+
+        let text = self.text();
+        let text = text.trim();
+        if text.is_empty() {
+            let reason = self
+                .candidates
+                .first()
+                .and_then(|candidate| candidate.finish_reason.as_deref())
+                .unwrap_or("no candidates");
+            return Err(LlmError::Other(format!(
+                "No content in LLM response (finish reason: {})",
+                reason
+            )));
+        }
+        Ok(text.to_string())
+    }
+}
+
+/// Concatenated text of the non-thought parts
+pub fn answer_text(parts: &[GeminiPart]) -> String {
+    parts
+        .iter()
+        .filter(|part| !part.thought)
+        .filter_map(|part| part.text.as_deref())
+        .collect()
+}
+
+/// A Gemini model that supports text generation
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GeminiModel {
+    /// Model id to pass as the model name (e.g. "gemini-2.5-flash")
+    pub id: String,
+    pub name: String,
+    pub input_token_limit: Option<u32>,
+    pub output_token_limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiModel {
+    name: String,
+    display_name: Option<String>,
+    input_token_limit: Option<u32>,
+    output_token_limit: Option<u32>,
+    #[serde(default)]
+    supported_generation_methods: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListModelsResponse {
+    #[serde(default)]
+    models: Vec<ApiModel>,
+    next_page_token: Option<String>,
+}
+
+/// Lists the models available to `api_key` that can generate content
+pub async fn list_models(
+    client: &Client,
+    base_url: &str,
+    api_key: &str,
+) -> Result<Vec<GeminiModel>, LlmError> {
+    let mut models = Vec::new();
+    let mut page_token: Option<String> = None;
+
+    for _ in 0..MAX_MODEL_PAGES {
+        let mut request = client
+            .get(format!("{}/v1beta/models", base_url))
+            .header(API_KEY_HEADER, api_key)
+            .query(&[("pageSize", "100")]);
+        if let Some(token) = &page_token {
+            request = request.query(&[("pageToken", token)]);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| LlmError::Network(format!("Failed to list Gemini models: {}", e)))?;
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let retry_after = response
+                .headers()
+                .get(header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
+            let body = response.text().await.unwrap_or_default();
+            return Err(LlmError::from_response(
+                status,
+                retry_after.as_deref(),
+                &body,
+            ));
+        }
+
+        let page: ListModelsResponse = response
+            .json()
+            .await
+            .map_err(|e| LlmError::Other(format!("Failed to parse Gemini model list: {}", e)))?;
+        models.extend(
+            page.models
+                .into_iter()
+                .filter(|model| {
+                    model
+                        .supported_generation_methods
+                        .iter()
+                        .any(|method| method == "generateContent")
+                })
+                .map(|model| {
+                    let id = model.name.trim_start_matches("models/").to_string();
+                    GeminiModel {
+                        name: model.display_name.unwrap_or_else(|| id.clone()),
+                        id,
+                        input_token_limit: model.input_token_limit,
+                        output_token_limit: model.output_token_limit,
+                    }
+                }),
+        );
+
+        page_token = page.next_page_token.filter(|token| !token.is_empty());
+        if page_token.is_none() {
+            break;
+        }
+    }
+
+    Ok(models)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::CannedServer;
+
+    #[test]
+    fn test_build_request_maps_roles_and_system_prompt() {
+        let messages = vec![
+            ChatMessage::user("What was decided?"),
+            ChatMessage::assistant("To ship v2."),
+            ChatMessage::user("When?"),
+        ];
+        let request = serde_json::to_value(build_request("Be brief.", &messages, None)).unwrap();
+
+        assert_eq!(
+            request["systemInstruction"],
+            serde_json::json!({ "parts": [{ "text": "Be brief." }] })
+        );
+        let roles: Vec<&str> = request["contents"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|content| content["role"].as_str().unwrap())
+            .collect();
+        assert_eq!(roles, vec!["user", "model", "user"]);
+        assert!(request.get("generationConfig").is_none());
+
+        assert_eq!(
+            generate_url("http://127.0.0.1:9", "models/gemini-2.5-flash", true),
+            "http://127.0.0.1:9/v1beta/models/gemini-2.5-flash:streamGenerateContent?alt=sse"
+        );
+    }
+
+    #[test]
+    fn test_response_text_skips_thoughts_and_reports_blocks() {
+        let response: GeminiResponse = serde_json::from_str(
+            r#"{"candidates": [{"content": {"role": "model", "parts": [
+                {"text": "Thinking it over", "thought": true},
+                {"text": "Ship "}, {"text": "v2.\n"}
+            ]}, "finishReason": "STOP"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(response.into_text().unwrap(), "Ship v2.");
+
+        let blocked: GeminiResponse =
+            serde_json::from_str(r#"{"promptFeedback": {"blockReason": "SAFETY"}}"#).unwrap();
+        assert_eq!(
+            blocked.into_text().unwrap_err().to_string(),
+            "Gemini blocked the prompt (SAFETY)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_models_against_mock_server() {
+        let first_page = r#"{"models": [
+            {"name": "models/gemini-2.5-flash", "displayName": "Gemini 2.5 Flash",
+             "inputTokenLimit": 1048576, "outputTokenLimit": 65536,
+             "supportedGenerationMethods": ["generateContent", "countTokens"]},
+            {"name": "models/text-embedding-004", "supportedGenerationMethods": ["embedContent"]}
+        ], "nextPageToken": "page-2"}"#;
+        let second_page = r#"{"models": [
+            {"name": "models/gemini-2.5-pro", "supportedGenerationMethods": ["generateContent"]}
+        ]}"#;
+        let server = CannedServer::start(vec![(200, first_page), (200, second_page)]).await;
+
+        let models = list_models(&Client::new(), &server.base_url, "test-key")
+            .await
+            .unwrap();
+        let ids: Vec<&str> = models.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["gemini-2.5-flash", "gemini-2.5-pro"]);
+        assert_eq!(models[0].name, "Gemini 2.5 Flash");
+        assert_eq!(models[1].name, "gemini-2.5-pro");
+
+        let requests = server.requests();
+        assert!(requests[0]
+            .to_lowercase()
+            .contains("x-goog-api-key: test-key"));
+        assert!(requests[1].contains("pageToken=page-2"));
+    }
+
+    #[tokio::test]
+    async fn test_list_models_classifies_invalid_key() {
+        let body = r#"{"error": {"code": 400, "message": "API key not valid. Please pass a valid API key.", "status": "INVALID_ARGUMENT"}}"#;
+        let server = CannedServer::start(vec![(400, body)]).await;
+
+        let error = list_models(&Client::new(), &server.base_url, "bad-key")
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), "auth");
+    }
+}
@@ -0,0 +1,31 @@
+use crate::database::repositories::setting::SettingsRepository;
+use crate::state::AppState;
+use crate::summary::gemini::{self, GeminiModel};
+use tauri::Runtime;
+use tracing::info;
+
+/// Lists the Gemini models that can summarize
+///
+/// Uses `api_key` when given (e.g. while the user is still typing it into
+/// settings), otherwise the saved key.
+#[tauri::command]
+pub async fn api_get_gemini_models<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    api_key: Option<String>,
+) -> Result<Vec<GeminiModel>, String> {
+    info!("api_get_gemini_models called");
+
+    let api_key = match api_key.filter(|key| !key.trim().is_empty()) {
+        Some(key) => key.trim().to_string(),
+        None => SettingsRepository::get_api_key(state.db_manager.pool(), "gemini")
+            .await
+            .map_err(|e| format!("Failed to retrieve API key for gemini: {}", e))?
+            .filter(|key| !key.is_empty())
+            .ok_or_else(|| "API key not found for gemini".to_string())?,
+    };
+
+    gemini::list_models(&reqwest::Client::new(), &gemini::base_url(), &api_key)
+        .await
+        .map_err(String::from)
+}
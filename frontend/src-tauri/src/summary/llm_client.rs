@@ -1,19 +1,50 @@
+use crate::summary::gemini::{self, GeminiResponse};
+use crate::summary::llm_error::LlmError;
+use crate::summary::streaming::{parse_stream_line, LineBuffer, StreamEvent, StreamFormat};
+use futures_util::StreamExt;
+use once_cell::sync::Lazy;
 use reqwest::{header, Client};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::path::PathBuf;
+use std::sync::Mutex;
 use std::time::Duration;
 use tokio_util::sync::CancellationToken;
-use tracing::info;
+use tracing::{info, warn};
 
 const REQUEST_TIMEOUT_DURATION: Duration = Duration::from_secs(300);
 
+/// Longest gap between chunks of a streamed response before it counts as stalled
+const STREAM_IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+
+// Endpoint/model pairs that rejected a native JSON mode; later JSON requests
+// to them go straight to prompt-only JSON
+static JSON_MODE_UNSUPPORTED: Lazy<Mutex<HashSet<String>>> =
+    Lazy::new(|| Mutex::new(HashSet::new()));
+
 // Generic structure for OpenAI-compatible API chat messages
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ChatMessage {
     pub role: String,
     pub content: String,
 }
 
+impl ChatMessage {
+    pub fn user(content: &str) -> Self {
+        Self {
+            role: "user".to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    pub fn assistant(content: &str) -> Self {
+        Self {
+            role: "assistant".to_string(),
+            content: content.to_string(),
+        }
+    }
+}
+
 // Generic structure for OpenAI-compatible API chat requests
 #[derive(Debug, Serialize)]
 pub struct ChatRequest {
@@ -25,6 +56,8 @@ pub struct ChatRequest {
     pub temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
 }
 
 // Generic structure for OpenAI-compatible API chat responses
@@ -50,6 +83,16 @@ pub struct ClaudeRequest {
     pub max_tokens: u32,
     pub system: String,
     pub messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+}
+
+// Ollama native chat request structure (used for NDJSON streaming)
+#[derive(Debug, Serialize)]
+pub struct OllamaChatRequest {
+    pub model: String,
+    pub messages: Vec<ChatMessage>,
+    pub stream: bool,
 }
 
 // Claude-specific response structure
@@ -73,6 +116,7 @@ pub enum LLMProvider {
     OpenRouter,
     BuiltInAI,
     CustomOpenAI,
+    Gemini,
 }
 
 impl LLMProvider {
@@ -86,6 +130,7 @@ impl LLMProvider {
             "openrouter" => Ok(Self::OpenRouter),
             "builtin-ai" | "local-llama" | "localllama" => Ok(Self::BuiltInAI),
             "custom-openai" => Ok(Self::CustomOpenAI),
+            "gemini" => Ok(Self::Gemini),
             _ => Err(format!("Unsupported LLM provider: {}", s)),
         }
     }
@@ -109,7 +154,7 @@ impl LLMProvider {
 /// * `cancellation_token` - Optional token to cancel the request
 ///
 /// # Returns
-/// The generated summary text or an error message
+/// The generated summary text, or the classified reason the request failed
 pub async fn generate_summary(
     client: &Client,
     provider: &LLMProvider,
@@ -124,30 +169,450 @@ pub async fn generate_summary(
     top_p: Option<f32>,
     app_data_dir: Option<&PathBuf>,
     cancellation_token: Option<&CancellationToken>,
-) -> Result<String, String> {
+) -> Result<String, LlmError> {
+    generate_completion(
+        client,
+        provider,
+        model_name,
+        api_key,
+        system_prompt,
+        &[ChatMessage::user(user_prompt)],
+        ollama_endpoint,
+        custom_openai_endpoint,
+        max_tokens,
+        temperature,
+        top_p,
+        app_data_dir,
+        cancellation_token,
+        None,
+    )
+    .await
+}
+
+/// Continues a multi-turn conversation using the specified LLM provider
+///
+/// Takes the same arguments as [`generate_summary`], with the conversation so
+/// far (alternating user/assistant turns, ending with the user's latest
+/// message) in place of a single user prompt. BuiltInAI takes one prompt, so
+/// earlier turns are folded into it as a transcript of the conversation.
+pub async fn generate_chat(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    api_key: &str,
+    system_prompt: &str,
+    messages: &[ChatMessage],
+    ollama_endpoint: Option<&str>,
+    custom_openai_endpoint: Option<&str>,
+    max_tokens: Option<u32>,
+    temperature: Option<f32>,
+    top_p: Option<f32>,
+    app_data_dir: Option<&PathBuf>,
+    cancellation_token: Option<&CancellationToken>,
+) -> Result<String, LlmError> {
+    if messages.is_empty() {
+        return Err(LlmError::Other("Conversation has no messages".to_string()));
+    }
+
+    generate_completion(
+        client,
+        provider,
+        model_name,
+        api_key,
+        system_prompt,
+        messages,
+        ollama_endpoint,
+        custom_openai_endpoint,
+        max_tokens,
+        temperature,
+        top_p,
+        app_data_dir,
+        cancellation_token,
+        None,
+    )
+    .await
+}
+
+/// Generates a JSON document matching `schema` using the specified LLM provider
+///
+/// Takes the same arguments as [`generate_summary`] plus the JSON schema of the
+/// expected output. Providers with a native JSON mode are asked to enforce it:
+/// * OpenAI, OpenRouter, CustomOpenAI, Ollama - `response_format` with a strict `json_schema`
+/// * Groq - `response_format` of type `json_object`
+/// * Gemini - `generationConfig.responseJsonSchema`
+/// * BuiltInAI - grammar-constrained sampling in the llama-helper sidecar
+/// * Claude - no native JSON mode; the prompt must describe the schema
+///
+/// Many OpenAI-compatible servers don't support `json_schema`; when the JSON
+/// mode is rejected (HTTP 400/422) the request is sent again without it, so
+/// the prompt must describe the schema for every provider. The raw completion
+/// is returned; callers are expected to validate it.
+pub async fn generate_summary_json(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    api_key: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    ollama_endpoint: Option<&str>,
+    custom_openai_endpoint: Option<&str>,
+    max_tokens: Option<u32>,
+    temperature: Option<f32>,
+    top_p: Option<f32>,
+    app_data_dir: Option<&PathBuf>,
+    cancellation_token: Option<&CancellationToken>,
+    schema: &serde_json::Value,
+) -> Result<String, LlmError> {
+    generate_completion(
+        client,
+        provider,
+        model_name,
+        api_key,
+        system_prompt,
+        &[ChatMessage::user(user_prompt)],
+        ollama_endpoint,
+        custom_openai_endpoint,
+        max_tokens,
+        temperature,
+        top_p,
+        app_data_dir,
+        cancellation_token,
+        Some(schema),
+    )
+    .await
+}
+
+/// Shared non-streaming completion path, optionally constrained to a JSON schema
+async fn generate_completion(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    api_key: &str,
+    system_prompt: &str,
+    messages: &[ChatMessage],
+    ollama_endpoint: Option<&str>,
+    custom_openai_endpoint: Option<&str>,
+    max_tokens: Option<u32>,
+    temperature: Option<f32>,
+    top_p: Option<f32>,
+    app_data_dir: Option<&PathBuf>,
+    cancellation_token: Option<&CancellationToken>,
+    json_schema: Option<&serde_json::Value>,
+) -> Result<String, LlmError> {
     // Check if cancelled before starting
     if let Some(token) = cancellation_token {
         if token.is_cancelled() {
-            return Err("Summary generation was cancelled".to_string());
+            return Err(LlmError::Cancelled);
         }
     }
 
     // Handle BuiltInAI provider separately (uses local sidecar, no HTTP API)
     if provider == &LLMProvider::BuiltInAI {
-        let app_data_dir = app_data_dir
-            .ok_or_else(|| "app_data_dir is required for BuiltInAI provider".to_string())?;
+        let app_data_dir = app_data_dir.ok_or_else(|| {
+            LlmError::Other("app_data_dir is required for BuiltInAI provider".to_string())
+        })?;
+        // The sidecar takes a single prompt
+        let user_prompt = single_prompt(messages);
 
-        return crate::summary::summary_engine::generate_with_builtin(
+        let result = match json_schema {
+            Some(schema) => {
+                crate::summary::summary_engine::generate_with_builtin_json(
+                    app_data_dir,
+                    model_name,
+                    system_prompt,
+                    &user_prompt,
+                    cancellation_token,
+                    schema,
+                )
+                .await
+            }
+            None => {
+                crate::summary::summary_engine::generate_with_builtin(
+                    app_data_dir,
+                    model_name,
+                    system_prompt,
+                    &user_prompt,
+                    cancellation_token,
+                )
+                .await
+            }
+        };
+        return result.map_err(|e| builtin_error(e, cancellation_token));
+    }
+
+    let (api_url, headers, mut request_body) = build_provider_request(
+        provider,
+        model_name,
+        api_key,
+        system_prompt,
+        messages,
+        ollama_endpoint,
+        custom_openai_endpoint,
+        max_tokens,
+        temperature,
+        top_p,
+        false,
+    )
+    .map_err(LlmError::Other)?;
+
+    // Keep the body without JSON mode around in case the provider rejects it
+    let json_mode_key = format!("{}:{}:{}", provider_name(provider), api_url, model_name);
+    let json_mode_unsupported = JSON_MODE_UNSUPPORTED
+        .lock()
+        .map(|unsupported| unsupported.contains(&json_mode_key))
+        .unwrap_or(false);
+    let mut plain_body = None;
+    if let Some(schema) = json_schema.filter(|_| !json_mode_unsupported) {
+        let plain = request_body.clone();
+        if apply_json_response_format(provider, &mut request_body, schema) {
+            plain_body = Some(plain);
+        }
+    }
+
+    info!("🐞 LLM Request to {}: model={}", provider_name(provider), model_name);
+
+    let mut response = send_provider_request(
+        client,
+        api_url.clone(),
+        headers.clone(),
+        &request_body,
+        cancellation_token,
+        false,
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status().as_u16();
+        let error = error_from_response(response).await;
+        let Some(plain_body) = plain_body.filter(|_| rejects_json_mode(status, &error)) else {
+            return Err(error);
+        };
+
+        warn!(
+            "{} rejected the JSON response format ({}), retrying with the schema in the prompt only",
+            provider_name(provider),
+            error
+        );
+        if let Ok(mut unsupported) = JSON_MODE_UNSUPPORTED.lock() {
+            unsupported.insert(json_mode_key);
+        }
+        response = send_provider_request(client, api_url, headers, &plain_body, cancellation_token, false).await?;
+        if !response.status().is_success() {
+            return Err(error_from_response(response).await);
+        }
+    }
+
+    // Parse response based on provider
+    if provider == &LLMProvider::Gemini {
+        let chat_response = response
+            .json::<GeminiResponse>()
+            .await
+            .map_err(|e| LlmError::Other(format!("Failed to parse LLM response: {}", e)))?;
+
+        info!("🐞 LLM Response received from Gemini");
+
+        chat_response.into_text()
+    } else if provider == &LLMProvider::Claude {
+        let chat_response = response
+            .json::<ClaudeChatResponse>()
+            .await
+            .map_err(|e| LlmError::Other(format!("Failed to parse LLM response: {}", e)))?;
+
+        info!("🐞 LLM Response received from Claude");
+
+        let content = chat_response
+            .content
+            .get(0)
+            .ok_or_else(|| LlmError::Other("No content in LLM response".to_string()))?
+            .text
+            .trim();
+        Ok(content.to_string())
+    } else {
+        let chat_response = response
+            .json::<ChatResponse>()
+            .await
+            .map_err(|e| LlmError::Other(format!("Failed to parse LLM response: {}", e)))?;
+
+        info!("🐞 LLM Response received from {}", provider_name(provider));
+
+        let content = chat_response
+            .choices
+            .get(0)
+            .ok_or_else(|| LlmError::Other("No content in LLM response".to_string()))?
+            .message
+            .content
+            .trim();
+        Ok(content.to_string())
+    }
+}
+
+/// Callback invoked with each text delta while a completion is streamed
+pub type StreamCallback<'a> = dyn Fn(&str) + Send + Sync + 'a;
+
+/// Generates a summary using the specified LLM provider, streaming the output
+///
+/// Takes the same arguments as [`generate_summary`] plus `on_delta`, which is
+/// called with every piece of text as it arrives. The full completion is still
+/// returned so callers can post-process it like the non-streaming path.
+///
+/// Wire formats per provider:
+/// * OpenAI, Groq, OpenRouter, CustomOpenAI - Server-Sent Events
+/// * Claude - Anthropic message event stream
+/// * Ollama - NDJSON from the native `/api/chat` endpoint
+/// * Gemini - Server-Sent Events from `streamGenerateContent?alt=sse`
+/// * BuiltInAI - incremental token frames from the llama-helper sidecar
+pub async fn generate_summary_stream(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    api_key: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    ollama_endpoint: Option<&str>,
+    custom_openai_endpoint: Option<&str>,
+    max_tokens: Option<u32>,
+    temperature: Option<f32>,
+    top_p: Option<f32>,
+    app_data_dir: Option<&PathBuf>,
+    cancellation_token: Option<&CancellationToken>,
+    on_delta: &StreamCallback<'_>,
+) -> Result<String, LlmError> {
+    // Check if cancelled before starting
+    if let Some(token) = cancellation_token {
+        if token.is_cancelled() {
+            return Err(LlmError::Cancelled);
+        }
+    }
+
+    // BuiltInAI streams token frames over the sidecar's stdout instead of HTTP
+    if provider == &LLMProvider::BuiltInAI {
+        let app_data_dir = app_data_dir.ok_or_else(|| {
+            LlmError::Other("app_data_dir is required for BuiltInAI provider".to_string())
+        })?;
+
+        return crate::summary::summary_engine::generate_with_builtin_stream(
             app_data_dir,
             model_name,
             system_prompt,
             user_prompt,
             cancellation_token,
+            on_delta,
         )
         .await
-        .map_err(|e| e.to_string());
+        .map(|text| text.trim().to_string())
+        .map_err(|e| builtin_error(e, cancellation_token));
     }
 
+    let (api_url, headers, request_body) = build_provider_request(
+        provider,
+        model_name,
+        api_key,
+        system_prompt,
+        &[ChatMessage::user(user_prompt)],
+        ollama_endpoint,
+        custom_openai_endpoint,
+        max_tokens,
+        temperature,
+        top_p,
+        true,
+    )
+    .map_err(LlmError::Other)?;
+
+    info!(
+        "🐞 LLM Streaming Request to {}: model={}",
+        provider_name(provider),
+        model_name
+    );
+
+    let response = send_provider_request(client, api_url, headers, &request_body, cancellation_token, true).await?;
+
+    if !response.status().is_success() {
+        return Err(error_from_response(response).await);
+    }
+
+    let format = stream_format(provider);
+    let mut stream = response.bytes_stream();
+    let mut lines = LineBuffer::new();
+    let mut output = String::new();
+
+    'stream: loop {
+        // Race each network read against cancellation and the idle timeout
+        let read = tokio::time::timeout(STREAM_IDLE_TIMEOUT, stream.next());
+        let next = if let Some(token) = cancellation_token {
+            tokio::select! {
+                next = read => next,
+                _ = token.cancelled() => {
+                    return Err(LlmError::Cancelled);
+                }
+            }
+        } else {
+            read.await
+        };
+        let next = next.map_err(|_| {
+            LlmError::Network(format!(
+                "LLM stream stalled: no data for {} seconds",
+                STREAM_IDLE_TIMEOUT.as_secs()
+            ))
+        })?;
+
+        let (pending_lines, finished) = match next {
+            Some(Ok(chunk)) => (lines.push(&chunk), false),
+            Some(Err(e)) => {
+                return Err(LlmError::Network(format!("Failed to read LLM stream: {}", e)));
+            }
+            None => (lines.finish().into_iter().collect(), true),
+        };
+
+        for line in &pending_lines {
+            match parse_stream_line(format, line) {
+                StreamEvent::Delta(text) => {
+                    output.push_str(&text);
+                    on_delta(&text);
+                }
+                StreamEvent::Done => break 'stream,
+                StreamEvent::Error(message) => {
+                    return Err(LlmError::from_stream_error(&message));
+                }
+                StreamEvent::Ignore => {}
+            }
+        }
+
+        if finished {
+            break;
+        }
+    }
+
+    info!(
+        "🐞 LLM Stream completed from {} ({} chars)",
+        provider_name(provider),
+        output.len()
+    );
+
+    let content = output.trim();
+    if content.is_empty() {
+        return Err(LlmError::Other("No content in LLM response".to_string()));
+    }
+    Ok(content.to_string())
+}
+
+/// Builds the endpoint URL, headers and JSON body for an HTTP-based provider
+///
+/// When `stream` is true the body asks the provider for incremental output.
+/// Ollama switches to its native `/api/chat` endpoint in that case so the
+/// response arrives as NDJSON, and Gemini to `streamGenerateContent`.
+fn build_provider_request(
+    provider: &LLMProvider,
+    model_name: &str,
+    api_key: &str,
+    system_prompt: &str,
+    messages: &[ChatMessage],
+    ollama_endpoint: Option<&str>,
+    custom_openai_endpoint: Option<&str>,
+    max_tokens: Option<u32>,
+    temperature: Option<f32>,
+    top_p: Option<f32>,
+    stream: bool,
+) -> Result<(String, header::HeaderMap, serde_json::Value), String> {
     let (api_url, mut headers) = match provider {
         LLMProvider::OpenAI => (
             "https://api.openai.com/v1/chat/completions".to_string(),
@@ -165,10 +630,9 @@ pub async fn generate_summary(
             let host = ollama_endpoint
                 .map(|s| s.to_string())
                 .unwrap_or_else(|| "http://localhost:11434".to_string());
-            (
-                format!("{}/v1/chat/completions", host),
-                header::HeaderMap::new(),
-            )
+            // Streaming uses the native endpoint, which emits NDJSON
+            let path = if stream { "api/chat" } else { "v1/chat/completions" };
+            (format!("{}/{}", host, path), header::HeaderMap::new())
         }
         LLMProvider::CustomOpenAI => {
             let endpoint = custom_openai_endpoint
@@ -194,14 +658,27 @@ pub async fn generate_summary(
             );
             ("https://api.anthropic.com/v1/messages".to_string(), header_map)
         }
+        LLMProvider::Gemini => {
+            let mut header_map = header::HeaderMap::new();
+            header_map.insert(
+                gemini::API_KEY_HEADER,
+                api_key
+                    .parse()
+                    .map_err(|_| "Invalid API key format".to_string())?,
+            );
+            (
+                gemini::generate_url(&gemini::base_url(), model_name, stream),
+                header_map,
+            )
+        }
         LLMProvider::BuiltInAI => {
             // This case is handled earlier with early returns
             unreachable!("BuiltInAI is handled before this match statement")
         }
     };
 
-    // Add authorization header for non-Claude providers
-    if provider != &LLMProvider::Claude {
+    // Add authorization header for providers without their own key header
+    if provider != &LLMProvider::Claude && provider != &LLMProvider::Gemini {
         headers.insert(
             header::AUTHORIZATION,
             format!("Bearer {}", api_key)
@@ -216,8 +693,27 @@ pub async fn generate_summary(
             .map_err(|_| "Invalid content type".to_string())?,
     );
 
+    // OpenAI-style APIs take the system prompt as the first message
+    let with_system = || {
+        let mut all = Vec::with_capacity(messages.len() + 1);
+        all.push(ChatMessage {
+            role: "system".to_string(),
+            content: system_prompt.to_string(),
+        });
+        all.extend_from_slice(messages);
+        all
+    };
+
     // Build request body based on provider
-    let request_body = if provider != &LLMProvider::Claude {
+    let request_body = if provider == &LLMProvider::Gemini {
+        serde_json::json!(gemini::build_request(system_prompt, messages, None))
+    } else if stream && provider == &LLMProvider::Ollama {
+        serde_json::json!(OllamaChatRequest {
+            model: model_name.to_string(),
+            messages: with_system(),
+            stream: true,
+        })
+    } else if provider != &LLMProvider::Claude {
         // For CustomOpenAI, apply optional parameters if provided
         let (max_tokens_val, temperature_val, top_p_val) = if provider == &LLMProvider::CustomOpenAI {
             (max_tokens, temperature, top_p)
@@ -227,108 +723,176 @@ pub async fn generate_summary(
 
         serde_json::json!(ChatRequest {
             model: model_name.to_string(),
-            messages: vec![
-                ChatMessage {
-                    role: "system".to_string(),
-                    content: system_prompt.to_string(),
-                },
-                ChatMessage {
-                    role: "user".to_string(),
-                    content: user_prompt.to_string(),
-                }
-            ],
+            messages: with_system(),
             max_tokens: max_tokens_val,
             temperature: temperature_val,
             top_p: top_p_val,
+            stream: stream.then_some(true),
         })
     } else {
         serde_json::json!(ClaudeRequest {
             system: system_prompt.to_string(),
             model: model_name.to_string(),
             max_tokens: 2048,
-            messages: vec![ChatMessage {
-                role: "user".to_string(),
-                content: user_prompt.to_string(),
-            }]
+            messages: messages.to_vec(),
+            stream: stream.then_some(true),
         })
     };
 
-    info!("🐞 LLM Request to {}: model={}", provider_name(provider), model_name);
+    Ok((api_url, headers, request_body))
+}
 
-    // Send request with timeout and cancellation support
-    let request_future = client
-        .post(api_url)
-        .headers(headers)
-        .json(&request_body)
-        .timeout(REQUEST_TIMEOUT_DURATION)
-        .send();
+/// Folds a conversation into one prompt for providers that take a single prompt
+///
+/// A lone user message is passed through unchanged.
+fn single_prompt(messages: &[ChatMessage]) -> String {
+    match messages {
+        [only] if only.role == "user" => only.content.clone(),
+        _ => {
+            let mut prompt = String::new();
+            for message in messages {
+                let speaker = if message.role == "assistant" {
+                    "Assistant"
+                } else {
+                    "User"
+                };
+                prompt.push_str(&format!("{}: {}\n\n", speaker, message.content.trim()));
+            }
+            prompt.push_str("Assistant:");
+            prompt
+        }
+    }
+}
+
+/// Asks the provider to return JSON matching `schema`, where it supports that natively
+///
+/// Returns whether the request body was changed.
+fn apply_json_response_format(
+    provider: &LLMProvider,
+    request_body: &mut serde_json::Value,
+    schema: &serde_json::Value,
+) -> bool {
+    let response_format = match provider {
+        LLMProvider::OpenAI
+        | LLMProvider::OpenRouter
+        | LLMProvider::CustomOpenAI
+        | LLMProvider::Ollama => serde_json::json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": "meeting_summary",
+                "strict": true,
+                "schema": schema,
+            }
+        }),
+        // Groq only guarantees syntactically valid JSON across its models
+        LLMProvider::Groq => serde_json::json!({ "type": "json_object" }),
+        // Configured through generationConfig rather than response_format
+        LLMProvider::Gemini => {
+            gemini::apply_json_schema(request_body, schema);
+            return true;
+        }
+        // No JSON mode; the schema is described in the prompt instead
+        LLMProvider::Claude => return false,
+        // Handled by the sidecar before any HTTP request is built
+        LLMProvider::BuiltInAI => return false,
+    };
+
+    match request_body.as_object_mut() {
+        Some(body) => {
+            body.insert("response_format".to_string(), response_format);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Whether a failed request looks like the provider refusing its JSON mode
+/// (unsupported `response_format` or schema) rather than failing to serve it
+fn rejects_json_mode(status: u16, error: &LlmError) -> bool {
+    matches!(status, 400 | 422) && matches!(error, LlmError::Other(_))
+}
+
+/// Sends a prepared provider request, racing it against the cancellation token
+///
+/// With `stream` set only the wait for the response headers is timed; the
+/// caller bounds each read of the body with [`STREAM_IDLE_TIMEOUT`].
+async fn send_provider_request(
+    client: &Client,
+    api_url: String,
+    headers: header::HeaderMap,
+    request_body: &serde_json::Value,
+    cancellation_token: Option<&CancellationToken>,
+    stream: bool,
+) -> Result<reqwest::Response, LlmError> {
+    let mut request = client.post(api_url).headers(headers).json(request_body);
+    if !stream {
+        // A streamed body can take longer than this; its reads are bounded by
+        // STREAM_IDLE_TIMEOUT instead so long reports aren't cut off mid-stream
+        request = request.timeout(REQUEST_TIMEOUT_DURATION);
+    }
+    // Either way the response headers must arrive within the request timeout
+    let request_future = tokio::time::timeout(REQUEST_TIMEOUT_DURATION, request.send());
 
     // Use tokio::select to race between cancellation and request completion
-    let response = if let Some(token) = cancellation_token {
+    let result = if let Some(token) = cancellation_token {
         tokio::select! {
-            result = request_future => {
-                result.map_err(|e| {
-                    if e.is_timeout() {
-                        format!("LLM request timed out after 60 seconds")
-                    } else {
-                        format!("Failed to send request to LLM: {}", e)
-                    }
-                })?
-            }
+            result = request_future => result,
             _ = token.cancelled() => {
-                return Err("Summary generation was cancelled".to_string());
+                return Err(LlmError::Cancelled);
             }
         }
     } else {
-        request_future.await.map_err(|e| {
-            if e.is_timeout() {
-                format!("LLM request timed out after 60 seconds")
-            } else {
-                format!("Failed to send request to LLM: {}", e)
-            }
-        })?
+        request_future.await
     };
 
-    if !response.status().is_success() {
-        let error_body = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("LLM API request failed: {}", error_body));
-    }
-
-    // Parse response based on provider
-    if provider == &LLMProvider::Claude {
-        let chat_response = response
-            .json::<ClaudeChatResponse>()
-            .await
-            .map_err(|e| format!("Failed to parse LLM response: {}", e))?;
+    let timed_out = || {
+        LlmError::Network(format!(
+            "LLM request timed out after {} seconds",
+            REQUEST_TIMEOUT_DURATION.as_secs()
+        ))
+    };
+    result.map_err(|_| timed_out())?.map_err(|e| {
+        if e.is_timeout() {
+            timed_out()
+        } else if e.is_builder() {
+            LlmError::Other(format!("Invalid LLM request: {}", e))
+        } else {
+            LlmError::Network(format!("Failed to send request to LLM: {}", e))
+        }
+    })
+}
 
-        info!("🐞 LLM Response received from Claude");
+/// Classifies a non-success provider response from its status, `Retry-After` header and body
+async fn error_from_response(response: reqwest::Response) -> LlmError {
+    let status = response.status().as_u16();
+    let retry_after = response
+        .headers()
+        .get(header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let body = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "Unknown error".to_string());
+    LlmError::from_response(status, retry_after.as_deref(), &body)
+}
 
-        let content = chat_response
-            .content
-            .get(0)
-            .ok_or("No content in LLM response")?
-            .text
-            .trim();
-        Ok(content.to_string())
+/// Classifies a BuiltInAI sidecar failure; the sidecar only reports plain messages
+fn builtin_error(error: anyhow::Error, cancellation_token: Option<&CancellationToken>) -> LlmError {
+    if cancellation_token.is_some_and(|token| token.is_cancelled()) {
+        LlmError::Cancelled
     } else {
-        let chat_response = response
-            .json::<ChatResponse>()
-            .await
-            .map_err(|e| format!("Failed to parse LLM response: {}", e))?;
-
-        info!("🐞 LLM Response received from {}", provider_name(provider));
+        LlmError::Other(error.to_string())
+    }
+}
 
-        let content = chat_response
-            .choices
-            .get(0)
-            .ok_or("No content in LLM response")?
-            .message
-            .content
-            .trim();
-        Ok(content.to_string())
+/// Maps each HTTP provider to the wire format of its streaming responses
+fn stream_format(provider: &LLMProvider) -> StreamFormat {
+    match provider {
+        LLMProvider::Claude => StreamFormat::ClaudeSse,
+        LLMProvider::Ollama => StreamFormat::OllamaNdjson,
+        LLMProvider::Gemini => StreamFormat::GeminiSse,
+        _ => StreamFormat::OpenAISse,
     }
 }
 
@@ -342,5 +906,6 @@ fn provider_name(provider: &LLMProvider) -> &str {
         LLMProvider::BuiltInAI => "Built-in AI",
         LLMProvider::OpenRouter => "OpenRouter",
         LLMProvider::CustomOpenAI => "Custom OpenAI",
+        LLMProvider::Gemini => "Gemini",
     }
 }
@@ -0,0 +1,225 @@
+// Classified errors from LLM provider requests
+// Callers use the class to decide whether to retry the same provider, fall
+// back to the next one in the chain, or stop (cancellation)
+
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Why an LLM request failed
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum LlmError {
+    /// Missing, invalid or unauthorized API key (HTTP 401/403, or Gemini's 400)
+    #[error("Authentication failed: {0}")]
+    Auth(String),
+    /// Too many requests (HTTP 429); `retry_after` is the wait the provider asked for
+    #[error("Rate limited: {message}")]
+    RateLimited {
+        message: String,
+        retry_after: Option<Duration>,
+    },
+    /// The prompt doesn't fit in the model's context window
+    #[error("Context window exceeded: {0}")]
+    ContextOverflow(String),
+    /// The provider couldn't be reached, or the request timed out
+    #[error("Network error: {0}")]
+    Network(String),
+    /// The provider failed or is overloaded (HTTP 408, 5xx)
+    #[error("Provider error (HTTP {status}): {message}")]
+    Server { status: u16, message: String },
+    #[error("Summary generation was cancelled")]
+    Cancelled,
+    /// Anything else: rejected requests, unparseable responses, sidecar failures
+    #[error("{0}")]
+    Other(String),
+}
+
+impl LlmError {
+    /// Classifies a non-success HTTP response from its status, `Retry-After`
+    /// header and body
+    pub fn from_response(status: u16, retry_after: Option<&str>, body: &str) -> Self {
+        let message = error_message(body).unwrap_or_else(|| format!("HTTP {}", status));
+
+        // Providers report overflow as 400, 413 or 422 depending on the API
+        if status == 413 || is_context_overflow(&message) {
+            return Self::ContextOverflow(message);
+        }
+
+        // Gemini rejects bad keys with 400 INVALID_ARGUMENT rather than 401
+        if status == 400 && message.to_lowercase().contains("api key not valid") {
+            return Self::Auth(message);
+        }
+
+        match status {
+            401 | 403 => Self::Auth(message),
+            429 => Self::RateLimited {
+                message,
+                retry_after: retry_after.and_then(|value| parse_retry_after(value, Utc::now())),
+            },
+            408 | 500..=599 => Self::Server { status, message },
+            _ => Self::Other(format!("LLM API request failed: {}", message)),
+        }
+    }
+
+    /// Classifies an error event received in the middle of a stream
+    pub fn from_stream_error(message: &str) -> Self {
+        if is_context_overflow(message) {
+            Self::ContextOverflow(message.to_string())
+        } else {
+            Self::Other(format!("LLM API stream failed: {}", message))
+        }
+    }
+
+    /// Short machine-readable name of the class, as stored in process metadata
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Auth(_) => "auth",
+            Self::RateLimited { .. } => "rate_limit",
+            Self::ContextOverflow(_) => "context_overflow",
+            Self::Network(_) => "network",
+            Self::Server { .. } => "server",
+            Self::Cancelled => "cancelled",
+            Self::Other(_) => "other",
+        }
+    }
+
+    /// Whether the same request may succeed if sent again later
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::RateLimited { .. } | Self::Network(_) | Self::Server { .. }
+        )
+    }
+
+    /// Wait requested by the provider before retrying, if any
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+impl From<LlmError> for String {
+    fn from(error: LlmError) -> Self {
+        error.to_string()
+    }
+}
+
+/// Human-readable message of an error body
+///
+/// Understands `{"error": {"message": ...}}` (OpenAI, Claude, Groq and most
+/// compatible servers) and `{"error": "..."}` (Ollama); anything else is
+/// returned as-is.
+fn error_message(body: &str) -> Option<String> {
+    let body = body.trim();
+    if body.is_empty() {
+        return None;
+    }
+
+    let parsed = serde_json::from_str::<serde_json::Value>(body).ok();
+    let message = parsed.as_ref().and_then(|value| {
+        let error = value.get("error")?;
+        error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .or_else(|| error.as_str())
+            .map(str::to_string)
+    });
+    Some(message.unwrap_or_else(|| body.to_string()))
+}
+
+fn is_context_overflow(message: &str) -> bool {
+    const MARKERS: &[&str] = &[
+        "context_length_exceeded",
+        "context length",
+        "context window",
+        "maximum context",
+        "maximum number of tokens",
+        "prompt is too long",
+        "too many tokens",
+        "exceeds the available context",
+    ];
+    let message = message.to_lowercase();
+    MARKERS.iter().any(|marker| message.contains(marker))
+}
+
+/// Parses a `Retry-After` value: delay in seconds or an HTTP date
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<f64>() {
+        return (seconds.is_finite() && seconds >= 0.0).then(|| Duration::from_secs_f64(seconds));
+    }
+
+    let at = DateTime::parse_from_rfc2822(value)
+        .ok()?
+        .with_timezone(&Utc);
+    Some((at - now).to_std().unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_response_classifies_status_and_body() {
+        let body = r#"{"error": {"message": "Incorrect API key provided", "type": "invalid_request_error"}}"#;
+        assert_eq!(
+            LlmError::from_response(401, None, body),
+            LlmError::Auth("Incorrect API key provided".to_string())
+        );
+
+        let rate_limited = LlmError::from_response(429, Some("12"), "");
+        assert_eq!(rate_limited.kind(), "rate_limit");
+        assert_eq!(rate_limited.retry_after(), Some(Duration::from_secs(12)));
+        assert!(rate_limited.is_retryable());
+
+        let overflow = LlmError::from_response(
+            400,
+            None,
+            r#"{"error": {"message": "This model's maximum context length is 8192 tokens"}}"#,
+        );
+        assert_eq!(overflow.kind(), "context_overflow");
+        assert!(!overflow.is_retryable());
+        let gemini_overflow = r#"{"error": {"code": 400, "message": "The input token count (1200000) exceeds the maximum number of tokens allowed (1048576).", "status": "INVALID_ARGUMENT"}}"#;
+        assert_eq!(
+            LlmError::from_response(400, None, gemini_overflow).kind(),
+            "context_overflow"
+        );
+
+        // Ollama reports errors as a bare string
+        assert_eq!(
+            LlmError::from_response(503, None, r#"{"error": "server busy"}"#),
+            LlmError::Server {
+                status: 503,
+                message: "server busy".to_string()
+            }
+        );
+        assert_eq!(
+            LlmError::from_response(404, None, "model not found").to_string(),
+            "LLM API request failed: model not found"
+        );
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(parse_retry_after("30", now), Some(Duration::from_secs(30)));
+        assert_eq!(
+            parse_retry_after("1.5", now),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:45 GMT", now),
+            Some(Duration::from_secs(45))
+        );
+        // Dates in the past mean "retry now"
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+        assert_eq!(parse_retry_after("-5", now), None);
+    }
+}
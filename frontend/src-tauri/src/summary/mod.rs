@@ -1,10 +1,15 @@
 /// Summary module - handles all meeting summary generation functionality
 ///
 /// This module contains:
-/// - LLM client for communicating with various AI providers (OpenAI, Claude, Groq, Ollama, OpenRouter, CustomOpenAI)
+/// - LLM client for communicating with various AI providers (OpenAI, Claude, Groq, Ollama, OpenRouter, CustomOpenAI, Gemini)
 /// - Processor for chunking transcripts and generating summaries
 /// - Service layer for orchestrating summary generation
+/// - Classified provider errors, per-provider retry and an ordered fallback chain
+/// - Stream parsers for incremental output from each provider
+/// - Structured (JSON) summaries validated against the template schema
+/// - Tokenizers for model-accurate token counting and chunking
 /// - Templates for structured meeting summary generation
+/// - Version history of generated and edited summaries, with section-aware diffs
 /// - Tauri commands for frontend integration
 
 use serde::{Deserialize, Serialize};
@@ -31,12 +36,22 @@ pub struct CustomOpenAIConfig {
 }
 
 pub mod commands;
+pub mod fallback;
+pub mod fallback_commands;
+pub mod gemini;
+pub mod gemini_commands;
 pub mod llm_client;
+pub mod llm_error;
 pub mod processor;
 pub mod service;
+pub mod streaming;
+pub mod structured;
 pub mod summary_engine;
 pub mod template_commands;
 pub mod templates;
+pub mod tokenizer;
+pub mod version_commands;
+pub mod versions;
 
 // Re-export Tauri commands (with their generated __cmd__ variants)
 pub use commands::{
@@ -51,10 +66,30 @@ pub use template_commands::{
     api_get_template_details, api_list_templates, api_validate_template,
 };
 
+// Re-export fallback chain commands
+pub use fallback_commands::{
+    __cmd__api_get_summary_fallback_chain, __cmd__api_save_summary_fallback_chain,
+    api_get_summary_fallback_chain, api_save_summary_fallback_chain,
+};
+
+// Re-export Gemini commands
+pub use gemini_commands::{__cmd__api_get_gemini_models, api_get_gemini_models};
+
+// Re-export summary version commands
+pub use version_commands::{
+    __cmd__api_diff_summary_versions, __cmd__api_list_summary_versions,
+    __cmd__api_restore_summary_version, api_diff_summary_versions, api_list_summary_versions,
+    api_restore_summary_version,
+};
+
 // Re-export commonly used items
-pub use llm_client::LLMProvider;
+pub use fallback::{FallbackTarget, RetryPolicy};
+pub use llm_client::{LLMProvider, StreamCallback};
+pub use llm_error::LlmError;
 pub use processor::{
-    chunk_text, clean_llm_markdown_output, extract_meeting_name_from_markdown,
-    generate_meeting_summary, rough_token_count,
+    chunk_text, chunk_text_with_tokenizer, clean_llm_markdown_output, extract_meeting_name_from_markdown,
+    generate_meeting_summary, rough_token_count, ChunkOutcome, ChunkProcessingConfig,
+    ChunkStatus, MeetingSummaryOutput, SummaryOutputFormat,
 };
-pub use service::SummaryService;
+pub use structured::StructuredSummary;
+pub use service::{ProviderConnection, SummaryService};
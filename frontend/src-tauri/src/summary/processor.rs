@@ -1,21 +1,429 @@
-use crate::summary::llm_client::{generate_summary, LLMProvider};
+use crate::summary::fallback::RetryPolicy;
+use crate::summary::llm_client::{
+    generate_summary, generate_summary_json, generate_summary_stream, LLMProvider, StreamCallback,
+};
+use crate::summary::llm_error::LlmError;
+use crate::summary::structured::{self, StructuredSummary};
 use crate::summary::templates;
+use crate::summary::tokenizer::{
+    ensure_tiktoken_table, resolve_tokenizer, HeuristicTokenizer, Tokenizer,
+};
+use futures_util::StreamExt;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tokio_util::sync::CancellationToken;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 // Compile regex once and reuse (significant performance improvement for repeated calls)
 static THINKING_TAG_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?s)<think(?:ing)?>.*?</think(?:ing)?>").unwrap()
 });
 
+const CHUNK_SYSTEM_PROMPT: &str = "You are an expert meeting summarizer.";
+const CHUNK_USER_PROMPT_TEMPLATE: &str = "Provide a concise but comprehensive summary of the following transcript chunk. Capture all key points, decisions, action items, and mentioned individuals.\n\n<transcript_chunk>\n{}\n</transcript_chunk>";
+const COMBINE_SYSTEM_PROMPT: &str = "You are an expert at synthesizing meeting summaries.";
+const COMBINE_USER_PROMPT_TEMPLATE: &str = "The following are consecutive summaries of a meeting. Combine them into a single, coherent, and detailed narrative summary that retains all important details, organized logically.\n\n<summaries>\n{}\n</summaries>";
+const SUMMARY_SEPARATOR: &str = "\n---\n";
+/// Attempts per invalid section when re-requesting structured output
+const STRUCTURED_SECTION_RETRIES: u32 = 2;
+/// Added to final-report system prompts when the user's own notes are included
+const USER_NOTES_INSTRUCTION: &str = "\n**USER NOTES:**\n`<user_notes>` holds notes the user took during the meeting. They are part of the source text and take priority: make sure every point in them is reflected in the report, and prefer their wording for names, decisions and action items. Times like [12:34] are positions in the recording.\n";
+
+/// Result of a full meeting summary run
+#[derive(Debug, Clone)]
+pub struct MeetingSummaryOutput {
+    /// Final cleaned markdown report
+    pub markdown: String,
+    /// Number of transcript chunks summarized successfully (1 for single-pass)
+    pub chunk_count: i64,
+    /// Outcome of every chunk/group request made during map-reduce
+    pub chunk_outcomes: Vec<ChunkOutcome>,
+    /// Typed report the markdown was rendered from (structured format only)
+    pub structured: Option<StructuredSummary>,
+}
+
+/// How the final report is requested from the model
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SummaryOutputFormat {
+    /// Free-form markdown filled in from the template (streamed when possible)
+    #[default]
+    Markdown,
+    /// JSON validated against a schema derived from the template; markdown is
+    /// rendered from it deterministically
+    Structured,
+}
+
+/// Final state of a single chunk summarization request
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChunkStatus {
+    /// Succeeded on the first attempt
+    Ok,
+    /// Succeeded after one or more retries
+    Retried,
+    /// Gave up after exhausting all retries
+    Failed,
+}
+
+/// Per-chunk record stored in `SummaryProcess.metadata`
+#[derive(Debug, Clone, Serialize)]
+pub struct ChunkOutcome {
+    /// Reduction level (0 = transcript chunks, 1+ = combined summaries)
+    pub level: usize,
+    /// Position of the chunk within its level (transcript order)
+    pub index: usize,
+    pub status: ChunkStatus,
+    pub attempts: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Concurrency and retry settings for summarization requests
+#[derive(Debug, Clone)]
+pub struct ChunkProcessingConfig {
+    /// Maximum number of chunk requests in flight at once
+    pub max_concurrency: usize,
+    /// Retries of each request on transient errors
+    pub retry: RetryPolicy,
+}
+
+impl ChunkProcessingConfig {
+    /// Default settings for a provider, overridable through
+    /// `MEETILY_SUMMARY_CONCURRENCY_<PROVIDER>` (e.g. `MEETILY_SUMMARY_CONCURRENCY_OLLAMA=4`)
+    pub fn for_provider(provider: &LLMProvider) -> Self {
+        let (env_suffix, default_concurrency) = match provider {
+            // The sidecar serves one request at a time
+            LLMProvider::BuiltInAI => ("BUILTIN_AI", 1),
+            // Matches Ollama's default OLLAMA_NUM_PARALLEL
+            LLMProvider::Ollama => ("OLLAMA", 2),
+            LLMProvider::CustomOpenAI => ("CUSTOM_OPENAI", 2),
+            LLMProvider::OpenAI => ("OPENAI", 4),
+            LLMProvider::Claude => ("CLAUDE", 4),
+            LLMProvider::Groq => ("GROQ", 4),
+            LLMProvider::OpenRouter => ("OPENROUTER", 4),
+            LLMProvider::Gemini => ("GEMINI", 4),
+        };
+
+        let max_concurrency = std::env::var(format!("MEETILY_SUMMARY_CONCURRENCY_{}", env_suffix))
+            .ok()
+            .and_then(|s| s.parse::<usize>().ok())
+            .unwrap_or(default_concurrency)
+            .max(1);
+
+        Self {
+            max_concurrency,
+            retry: RetryPolicy::default(),
+        }
+    }
+}
+
+/// Bundles the provider settings needed for every chunk request
+struct ChunkRequester<'a> {
+    client: &'a Client,
+    provider: &'a LLMProvider,
+    model_name: &'a str,
+    api_key: &'a str,
+    ollama_endpoint: Option<&'a str>,
+    custom_openai_endpoint: Option<&'a str>,
+    max_tokens: Option<u32>,
+    temperature: Option<f32>,
+    top_p: Option<f32>,
+    app_data_dir: Option<&'a PathBuf>,
+    cancellation_token: Option<&'a CancellationToken>,
+    config: ChunkProcessingConfig,
+}
+
+impl ChunkRequester<'_> {
+    fn is_cancelled(&self) -> bool {
+        self.cancellation_token
+            .map(|token| token.is_cancelled())
+            .unwrap_or(false)
+    }
+
+    async fn request(&self, system_prompt: &str, user_prompt: &str) -> Result<String, LlmError> {
+        generate_summary(
+            self.client,
+            self.provider,
+            self.model_name,
+            self.api_key,
+            system_prompt,
+            user_prompt,
+            self.ollama_endpoint,
+            self.custom_openai_endpoint,
+            self.max_tokens,
+            self.temperature,
+            self.top_p,
+            self.app_data_dir,
+            self.cancellation_token,
+        )
+        .await
+    }
+
+    /// Runs one request, retrying transient failures per the retry policy while
+    /// `can_retry` allows it
+    ///
+    /// Waits at least as long as the provider's `Retry-After`, and gives up when
+    /// that is too long so the caller can fall back to another provider.
+    /// Returns the number of attempts made along with the result.
+    async fn with_retry<T, F, Fut>(
+        &self,
+        what: &str,
+        can_retry: impl Fn() -> bool,
+        mut request: F,
+    ) -> (u32, Result<T, LlmError>)
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, LlmError>>,
+    {
+        let mut attempts = 0;
+        loop {
+            if self.is_cancelled() {
+                return (attempts, Err(LlmError::Cancelled));
+            }
+
+            attempts += 1;
+            let error = match request().await {
+                Ok(value) => return (attempts, Ok(value)),
+                Err(e) => e,
+            };
+            let delay = match self.config.retry.next_delay(attempts, &error) {
+                Some(delay) if can_retry() => delay,
+                _ => return (attempts, Err(error)),
+            };
+            warn!(
+                "{} failed (attempt {}): {}. Retrying in {:?}",
+                what, attempts, error, delay
+            );
+            if let Some(token) = self.cancellation_token {
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    _ = token.cancelled() => return (attempts, Err(LlmError::Cancelled)),
+                }
+            } else {
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+
+    async fn generate(&self, system_prompt: &str, user_prompt: &str) -> Result<String, LlmError> {
+        self.with_retry(
+            "Summary request",
+            || true,
+            || self.request(system_prompt, user_prompt),
+        )
+        .await
+        .1
+    }
+
+    async fn generate_json(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        schema: &serde_json::Value,
+    ) -> Result<String, LlmError> {
+        let request = || {
+            generate_summary_json(
+                self.client,
+                self.provider,
+                self.model_name,
+                self.api_key,
+                system_prompt,
+                user_prompt,
+                self.ollama_endpoint,
+                self.custom_openai_endpoint,
+                self.max_tokens,
+                self.temperature,
+                self.top_p,
+                self.app_data_dir,
+                self.cancellation_token,
+                schema,
+            )
+        };
+        self.with_retry("Structured report request", || true, request)
+            .await
+            .1
+    }
+
+    /// Streams a completion to `on_delta`. Text that has been forwarded can't
+    /// be taken back, so only failures before the first delta are retried
+    async fn generate_stream(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        on_delta: &StreamCallback<'_>,
+    ) -> Result<String, LlmError> {
+        let streamed = AtomicBool::new(false);
+        let forward = |delta: &str| {
+            streamed.store(true, Ordering::Relaxed);
+            on_delta(delta);
+        };
+        let request = || {
+            generate_summary_stream(
+                self.client,
+                self.provider,
+                self.model_name,
+                self.api_key,
+                system_prompt,
+                user_prompt,
+                self.ollama_endpoint,
+                self.custom_openai_endpoint,
+                self.max_tokens,
+                self.temperature,
+                self.top_p,
+                self.app_data_dir,
+                self.cancellation_token,
+                &forward,
+            )
+        };
+        self.with_retry(
+            "Streamed summary request",
+            || !streamed.load(Ordering::Relaxed),
+            request,
+        )
+        .await
+        .1
+    }
+
+    /// Summarizes one chunk, retrying transient failures
+    ///
+    /// Cancellation and authentication errors abort the whole run, since every
+    /// other chunk would fail the same way. Any other failure skips the chunk
+    /// and is returned alongside its outcome.
+    async fn summarize_chunk(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        level: usize,
+        index: usize,
+    ) -> Result<(Result<String, LlmError>, ChunkOutcome), LlmError> {
+        let what = format!("Chunk {} (level {})", index + 1, level);
+        let (attempts, result) = self
+            .with_retry(&what, || true, || self.request(system_prompt, user_prompt))
+            .await;
+
+        match result {
+            Ok(summary) => {
+                let status = if attempts == 1 {
+                    ChunkStatus::Ok
+                } else {
+                    ChunkStatus::Retried
+                };
+                info!("✓ Chunk {} (level {}) processed successfully", index + 1, level);
+                Ok((
+                    Ok(summary),
+                    ChunkOutcome {
+                        level,
+                        index,
+                        status,
+                        attempts,
+                        error: None,
+                    },
+                ))
+            }
+            Err(e @ (LlmError::Cancelled | LlmError::Auth(_))) => Err(e),
+            Err(e) => {
+                error!(
+                    "Failed processing chunk {} (level {}) after {} attempts: {}",
+                    index + 1,
+                    level,
+                    attempts,
+                    e
+                );
+                let outcome = ChunkOutcome {
+                    level,
+                    index,
+                    status: ChunkStatus::Failed,
+                    attempts,
+                    error: Some(e.to_string()),
+                };
+                Ok((Err(e), outcome))
+            }
+        }
+    }
+
+    /// Summarizes all prompts concurrently (bounded by `max_concurrency`)
+    ///
+    /// Results come back in the same order as `user_prompts`, so transcript
+    /// order is preserved. Chunks that still fail after retries are skipped
+    /// and recorded in `outcomes`; if every chunk fails, the last chunk's error
+    /// is returned so its class (rate limit, network, ...) reaches the caller.
+    async fn summarize_all(
+        &self,
+        system_prompt: &str,
+        user_prompts: &[String],
+        level: usize,
+        outcomes: &mut Vec<ChunkOutcome>,
+    ) -> Result<Vec<String>, LlmError> {
+        let total = user_prompts.len();
+        let mut results = futures_util::stream::iter(user_prompts.iter().enumerate())
+            .map(|(index, user_prompt)| {
+                info!("Processing chunk {}/{} (level {})", index + 1, total, level);
+                self.summarize_chunk(system_prompt, user_prompt, level, index)
+            })
+            .buffered(self.config.max_concurrency);
+
+        let mut summaries = Vec::with_capacity(total);
+        let mut last_error = None;
+        while let Some(result) = results.next().await {
+            let (summary, outcome) = result?;
+            outcomes.push(outcome);
+            match summary {
+                Ok(summary) => summaries.push(summary),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        match last_error {
+            Some(error) if summaries.is_empty() => {
+                error!("All {} requests at level {} failed", total, level);
+                Err(error)
+            }
+            _ => Ok(summaries),
+        }
+    }
+}
+
+/// Packs consecutive summaries into groups that each fit within `budget_tokens`
+///
+/// Order is preserved; a summary larger than the budget gets a group of its own.
+fn group_summaries_by_budget(
+    summaries: &[String],
+    budget_tokens: usize,
+    tokenizer: &dyn Tokenizer,
+) -> Vec<String> {
+    let mut groups = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut current_tokens = 0;
+    let separator_tokens = tokenizer.count_tokens(SUMMARY_SEPARATOR);
+
+    for summary in summaries {
+        let tokens = tokenizer.count_tokens(summary) + separator_tokens;
+        if !current.is_empty() && current_tokens + tokens > budget_tokens {
+            groups.push(current.join(SUMMARY_SEPARATOR));
+            current.clear();
+            current_tokens = 0;
+        }
+        current.push(summary);
+        current_tokens += tokens;
+    }
+
+    if !current.is_empty() {
+        groups.push(current.join(SUMMARY_SEPARATOR));
+    }
+    groups
+}
+
 /// Rough token count estimation using character count
+///
+/// Used when no real tokenizer is available for the model (see `tokenizer::resolve_tokenizer`)
 pub fn rough_token_count(s: &str) -> usize {
-    let char_count = s.chars().count();
-    (char_count as f64 * 0.35).ceil() as usize
+    HeuristicTokenizer.count_tokens(s)
 }
 
 /// Chunks text into overlapping segments based on token count
@@ -92,6 +500,100 @@ pub fn chunk_text(text: &str, chunk_size_tokens: usize, overlap_tokens: usize) -
     chunks
 }
 
+/// Chunks text into overlapping segments measured with a real tokenizer
+///
+/// Chunk ends snap to token boundaries, preferring a sentence or word boundary
+/// in the back half of the window so chunks don't cut words in two.
+///
+/// # Arguments
+/// * `text` - The text to chunk
+/// * `chunk_size_tokens` - Maximum tokens per chunk
+/// * `overlap_tokens` - Number of overlapping tokens between chunks
+/// * `tokenizer` - Tokenizer matching the target model
+///
+/// # Returns
+/// Vector of text chunks, each at most `chunk_size_tokens` tokens long
+pub fn chunk_text_with_tokenizer(
+    text: &str,
+    chunk_size_tokens: usize,
+    overlap_tokens: usize,
+    tokenizer: &dyn Tokenizer,
+) -> Vec<String> {
+    info!(
+        "Chunking text with {} tokenizer, chunk_size: {} and overlap: {}",
+        tokenizer.name(),
+        chunk_size_tokens,
+        overlap_tokens
+    );
+
+    if text.is_empty() || chunk_size_tokens == 0 {
+        return vec![];
+    }
+
+    let spans = tokenizer.token_spans(text);
+    let total_tokens = spans.len();
+    if total_tokens <= chunk_size_tokens {
+        info!("Text is shorter than chunk size, returning as a single chunk.");
+        return vec![text.to_string()];
+    }
+
+    // Byte-level BPE tokens can end inside a multibyte character, so chunk
+    // edges are widened to whole characters before slicing
+    let char_range = |start_tok: usize, end_tok: usize| {
+        let mut start = spans[start_tok].start;
+        while !text.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut end = spans[end_tok - 1].end;
+        while !text.is_char_boundary(end) {
+            end += 1;
+        }
+        start..end
+    };
+
+    let mut chunks = Vec::new();
+    let mut start_tok = 0;
+
+    while start_tok < total_tokens {
+        let mut end_tok = (start_tok + chunk_size_tokens).min(total_tokens);
+
+        // Try to break at sentence or word boundary for cleaner chunks
+        if end_tok < total_tokens {
+            let range = char_range(start_tok, end_tok);
+            let window = &text[range.clone()];
+            let min_end = start_tok + chunk_size_tokens / 2;
+            let boundary = window
+                .rfind(". ")
+                .map(|pos| pos + 2)
+                .or_else(|| window.rfind(' ').map(|pos| pos + 1))
+                .map(|pos| range.start + pos);
+
+            if let Some(boundary) = boundary {
+                // Last token that ends at or before the boundary (BPE tokens often
+                // carry the leading space, so the boundary may fall inside one)
+                let boundary_tok =
+                    start_tok + spans[start_tok..end_tok].partition_point(|s| s.end <= boundary);
+                if boundary_tok > min_end {
+                    end_tok = boundary_tok;
+                }
+            }
+        }
+
+        let range = char_range(start_tok, end_tok);
+        chunks.push(text[range.clone()].to_string());
+
+        if end_tok >= total_tokens || range.end == text.len() {
+            break;
+        }
+
+        // Move to next chunk with overlap, always making progress
+        start_tok = end_tok.saturating_sub(overlap_tokens).max(start_tok + 1);
+    }
+
+    info!("Created {} chunks from text", chunks.len());
+    chunks
+}
+
 /// Cleans markdown output from LLM by removing thinking tags and code fences
 ///
 /// # Arguments
@@ -144,7 +646,9 @@ pub fn extract_meeting_name_from_markdown(markdown: &str) -> Option<String> {
 /// * `api_key` - API key for the provider
 /// * `text` - Full transcript text to summarize
 /// * `custom_prompt` - Optional user-provided context
+/// * `user_notes` - Optional notes the user took, given priority next to the transcript
 /// * `template_id` - Template identifier (e.g., "daily_standup", "standard_meeting")
+/// * `output_format` - Whether the final report is free markdown or schema-validated JSON
 /// * `token_threshold` - Token limit for single-pass processing (default 4000)
 /// * `ollama_endpoint` - Optional custom Ollama endpoint
 /// * `custom_openai_endpoint` - Optional custom OpenAI-compatible endpoint
@@ -153,9 +657,11 @@ pub fn extract_meeting_name_from_markdown(markdown: &str) -> Option<String> {
 /// * `top_p` - Optional top_p (CustomOpenAI provider)
 /// * `app_data_dir` - Optional app data directory (BuiltInAI provider)
 /// * `cancellation_token` - Optional cancellation token to stop processing
+/// * `on_delta` - Optional callback that receives the final report as it streams in (markdown format only)
 ///
 /// # Returns
-/// Tuple of (final_summary_markdown, number_of_chunks_processed)
+/// The final markdown together with per-chunk processing outcomes, or the
+/// classified error that stopped the run
 pub async fn generate_meeting_summary(
     client: &Client,
     provider: &LLMProvider,
@@ -163,7 +669,9 @@ pub async fn generate_meeting_summary(
     api_key: &str,
     text: &str,
     custom_prompt: &str,
+    user_notes: Option<&str>,
     template_id: &str,
+    output_format: SummaryOutputFormat,
     token_threshold: usize,
     ollama_endpoint: Option<&str>,
     custom_openai_endpoint: Option<&str>,
@@ -172,11 +680,12 @@ pub async fn generate_meeting_summary(
     top_p: Option<f32>,
     app_data_dir: Option<&PathBuf>,
     cancellation_token: Option<&CancellationToken>,
-) -> Result<(String, i64), String> {
+    on_delta: Option<&StreamCallback<'_>>,
+) -> Result<MeetingSummaryOutput, LlmError> {
     // Check cancellation at the start
     if let Some(token) = cancellation_token {
         if token.is_cancelled() {
-            return Err("Summary generation was cancelled".to_string());
+            return Err(LlmError::Cancelled);
         }
     }
     info!(
@@ -184,11 +693,35 @@ pub async fn generate_meeting_summary(
         provider, model_name
     );
 
-    let total_tokens = rough_token_count(text);
-    info!("Transcript length: {} tokens", total_tokens);
+    if let Err(e) = ensure_tiktoken_table(client, provider, model_name, app_data_dir).await {
+        warn!("Could not fetch tokenizer table for {}: {}", model_name, e);
+    }
+    let tokenizer = resolve_tokenizer(provider, model_name, app_data_dir);
+    let total_tokens = tokenizer.count_tokens(text);
+    info!(
+        "Transcript length: {} tokens ({} tokenizer)",
+        total_tokens,
+        tokenizer.name()
+    );
 
     let content_to_summarize: String;
     let successful_chunk_count: i64;
+    let mut chunk_outcomes: Vec<ChunkOutcome> = Vec::new();
+
+    let requester = ChunkRequester {
+        client,
+        provider,
+        model_name,
+        api_key,
+        ollama_endpoint,
+        custom_openai_endpoint,
+        max_tokens,
+        temperature,
+        top_p,
+        app_data_dir,
+        cancellation_token,
+        config: ChunkProcessingConfig::for_provider(provider),
+    };
 
     // Strategy: Use single-pass for cloud providers or short transcripts
     // Use multi-level chunking for Ollama/BuiltInAI with long transcripts
@@ -206,108 +739,126 @@ pub async fn generate_meeting_summary(
             total_tokens, token_threshold
         );
 
+        info!(
+            "Chunk processing: concurrency {}, max attempts per request {}",
+            requester.config.max_concurrency, requester.config.retry.max_attempts
+        );
+
         // Reserve 300 tokens for prompt overhead
-        let chunks = chunk_text(text, token_threshold - 300, 100);
+        let chunks = chunk_text_with_tokenizer(text, token_threshold - 300, 100, tokenizer.as_ref());
         let num_chunks = chunks.len();
         info!("Split transcript into {} chunks", num_chunks);
 
-        let mut chunk_summaries = Vec::new();
-        let system_prompt_chunk = "You are an expert meeting summarizer.";
-        let user_prompt_template_chunk = "Provide a concise but comprehensive summary of the following transcript chunk. Capture all key points, decisions, action items, and mentioned individuals.\n\n<transcript_chunk>\n{}\n</transcript_chunk>";
-
-        for (i, chunk) in chunks.iter().enumerate() {
-            // Check for cancellation before processing each chunk
-            if let Some(token) = cancellation_token {
-                if token.is_cancelled() {
-                    info!("Summary generation cancelled during chunk {}/{}", i + 1, num_chunks);
-                    return Err("Summary generation was cancelled".to_string());
-                }
-            }
-
-            info!("Processing chunk {}/{}", i + 1, num_chunks);
-            let user_prompt_chunk = user_prompt_template_chunk.replace("{}", chunk.as_str());
-
-            match generate_summary(
-                client,
-                provider,
-                model_name,
-                api_key,
-                system_prompt_chunk,
-                &user_prompt_chunk,
-                ollama_endpoint,
-                custom_openai_endpoint,
-                max_tokens,
-                temperature,
-                top_p,
-                app_data_dir,
-                cancellation_token,
-            )
-            .await
-            {
-                Ok(summary) => {
-                    chunk_summaries.push(summary);
-                    info!("✓ Chunk {}/{} processed successfully", i + 1, num_chunks);
-                }
-                Err(e) => {
-                    // Check if error is due to cancellation
-                    if e.contains("cancelled") {
-                        return Err(e);
-                    }
-                    error!("Failed processing chunk {}/{}: {}", i + 1, num_chunks, e);
-                }
-            }
-        }
+        // Map: summarize every transcript chunk (level 0)
+        let chunk_prompts: Vec<String> = chunks
+            .iter()
+            .map(|chunk| CHUNK_USER_PROMPT_TEMPLATE.replace("{}", chunk))
+            .collect();
+        let mut summaries = requester
+            .summarize_all(CHUNK_SYSTEM_PROMPT, &chunk_prompts, 0, &mut chunk_outcomes)
+            .await?;
 
-        if chunk_summaries.is_empty() {
-            return Err(
-                "Multi-level summarization failed: No chunks were processed successfully."
-                    .to_string(),
-            );
+        if summaries.is_empty() {
+            return Err(LlmError::Other(
+                "Multi-level summarization failed: the transcript produced no chunks".to_string(),
+            ));
         }
 
-        successful_chunk_count = chunk_summaries.len() as i64;
+        successful_chunk_count = summaries.len() as i64;
         info!(
             "Successfully processed {} out of {} chunks",
             successful_chunk_count, num_chunks
         );
 
+        // Reduce: combine neighbouring summaries until they fit in one request
+        let mut level = 1;
+        while summaries.len() > 1
+            && tokenizer.count_tokens(&summaries.join(SUMMARY_SEPARATOR)) > token_threshold
+        {
+            let groups = group_summaries_by_budget(
+                &summaries,
+                token_threshold.saturating_sub(300),
+                tokenizer.as_ref(),
+            );
+            if groups.len() >= summaries.len() {
+                // Every summary is already at the budget on its own; stop reducing
+                warn!(
+                    "Cannot reduce {} summaries further at level {}, combining as-is",
+                    summaries.len(),
+                    level
+                );
+                break;
+            }
+
+            info!(
+                "Reducing {} summaries into {} groups (level {})",
+                summaries.len(),
+                groups.len(),
+                level
+            );
+            let group_prompts: Vec<String> = groups
+                .iter()
+                .map(|group| COMBINE_USER_PROMPT_TEMPLATE.replace("{}", group))
+                .collect();
+            let reduced = requester
+                .summarize_all(COMBINE_SYSTEM_PROMPT, &group_prompts, level, &mut chunk_outcomes)
+                .await?;
+
+            summaries = reduced;
+            level += 1;
+        }
+
         // Combine chunk summaries if multiple chunks
-        content_to_summarize = if chunk_summaries.len() > 1 {
+        content_to_summarize = if summaries.len() > 1 {
             info!(
                 "Combining {} chunk summaries into cohesive summary",
-                chunk_summaries.len()
+                summaries.len()
             );
-            let combined_text = chunk_summaries.join("\n---\n");
-            let system_prompt_combine = "You are an expert at synthesizing meeting summaries.";
-            let user_prompt_combine_template = "The following are consecutive summaries of a meeting. Combine them into a single, coherent, and detailed narrative summary that retains all important details, organized logically.\n\n<summaries>\n{}\n</summaries>";
-
-            let user_prompt_combine = user_prompt_combine_template.replace("{}", &combined_text);
-            generate_summary(
-                client,
-                provider,
-                model_name,
-                api_key,
-                system_prompt_combine,
-                &user_prompt_combine,
-                ollama_endpoint,
-                custom_openai_endpoint,
-                max_tokens,
-                temperature,
-                top_p,
-                app_data_dir,
-                cancellation_token,
-            )
-            .await?
+            let combined_text = summaries.join(SUMMARY_SEPARATOR);
+            let user_prompt_combine = COMBINE_USER_PROMPT_TEMPLATE.replace("{}", &combined_text);
+            requester
+                .generate(COMBINE_SYSTEM_PROMPT, &user_prompt_combine)
+                .await?
         } else {
-            chunk_summaries.remove(0)
+            summaries.remove(0)
         };
     }
 
-    info!("Generating final markdown report with template: {}", template_id);
-
     // Load the template using the provided template_id
     let template = templates::get_template(template_id)
-        .map_err(|e| format!("Failed to load template '{}': {}", template_id, e))?;
+        .map_err(|e| {
+            LlmError::Other(format!("Failed to load template '{}': {}", template_id, e))
+        })?;
+
+    // Check cancellation before final summary generation
+    if let Some(token) = cancellation_token {
+        if token.is_cancelled() {
+            info!("Summary generation cancelled before final summary");
+            return Err(LlmError::Cancelled);
+        }
+    }
+
+    if output_format == SummaryOutputFormat::Structured {
+        info!("Generating final structured report with template: {}", template_id);
+        let structured = generate_structured_report(
+            &requester,
+            &template,
+            &content_to_summarize,
+            custom_prompt,
+            user_notes,
+        )
+        .await?;
+
+        info!("Summary generation completed successfully");
+        return Ok(MeetingSummaryOutput {
+            markdown: structured::render_markdown(&structured),
+            chunk_count: successful_chunk_count,
+            chunk_outcomes,
+            structured: Some(structured),
+        });
+    }
+
+    info!("Generating final markdown report with template: {}", template_id);
 
     // Generate markdown structure and section instructions using template methods
     let clean_template_markdown = template.to_markdown_structure();
@@ -348,35 +899,188 @@ pub async fn generate_meeting_summary(
         final_user_prompt.push_str(custom_prompt);
         final_user_prompt.push_str("\n</user_context>");
     }
+    let (final_system_prompt, final_user_prompt) =
+        with_user_notes((final_system_prompt, final_user_prompt), user_notes);
 
-    // Check cancellation before final summary generation
-    if let Some(token) = cancellation_token {
-        if token.is_cancelled() {
-            info!("Summary generation cancelled before final summary");
-            return Err("Summary generation was cancelled".to_string());
+    // Only the final report is streamed; chunk summaries are intermediate
+    let raw_markdown = match on_delta {
+        Some(on_delta) => {
+            requester
+                .generate_stream(&final_system_prompt, &final_user_prompt, on_delta)
+                .await?
         }
-    }
-
-    let raw_markdown = generate_summary(
-        client,
-        provider,
-        model_name,
-        api_key,
-        &final_system_prompt,
-        &final_user_prompt,
-        ollama_endpoint,
-        custom_openai_endpoint,
-        max_tokens,
-        temperature,
-        top_p,
-        app_data_dir,
-        cancellation_token,
-    )
-    .await?;
+        None => {
+            requester
+                .generate(&final_system_prompt, &final_user_prompt)
+                .await?
+        }
+    };
 
     // Clean the output
     let final_markdown = clean_llm_markdown_output(&raw_markdown);
 
     info!("Summary generation completed successfully");
-    Ok((final_markdown, successful_chunk_count))
+    Ok(MeetingSummaryOutput {
+        markdown: final_markdown,
+        chunk_count: successful_chunk_count,
+        chunk_outcomes,
+        structured: None,
+    })
+}
+
+/// Adds the user's notes to final-report prompts, ahead of the transcript
+fn with_user_notes(prompts: (String, String), user_notes: Option<&str>) -> (String, String) {
+    let (system_prompt, user_prompt) = prompts;
+    match user_notes.map(str::trim).filter(|notes| !notes.is_empty()) {
+        Some(notes) => (
+            format!("{}{}", system_prompt, USER_NOTES_INSTRUCTION),
+            format!("\n<user_notes>\n{}\n</user_notes>\n{}", notes, user_prompt),
+        ),
+        None => (system_prompt, user_prompt),
+    }
+}
+
+/// Requests the final report as JSON and validates it against the template schema
+///
+/// Sections that are missing or can't be repaired are re-requested one at a
+/// time; any still invalid after `STRUCTURED_SECTION_RETRIES` attempts are left
+/// empty and listed in `unresolved_sections`.
+async fn generate_structured_report(
+    requester: &ChunkRequester<'_>,
+    template: &templates::Template,
+    source: &str,
+    custom_prompt: &str,
+    user_notes: Option<&str>,
+) -> Result<StructuredSummary, LlmError> {
+    let specs = structured::field_specs(template);
+    let schema = structured::report_schema(&specs);
+    let (system_prompt, user_prompt) = with_user_notes(
+        structured::report_prompts(&specs, source, custom_prompt),
+        user_notes,
+    );
+
+    let raw = requester.generate_json(&system_prompt, &user_prompt, &schema).await?;
+    let mut contents = match structured::parse_json_object(&raw) {
+        Some(report) => structured::validate_report(&specs, &report),
+        None => {
+            warn!("Structured report was not valid JSON, requesting sections individually");
+            vec![None; specs.len()]
+        }
+    };
+
+    for (spec, content) in specs.iter().zip(contents.iter_mut()) {
+        if content.is_some() {
+            continue;
+        }
+
+        for attempt in 1..=STRUCTURED_SECTION_RETRIES {
+            if requester.is_cancelled() {
+                return Err(LlmError::Cancelled);
+            }
+
+            info!(
+                "Re-requesting invalid section '{}' (attempt {}/{})",
+                spec.key, attempt, STRUCTURED_SECTION_RETRIES
+            );
+            let (system_prompt, user_prompt) = with_user_notes(
+                structured::field_retry_prompts(spec, source, custom_prompt),
+                user_notes,
+            );
+            match requester
+                .generate_json(&system_prompt, &user_prompt, &spec.retry_schema())
+                .await
+            {
+                Ok(raw) => {
+                    *content = structured::parse_json_object(&raw)
+                        .and_then(|value| value.get("content").and_then(|v| spec.coerce(v)));
+                    if content.is_some() {
+                        break;
+                    }
+                }
+                Err(LlmError::Cancelled) => return Err(LlmError::Cancelled),
+                Err(e) => warn!("Retry for section '{}' failed: {}", spec.key, e),
+            }
+        }
+
+        if content.is_none() {
+            warn!(
+                "Section '{}' is still invalid after {} retries, leaving it empty",
+                spec.key, STRUCTURED_SECTION_RETRIES
+            );
+        }
+    }
+
+    Ok(structured::assemble_summary(&specs, contents))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::tokenizer::BpeTokenizer;
+
+    #[test]
+    fn test_group_summaries_preserves_order() {
+        let summaries: Vec<String> = (0..6).map(|i| format!("summary {} {}", i, "x".repeat(90))).collect();
+        // Each summary is ~35 tokens, so two fit in an 80 token budget
+        let groups = group_summaries_by_budget(&summaries, 80, &HeuristicTokenizer);
+        assert_eq!(groups.len(), 3);
+        assert!(groups[0].starts_with("summary 0"));
+        assert!(groups[0].contains("summary 1"));
+        assert!(groups[2].contains("summary 5"));
+    }
+
+    #[test]
+    fn test_group_summaries_oversized_item_gets_own_group() {
+        let summaries = vec!["a".repeat(1000), "b".to_string(), "c".to_string()];
+        let groups = group_summaries_by_budget(&summaries, 50, &HeuristicTokenizer);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0], "a".repeat(1000));
+        assert_eq!(groups[1], format!("b{}c", SUMMARY_SEPARATOR));
+    }
+
+    #[test]
+    fn test_with_user_notes_prepends_notes_block() {
+        let prompts = ("system".to_string(), "<transcript_chunks>".to_string());
+        let (system, user) = with_user_notes(prompts.clone(), Some("  - [01:05] Ask about QA\n"));
+        assert!(system.starts_with("system") && system.contains("<user_notes>"));
+        assert_eq!(
+            user,
+            "\n<user_notes>\n- [01:05] Ask about QA\n</user_notes>\n<transcript_chunks>"
+        );
+
+        assert_eq!(with_user_notes(prompts.clone(), Some(" \n")), prompts);
+        assert_eq!(with_user_notes(prompts.clone(), None), prompts);
+    }
+
+    #[test]
+    fn test_chunk_text_with_tokenizer_respects_budget() {
+        let text = (0..200)
+            .map(|i| format!("Sentence number {} is here.", i))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let chunks = chunk_text_with_tokenizer(&text, 100, 10, &HeuristicTokenizer);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(HeuristicTokenizer.token_spans(chunk).len() <= 100);
+        }
+        // Chunks break after a sentence and overlap with their neighbour
+        assert!(chunks[0].trim_end().ends_with('.'));
+        assert!(chunks.last().unwrap().ends_with("199 is here."));
+        let tail: String = chunks[0].chars().rev().take(10).collect::<Vec<_>>().into_iter().rev().collect();
+        assert!(chunks[1].contains(tail.trim()));
+    }
+
+    #[test]
+    fn test_chunk_text_with_byte_level_tokenizer_keeps_characters_whole() {
+        // No merges apply to CJK or emoji, so every byte is its own token
+        let tokenizer = BpeTokenizer::from_tiktoken("test", "aA== 0\n").unwrap();
+        let text = "会议开始了。我们讨论预算 🚀🚀 然后安排下周的发布计划";
+        let chunks = chunk_text_with_tokenizer(text, 10, 2, &tokenizer);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(text.contains(chunk.as_str()));
+        }
+        assert!(chunks[0].starts_with("会议"));
+        assert!(chunks.last().unwrap().ends_with("计划"));
+    }
 }
@@ -1,14 +1,22 @@
 use crate::database::repositories::{
     meeting::MeetingsRepository, setting::SettingsRepository, summary::SummaryProcessesRepository,
+    summary_version::{NewSummaryVersion, SummaryVersionsRepository},
+};
+use crate::summary::fallback::{build_chain, FallbackTarget, ProviderAttempt};
+use crate::summary::llm_client::{LLMProvider, StreamCallback};
+use crate::summary::llm_error::LlmError;
+use crate::summary::processor::{
+    extract_meeting_name_from_markdown, generate_meeting_summary, rough_token_count, ChunkStatus,
+    MeetingSummaryOutput, SummaryOutputFormat,
 };
-use crate::summary::llm_client::LLMProvider;
-use crate::summary::processor::{extract_meeting_name_from_markdown, generate_meeting_summary};
 use crate::ollama::metadata::ModelMetadataCache;
+use serde::Serialize;
 use sqlx::SqlitePool;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Emitter, Manager};
 use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
 use once_cell::sync::Lazy;
@@ -22,6 +30,61 @@ static METADATA_CACHE: Lazy<ModelMetadataCache> = Lazy::new(|| {
 static CANCELLATION_REGISTRY: Lazy<Arc<Mutex<HashMap<String, CancellationToken>>>> =
     Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
 
+/// Event emitted while the final summary is streamed from the provider
+const SUMMARY_STREAM_EVENT: &str = "summary-stream";
+
+/// Minimum gap between the markdown snapshots carried by `summary-stream` events
+const STREAM_SNAPSHOT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Payload of a `summary-stream` event
+#[derive(Debug, Clone, Serialize)]
+pub struct SummaryStreamEvent {
+    pub meeting_id: String,
+    /// Text received since the previous event
+    pub delta: String,
+    /// Partial markdown received so far; only on the first event of an attempt
+    /// and then at most every `STREAM_SNAPSHOT_INTERVAL`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub markdown: Option<String>,
+}
+
+/// Markdown streamed by the current attempt and when it was last sent in full
+#[derive(Debug, Default)]
+struct StreamedMarkdown {
+    text: String,
+    last_snapshot: Option<Instant>,
+}
+
+impl StreamedMarkdown {
+    /// Appends a delta, returning a snapshot of the whole text when one is due
+    fn push(&mut self, delta: &str) -> Option<String> {
+        self.text.push_str(delta);
+        if matches!(self.last_snapshot, Some(at) if at.elapsed() < STREAM_SNAPSHOT_INTERVAL) {
+            return None;
+        }
+        self.last_snapshot = Some(Instant::now());
+        Some(self.text.clone())
+    }
+
+    /// Starts over for a new attempt; its first delta carries a fresh snapshot
+    fn reset(&mut self) {
+        self.text.clear();
+        self.last_snapshot = None;
+    }
+}
+
+/// Provider, credentials and endpoints needed to call an LLM, resolved from settings
+#[derive(Debug, Clone)]
+pub struct ProviderConnection {
+    pub provider: LLMProvider,
+    pub api_key: String,
+    pub ollama_endpoint: Option<String>,
+    pub custom_openai_endpoint: Option<String>,
+    pub max_tokens: Option<u32>,
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+}
+
 /// Summary service - handles all summary generation logic
 pub struct SummaryService;
 
@@ -58,71 +121,32 @@ impl SummaryService {
         }
     }
 
-    /// Processes transcript in the background and generates summary
-    ///
-    /// This function is designed to be spawned as an async task and does not block
-    /// the main thread. It updates the database with progress and results.
-    ///
-    /// # Arguments
-    /// * `_app` - Tauri app handle (for future use)
-    /// * `pool` - SQLx connection pool
-    /// * `meeting_id` - Unique identifier for the meeting
-    /// * `text` - Full transcript text
-    /// * `model_provider` - LLM provider name (e.g., "ollama", "openai")
-    /// * `model_name` - Specific model (e.g., "gpt-4", "llama3.2:latest")
-    /// * `custom_prompt` - Optional user-provided context
-    /// * `template_id` - Template identifier (e.g., "daily_standup", "standard_meeting")
-    pub async fn process_transcript_background<R: tauri::Runtime>(
-        _app: AppHandle<R>,
-        pool: SqlitePool,
-        meeting_id: String,
-        text: String,
-        model_provider: String,
-        model_name: String,
-        custom_prompt: String,
-        template_id: String,
-    ) {
-        let start_time = Instant::now();
-        info!(
-            "Starting background processing for meeting_id: {}",
-            meeting_id
-        );
-
-        // Register cancellation token for this meeting
-        let cancellation_token = Self::register_cancellation_token(&meeting_id);
-
-        // Parse provider
-        let provider = match LLMProvider::from_str(&model_provider) {
-            Ok(p) => p,
-            Err(e) => {
-                Self::update_process_failed(&pool, &meeting_id, &e).await;
-                return;
-            }
-        };
+    /// Resolves the API key and endpoints for `model_provider` (e.g., "ollama", "openai")
+    pub async fn resolve_provider(
+        pool: &SqlitePool,
+        model_provider: &str,
+    ) -> Result<ProviderConnection, String> {
+        let provider = LLMProvider::from_str(model_provider)?;
 
         // Validate and setup api_key, Flexible for Ollama, BuiltInAI, and CustomOpenAI
         let api_key = if provider == LLMProvider::Ollama || provider == LLMProvider::BuiltInAI || provider == LLMProvider::CustomOpenAI {
             // These providers don't require API keys from the standard database column
             String::new()
         } else {
-            match SettingsRepository::get_api_key(&pool, &model_provider).await {
+            match SettingsRepository::get_api_key(pool, model_provider).await {
                 Ok(Some(key)) if !key.is_empty() => key,
                 Ok(None) | Ok(Some(_)) => {
-                    let err_msg = format!("API key not found for {}", &model_provider);
-                    Self::update_process_failed(&pool, &meeting_id, &err_msg).await;
-                    return;
+                    return Err(format!("API key not found for {}", model_provider));
                 }
                 Err(e) => {
-                    let err_msg = format!("Failed to retrieve API key for {}: {}", &model_provider, e);
-                    Self::update_process_failed(&pool, &meeting_id, &err_msg).await;
-                    return;
+                    return Err(format!("Failed to retrieve API key for {}: {}", model_provider, e));
                 }
             }
         };
 
         // Get Ollama endpoint if provider is Ollama
         let ollama_endpoint = if provider == LLMProvider::Ollama {
-            match SettingsRepository::get_model_config(&pool).await {
+            match SettingsRepository::get_model_config(pool).await {
                 Ok(Some(config)) => config.ollama_endpoint,
                 Ok(None) => None,
                 Err(e) => {
@@ -135,44 +159,50 @@ impl SummaryService {
         };
 
         // Get CustomOpenAI config if provider is CustomOpenAI
-        let (custom_openai_endpoint, custom_openai_api_key, custom_openai_max_tokens, custom_openai_temperature, custom_openai_top_p) =
-            if provider == LLMProvider::CustomOpenAI {
-                match SettingsRepository::get_custom_openai_config(&pool).await {
-                    Ok(Some(config)) => {
-                        info!("✓ Using custom OpenAI endpoint: {}", config.endpoint);
-                        (
-                            Some(config.endpoint),
-                            config.api_key,
-                            config.max_tokens.map(|t| t as u32),
-                            config.temperature,
-                            config.top_p,
-                        )
-                    }
-                    Ok(None) => {
-                        let err_msg = "Custom OpenAI provider selected but no configuration found";
-                        Self::update_process_failed(&pool, &meeting_id, err_msg).await;
-                        return;
-                    }
-                    Err(e) => {
-                        let err_msg = format!("Failed to retrieve custom OpenAI config: {}", e);
-                        Self::update_process_failed(&pool, &meeting_id, &err_msg).await;
-                        return;
-                    }
+        if provider == LLMProvider::CustomOpenAI {
+            return match SettingsRepository::get_custom_openai_config(pool).await {
+                Ok(Some(config)) => {
+                    info!("✓ Using custom OpenAI endpoint: {}", config.endpoint);
+                    Ok(ProviderConnection {
+                        provider,
+                        // Use its API key (if any) instead of the empty string
+                        api_key: config.api_key.unwrap_or_default(),
+                        ollama_endpoint,
+                        custom_openai_endpoint: Some(config.endpoint),
+                        max_tokens: config.max_tokens.map(|t| t as u32),
+                        temperature: config.temperature,
+                        top_p: config.top_p,
+                    })
+                }
+                Ok(None) => {
+                    Err("Custom OpenAI provider selected but no configuration found".to_string())
                 }
-            } else {
-                (None, None, None, None, None)
+                Err(e) => Err(format!("Failed to retrieve custom OpenAI config: {}", e)),
             };
+        }
 
-        // For CustomOpenAI, use its API key (if any) instead of the empty string
-        let final_api_key = if provider == LLMProvider::CustomOpenAI {
-            custom_openai_api_key.unwrap_or_default()
-        } else {
-            api_key
-        };
+        Ok(ProviderConnection {
+            provider,
+            api_key,
+            ollama_endpoint,
+            custom_openai_endpoint: None,
+            max_tokens: None,
+            temperature: None,
+            top_p: None,
+        })
+    }
 
-        // Dynamically fetch context size based on provider and model
-        let token_threshold = if provider == LLMProvider::Ollama {
-            match METADATA_CACHE.get_or_fetch(&model_name, ollama_endpoint.as_deref()).await {
+    /// Usable context (in tokens) for a model, leaving room for prompt overhead
+    ///
+    /// Ollama models report their context size (cached for 5 minutes), BuiltInAI
+    /// models come from the registry, and cloud providers are treated as unlimited.
+    pub async fn context_token_limit(
+        provider: &LLMProvider,
+        model_name: &str,
+        ollama_endpoint: Option<&str>,
+    ) -> usize {
+        if *provider == LLMProvider::Ollama {
+            match METADATA_CACHE.get_or_fetch(model_name, ollama_endpoint).await {
                 Ok(metadata) => {
                     // Reserve 300 tokens for prompt overhead
                     let optimal = metadata.context_size.saturating_sub(300);
@@ -190,10 +220,10 @@ impl SummaryService {
                     4000  // Fallback to safe default
                 }
             }
-        } else if provider == LLMProvider::BuiltInAI {
+        } else if *provider == LLMProvider::BuiltInAI {
             // Get model's context size from registry
             use crate::summary::summary_engine::models;
-            let model = models::get_model_by_name(&model_name)
+            let model = models::get_model_by_name(model_name)
                 .ok_or_else(|| format!("Unknown model: {}", model_name));
 
             match model {
@@ -214,39 +244,250 @@ impl SummaryService {
         } else {
             // Cloud providers (OpenAI, Claude, Groq, CustomOpenAI) handle large contexts automatically
             100000  // Effectively unlimited for single-pass processing
+        }
+    }
+
+    /// Runs the whole summary on one provider
+    ///
+    /// The streamed markdown is reset first, since an earlier provider may have
+    /// streamed part of its report before failing.
+    async fn summarize_with_provider(
+        client: &reqwest::Client,
+        connection: &ProviderConnection,
+        model_name: &str,
+        text: &str,
+        summary_context: &str,
+        user_notes: Option<&str>,
+        template_id: &str,
+        output_format: SummaryOutputFormat,
+        app_data_dir: Option<&PathBuf>,
+        cancellation_token: &CancellationToken,
+        on_delta: &StreamCallback<'_>,
+        streamed_markdown: &Mutex<StreamedMarkdown>,
+    ) -> Result<MeetingSummaryOutput, LlmError> {
+        // Dynamically fetch context size based on provider and model
+        let token_threshold = Self::context_token_limit(
+            &connection.provider,
+            model_name,
+            connection.ollama_endpoint.as_deref(),
+        )
+        .await;
+
+        if let Ok(mut buffer) = streamed_markdown.lock() {
+            buffer.reset();
+        }
+
+        // Requests retry transient errors themselves; a failure here moves the
+        // chain on to the next provider
+        generate_meeting_summary(
+            client,
+            &connection.provider,
+            model_name,
+            &connection.api_key,
+            text,
+            summary_context,
+            user_notes,
+            template_id,
+            output_format,
+            token_threshold,
+            connection.ollama_endpoint.as_deref(),
+            connection.custom_openai_endpoint.as_deref(),
+            connection.max_tokens,
+            connection.temperature,
+            connection.top_p,
+            app_data_dir,
+            Some(cancellation_token),
+            Some(on_delta),
+        )
+        .await
+    }
+
+    /// Processes transcript in the background and generates summary
+    ///
+    /// This function is designed to be spawned as an async task and does not block
+    /// the main thread. It updates the database with progress and results.
+    ///
+    /// The selected provider is tried first, followed by the configured fallback
+    /// chain (see `SettingsRepository::get_summary_fallback_chain`). Requests are
+    /// retried on transient errors per `RetryPolicy`; a provider that still
+    /// fails hands over to the next one. The provider that succeeded and every
+    /// failed one are recorded in the process metadata.
+    ///
+    /// # Arguments
+    /// * `_app` - Tauri app handle (for future use)
+    /// * `pool` - SQLx connection pool
+    /// * `meeting_id` - Unique identifier for the meeting
+    /// * `text` - Full transcript text
+    /// * `model_provider` - LLM provider name (e.g., "ollama", "openai")
+    /// * `model_name` - Specific model (e.g., "gpt-4", "llama3.2:latest")
+    /// * `custom_prompt` - Optional user-provided context
+    /// * `template_id` - Template identifier (e.g., "daily_standup", "standard_meeting")
+    /// * `include_notes` - Whether the user's meeting notes are passed as priority context
+    pub async fn process_transcript_background<R: tauri::Runtime>(
+        _app: AppHandle<R>,
+        pool: SqlitePool,
+        meeting_id: String,
+        text: String,
+        model_provider: String,
+        model_name: String,
+        custom_prompt: String,
+        template_id: String,
+        output_format: SummaryOutputFormat,
+        include_notes: bool,
+    ) {
+        let start_time = Instant::now();
+        info!(
+            "Starting background processing for meeting_id: {}",
+            meeting_id
+        );
+
+        // Register cancellation token for this meeting
+        let cancellation_token = Self::register_cancellation_token(&meeting_id);
+
+        let fallbacks = SettingsRepository::get_summary_fallback_chain(&pool)
+            .await
+            .unwrap_or_else(|e| {
+                warn!("Failed to load summary fallback chain: {}", e);
+                Vec::new()
+            });
+        let primary = FallbackTarget {
+            provider: model_provider.clone(),
+            model: model_name.clone(),
+        };
+        let chain = build_chain(primary, &fallbacks);
+
+        // Remind the model of open action items from earlier related meetings
+        let summary_context = match crate::action_items::carried_over_context(&pool, &meeting_id).await {
+            Some(context) if custom_prompt.trim().is_empty() => context,
+            Some(context) => format!("{}\n\n{}", custom_prompt, context),
+            None => custom_prompt.clone(),
+        };
+        let user_notes = if include_notes {
+            crate::notes::summary_context(&pool, &meeting_id).await
+        } else {
+            None
         };
 
         // Get app data directory for BuiltInAI provider
         let app_data_dir = _app.path().app_data_dir().ok();
 
-        // Generate summary
+        // Forward partial markdown to the UI as the final report streams in
+        let streamed_markdown = Arc::new(Mutex::new(StreamedMarkdown::default()));
+        let stream_buffer = Arc::clone(&streamed_markdown);
+        let stream_app = _app.clone();
+        let stream_meeting_id = meeting_id.clone();
+        let on_delta = move |delta: &str| {
+            let markdown = match stream_buffer.lock() {
+                Ok(mut buffer) => buffer.push(delta),
+                Err(_) => return,
+            };
+            let event = SummaryStreamEvent {
+                meeting_id: stream_meeting_id.clone(),
+                delta: delta.to_string(),
+                markdown,
+            };
+            if let Err(e) = stream_app.emit(SUMMARY_STREAM_EVENT, &event) {
+                warn!("Failed to emit summary stream event: {}", e);
+            }
+        };
+        let on_delta: &StreamCallback<'_> = &on_delta;
+
+        // Generate summary, moving down the chain until a provider succeeds
         let client = reqwest::Client::new();
-        let result = generate_meeting_summary(
-            &client,
-            &provider,
-            &model_name,
-            &final_api_key,
-            &text,
-            &custom_prompt,
-            &template_id,
-            token_threshold,
-            ollama_endpoint.as_deref(),
-            custom_openai_endpoint.as_deref(),
-            custom_openai_max_tokens,
-            custom_openai_temperature,
-            custom_openai_top_p,
-            app_data_dir.as_ref(),
-            Some(&cancellation_token),
-        )
-        .await;
+        let mut provider_attempts: Vec<ProviderAttempt> = Vec::new();
+        let mut result = Err(LlmError::Other("No summary provider configured".to_string()));
+        let mut used_target = None;
+        for target in &chain {
+            let outcome = match Self::resolve_provider(&pool, &target.provider).await {
+                Ok(connection) => {
+                    info!(
+                        "Summarizing {} with {}/{}",
+                        meeting_id, target.provider, target.model
+                    );
+                    Self::summarize_with_provider(
+                        &client,
+                        &connection,
+                        &target.model,
+                        &text,
+                        &summary_context,
+                        user_notes.as_deref(),
+                        &template_id,
+                        output_format,
+                        app_data_dir.as_ref(),
+                        &cancellation_token,
+                        on_delta,
+                        &streamed_markdown,
+                    )
+                    .await
+                }
+                Err(e) => Err(LlmError::Other(e)),
+            };
+
+            match outcome {
+                Ok(output) => {
+                    provider_attempts.push(ProviderAttempt::succeeded(target));
+                    used_target = Some(target);
+                    result = Ok(output);
+                    break;
+                }
+                Err(LlmError::Cancelled) => {
+                    result = Err(LlmError::Cancelled);
+                    break;
+                }
+                Err(e) => {
+                    warn!(
+                        "Summary provider {}/{} failed ({}): {}",
+                        target.provider,
+                        target.model,
+                        e.kind(),
+                        e
+                    );
+                    provider_attempts.push(ProviderAttempt::failed(target, &e));
+                    result = Err(e);
+                }
+            }
+        }
 
         let duration = start_time.elapsed().as_secs_f64();
 
         // Clean up cancellation token regardless of outcome
         Self::cleanup_cancellation_token(&meeting_id);
 
+        if !provider_attempts.is_empty() {
+            let metadata = serde_json::json!({
+                "provider_used": used_target,
+                "provider_attempts": provider_attempts,
+            });
+            if let Err(e) =
+                SummaryProcessesRepository::merge_process_metadata(&pool, &meeting_id, &metadata).await
+            {
+                warn!("Failed to save provider attempts for {}: {}", meeting_id, e);
+            }
+        }
+
         match result {
-            Ok((mut final_markdown, num_chunks)) => {
+            Ok(output) => {
+                let mut final_markdown = output.markdown;
+                let num_chunks = output.chunk_count;
+
+                // Record per-chunk outcomes of the map-reduce pass (if any)
+                if !output.chunk_outcomes.is_empty() {
+                    let failed = output
+                        .chunk_outcomes
+                        .iter()
+                        .filter(|o| o.status == ChunkStatus::Failed)
+                        .count();
+                    let metadata = serde_json::json!({
+                        "chunks": output.chunk_outcomes,
+                        "failed_chunks": failed,
+                    });
+                    if let Err(e) =
+                        SummaryProcessesRepository::merge_process_metadata(&pool, &meeting_id, &metadata).await
+                    {
+                        warn!("Failed to save chunk outcomes for {}: {}", meeting_id, e);
+                    }
+                }
+
                 if num_chunks == 0 && final_markdown.is_empty() {
                     Self::update_process_failed(
                         &pool,
@@ -296,9 +537,33 @@ impl SummaryService {
                 }
 
                 // Create result JSON with markdown only (summary_json will be added on first edit)
-                let result_json = serde_json::json!({
+                let mut result_json = serde_json::json!({
                     "markdown": final_markdown,
                 });
+                // Structured runs also keep the typed report the markdown was rendered from
+                if let Some(structured) = &output.structured {
+                    if !structured.unresolved_sections.is_empty() {
+                        warn!(
+                            "Structured summary for {} has unresolved sections: {:?}",
+                            meeting_id, structured.unresolved_sections
+                        );
+                    }
+                    result_json["structured"] = serde_json::json!(structured);
+                }
+
+                let version = NewSummaryVersion {
+                    source: "generated".to_string(),
+                    result: result_json.to_string(),
+                    provider: used_target.map(|t| t.provider.clone()),
+                    model: used_target.map(|t| t.model.clone()),
+                    template_id: Some(template_id.clone()),
+                    custom_prompt: Some(custom_prompt.clone()).filter(|p| !p.trim().is_empty()),
+                    input_tokens: Some(rough_token_count(&text) as i64),
+                    output_tokens: Some(rough_token_count(&final_markdown) as i64),
+                    chunk_count: Some(num_chunks),
+                    processing_time: Some(duration),
+                    restored_from: None,
+                };
 
                 // Update database with completed status
                 if let Err(e) = SummaryProcessesRepository::update_process_completed(
@@ -319,19 +584,51 @@ impl SummaryService {
                         "Summary saved successfully for meeting_id: {}",
                         meeting_id
                     );
+
+                    if let Err(e) =
+                        SummaryVersionsRepository::record(&pool, &meeting_id, &version).await
+                    {
+                        warn!("Failed to record summary version for {}: {}", meeting_id, e);
+                    }
+                    crate::semantic::commands::index_in_background(&_app);
+
+                    match crate::action_items::sync_from_summary(
+                        &pool,
+                        &meeting_id,
+                        output.structured.as_ref(),
+                        &final_markdown,
+                    )
+                    .await
+                    {
+                        Ok(_) => {
+                            let payload = serde_json::json!({ "meetingId": meeting_id });
+                            if let Err(e) =
+                                _app.emit(crate::action_items::ACTION_ITEMS_UPDATED_EVENT, payload)
+                            {
+                                warn!("Failed to emit action items event: {}", e);
+                            }
+                        }
+                        Err(e) => warn!("Failed to extract action items for {}: {}", meeting_id, e),
+                    }
                 }
             }
-            Err(e) => {
-                // Check if error is due to cancellation
-                if e.contains("cancelled") {
-                    info!("Summary generation was cancelled for meeting_id: {}", meeting_id);
-                    if let Err(db_err) = SummaryProcessesRepository::update_process_cancelled(&pool, &meeting_id).await {
-                        error!("Failed to update DB status to cancelled for {}: {}", meeting_id, db_err);
-                    }
-                } else {
-                    Self::update_process_failed(&pool, &meeting_id, &e).await;
+            Err(LlmError::Cancelled) => {
+                info!("Summary generation was cancelled for meeting_id: {}", meeting_id);
+                if let Err(db_err) = SummaryProcessesRepository::update_process_cancelled(&pool, &meeting_id).await {
+                    error!("Failed to update DB status to cancelled for {}: {}", meeting_id, db_err);
                 }
             }
+            Err(e) if provider_attempts.len() > 1 => {
+                let message = format!(
+                    "All {} summary providers failed. Last error: {}",
+                    provider_attempts.len(),
+                    e
+                );
+                Self::update_process_failed(&pool, &meeting_id, &message).await;
+            }
+            Err(e) => {
+                Self::update_process_failed(&pool, &meeting_id, &e.to_string()).await;
+            }
         }
     }
 
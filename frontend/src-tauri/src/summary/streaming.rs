@@ -0,0 +1,309 @@
+// Incremental parsing of streamed LLM completions
+// Turns raw response bytes from each provider's wire format into text deltas
+
+use serde_json::Value;
+
+/// Wire format used by a provider when streaming a completion
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StreamFormat {
+    /// Server-Sent Events with OpenAI-style `choices[0].delta.content` payloads
+    /// (OpenAI, Groq, OpenRouter, CustomOpenAI)
+    OpenAISse,
+    /// Server-Sent Events with Anthropic `content_block_delta` payloads (Claude)
+    ClaudeSse,
+    /// Newline-delimited JSON from Ollama's native `/api/chat` endpoint
+    OllamaNdjson,
+    /// Server-Sent Events carrying partial `GenerateContentResponse`s (Gemini)
+    GeminiSse,
+}
+
+/// A single parsed event from a streamed completion
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEvent {
+    /// A piece of generated text
+    Delta(String),
+    /// The provider signalled the end of the stream
+    Done,
+    /// The provider reported an error mid-stream
+    Error(String),
+    /// Keep-alives, metadata and other frames that carry no text
+    Ignore,
+}
+
+/// Splits a byte stream into complete lines, buffering partial lines
+/// (and partial UTF-8 sequences) across network chunks
+#[derive(Debug, Default)]
+pub struct LineBuffer {
+    pending: Vec<u8>,
+}
+
+impl LineBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a network chunk and returns every line it completed
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.pending.extend_from_slice(chunk);
+
+        let mut lines = Vec::new();
+        while let Some(newline_pos) = self.pending.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=newline_pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            lines.push(line.trim_end_matches(['\r', '\n']).to_string());
+        }
+        lines
+    }
+
+    /// Returns whatever is left once the stream has ended
+    pub fn finish(&mut self) -> Option<String> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        let rest = String::from_utf8_lossy(&self.pending).trim().to_string();
+        self.pending.clear();
+        if rest.is_empty() {
+            None
+        } else {
+            Some(rest)
+        }
+    }
+}
+
+/// Parses one line of a streamed response in the given format
+pub fn parse_stream_line(format: StreamFormat, line: &str) -> StreamEvent {
+    let line = line.trim();
+    if line.is_empty() {
+        return StreamEvent::Ignore;
+    }
+
+    match format {
+        StreamFormat::OpenAISse => parse_openai_sse_line(line),
+        StreamFormat::ClaudeSse => parse_claude_sse_line(line),
+        StreamFormat::OllamaNdjson => parse_ollama_line(line),
+        StreamFormat::GeminiSse => parse_gemini_sse_line(line),
+    }
+}
+
+/// Extracts the payload of an SSE `data:` field, ignoring comments and other fields
+fn sse_data(line: &str) -> Option<&str> {
+    line.strip_prefix("data:").map(|data| data.trim_start())
+}
+
+fn parse_openai_sse_line(line: &str) -> StreamEvent {
+    let Some(data) = sse_data(line) else {
+        return StreamEvent::Ignore;
+    };
+    if data == "[DONE]" {
+        return StreamEvent::Done;
+    }
+
+    let json: Value = match serde_json::from_str(data) {
+        Ok(json) => json,
+        Err(_) => return StreamEvent::Ignore,
+    };
+
+    if let Some(error) = json.get("error") {
+        let message = error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .map(|m| m.to_string())
+            .unwrap_or_else(|| error.to_string());
+        return StreamEvent::Error(message);
+    }
+
+    match json
+        .pointer("/choices/0/delta/content")
+        .and_then(|c| c.as_str())
+    {
+        Some(content) if !content.is_empty() => StreamEvent::Delta(content.to_string()),
+        _ => StreamEvent::Ignore,
+    }
+}
+
+fn parse_claude_sse_line(line: &str) -> StreamEvent {
+    // `event:` lines are redundant with the `type` field inside each data payload
+    let Some(data) = sse_data(line) else {
+        return StreamEvent::Ignore;
+    };
+
+    let json: Value = match serde_json::from_str(data) {
+        Ok(json) => json,
+        Err(_) => return StreamEvent::Ignore,
+    };
+
+    match json.get("type").and_then(|t| t.as_str()) {
+        Some("content_block_delta") => match json.pointer("/delta/text").and_then(|t| t.as_str()) {
+            Some(text) if !text.is_empty() => StreamEvent::Delta(text.to_string()),
+            _ => StreamEvent::Ignore,
+        },
+        Some("message_stop") => StreamEvent::Done,
+        Some("error") => StreamEvent::Error(
+            json.pointer("/error/message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("Unknown Claude streaming error")
+                .to_string(),
+        ),
+        _ => StreamEvent::Ignore,
+    }
+}
+
+fn parse_ollama_line(line: &str) -> StreamEvent {
+    let json: Value = match serde_json::from_str(line) {
+        Ok(json) => json,
+        Err(_) => return StreamEvent::Ignore,
+    };
+
+    if let Some(error) = json.get("error").and_then(|e| e.as_str()) {
+        return StreamEvent::Error(error.to_string());
+    }
+
+    if let Some(content) = json.pointer("/message/content").and_then(|c| c.as_str()) {
+        if !content.is_empty() {
+            return StreamEvent::Delta(content.to_string());
+        }
+    }
+
+    if json.get("done").and_then(|d| d.as_bool()) == Some(true) {
+        StreamEvent::Done
+    } else {
+        StreamEvent::Ignore
+    }
+}
+
+fn parse_gemini_sse_line(line: &str) -> StreamEvent {
+    let Some(data) = sse_data(line) else {
+        return StreamEvent::Ignore;
+    };
+
+    let json: Value = match serde_json::from_str(data) {
+        Ok(json) => json,
+        Err(_) => return StreamEvent::Ignore,
+    };
+
+    if let Some(error) = json.get("error") {
+        let message = error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .map(|m| m.to_string())
+            .unwrap_or_else(|| error.to_string());
+        return StreamEvent::Error(message);
+    }
+
+    // Gemini has no end-of-stream event; the connection simply closes
+    let text: String = json
+        .pointer("/candidates/0/content/parts")
+        .and_then(|parts| parts.as_array())
+        .map(|parts| {
+            parts
+                .iter()
+                // Skip reasoning summaries from thinking models
+                .filter(|part| part.get("thought").and_then(|t| t.as_bool()) != Some(true))
+                .filter_map(|part| part.get("text").and_then(|t| t.as_str()))
+                .collect()
+        })
+        .unwrap_or_default();
+    if text.is_empty() {
+        StreamEvent::Ignore
+    } else {
+        StreamEvent::Delta(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_buffer_splits_across_chunks() {
+        let mut buffer = LineBuffer::new();
+        assert!(buffer.push(b"data: {\"a\"").is_empty());
+        let lines = buffer.push(b":1}\r\ndata: [DONE]\n");
+        assert_eq!(lines, vec!["data: {\"a\":1}", "data: [DONE]"]);
+        assert!(buffer.finish().is_none());
+    }
+
+    #[test]
+    fn test_line_buffer_keeps_split_utf8() {
+        let mut buffer = LineBuffer::new();
+        let bytes = "héllo\n".as_bytes();
+        assert!(buffer.push(&bytes[..2]).is_empty());
+        assert_eq!(buffer.push(&bytes[2..]), vec!["héllo"]);
+    }
+
+    #[test]
+    fn test_openai_sse_delta_and_done() {
+        let line = r#"data: {"choices":[{"delta":{"content":"Hello"}}]}"#;
+        assert_eq!(
+            parse_stream_line(StreamFormat::OpenAISse, line),
+            StreamEvent::Delta("Hello".to_string())
+        );
+        assert_eq!(
+            parse_stream_line(StreamFormat::OpenAISse, "data: [DONE]"),
+            StreamEvent::Done
+        );
+        assert_eq!(
+            parse_stream_line(StreamFormat::OpenAISse, ": keep-alive"),
+            StreamEvent::Ignore
+        );
+    }
+
+    #[test]
+    fn test_claude_sse_events() {
+        let delta = r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#;
+        assert_eq!(
+            parse_stream_line(StreamFormat::ClaudeSse, delta),
+            StreamEvent::Delta("Hi".to_string())
+        );
+        assert_eq!(
+            parse_stream_line(StreamFormat::ClaudeSse, "event: content_block_delta"),
+            StreamEvent::Ignore
+        );
+        assert_eq!(
+            parse_stream_line(StreamFormat::ClaudeSse, r#"data: {"type":"message_stop"}"#),
+            StreamEvent::Done
+        );
+        let error = r#"data: {"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#;
+        assert_eq!(
+            parse_stream_line(StreamFormat::ClaudeSse, error),
+            StreamEvent::Error("Overloaded".to_string())
+        );
+    }
+
+    #[test]
+    fn test_ollama_ndjson() {
+        let line = r#"{"model":"llama3.2","message":{"role":"assistant","content":"Hey"},"done":false}"#;
+        assert_eq!(
+            parse_stream_line(StreamFormat::OllamaNdjson, line),
+            StreamEvent::Delta("Hey".to_string())
+        );
+        let done = r#"{"model":"llama3.2","message":{"role":"assistant","content":""},"done":true}"#;
+        assert_eq!(
+            parse_stream_line(StreamFormat::OllamaNdjson, done),
+            StreamEvent::Done
+        );
+        assert_eq!(
+            parse_stream_line(StreamFormat::OllamaNdjson, r#"{"error":"model not found"}"#),
+            StreamEvent::Error("model not found".to_string())
+        );
+    }
+
+    #[test]
+    fn test_gemini_sse_parts() {
+        let line = r#"data: {"candidates": [{"content": {"role": "model", "parts": [{"text": "Plan", "thought": true}, {"text": "Ship v2"}]}}]}"#;
+        assert_eq!(
+            parse_stream_line(StreamFormat::GeminiSse, line),
+            StreamEvent::Delta("Ship v2".to_string())
+        );
+        let usage_only = r#"data: {"candidates": [{"finishReason": "STOP"}], "usageMetadata": {"totalTokenCount": 42}}"#;
+        assert_eq!(
+            parse_stream_line(StreamFormat::GeminiSse, usage_only),
+            StreamEvent::Ignore
+        );
+        let error = r#"data: {"error": {"code": 429, "message": "Resource exhausted"}}"#;
+        assert_eq!(
+            parse_stream_line(StreamFormat::GeminiSse, error),
+            StreamEvent::Error("Resource exhausted".to_string())
+        );
+    }
+}
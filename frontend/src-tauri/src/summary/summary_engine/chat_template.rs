@@ -0,0 +1,211 @@
+// Chat template selection and rendering for custom GGUF models
+// Known prompt formats are matched from the model's embedded template or its
+// architecture; anything else is rendered from the embedded Jinja template
+
+use anyhow::{anyhow, Result};
+use minijinja::{Environment, ErrorKind};
+use serde::Serialize;
+
+use super::gguf::GgufMetadata;
+
+/// Template name for models rendered from their embedded Jinja template
+pub const JINJA_TEMPLATE: &str = "jinja";
+
+/// Fallback when neither the embedded template nor the architecture is recognized
+const DEFAULT_TEMPLATE: &str = "chatml";
+
+/// Prompt format chosen for a model
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TemplateChoice {
+    /// Name accepted by `models::format_prompt`, or `JINJA_TEMPLATE`
+    pub template: String,
+    pub stop_tokens: Vec<String>,
+}
+
+/// Embedded Jinja chat template (`tokenizer.chat_template`), if any
+pub fn embedded_template(metadata: &GgufMetadata) -> Option<&str> {
+    metadata
+        .get_str("tokenizer.chat_template")
+        .filter(|template| !template.trim().is_empty())
+}
+
+/// Text of a special token referenced by id (e.g. `tokenizer.ggml.eos_token_id`)
+pub fn token_text(metadata: &GgufMetadata, id_key: &str) -> Option<String> {
+    let id = metadata.get_u64(id_key)? as usize;
+    metadata
+        .get_string_array("tokenizer.ggml.tokens")?
+        .into_iter()
+        .nth(id)
+}
+
+/// Picks the prompt format for a model from its metadata
+///
+/// Marker tokens in the embedded template identify the common formats; an
+/// embedded template in any other format is rendered as-is, and models
+/// without one fall back to a format by architecture.
+pub fn choose_template(metadata: &GgufMetadata) -> TemplateChoice {
+    if let Some(embedded) = embedded_template(metadata) {
+        if let Some(name) = template_from_markers(embedded) {
+            return named(name);
+        }
+        if render_jinja(embedded, None, "system", "user").is_ok() {
+            let stop_tokens = token_text(metadata, "tokenizer.ggml.eos_token_id")
+                .into_iter()
+                .collect();
+            return TemplateChoice {
+                template: JINJA_TEMPLATE.to_string(),
+                stop_tokens,
+            };
+        }
+        log::warn!("Embedded chat template can't be rendered, choosing by architecture");
+    }
+
+    let name = match metadata.architecture().unwrap_or_default() {
+        "gemma" | "gemma2" | "gemma3" => "gemma3",
+        "qwen2" | "qwen2moe" | "qwen3" | "qwen3moe" => "qwen",
+        "phi3" => "phi",
+        "llama" => "llama3",
+        _ => DEFAULT_TEMPLATE,
+    };
+    named(name)
+}
+
+fn template_from_markers(template: &str) -> Option<&'static str> {
+    if template.contains("<start_of_turn>") {
+        Some("gemma3")
+    } else if template.contains("<|start_header_id|>") {
+        Some("llama3")
+    } else if template.contains("<|im_start|>") {
+        Some("chatml")
+    } else if template.contains("<|user|>") && template.contains("<|end|>") {
+        Some("phi")
+    } else if template.contains("[INST]") {
+        Some("mistral")
+    } else {
+        None
+    }
+}
+
+fn named(name: &str) -> TemplateChoice {
+    let stop_token = match name {
+        "gemma3" => "<end_of_turn>",
+        "llama3" => "<|eot_id|>",
+        "phi" => "<|end|>",
+        "mistral" => "</s>",
+        _ => "<|im_end|>",
+    };
+    TemplateChoice {
+        template: name.to_string(),
+        stop_tokens: vec![stop_token.to_string()],
+    }
+}
+
+#[derive(Serialize)]
+struct TemplateMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+/// Renders a Hugging Face style Jinja chat template for one system and one
+/// user message, ending with the generation prompt
+///
+/// `bos_token` is left empty by default: llama-helper already adds BOS when
+/// tokenizing, and templates that print it would otherwise double it.
+pub fn render_jinja(
+    template: &str,
+    eos_token: Option<&str>,
+    system_prompt: &str,
+    user_prompt: &str,
+) -> Result<String> {
+    let mut env = Environment::new();
+    env.set_unknown_method_callback(minijinja_contrib::pycompat::unknown_method_callback);
+    env.add_function(
+        "raise_exception",
+        |message: String| -> Result<String, minijinja::Error> {
+            Err(minijinja::Error::new(ErrorKind::InvalidOperation, message))
+        },
+    );
+    env.add_template("chat", template)
+        .map_err(|e| anyhow!("Invalid chat template: {}", e))?;
+
+    let messages = [
+        TemplateMessage {
+            role: "system",
+            content: system_prompt,
+        },
+        TemplateMessage {
+            role: "user",
+            content: user_prompt,
+        },
+    ];
+    env.get_template("chat")?
+        .render(minijinja::context! {
+            messages => messages,
+            add_generation_prompt => true,
+            bos_token => "",
+            eos_token => eos_token.unwrap_or_default(),
+        })
+        .map_err(|e| anyhow!("Failed to render chat template: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::summary_engine::gguf::{read_metadata_from, tests::build_gguf, GgufValue};
+
+    fn metadata(entries: &[(&str, GgufValue)]) -> GgufMetadata {
+        read_metadata_from(&mut build_gguf(entries).as_slice()).unwrap()
+    }
+
+    fn string(value: &str) -> GgufValue {
+        GgufValue::String(value.to_string())
+    }
+
+    #[test]
+    fn test_choose_template_from_markers_and_architecture() {
+        let qwen = metadata(&[
+            ("general.architecture", string("qwen2")),
+            (
+                "tokenizer.chat_template",
+                string("{% for message in messages %}<|im_start|>{{ message.role }}\n{{ message.content }}<|im_end|>\n{% endfor %}"),
+            ),
+        ]);
+        assert_eq!(choose_template(&qwen), named("chatml"));
+
+        let llama = metadata(&[("general.architecture", string("llama"))]);
+        assert_eq!(choose_template(&llama).template, "llama3");
+        assert_eq!(choose_template(&llama).stop_tokens, vec!["<|eot_id|>"]);
+
+        let unknown = metadata(&[("general.architecture", string("rwkv6"))]);
+        assert_eq!(choose_template(&unknown).template, "chatml");
+    }
+
+    #[test]
+    fn test_unrecognized_embedded_template_is_rendered() {
+        let template = "{{ bos_token }}{% for message in messages %}\
+            {% if message['role'] == 'system' %}### System: {{ message['content'].strip() }}\n\
+            {% elif message['role'] == 'user' %}### User: {{ message['content'] }}\n\
+            {% else %}{{ raise_exception('Unexpected role') }}{% endif %}{% endfor %}\
+            {% if add_generation_prompt %}### Assistant:{% endif %}";
+        let model = metadata(&[
+            ("general.architecture", string("olmo")),
+            ("tokenizer.chat_template", string(template)),
+            ("tokenizer.ggml.eos_token_id", GgufValue::U32(1)),
+            (
+                "tokenizer.ggml.tokens",
+                GgufValue::Array(vec![string("<s>"), string("<|endoftext|>")]),
+            ),
+        ]);
+
+        let choice = choose_template(&model);
+        assert_eq!(choice.template, JINJA_TEMPLATE);
+        assert_eq!(choice.stop_tokens, vec!["<|endoftext|>"]);
+        assert_eq!(
+            render_jinja(template, None, " Summarize. ", "Notes").unwrap(),
+            "### System: Summarize.\n### User: Notes\n### Assistant:"
+        );
+
+        let broken = "{% for message in messages %}{{ raise_exception('System role not supported') }}{% endfor %}";
+        assert!(render_jinja(broken, None, "system", "user").is_err());
+    }
+}
@@ -15,6 +15,7 @@ use tokio_util::sync::CancellationToken;
 
 use super::models;
 use super::sidecar::SidecarManager;
+use crate::summary::llm_client::StreamCallback;
 
 // ============================================================================
 // Request/Response Types
@@ -33,6 +34,11 @@ enum Request {
         top_k: Option<i32>,
         top_p: Option<f32>,
         stop_tokens: Option<Vec<String>>,
+        // Ask the sidecar for incremental token frames
+        stream: Option<bool>,
+        // Constrain sampling to JSON matching this schema (converted to a grammar by the sidecar)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        json_schema: Option<serde_json::Value>,
     },
 }
 
@@ -132,6 +138,77 @@ pub async fn generate_with_builtin(
     system_prompt: &str,
     user_prompt: &str,
     cancellation_token: Option<&CancellationToken>,
+) -> Result<String> {
+    run_generation(
+        app_data_dir,
+        model_name,
+        system_prompt,
+        user_prompt,
+        cancellation_token,
+        None,
+        None,
+    )
+    .await
+}
+
+/// Generate JSON using built-in AI, constrained to match `schema`
+///
+/// The sidecar converts the schema to a grammar and only samples tokens that
+/// keep the output valid, so the result always parses (unless it is cut off
+/// by the token limit).
+pub async fn generate_with_builtin_json(
+    app_data_dir: &PathBuf,
+    model_name: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    cancellation_token: Option<&CancellationToken>,
+    schema: &serde_json::Value,
+) -> Result<String> {
+    run_generation(
+        app_data_dir,
+        model_name,
+        system_prompt,
+        user_prompt,
+        cancellation_token,
+        None,
+        Some(schema),
+    )
+    .await
+}
+
+/// Generate text using built-in AI, forwarding tokens as they are produced
+///
+/// Same as [`generate_with_builtin`], but the sidecar emits incremental token
+/// frames which are passed to `on_delta`. Returns the complete text at the end.
+pub async fn generate_with_builtin_stream(
+    app_data_dir: &PathBuf,
+    model_name: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    cancellation_token: Option<&CancellationToken>,
+    on_delta: &StreamCallback<'_>,
+) -> Result<String> {
+    run_generation(
+        app_data_dir,
+        model_name,
+        system_prompt,
+        user_prompt,
+        cancellation_token,
+        Some(on_delta),
+        None,
+    )
+    .await
+}
+
+/// Shared implementation for streaming, non-streaming and JSON generation
+async fn run_generation(
+    app_data_dir: &PathBuf,
+    model_name: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    cancellation_token: Option<&CancellationToken>,
+    on_delta: Option<&StreamCallback<'_>>,
+    json_schema: Option<&serde_json::Value>,
 ) -> Result<String> {
     // Check cancellation at start
     if let Some(token) = cancellation_token {
@@ -143,16 +220,16 @@ pub async fn generate_with_builtin(
     log::info!("Built-in AI generation request");
     log::info!("Model: {}", model_name);
 
+    // Resolve model path with caching (avoids repeated filesystem I/O)
+    // This also loads the custom model registry if needed
+    let model_path = get_cached_model_path(app_data_dir, model_name)?;
+
     // Get model definition
     let model_def = models::get_model_by_name(model_name)
         .ok_or_else(|| anyhow!("Unknown model: {}", model_name))?;
 
-    // Resolve model path with caching (avoids repeated filesystem I/O)
-    let model_path = get_cached_model_path(app_data_dir, model_name)?;
-
     // Apply model-specific chat template
-    let formatted_prompt =
-        models::format_prompt(&model_def.template, system_prompt, user_prompt)?;
+    let formatted_prompt = models::render_prompt(&model_def, system_prompt, user_prompt)?;
     // Get or initialize sidecar manager
     let manager = {
         let mut global_manager = SIDECAR_MANAGER.lock().await;
@@ -184,19 +261,32 @@ pub async fn generate_with_builtin(
         top_k: Some(model_def.sampling.top_k),
         top_p: Some(model_def.sampling.top_p),
         stop_tokens: Some(model_def.sampling.stop_tokens.clone()),
+        stream: on_delta.map(|_| true),
+        json_schema: json_schema.cloned(),
     };
 
     let request_json = serde_json::to_string(&request)?;
 
-    // Send request with timeout
+    // Send request with timeout (between frames when streaming)
     let timeout = Duration::from_secs(models::GENERATION_TIMEOUT_SECS);
 
     log::info!("Sending generation request to sidecar");
 
+    let send_future = async {
+        match on_delta {
+            Some(on_delta) => {
+                manager
+                    .send_request_streaming(request_json, timeout, on_delta)
+                    .await
+            }
+            None => manager.send_request(request_json, timeout).await,
+        }
+    };
+
     // Race between send_request and cancellation token
     let response_json = if let Some(token) = cancellation_token {
         tokio::select! {
-            result = manager.send_request(request_json, timeout) => {
+            result = send_future => {
                 result?
             }
             _ = token.cancelled() => {
@@ -209,7 +299,7 @@ pub async fn generate_with_builtin(
             }
         }
     } else {
-        manager.send_request(request_json, timeout).await?
+        send_future.await?
     };
 
     // Check cancellation before parsing response
@@ -236,6 +326,25 @@ pub async fn generate_with_builtin(
     }
 }
 
+/// Delete the sidecar's saved prompt cache
+///
+/// Session files hold the prompt of the last summary, i.e. meeting text, and
+/// are not tied to one meeting, so all of them go when a meeting is deleted.
+pub async fn forget_prompt_cache(app_data_dir: &PathBuf) -> Result<()> {
+    if let Ok(manager) = get_sidecar_manager().await {
+        if let Err(e) = manager.forget_sessions().await {
+            log::warn!("Sidecar could not drop its prompt cache: {}", e);
+        }
+    }
+
+    let sessions_dir = models::get_models_directory(app_data_dir).join("sessions");
+    match tokio::fs::remove_dir_all(&sessions_dir).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e).with_context(|| format!("Failed to remove {}", sessions_dir.display())),
+    }
+}
+
 /// Shutdown the global sidecar (graceful cleanup)
 /// Detaches the current manager and spawns a background task to drain active requests
 pub async fn shutdown_sidecar_gracefully() -> Result<()> {
@@ -304,6 +413,8 @@ mod tests {
             top_k: Some(64),
             top_p: Some(0.95),
             stop_tokens: Some(vec!["<end_of_turn>".to_string()]),
+            stream: None,
+            json_schema: None,
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -311,6 +422,8 @@ mod tests {
         assert!(json.contains("\"prompt\":\"test prompt\""));
         assert!(json.contains("\"max_tokens\":512"));
         assert!(json.contains("\"temperature\":1.0"));
+        assert!(json.contains("\"stream\":null"));
+        assert!(!json.contains("json_schema"));
     }
 
     #[test]
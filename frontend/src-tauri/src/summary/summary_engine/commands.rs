@@ -6,6 +6,7 @@ use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager, Runtime, State};
 use tokio::sync::Mutex;
 
+use super::custom_models::{self, GgufInspection};
 use super::model_manager::{DownloadProgress, ModelInfo, ModelManager};
 
 // ============================================================================
@@ -22,9 +23,12 @@ pub async fn init_model_manager<R: Runtime>(app: &AppHandle<R>) -> anyhow::Resul
     let manager = ModelManager::new_with_models_dir(Some(models_dir))?;
     manager.init().await?;
 
+    let manager = Arc::new(manager);
+    crate::model_manager::registry().register(manager.clone());
+
     let state: State<ModelManagerState> = app.state();
     let mut manager_lock = state.0.lock().await;
-    *manager_lock = Some(Arc::new(manager));
+    *manager_lock = Some(manager);
 
     log::info!("Built-in AI model manager initialized");
     Ok(())
@@ -232,6 +236,65 @@ pub async fn builtin_ai_delete_model(
         .map_err(|e| e.to_string())
 }
 
+/// Read a local GGUF file's metadata and the prompt format it would use
+#[tauri::command]
+pub async fn builtin_ai_inspect_gguf(path: String) -> Result<GgufInspection, String> {
+    custom_models::inspect(std::path::Path::new(&path)).map_err(|e| format!("{:#}", e))
+}
+
+/// Register a local GGUF file as a built-in AI model
+#[tauri::command]
+pub async fn builtin_ai_register_model<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, ModelManagerState>,
+    path: String,
+    display_name: Option<String>,
+) -> Result<ModelInfo, String> {
+    let manager = {
+        // Ensure manager is initialized
+        {
+            let manager_lock = state.0.lock().await;
+            if manager_lock.is_none() {
+                drop(manager_lock);
+                init_model_manager(&app)
+                    .await
+                    .map_err(|e| format!("Failed to initialize model manager: {}", e))?;
+            }
+        }
+
+        let manager_lock = state.0.lock().await;
+        manager_lock
+            .as_ref()
+            .ok_or_else(|| "Model manager not initialized".to_string())?
+            .clone()
+    };
+
+    manager
+        .register_custom_model(std::path::Path::new(&path), display_name.as_deref())
+        .await
+        .map_err(|e| format!("{:#}", e))
+}
+
+/// Remove a custom model from the list (its file is kept)
+#[tauri::command]
+pub async fn builtin_ai_unregister_model(
+    state: State<'_, ModelManagerState>,
+    model_name: String,
+) -> Result<bool, String> {
+    let manager = {
+        let manager_lock = state.0.lock().await;
+        manager_lock
+            .as_ref()
+            .ok_or_else(|| "Model manager not initialized".to_string())?
+            .clone()
+    };
+
+    manager
+        .unregister_custom_model(&model_name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Check if a model is ready to use
 #[tauri::command]
 pub async fn builtin_ai_is_model_ready<R: Runtime>(
@@ -346,9 +409,12 @@ pub async fn init_model_manager_at_startup<R: Runtime>(
         .await
         .map_err(|e| format!("Failed to initialize ModelManager: {}", e))?;
 
+    let manager = Arc::new(manager);
+    crate::model_manager::registry().register(manager.clone());
+
     let state: State<ModelManagerState> = app.state();
     let mut manager_lock = state.0.lock().await;
-    *manager_lock = Some(Arc::new(manager));
+    *manager_lock = Some(manager);
 
     log::info!("ModelManager initialized at startup");
     Ok(())
@@ -0,0 +1,316 @@
+// Registry of user-supplied GGUF models for built-in AI
+// Custom models stay where the user keeps them; their definitions (read from
+// the GGUF header at registration) are stored in `custom_models.json` in the
+// models directory and listed alongside the built-in models
+
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use anyhow::{anyhow, Context, Result};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use super::chat_template::{self, TemplateChoice, JINJA_TEMPLATE};
+use super::gguf::{self, GgufMetadata};
+use super::models::{ModelDef, SamplingParams};
+
+/// Registry file in the models directory
+const REGISTRY_FILE_NAME: &str = "custom_models.json";
+
+/// Prefix of custom model names, keeping them apart from built-in "family:variant" names
+pub const CUSTOM_MODEL_PREFIX: &str = "custom:";
+
+/// Context used when the GGUF header doesn't declare one
+const DEFAULT_CONTEXT_SIZE: u32 = 4096;
+
+/// Upper bound for the context window, as used by the built-in models for local inference
+const MAX_CONTEXT_SIZE: u32 = 32768;
+
+/// Registered models, loaded from the registry file of the current models directory
+static REGISTRY: Lazy<RwLock<Vec<ModelDef>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+/// What registering a GGUF file would produce, for previewing in the UI
+#[derive(Debug, Clone, Serialize)]
+pub struct GgufInspection {
+    pub path: PathBuf,
+    pub display_name: String,
+    pub architecture: Option<String>,
+    /// Context length declared by the model (before capping)
+    pub context_length: Option<u64>,
+    pub layer_count: Option<u64>,
+    pub size_mb: u64,
+    pub has_embedded_template: bool,
+    /// Prompt format that will be used
+    pub template: TemplateChoice,
+}
+
+/// Custom models currently registered
+pub fn registered() -> Vec<ModelDef> {
+    REGISTRY.read().unwrap().clone()
+}
+
+/// Reload the registry from `models_dir`; a missing file means no custom models
+pub fn load(models_dir: &Path) -> Result<()> {
+    let models = read_registry(&models_dir.join(REGISTRY_FILE_NAME))?;
+    *REGISTRY.write().unwrap() = models;
+    Ok(())
+}
+
+/// Read a GGUF file's header and describe how it would be registered
+pub fn inspect(path: &Path) -> Result<GgufInspection> {
+    let metadata = gguf::read_metadata(path)?;
+    let size_bytes = std::fs::metadata(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?
+        .len();
+
+    Ok(GgufInspection {
+        path: path.to_path_buf(),
+        display_name: default_display_name(&metadata, path),
+        architecture: metadata.architecture().map(str::to_string),
+        context_length: metadata.arch_u64("context_length"),
+        layer_count: metadata.arch_u64("block_count"),
+        size_mb: size_bytes / (1024 * 1024),
+        has_embedded_template: chat_template::embedded_template(&metadata).is_some(),
+        template: chat_template::choose_template(&metadata),
+    })
+}
+
+/// Register a GGUF file as a built-in AI model
+///
+/// Registering the same file again replaces its entry (picking up a new
+/// display name or a re-read header) under the same model name.
+pub fn register(models_dir: &Path, path: &Path, display_name: Option<&str>) -> Result<ModelDef> {
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("Model file not found: {}", path.display()))?;
+    let metadata = gguf::read_metadata(&path)?;
+    let size_bytes = std::fs::metadata(&path)?.len();
+
+    let registry_path = models_dir.join(REGISTRY_FILE_NAME);
+    let mut models = read_registry(&registry_path)?;
+    let existing = models.iter().position(|m| Path::new(&m.gguf_file) == path);
+
+    let name = match existing {
+        Some(index) => models[index].name.clone(),
+        None => unique_name(&path, &models),
+    };
+    let display_name = display_name
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| default_display_name(&metadata, &path));
+    let model = model_def(name, display_name, &path, &metadata, size_bytes);
+
+    match existing {
+        Some(index) => models[index] = model.clone(),
+        None => models.push(model.clone()),
+    }
+    write_registry(&registry_path, &models)?;
+    *REGISTRY.write().unwrap() = models;
+
+    log::info!(
+        "Registered custom model '{}' ({}, template {})",
+        model.name,
+        path.display(),
+        model.template
+    );
+    Ok(model)
+}
+
+/// Remove a custom model from the registry; the GGUF file itself is left alone
+pub fn unregister(models_dir: &Path, model_name: &str) -> Result<bool> {
+    let registry_path = models_dir.join(REGISTRY_FILE_NAME);
+    let mut models = read_registry(&registry_path)?;
+    let before = models.len();
+    models.retain(|m| m.name != model_name);
+    if models.len() == before {
+        return Ok(false);
+    }
+
+    write_registry(&registry_path, &models)?;
+    *REGISTRY.write().unwrap() = models;
+    log::info!("Unregistered custom model '{}'", model_name);
+    Ok(true)
+}
+
+fn model_def(
+    name: String,
+    display_name: String,
+    path: &Path,
+    metadata: &GgufMetadata,
+    size_bytes: u64,
+) -> ModelDef {
+    let choice = chat_template::choose_template(metadata);
+    let chat_template = (choice.template == JINJA_TEMPLATE)
+        .then(|| chat_template::embedded_template(metadata).map(str::to_string))
+        .flatten();
+    let context_size = metadata
+        .arch_u64("context_length")
+        .map(|length| length.min(MAX_CONTEXT_SIZE as u64) as u32)
+        .unwrap_or(DEFAULT_CONTEXT_SIZE);
+    let architecture = metadata.architecture().unwrap_or("unknown");
+
+    ModelDef {
+        name,
+        display_name,
+        // Absolute path: joining it onto the models directory yields it unchanged
+        gguf_file: path.to_string_lossy().to_string(),
+        template: choice.template,
+        download_url: String::new(),
+        size_mb: size_bytes / (1024 * 1024),
+        context_size,
+        layer_count: metadata.arch_u64("block_count").unwrap_or(0) as u32,
+        // llama.cpp's defaults, as nothing better is known about the model
+        sampling: SamplingParams {
+            temperature: 0.8,
+            top_k: 40,
+            top_p: 0.95,
+            stop_tokens: choice.stop_tokens,
+        },
+        description: format!(
+            "Custom GGUF model ({}, {} token context) from {}",
+            architecture,
+            context_size,
+            path.display()
+        ),
+        custom: true,
+        chat_template,
+    }
+}
+
+fn default_display_name(metadata: &GgufMetadata, path: &Path) -> String {
+    metadata
+        .get_str("general.name")
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .or_else(|| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| "Custom model".to_string())
+}
+
+/// "custom:<file-stem>", with a numeric suffix if that name is taken
+fn unique_name(path: &Path, existing: &[ModelDef]) -> String {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let mut slug = String::with_capacity(stem.len());
+    for c in stem.chars() {
+        if c.is_ascii_alphanumeric() || c == '.' || c == '_' {
+            slug.push(c);
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = match slug.trim_matches('-') {
+        "" => "model",
+        slug => slug,
+    };
+
+    let base = format!("{}{}", CUSTOM_MODEL_PREFIX, slug);
+    let mut name = base.clone();
+    let mut suffix = 2;
+    while existing.iter().any(|m| m.name == name) {
+        name = format!("{}-{}", base, suffix);
+        suffix += 1;
+    }
+    name
+}
+
+fn read_registry(path: &Path) -> Result<Vec<ModelDef>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let models: Vec<ModelDef> = serde_json::from_str(&contents)
+        .map_err(|e| anyhow!("Invalid model registry {}: {}", path.display(), e))?;
+    Ok(models
+        .into_iter()
+        .map(|m| ModelDef { custom: true, ..m })
+        .collect())
+}
+
+fn write_registry(path: &Path, models: &[ModelDef]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(models)?;
+    // Write then rename so a crash can't leave a truncated registry
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, json)?;
+    std::fs::rename(&tmp_path, path).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::summary_engine::gguf::{tests::build_gguf, GgufValue};
+
+    fn write_model(dir: &Path, file_name: &str) -> PathBuf {
+        let bytes = build_gguf(&[
+            (
+                "general.architecture",
+                GgufValue::String("qwen3".to_string()),
+            ),
+            (
+                "general.name",
+                GgufValue::String("Qwen3 4B Instruct".to_string()),
+            ),
+            ("qwen3.context_length", GgufValue::U32(262144)),
+            ("qwen3.block_count", GgufValue::U32(36)),
+        ]);
+        let path = dir.join(file_name);
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_register_reads_header_and_persists() {
+        let models_dir = tempfile::tempdir().unwrap();
+        let files_dir = tempfile::tempdir().unwrap();
+        let path = write_model(files_dir.path(), "Qwen3-4B-Instruct-Q4_K_M.gguf");
+
+        let model = register(models_dir.path(), &path, None).unwrap();
+        assert_eq!(model.name, "custom:qwen3-4b-instruct-q4_k_m");
+        assert_eq!(model.display_name, "Qwen3 4B Instruct");
+        assert_eq!(model.template, "qwen");
+        assert_eq!(model.context_size, MAX_CONTEXT_SIZE);
+        assert_eq!(model.layer_count, 36);
+        assert!(model.custom);
+        assert_eq!(
+            models_dir.path().join("summary").join(&model.gguf_file),
+            path.canonicalize().unwrap()
+        );
+
+        // Re-registering the same file keeps its name; a copy gets a new one
+        let renamed = register(models_dir.path(), &path, Some("My Qwen")).unwrap();
+        assert_eq!(renamed.name, model.name);
+        let copy_dir = tempfile::tempdir().unwrap();
+        let copy = write_model(copy_dir.path(), "Qwen3-4B-Instruct-Q4_K_M.gguf");
+        let second = register(models_dir.path(), &copy, None).unwrap();
+        assert_eq!(second.name, "custom:qwen3-4b-instruct-q4_k_m-2");
+
+        let stored = read_registry(&models_dir.path().join(REGISTRY_FILE_NAME)).unwrap();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].display_name, "My Qwen");
+
+        assert!(unregister(models_dir.path(), &second.name).unwrap());
+        assert!(!unregister(models_dir.path(), &second.name).unwrap());
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_register_rejects_non_gguf() {
+        let models_dir = tempfile::tempdir().unwrap();
+        let path = models_dir.path().join("notes.txt");
+        std::fs::write(&path, "not a model").unwrap();
+        assert!(register(models_dir.path(), &path, None).is_err());
+        assert!(registered()
+            .iter()
+            .all(|m| m.gguf_file != path.to_string_lossy()));
+    }
+}
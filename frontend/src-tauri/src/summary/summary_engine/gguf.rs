@@ -0,0 +1,362 @@
+// GGUF metadata reader
+// Parses the key/value header of a GGUF file without loading any tensors
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+
+/// Upper bound for a single string/array length, protects against corrupt headers
+const MAX_ELEMENT_COUNT: u64 = 64 * 1024 * 1024;
+
+/// Most elements/bytes reserved up front; lengths come from the file, so
+/// larger values only grow as data actually arrives
+const MAX_PREALLOCATION: usize = 4096;
+
+/// A single metadata value from a GGUF header
+#[derive(Debug, Clone, PartialEq)]
+pub enum GgufValue {
+    U8(u8),
+    I8(i8),
+    U16(u16),
+    I16(i16),
+    U32(u32),
+    I32(i32),
+    U64(u64),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    Bool(bool),
+    String(String),
+    Array(Vec<GgufValue>),
+}
+
+impl GgufValue {
+    /// Integer value as u64 (any integer type, negatives rejected)
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            GgufValue::U8(v) => Some(v as u64),
+            GgufValue::U16(v) => Some(v as u64),
+            GgufValue::U32(v) => Some(v as u64),
+            GgufValue::U64(v) => Some(v),
+            GgufValue::I8(v) if v >= 0 => Some(v as u64),
+            GgufValue::I16(v) if v >= 0 => Some(v as u64),
+            GgufValue::I32(v) if v >= 0 => Some(v as u64),
+            GgufValue::I64(v) if v >= 0 => Some(v as u64),
+            _ => None,
+        }
+    }
+
+    pub fn as_f32(&self) -> Option<f32> {
+        match *self {
+            GgufValue::F32(v) => Some(v),
+            GgufValue::F64(v) => Some(v as f32),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            GgufValue::Bool(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            GgufValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[GgufValue]> {
+        match self {
+            GgufValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+/// Parsed GGUF header metadata
+#[derive(Debug, Clone)]
+pub struct GgufMetadata {
+    pub version: u32,
+    pub tensor_count: u64,
+    pub values: HashMap<String, GgufValue>,
+}
+
+impl GgufMetadata {
+    pub fn get(&self, key: &str) -> Option<&GgufValue> {
+        self.values.get(key)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(|v| v.as_str())
+    }
+
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        self.get(key).and_then(|v| v.as_u64())
+    }
+
+    /// Model architecture (e.g., "llama", "gemma3", "qwen2")
+    pub fn architecture(&self) -> Option<&str> {
+        self.get_str("general.architecture")
+    }
+
+    /// Architecture-scoped integer, e.g. `arch_u64("context_length")`
+    /// reads `gemma3.context_length` for a Gemma 3 model
+    pub fn arch_u64(&self, suffix: &str) -> Option<u64> {
+        let arch = self.architecture()?;
+        self.get_u64(&format!("{}.{}", arch, suffix))
+    }
+
+    /// Array of strings (e.g., `tokenizer.ggml.tokens`)
+    pub fn get_string_array(&self, key: &str) -> Option<Vec<String>> {
+        self.get(key)?
+            .as_array()?
+            .iter()
+            .map(|v| v.as_str().map(|s| s.to_string()))
+            .collect()
+    }
+
+    /// Array of floats (e.g., `tokenizer.ggml.scores`)
+    pub fn get_f32_array(&self, key: &str) -> Option<Vec<f32>> {
+        self.get(key)?.as_array()?.iter().map(|v| v.as_f32()).collect()
+    }
+}
+
+/// Read the metadata header of a GGUF file
+pub fn read_metadata(path: &Path) -> Result<GgufMetadata> {
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open GGUF file: {}", path.display()))?;
+    read_metadata_from(&mut BufReader::new(file))
+        .with_context(|| format!("Failed to read GGUF metadata from {}", path.display()))
+}
+
+/// Read GGUF metadata from any reader positioned at the start of the file
+pub fn read_metadata_from<R: Read>(reader: &mut R) -> Result<GgufMetadata> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != b"GGUF" {
+        return Err(anyhow!("Not a GGUF file (magic {:?})", magic));
+    }
+
+    let version = read_u32(reader)?;
+    if !(2..=3).contains(&version) {
+        return Err(anyhow!("Unsupported GGUF version: {}", version));
+    }
+
+    let tensor_count = read_u64(reader)?;
+    let kv_count = read_u64(reader)?;
+    if kv_count > MAX_ELEMENT_COUNT {
+        return Err(anyhow!("Implausible metadata count: {}", kv_count));
+    }
+
+    let mut values = HashMap::with_capacity((kv_count as usize).min(MAX_PREALLOCATION));
+    for _ in 0..kv_count {
+        let key = read_string(reader)?;
+        let value_type = read_u32(reader)?;
+        let value = read_value(reader, value_type)?;
+        values.insert(key, value);
+    }
+
+    Ok(GgufMetadata {
+        version,
+        tensor_count,
+        values,
+    })
+}
+
+fn read_value<R: Read>(reader: &mut R, value_type: u32) -> Result<GgufValue> {
+    Ok(match value_type {
+        0 => GgufValue::U8(read_array::<1, R>(reader)?[0]),
+        1 => GgufValue::I8(read_array::<1, R>(reader)?[0] as i8),
+        2 => GgufValue::U16(u16::from_le_bytes(read_array(reader)?)),
+        3 => GgufValue::I16(i16::from_le_bytes(read_array(reader)?)),
+        4 => GgufValue::U32(read_u32(reader)?),
+        5 => GgufValue::I32(i32::from_le_bytes(read_array(reader)?)),
+        6 => GgufValue::F32(f32::from_le_bytes(read_array(reader)?)),
+        7 => GgufValue::Bool(read_array::<1, R>(reader)?[0] != 0),
+        8 => GgufValue::String(read_string(reader)?),
+        9 => {
+            let item_type = read_u32(reader)?;
+            let len = read_u64(reader)?;
+            if len > MAX_ELEMENT_COUNT {
+                return Err(anyhow!("Implausible array length: {}", len));
+            }
+            let mut items = Vec::with_capacity((len as usize).min(MAX_PREALLOCATION));
+            for _ in 0..len {
+                items.push(read_value(reader, item_type)?);
+            }
+            GgufValue::Array(items)
+        }
+        10 => GgufValue::U64(read_u64(reader)?),
+        11 => GgufValue::I64(i64::from_le_bytes(read_array(reader)?)),
+        12 => GgufValue::F64(f64::from_le_bytes(read_array(reader)?)),
+        other => return Err(anyhow!("Unknown GGUF value type: {}", other)),
+    })
+}
+
+fn read_array<const N: usize, R: Read>(reader: &mut R) -> Result<[u8; N]> {
+    let mut buf = [0u8; N];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32> {
+    Ok(u32::from_le_bytes(read_array(reader)?))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64> {
+    Ok(u64::from_le_bytes(read_array(reader)?))
+}
+
+fn read_string<R: Read>(reader: &mut R) -> Result<String> {
+    let len = read_u64(reader)?;
+    if len > MAX_ELEMENT_COUNT {
+        return Err(anyhow!("Implausible string length: {}", len));
+    }
+    let mut buf = Vec::with_capacity((len as usize).min(MAX_PREALLOCATION));
+    reader.take(len).read_to_end(&mut buf)?;
+    if buf.len() as u64 != len {
+        return Err(anyhow!(
+            "GGUF string truncated ({} of {} bytes)",
+            buf.len(),
+            len
+        ));
+    }
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Builds a minimal GGUF v3 header with the given key/value pairs
+    pub(crate) fn build_gguf(entries: &[(&str, GgufValue)]) -> Vec<u8> {
+        fn write_string(out: &mut Vec<u8>, s: &str) {
+            out.extend_from_slice(&(s.len() as u64).to_le_bytes());
+            out.extend_from_slice(s.as_bytes());
+        }
+
+        fn type_id(value: &GgufValue) -> u32 {
+            match value {
+                GgufValue::U8(_) => 0,
+                GgufValue::I8(_) => 1,
+                GgufValue::U16(_) => 2,
+                GgufValue::I16(_) => 3,
+                GgufValue::U32(_) => 4,
+                GgufValue::I32(_) => 5,
+                GgufValue::F32(_) => 6,
+                GgufValue::Bool(_) => 7,
+                GgufValue::String(_) => 8,
+                GgufValue::Array(_) => 9,
+                GgufValue::U64(_) => 10,
+                GgufValue::I64(_) => 11,
+                GgufValue::F64(_) => 12,
+            }
+        }
+
+        fn write_value(out: &mut Vec<u8>, value: &GgufValue) {
+            match value {
+                GgufValue::U8(v) => out.push(*v),
+                GgufValue::I8(v) => out.push(*v as u8),
+                GgufValue::U16(v) => out.extend_from_slice(&v.to_le_bytes()),
+                GgufValue::I16(v) => out.extend_from_slice(&v.to_le_bytes()),
+                GgufValue::U32(v) => out.extend_from_slice(&v.to_le_bytes()),
+                GgufValue::I32(v) => out.extend_from_slice(&v.to_le_bytes()),
+                GgufValue::U64(v) => out.extend_from_slice(&v.to_le_bytes()),
+                GgufValue::I64(v) => out.extend_from_slice(&v.to_le_bytes()),
+                GgufValue::F32(v) => out.extend_from_slice(&v.to_le_bytes()),
+                GgufValue::F64(v) => out.extend_from_slice(&v.to_le_bytes()),
+                GgufValue::Bool(v) => out.push(*v as u8),
+                GgufValue::String(s) => write_string(out, s),
+                GgufValue::Array(items) => {
+                    let item_type = items.first().map(type_id).unwrap_or(8);
+                    out.extend_from_slice(&item_type.to_le_bytes());
+                    out.extend_from_slice(&(items.len() as u64).to_le_bytes());
+                    for item in items {
+                        write_value(out, item);
+                    }
+                }
+            }
+        }
+
+        let mut out = Vec::new();
+        out.extend_from_slice(b"GGUF");
+        out.extend_from_slice(&3u32.to_le_bytes());
+        out.extend_from_slice(&0u64.to_le_bytes());
+        out.extend_from_slice(&(entries.len() as u64).to_le_bytes());
+        for (key, value) in entries {
+            write_string(&mut out, key);
+            out.extend_from_slice(&type_id(value).to_le_bytes());
+            write_value(&mut out, value);
+        }
+        out
+    }
+
+    #[test]
+    fn test_read_metadata_roundtrip() {
+        let bytes = build_gguf(&[
+            ("general.architecture", GgufValue::String("gemma3".to_string())),
+            ("gemma3.context_length", GgufValue::U32(32768)),
+            ("gemma3.block_count", GgufValue::U32(26)),
+            (
+                "tokenizer.ggml.tokens",
+                GgufValue::Array(vec![
+                    GgufValue::String("<unk>".to_string()),
+                    GgufValue::String("▁hi".to_string()),
+                ]),
+            ),
+        ]);
+
+        let metadata = read_metadata_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(metadata.version, 3);
+        assert_eq!(metadata.architecture(), Some("gemma3"));
+        assert_eq!(metadata.arch_u64("context_length"), Some(32768));
+        assert_eq!(metadata.arch_u64("block_count"), Some(26));
+        assert_eq!(
+            metadata.get_string_array("tokenizer.ggml.tokens"),
+            Some(vec!["<unk>".to_string(), "▁hi".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_truncated_lengths_fail_without_huge_allocations() {
+        let header = |count: u64| {
+            let mut out = Vec::new();
+            out.extend_from_slice(b"GGUF");
+            out.extend_from_slice(&3u32.to_le_bytes());
+            out.extend_from_slice(&0u64.to_le_bytes());
+            out.extend_from_slice(&count.to_le_bytes());
+            out
+        };
+
+        // A key announcing a 60 MiB string followed by a few bytes
+        let mut bytes = header(1);
+        bytes.extend_from_slice(&(60u64 * 1024 * 1024).to_le_bytes());
+        bytes.extend_from_slice(b"short");
+        let err = read_metadata_from(&mut bytes.as_slice()).unwrap_err();
+        assert!(err.to_string().contains("truncated"), "{}", err);
+
+        // An array announcing 60M elements with none present
+        let mut bytes = header(1);
+        bytes.extend_from_slice(&3u64.to_le_bytes());
+        bytes.extend_from_slice(b"key");
+        bytes.extend_from_slice(&9u32.to_le_bytes());
+        bytes.extend_from_slice(&4u32.to_le_bytes());
+        bytes.extend_from_slice(&(60u64 * 1024 * 1024).to_le_bytes());
+        assert!(read_metadata_from(&mut bytes.as_slice()).is_err());
+
+        // Millions of announced keys and nothing after them
+        assert!(read_metadata_from(&mut header(60 * 1024 * 1024).as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_rejects_non_gguf() {
+        let bytes = b"ggml\x00\x00\x00\x00".to_vec();
+        assert!(read_metadata_from(&mut bytes.as_slice()).is_err());
+    }
+}
@@ -1,60 +1,27 @@
 // Model manager for built-in AI models - handles downloads and lifecycle
 // Follows the same pattern as whisper_engine/whisper_engine.rs for consistency
 
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
-use reqwest::Client;
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
-use tokio::fs::{self, OpenOptions};
-use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::fs;
 use tokio::sync::RwLock;
-use tokio::time::timeout;
 
+use super::custom_models;
 use super::models::{get_available_models, get_model_by_name};
+use crate::model_integrity::{self, ModelVerification};
+use crate::model_manager::{self, CatalogEntry, EngineKind, ModelEngine};
+
+pub use crate::model_manager::DownloadProgress;
 
 // ============================================================================
 // Model Status Types
 // ============================================================================
 
-/// Detailed download progress info (MB-based with speed)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DownloadProgress {
-    /// Bytes downloaded so far
-    pub downloaded_bytes: u64,
-    /// Total file size in bytes
-    pub total_bytes: u64,
-    /// Downloaded in MB (for display)
-    pub downloaded_mb: f64,
-    /// Total size in MB (for display)
-    pub total_mb: f64,
-    /// Download speed in MB/s
-    pub speed_mbps: f64,
-    /// Percentage complete (0-100)
-    pub percent: u8,
-}
-
-impl DownloadProgress {
-    pub fn new(downloaded: u64, total: u64, speed_mbps: f64) -> Self {
-        let percent = if total > 0 {
-            ((downloaded as f64 / total as f64) * 100.0) as u8
-        } else {
-            0
-        };
-        Self {
-            downloaded_bytes: downloaded,
-            total_bytes: total,
-            downloaded_mb: downloaded as f64 / (1024.0 * 1024.0),
-            total_mb: total as f64 / (1024.0 * 1024.0),
-            speed_mbps,
-            percent,
-        }
-    }
-}
-
 /// Model status in the system
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -101,6 +68,9 @@ pub struct ModelInfo {
 
     /// GGUF filename on disk
     pub gguf_file: String,
+
+    /// Registered by the user from a local GGUF file
+    pub custom: bool,
 }
 
 // ============================================================================
@@ -113,12 +83,6 @@ pub struct ModelManager {
 
     /// Currently available models with their status
     available_models: Arc<RwLock<HashMap<String, ModelInfo>>>,
-
-    /// Active downloads (model names)
-    active_downloads: Arc<RwLock<HashSet<String>>>,
-
-    /// Cancellation flag for current download
-    cancel_download_flag: Arc<RwLock<Option<String>>>,
 }
 
 impl ModelManager {
@@ -159,8 +123,6 @@ impl ModelManager {
         Ok(Self {
             models_dir,
             available_models: Arc::new(RwLock::new(HashMap::new())),
-            active_downloads: Arc::new(RwLock::new(HashSet::new())),
-            cancel_download_flag: Arc::new(RwLock::new(None)),
         })
     }
 
@@ -187,41 +149,43 @@ impl ModelManager {
             self.models_dir.display()
         );
 
+        // Pick up custom models registered since the last scan
+        if let Err(e) = custom_models::load(&self.models_dir) {
+            log::error!("Failed to load custom model registry: {}", e);
+        }
+
         let model_defs = get_available_models();
         let mut models_map = HashMap::new();
 
         for model_def in model_defs {
-            let model_path = self.models_dir.join(&model_def.gguf_file);
+            let model_path = model_def.file_path(&self.models_dir);
             log::debug!(
                 "Checking model '{}' at path: {}",
                 model_def.name,
                 model_path.display()
             );
 
-            let is_actively_downloading = {
-                let active = self.active_downloads.read().await;
-                active.contains(&model_def.name)
-            };
-
-            // If actively downloading, preserve existing status from memory
-            if is_actively_downloading {
-                let existing_info = {
-                    let models = self.available_models.read().await;
-                    models.get(&model_def.name).cloned()
-                };
-
-                if let Some(info) = existing_info {
-                    // Preserve existing status (should be Downloading)
-                    models_map.insert(model_def.name.clone(), info);
-                    log::debug!(
-                        "Model '{}': Preserving Downloading status during scan",
-                        model_def.name
+            let status = if let Some(progress) =
+                model_manager::registry().progress(EngineKind::BuiltinAi, &model_def.name)
+            {
+                // The file is still a .part download
+                log::debug!("Model '{}': DOWNLOADING", model_def.name);
+                ModelStatus::Downloading {
+                    progress: progress.percent,
+                }
+            } else if model_def.custom {
+                // User files have no expected size; the header was checked at registration
+                if model_path.exists() {
+                    ModelStatus::Available
+                } else {
+                    log::warn!(
+                        "Custom model '{}': file missing at {}",
+                        model_def.name,
+                        model_path.display()
                     );
-                    continue;
+                    ModelStatus::Error(format!("Model file not found: {}", model_path.display()))
                 }
-            }
-
-            let status = if model_path.exists() {
+            } else if model_path.exists() {
                 // Check if file size matches expected size (basic validation)
                 match fs::metadata(&model_path).await {
                     Ok(metadata) => {
@@ -278,6 +242,7 @@ impl ModelManager {
                 context_size: model_def.context_size,
                 description: model_def.description.clone(),
                 gguf_file: model_def.gguf_file.clone(),
+                custom: model_def.custom,
             };
 
             models_map.insert(model_def.name.clone(), model_info);
@@ -304,6 +269,7 @@ impl ModelManager {
             .await
             .values()
             .cloned()
+            .map(with_download_progress)
             .collect()
     }
 
@@ -314,6 +280,7 @@ impl ModelManager {
             .await
             .get(model_name)
             .cloned()
+            .map(with_download_progress)
     }
 
     /// Check if a model is ready to use
@@ -337,436 +304,84 @@ impl ModelManager {
     pub async fn download_model(
         &self,
         model_name: &str,
-        progress_callback: Option<Box<dyn Fn(u8) + Send>>,
+        progress_callback: Option<Box<dyn Fn(u8) + Send + Sync>>,
     ) -> Result<()> {
         // Wrap the simple callback to use detailed progress internally
-        let detailed_callback: Option<Box<dyn Fn(DownloadProgress) + Send>> =
+        let detailed_callback: Option<Box<dyn Fn(DownloadProgress) + Send + Sync>> =
             progress_callback.map(|cb| {
-                Box::new(move |p: DownloadProgress| cb(p.percent)) as Box<dyn Fn(DownloadProgress) + Send>
+                Box::new(move |p: DownloadProgress| cb(p.percent)) as Box<dyn Fn(DownloadProgress) + Send + Sync>
             });
         self.download_model_detailed(model_name, detailed_callback).await
     }
 
     /// Download a model with detailed progress (MB, speed, etc.)
+    /// A download that fails SHA-256 verification is quarantined and downloaded again
     pub async fn download_model_detailed(
         &self,
         model_name: &str,
-        progress_callback: Option<Box<dyn Fn(DownloadProgress) + Send>>,
+        progress_callback: Option<Box<dyn Fn(DownloadProgress) + Send + Sync>>,
     ) -> Result<()> {
         log::info!("Starting download for model: {}", model_name);
 
-        // Check if already downloading
-        {
-            let active = self.active_downloads.read().await;
-            if active.contains(model_name) {
-                log::warn!("Download already in progress for model: {}", model_name);
-                return Err(anyhow!("Download already in progress"));
-            }
-        }
-
-        // Get model definition
         let model_def = get_model_by_name(model_name)
             .ok_or_else(|| anyhow!("Unknown model: {}", model_name))?;
-
-        // Add to active downloads
-        {
-            let mut active = self.active_downloads.write().await;
-            active.insert(model_name.to_string());
-        }
-
-        // Clear cancellation flag
-        {
-            let mut cancel_flag = self.cancel_download_flag.write().await;
-            *cancel_flag = None;
-        }
-
-        // Update status to downloading
-        {
-            let mut models = self.available_models.write().await;
-            if let Some(model_info) = models.get_mut(model_name) {
-                model_info.status = ModelStatus::Downloading { progress: 0 };
-            }
+        if model_def.custom {
+            return Err(anyhow!("Custom model '{}' is a local file and can't be downloaded", model_name));
         }
 
-        let file_path = self.models_dir.join(&model_def.gguf_file);
-
-        // Check if model already exists and is valid (skip re-download)
-        if file_path.exists() {
-            if let Ok(metadata) = fs::metadata(&file_path).await {
-                let file_size_mb = metadata.len() / (1024 * 1024);
-                let expected_min = (model_def.size_mb as f64 * 0.9) as u64;
-                let expected_max = (model_def.size_mb as f64 * 1.1) as u64;
-
-                if file_size_mb >= expected_min && file_size_mb <= expected_max {
-                    log::info!(
-                        "Model '{}' already exists and is valid ({} MB), skipping download",
-                        model_name,
-                        file_size_mb
-                    );
-
-                    // Update status to available
-                    {
-                        let mut models = self.available_models.write().await;
-                        if let Some(model_info) = models.get_mut(model_name) {
-                            model_info.status = ModelStatus::Available;
-                        }
-                    }
-
-                    // Remove from active downloads
-                    {
-                        let mut active = self.active_downloads.write().await;
-                        active.remove(model_name);
-                    }
-
-                    // Report 100% progress
-                    if let Some(ref callback) = progress_callback {
-                        let total = metadata.len();
-                        callback(DownloadProgress::new(total, total, 0.0));
-                    }
-
-                    return Ok(());
-                } else if file_size_mb > expected_max {
-                    // File is LARGER than expected - possibly corrupted or wrong file
-                    // Delete and re-download in this case
-                    log::warn!(
-                        "Model '{}' exists but is too large ({} MB, expected max {} MB), deleting and re-downloading",
-                        model_name,
-                        file_size_mb,
-                        expected_max
-                    );
-                    if let Err(e) = fs::remove_file(&file_path).await {
-                        log::warn!("Failed to delete oversized model file: {}", e);
-                    }
-                } else {
-                    // File is SMALLER than expected - likely partial download
-                    // DON'T DELETE - let resume logic handle it
-                    log::info!(
-                        "Model '{}' exists but is incomplete ({} MB, expected min {} MB), will resume download",
-                        model_name,
-                        file_size_mb,
-                        expected_min
-                    );
-                    // Continue to download/resume logic below
-                }
+        let on_progress = |progress: &DownloadProgress| {
+            if let Some(ref callback) = progress_callback {
+                callback(progress.clone());
             }
-        }
-
-        log::info!("Downloading from: {}", model_def.download_url);
-        log::info!("Saving to: {}", file_path.display());
-
-        // Create models directory if needed
-        if !self.models_dir.exists() {
-            fs::create_dir_all(&self.models_dir).await?;
-        }
-
-        // Check for existing partial download to resume
-        let existing_size: u64 = if file_path.exists() {
-            fs::metadata(&file_path)
-                .await
-                .map(|m| m.len())
-                .unwrap_or(0)
-        } else {
-            0
         };
-
-        // Download the file with optimized client settings
-        let client = Client::builder()
-            .tcp_nodelay(true) // Disable Nagle's algorithm for faster streaming
-            .pool_max_idle_per_host(1) // Keep connection alive
-            .timeout(Duration::from_secs(3600)) // 1 hour timeout for large files
-            .connect_timeout(Duration::from_secs(30))
-            .build()
-            .map_err(|e| anyhow!("Failed to create HTTP client: {}", e))?;
-
-        // Build request with Range header if resuming
-        let mut request = client.get(&model_def.download_url);
-        if existing_size > 0 {
-            log::info!(
-                "Resuming download from byte {} ({:.1} MB)",
-                existing_size,
-                existing_size as f64 / (1024.0 * 1024.0)
-            );
-            request = request.header("Range", format!("bytes={}-", existing_size));
-        }
-
-        let response = request
-            .send()
+        model_manager::registry()
+            .download(EngineKind::BuiltinAi, model_name, Some(&on_progress))
             .await
-            .map_err(|e| anyhow!("Failed to start download: {}", e))?;
-
-        // Check response status - 200 OK (full download) or 206 Partial Content (resume)
-        let (total_size, resuming) = if response.status() == reqwest::StatusCode::PARTIAL_CONTENT {
-            // Server supports resume - total size = existing + remaining
-            let remaining = response.content_length().unwrap_or(0);
-            log::info!("Server supports resume, {} MB remaining", remaining / (1024 * 1024));
-            (existing_size + remaining, true)
-        } else if response.status().is_success() {
-            // Server doesn't support resume or fresh download
-            if existing_size > 0 {
-                log::warn!("Server doesn't support resume, starting fresh download");
-            }
-            (response.content_length().unwrap_or(0), false)
-        } else {
-            let mut active = self.active_downloads.write().await;
-            active.remove(model_name);
-            return Err(anyhow!("Download failed with status: {}", response.status()));
-        };
-
-        log::info!("Total size: {} MB", total_size / (1024 * 1024));
-
-        // Open file for append if resuming, or create new
-        let file = if resuming {
-            OpenOptions::new()
-                .write(true)
-                .append(true)
-                .open(&file_path)
-                .await
-                .map_err(|e| anyhow!("Failed to open file for append: {}", e))?
-        } else {
-            fs::File::create(&file_path)
-                .await
-                .map_err(|e| anyhow!("Failed to create file: {}", e))?
-        };
-
-        // Use 8MB buffer to reduce disk I/O syscalls (major performance improvement)
-        let mut writer = BufWriter::with_capacity(8 * 1024 * 1024, file);
-
-        let mut downloaded: u64 = if resuming { existing_size } else { 0 };
-
-        // Emit initial progress (showing resumed position if applicable)
-        if let Some(ref callback) = progress_callback {
-            callback(DownloadProgress::new(downloaded, total_size, 0.0));
-        }
-        log::info!(
-            "Starting at {:.1} MB / {:.1} MB",
-            downloaded as f64 / (1024.0 * 1024.0),
-            total_size as f64 / (1024.0 * 1024.0)
-        );
-
-        let mut last_progress_percent = if total_size > 0 {
-            ((downloaded as f64 / total_size as f64) * 100.0) as u8
-        } else {
-            0
-        };
-        let mut last_report_time = std::time::Instant::now();
-        let mut bytes_since_last_report: u64 = 0;
-        let download_start_time = std::time::Instant::now();
-        let start_downloaded = downloaded;
-
-        use futures_util::StreamExt;
-        let mut stream = response.bytes_stream();
-
-        loop {
-            // Check for cancellation
-            {
-                let cancel_flag = self.cancel_download_flag.read().await;
-                if cancel_flag.as_ref() == Some(&model_name.to_string()) {
-                    log::info!("Download cancelled for model: {}", model_name);
-
-                    // Flush and keep partial file for resume on next attempt
-                    let _ = writer.flush().await;
-                    drop(writer);
-
-                    // Remove from active downloads
-                    let mut active = self.active_downloads.write().await;
-                    active.remove(model_name);
-
-                    // Update status
-                    {
-                        let mut models = self.available_models.write().await;
-                        if let Some(model_info) = models.get_mut(model_name) {
-                            model_info.status = ModelStatus::NotDownloaded;
-                        }
-                    }
-
-                    // Use special marker prefix to distinguish cancellation from other errors
-                    return Err(anyhow!("CANCELLED: Download cancelled by user"));
-                }
-            }
-
-            // Add per-chunk timeout (30 seconds) to detect stalled connections
-            let next_result = timeout(Duration::from_secs(30), stream.next()).await;
-
-            let chunk = match next_result {
-                // Timeout - no data received for 30 seconds
-                Err(_) => {
-                    log::warn!("Download timeout for {}: no data received for 30 seconds", model_name);
-                    let _ = writer.flush().await;
-
-                    // Cleanup: Remove from active downloads
-                    let mut active = self.active_downloads.write().await;
-                    active.remove(model_name);
-
-                    // Set model status to Error (NOT NotDownloaded) so UI can show retry button
-                    {
-                        let mut models = self.available_models.write().await;
-                        if let Some(model_info) = models.get_mut(model_name) {
-                            model_info.status = ModelStatus::Error("Download timeout - No data received for 30 seconds".to_string());
-                        }
-                    }
-
-                    return Err(anyhow!("Download timeout - No data received for 30 seconds"));
-                },
-                // Stream ended
-                Ok(None) => break,
-                // Got chunk result
-                Ok(Some(chunk_result)) => {
-                    match chunk_result {
-                        Ok(c) => c,
-                        // Detect error type for better user feedback
-                        Err(e) => {
-                            log::error!("Download error for {}: {:?}", model_name, e);
-                            let _ = writer.flush().await;
-
-                            // Cleanup: Remove from active downloads
-                            let mut active = self.active_downloads.write().await;
-                            active.remove(model_name);
-
-                            // Categorize error for user-friendly message
-                            let error_msg = if e.is_timeout() {
-                                "Connection timeout - Check your internet"
-                            } else if e.is_connect() {
-                                "Connection failed - Check your internet"
-                            } else if e.is_body() {
-                                "Stream interrupted - Network unstable"
-                            } else {
-                                "Download error"
-                            };
-
-                            // Set model status to Error (NOT NotDownloaded) so UI can show retry button
-                            {
-                                let mut models = self.available_models.write().await;
-                                if let Some(model_info) = models.get_mut(model_name) {
-                                    model_info.status = ModelStatus::Error(error_msg.to_string());
-                                }
-                            }
-
-                            return Err(anyhow!("{}: {}", error_msg, e));
-                        }
-                    }
-                }
-            };
-            let chunk_len = chunk.len() as u64;
-            writer
-                .write_all(&chunk)
-                .await
-                .map_err(|e| anyhow!("Error writing to file: {}", e))?;
-
-            downloaded += chunk_len;
-            bytes_since_last_report += chunk_len;
-
-            // Calculate progress
-            let progress_percent = if total_size > 0 {
-                let exact_percent = (downloaded as f64 / total_size as f64) * 100.0;
-                exact_percent.min(100.0) as u8
-            } else {
-                0
-            };
-
-            let elapsed_since_report = last_report_time.elapsed();
-            let is_download_complete = downloaded >= total_size;
-            let should_report = progress_percent > last_progress_percent
-                || is_download_complete  // Force report on completion
-                || elapsed_since_report.as_millis() >= 500;
-
-            if should_report {
-                // Calculate speed based on bytes downloaded since last report
-                let speed_mbps = if elapsed_since_report.as_secs_f64() > 0.0 {
-                    (bytes_since_last_report as f64 / (1024.0 * 1024.0)) / elapsed_since_report.as_secs_f64()
+            .map_err(|e| {
+                if model_manager::is_cancelled(&e) {
+                    // Marked so the commands don't report it as a failure
+                    anyhow!("CANCELLED: Download cancelled by user")
                 } else {
-                    // Fallback to overall average speed
-                    let total_elapsed = download_start_time.elapsed().as_secs_f64();
-                    if total_elapsed > 0.0 {
-                        ((downloaded - start_downloaded) as f64 / (1024.0 * 1024.0)) / total_elapsed
-                    } else {
-                        0.0
-                    }
-                };
-
-                log::info!(
-                    "Download: {:.1} MB / {:.1} MB ({:.1} MB/s)",
-                    downloaded as f64 / (1024.0 * 1024.0),
-                    total_size as f64 / (1024.0 * 1024.0),
-                    speed_mbps
-                );
-
-                // Update status
-                {
-                    let mut models = self.available_models.write().await;
-                    if let Some(model_info) = models.get_mut(model_name) {
-                        model_info.status = ModelStatus::Downloading {
-                            progress: if is_download_complete { 100 } else { progress_percent }
-                        };
-                    }
+                    e
                 }
-
-                // Call progress callback with detailed info
-                if let Some(ref callback) = progress_callback {
-                    callback(DownloadProgress::new(downloaded, total_size, speed_mbps));
-                }
-
-                last_progress_percent = progress_percent;
-                last_report_time = std::time::Instant::now();
-                bytes_since_last_report = 0;
-            }
-        }
-
-        writer.flush().await?;
-        drop(writer);
+            })?;
 
         log::info!("Download completed for model: {}", model_name);
+        Ok(())
+    }
 
-        {
-            let mut models = self.available_models.write().await;
-            if let Some(model_info) = models.get_mut(model_name) {
-                model_info.status = ModelStatus::Downloading { progress: 100 };
-            }
-        }
-
-        if let Some(ref callback) = progress_callback {
-            callback(DownloadProgress::new(total_size, total_size, 0.0));
-        }
-
-        // Small delay to ensure UI receives 100% event
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-
-        if let Err(e) = self.validate_gguf_file(&file_path).await {
-            log::error!("Downloaded file failed validation: {}", e);
-
-            // Clean up invalid file
-            let _ = fs::remove_file(&file_path).await;
-
-            // Update status
+    /// Re-check the SHA-256 of every downloaded built-in model, then rescan
+    /// Custom models are the user's own files and aren't checked
+    pub async fn verify_models(&self) -> Vec<ModelVerification> {
+        let mut results = Vec::new();
+        for model_def in get_available_models() {
+            let downloading = model_manager::registry()
+                .progress(EngineKind::BuiltinAi, &model_def.name)
+                .is_some();
+            if model_def.custom
+                || downloading
+                || !model_def.file_path(&self.models_dir).exists()
             {
-                let mut models = self.available_models.write().await;
-                if let Some(model_info) = models.get_mut(model_name) {
-                    model_info.status = ModelStatus::Error(format!("Validation failed: {}", e));
-                }
+                continue;
             }
 
-            // Remove from active downloads
-            let mut active = self.active_downloads.write().await;
-            active.remove(model_name);
-
-            return Err(anyhow!("File validation failed: {}", e));
-        }
-
-        // Update status to available
-        {
-            let mut models = self.available_models.write().await;
-            if let Some(model_info) = models.get_mut(model_name) {
-                model_info.status = ModelStatus::Available;
-                model_info.path = file_path.clone();
-            }
+            let check = model_integrity::verify_installed(
+                &self.models_dir,
+                &model_def.gguf_file,
+                Some(model_def.download_url.as_str()),
+            )
+            .await;
+            results.push(ModelVerification::new(
+                "builtin_ai",
+                &model_def.name,
+                vec![check],
+            ));
         }
 
-        // Remove from active downloads
-        {
-            let mut active = self.active_downloads.write().await;
-            active.remove(model_name);
+        if let Err(e) = self.scan_models().await {
+            log::error!("Failed to rescan models after verification: {}", e);
         }
-
-        Ok(())
+        results
     }
 
     /// Validate that a file is a valid GGUF model
@@ -793,29 +408,13 @@ impl ModelManager {
     }
 
     /// Cancel an ongoing download
+    /// The partial file is kept so the next download resumes where this one stopped
     pub async fn cancel_download(&self, model_name: &str) -> Result<()> {
         log::info!("Cancelling download for model: {}", model_name);
 
-        // Set cancellation flag - download loop will detect this and handle cleanup
-        {
-            let mut cancel_flag = self.cancel_download_flag.write().await;
-            *cancel_flag = Some(model_name.to_string());
+        if !model_manager::registry().cancel(EngineKind::BuiltinAi, model_name) {
+            log::warn!("No download in progress for model: {}", model_name);
         }
-
-        // Note: active_downloads cleanup is handled by the download loop when it detects
-        // the cancellation flag. This avoids double-removal race condition.
-
-        // Update status immediately for UI responsiveness
-        {
-            let mut models = self.available_models.write().await;
-            if let Some(model_info) = models.get_mut(model_name) {
-                model_info.status = ModelStatus::NotDownloaded;
-            }
-        }
-
-        // Brief delay to let download loop detect cancellation
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-
         Ok(())
     }
 
@@ -825,8 +424,15 @@ impl ModelManager {
 
         let model_def = get_model_by_name(model_name)
             .ok_or_else(|| anyhow!("Unknown model: {}", model_name))?;
+        if model_def.custom {
+            // Never delete the user's own file
+            return Err(anyhow!(
+                "Custom model '{}' can only be removed from the list, not deleted",
+                model_name
+            ));
+        }
 
-        let file_path = self.models_dir.join(&model_def.gguf_file);
+        let file_path = model_def.file_path(&self.models_dir);
 
         if file_path.exists() {
             fs::remove_file(&file_path).await?;
@@ -844,8 +450,86 @@ impl ModelManager {
         Ok(())
     }
 
+    /// Register a local GGUF file and rescan so it shows up in the model list
+    pub async fn register_custom_model(
+        &self,
+        path: &std::path::Path,
+        display_name: Option<&str>,
+    ) -> Result<ModelInfo> {
+        let model = custom_models::register(&self.models_dir, path, display_name)?;
+        self.scan_models().await?;
+        self.get_model_info(&model.name)
+            .await
+            .ok_or_else(|| anyhow!("Registered model '{}' missing after scan", model.name))
+    }
+
+    /// Remove a custom model from the list, leaving its file in place
+    pub async fn unregister_custom_model(&self, model_name: &str) -> Result<bool> {
+        let removed = custom_models::unregister(&self.models_dir, model_name)?;
+        if removed {
+            self.available_models.write().await.remove(model_name);
+        }
+        Ok(removed)
+    }
+
     /// Get models directory path
     pub fn get_models_directory(&self) -> PathBuf {
         self.models_dir.clone()
     }
 }
+
+/// Show a running download in place of the status found on disk
+fn with_download_progress(mut info: ModelInfo) -> ModelInfo {
+    if let Some(progress) = model_manager::registry().progress(EngineKind::BuiltinAi, &info.name) {
+        info.status = ModelStatus::Downloading {
+            progress: progress.percent,
+        };
+    }
+    info
+}
+
+#[async_trait]
+impl ModelEngine for ModelManager {
+    fn kind(&self) -> EngineKind {
+        EngineKind::BuiltinAi
+    }
+
+    fn models_dir(&self) -> PathBuf {
+        self.models_dir.clone()
+    }
+
+    /// Built-in models only; custom models are local files with nothing to download
+    fn catalog(&self) -> Vec<CatalogEntry> {
+        get_available_models()
+            .into_iter()
+            .filter(|model_def| !model_def.custom)
+            .map(|model_def| {
+                CatalogEntry::new(
+                    EngineKind::BuiltinAi,
+                    &model_def.name,
+                    &model_def.display_name,
+                    &model_def.description,
+                )
+                .with_file(
+                    &model_def.gguf_file,
+                    &model_def.download_url,
+                    model_def.size_mb * 1024 * 1024,
+                )
+            })
+            .collect()
+    }
+
+    async fn validate(&self, entry: &CatalogEntry) -> Result<()> {
+        for file in &entry.files {
+            self.validate_gguf_file(&self.models_dir.join(&file.path))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn refresh(&self) {
+        if let Err(e) = self.scan_models().await {
+            log::error!("Failed to rescan models: {}", e);
+        }
+    }
+}
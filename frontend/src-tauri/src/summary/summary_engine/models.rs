@@ -1,9 +1,12 @@
 // Model definitions and prompt templates for built-in AI summary generation
-// Designed for easy extension - just add new entries to get_available_models()
+// Designed for easy extension - just add new entries to builtin_models()
+// User-supplied GGUF models are registered at runtime (see custom_models.rs)
 
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use super::{chat_template, custom_models};
 
 // ============================================================================
 // Model Definitions
@@ -36,9 +39,10 @@ pub struct ModelDef {
     pub display_name: String,
 
     /// GGUF filename on disk (e.g., "gemma-3-1b-it-q4_0.gguf")
+    /// Absolute path for custom models, which stay outside the models directory
     pub gguf_file: String,
 
-    /// Template name for prompt formatting (e.g., "gemma3")
+    /// Template name for prompt formatting (e.g., "gemma3", or "jinja" for an embedded template)
     pub template: String,
 
     /// Download URL (HuggingFace or other source)
@@ -59,11 +63,33 @@ pub struct ModelDef {
 
     /// Short description for UI
     pub description: String,
+
+    /// Registered by the user rather than shipped with the app
+    #[serde(default)]
+    pub custom: bool,
+
+    /// Embedded Jinja chat template (custom models using the "jinja" template)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chat_template: Option<String>,
 }
 
-/// Get all available built-in AI models
-/// Add new models here - the system will automatically detect and manage them
+impl ModelDef {
+    /// Location of the model file; custom models keep their absolute path
+    pub fn file_path(&self, models_dir: &Path) -> PathBuf {
+        models_dir.join(&self.gguf_file)
+    }
+}
+
+/// Get all available models: the built-in ones followed by registered custom models
 pub fn get_available_models() -> Vec<ModelDef> {
+    let mut models = builtin_models();
+    models.extend(custom_models::registered());
+    models
+}
+
+/// Models shipped with the app
+/// Add new models here - the system will automatically detect and manage them
+pub fn builtin_models() -> Vec<ModelDef> {
     vec![
         // Gemma 3 1B - Fast tier
         ModelDef {
@@ -82,6 +108,8 @@ pub fn get_available_models() -> Vec<ModelDef> {
                 stop_tokens: vec!["<end_of_turn>".to_string()],
             },
             description: "Fastest model. Runs on any hardware with ~1GB RAM. Good for quick summaries.".to_string(),
+            custom: false,
+            chat_template: None,
         },
         ModelDef {
             name: "gemma3:4b".to_string(),
@@ -99,6 +127,8 @@ pub fn get_available_models() -> Vec<ModelDef> {
                 stop_tokens: vec!["<end_of_turn>".to_string()],
             },
             description: "Balanced model. Great quality/speed trade-off. Requires ~3.5GB RAM.".to_string(),
+            custom: false,
+            chat_template: None,
         },
     ]
 }
@@ -108,9 +138,9 @@ pub fn get_model_by_name(name: &str) -> Option<ModelDef> {
     get_available_models().into_iter().find(|m| m.name == name)
 }
 
-/// Get the default model (first in list)
+/// Get the default model (first built-in model)
 pub fn get_default_model() -> ModelDef {
-    get_available_models()
+    builtin_models()
         .into_iter()
         .next()
         .expect("At least one model must be defined")
@@ -118,13 +148,19 @@ pub fn get_default_model() -> ModelDef {
 
 /// Resolve model name to full file path in the models directory
 pub fn get_model_path(app_data_dir: &PathBuf, model_name: &str) -> Result<PathBuf> {
-    let model = get_model_by_name(model_name)
-        .ok_or_else(|| anyhow!("Unknown model: {}", model_name))?;
-
     let models_dir = get_models_directory(app_data_dir);
-    let model_path = models_dir.join(&model.gguf_file);
 
-    Ok(model_path)
+    // Custom models may be requested before the model manager loaded the registry
+    let model = match get_model_by_name(model_name) {
+        Some(model) => model,
+        None => {
+            custom_models::load(&models_dir)?;
+            get_model_by_name(model_name)
+                .ok_or_else(|| anyhow!("Unknown model: {}", model_name))?
+        }
+    };
+
+    Ok(model.file_path(&models_dir))
 }
 
 /// Get the models directory path for built-in AI
@@ -145,6 +181,39 @@ pub const GEMMA3_TEMPLATE: &str = "\
 <start_of_turn>model
 ";
 
+/// ChatML format (Qwen, Hermes, SmolLM and many fine-tunes)
+pub const CHATML_TEMPLATE: &str = "\
+<|im_start|>system
+{system_prompt}<|im_end|>
+<|im_start|>user
+{user_prompt}<|im_end|>
+<|im_start|>assistant
+";
+
+/// Llama 3.x format (BOS is added by the sidecar)
+pub const LLAMA3_TEMPLATE: &str = "\
+<|start_header_id|>system<|end_header_id|>
+
+{system_prompt}<|eot_id|><|start_header_id|>user<|end_header_id|>
+
+{user_prompt}<|eot_id|><|start_header_id|>assistant<|end_header_id|>
+
+";
+
+/// Mistral instruct format, which has no system role
+pub const MISTRAL_TEMPLATE: &str = "[INST] {system_prompt}
+
+{user_prompt} [/INST]";
+
+/// Phi-3 / Phi-3.5 format
+pub const PHI_TEMPLATE: &str = "\
+<|system|>
+{system_prompt}<|end|>
+<|user|>
+{user_prompt}<|end|>
+<|assistant|>
+";
+
 /// Format a prompt using the specified template
 ///
 /// # Arguments
@@ -161,6 +230,10 @@ pub fn format_prompt(
 ) -> Result<String> {
     let template = match template_name {
         "gemma3" => GEMMA3_TEMPLATE,
+        "chatml" | "qwen" => CHATML_TEMPLATE,
+        "llama3" => LLAMA3_TEMPLATE,
+        "mistral" => MISTRAL_TEMPLATE,
+        "phi" => PHI_TEMPLATE,
         _ => return Err(anyhow!("Unknown template: {}", template_name)),
     };
 
@@ -171,6 +244,24 @@ pub fn format_prompt(
     Ok(formatted)
 }
 
+/// Format a prompt for a model, rendering its embedded template if it uses one
+pub fn render_prompt(model: &ModelDef, system_prompt: &str, user_prompt: &str) -> Result<String> {
+    if model.template != chat_template::JINJA_TEMPLATE {
+        return format_prompt(&model.template, system_prompt, user_prompt);
+    }
+
+    let template = model
+        .chat_template
+        .as_deref()
+        .ok_or_else(|| anyhow!("Model {} has no embedded chat template", model.name))?;
+    chat_template::render_jinja(
+        template,
+        model.sampling.stop_tokens.first().map(String::as_str),
+        system_prompt,
+        user_prompt,
+    )
+}
+
 // ============================================================================
 // Configuration Constants
 // ============================================================================
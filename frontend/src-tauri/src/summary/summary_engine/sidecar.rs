@@ -3,7 +3,7 @@
 
 use std::path::PathBuf;
 use std::process::Stdio;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -52,6 +52,12 @@ pub struct SidecarManager {
 
     /// Idle timeout in seconds (configurable via env var)
     idle_timeout_secs: u64,
+
+    /// Prompt tokens sent since startup (for the prompt cache hit rate)
+    prompt_tokens_total: Arc<AtomicU64>,
+
+    /// Prompt tokens served from the sidecar's KV cache since startup
+    reused_tokens_total: Arc<AtomicU64>,
 }
 
 /// RAII guard for tracking active requests
@@ -101,6 +107,8 @@ impl SidecarManager {
             helper_binary_path,
             current_model_path: Arc::new(RwLock::new(None)),
             idle_timeout_secs,
+            prompt_tokens_total: Arc::new(AtomicU64::new(0)),
+            reused_tokens_total: Arc::new(AtomicU64::new(0)),
         })
     }
 
@@ -375,6 +383,7 @@ impl SidecarManager {
         match tokio::time::timeout(timeout, self.read_response()).await {
             Ok(Ok(response)) => {
                 self.update_activity().await;
+                self.record_cache_stats(&response);
                 Ok(response)
             }
             Ok(Err(e)) => Err(e),
@@ -389,6 +398,122 @@ impl SidecarManager {
         }
     }
 
+    /// Send a streaming request to the sidecar
+    ///
+    /// Token frames (`{"type":"token",...}`) are forwarded to `on_token` as they
+    /// arrive; the first non-token frame is returned as the final response.
+    /// The timeout applies to the wait for each frame, so a long generation
+    /// is only stopped once the sidecar stops producing tokens.
+    pub async fn send_request_streaming(
+        &self,
+        request_json: String,
+        idle_timeout: Duration,
+        on_token: &(dyn Fn(&str) + Send + Sync),
+    ) -> Result<String> {
+        // Track active request
+        let _guard = RequestGuard::new(self.active_request_count.clone());
+
+        // Write request to stdin
+        {
+            let mut stdin_lock = self.stdin_writer.lock().await;
+            let stdin = stdin_lock
+                .as_mut()
+                .ok_or_else(|| anyhow!("Sidecar not running"))?;
+
+            stdin
+                .write_all(request_json.as_bytes())
+                .await
+                .context("Failed to write request to stdin")?;
+            stdin
+                .write_all(b"\n")
+                .await
+                .context("Failed to write newline")?;
+            stdin.flush().await.context("Failed to flush stdin")?;
+        }
+
+        // Resolves to None when no frame arrives within the idle timeout
+        let read_frames = async {
+            loop {
+                let line = match tokio::time::timeout(idle_timeout, self.read_response()).await {
+                    Ok(line) => line?,
+                    Err(_) => return Ok(None),
+                };
+                let frame: serde_json::Value = match serde_json::from_str(&line) {
+                    Ok(frame) => frame,
+                    // Let the caller report unparseable output
+                    Err(_) => return Ok(Some(line)),
+                };
+
+                if frame.get("type").and_then(|t| t.as_str()) == Some("token") {
+                    if let Some(text) = frame.get("text").and_then(|t| t.as_str()) {
+                        on_token(text);
+                    }
+                    // Streaming counts as activity for the idle check
+                    self.update_activity().await;
+                    continue;
+                }
+
+                return Ok(Some(line));
+            }
+        };
+
+        match read_frames.await {
+            Ok(Some(response)) => {
+                self.update_activity().await;
+                self.record_cache_stats(&response);
+                Ok(response)
+            }
+            Ok(None) => {
+                // Stalled - shutdown sidecar to stop generation
+                log::error!(
+                    "Streaming request stalled for {:?}, shutting down sidecar",
+                    idle_timeout
+                );
+                if let Err(shutdown_err) = self.shutdown().await {
+                    log::error!("Failed to shutdown sidecar after timeout: {}", shutdown_err);
+                }
+                Err(anyhow!("Request stalled: no output for {:?}", idle_timeout))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Log prompt cache reuse reported in a `response` frame
+    fn record_cache_stats(&self, response: &str) {
+        let Ok(frame) = serde_json::from_str::<serde_json::Value>(response) else {
+            return;
+        };
+        if frame.get("type").and_then(|t| t.as_str()) != Some("response") {
+            return;
+        }
+        let Some(cache) = frame.get("cache") else {
+            return;
+        };
+
+        let prompt = cache.get("prompt_tokens").and_then(|v| v.as_u64()).unwrap_or(0);
+        let reused = cache.get("reused_tokens").and_then(|v| v.as_u64()).unwrap_or(0);
+        let restored = cache
+            .get("restored_from_disk")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let prompt_total = self.prompt_tokens_total.fetch_add(prompt, Ordering::Relaxed) + prompt;
+        let reused_total = self.reused_tokens_total.fetch_add(reused, Ordering::Relaxed) + reused;
+        let hit_rate = if prompt_total > 0 {
+            reused_total as f64 * 100.0 / prompt_total as f64
+        } else {
+            0.0
+        };
+
+        log::info!(
+            "Prompt cache: reused {}/{} tokens{} (overall hit rate {:.1}%)",
+            reused,
+            prompt,
+            if restored { " from disk" } else { "" },
+            hit_rate
+        );
+    }
+
     /// Read a single line response from stdout
     async fn read_response(&self) -> Result<String> {
         let mut stdout_lock = self.stdout_reader.lock().await;
@@ -440,6 +565,24 @@ impl SidecarManager {
         }
     }
 
+    /// Ask an idle sidecar to drop its prompt cache and delete its session files
+    ///
+    /// Skipped while a request is running, whose response would be mixed up
+    /// with this one; the caller deletes the files on disk either way.
+    pub async fn forget_sessions(&self) -> Result<()> {
+        if !self.is_healthy() || self.active_request_count.load(Ordering::SeqCst) > 0 {
+            return Ok(());
+        }
+        let request = serde_json::json!({"type": "forget_sessions"}).to_string();
+        let response = self.send_request(request, Duration::from_secs(10)).await?;
+        let resp: serde_json::Value = serde_json::from_str(&response)?;
+        if resp.get("type").and_then(|t| t.as_str()) == Some("sessions_forgotten") {
+            Ok(())
+        } else {
+            Err(anyhow!("Unexpected forget_sessions response: {}", response))
+        }
+    }
+
     /// Gracefully shutdown the sidecar
     /// Waits for active requests to complete before killing the process
     pub async fn shutdown_gracefully(&self) -> Result<()> {
@@ -0,0 +1,981 @@
+// Tokenizer abstraction used for token counting and chunking
+// Loads real vocabularies (GGUF, tiktoken BPE tables, tokenizer.json) and
+// falls back to the character heuristic when none is available
+
+use crate::model_integrity::DownloadHasher;
+use crate::summary::llm_client::LLMProvider;
+use crate::summary::summary_engine::{gguf, models};
+use anyhow::{anyhow, Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use tracing::{debug, info, warn};
+
+/// Tokens per character assumed by the heuristic fallback
+pub const HEURISTIC_TOKENS_PER_CHAR: f64 = 0.35;
+
+/// SentencePiece word-boundary marker
+const SPM_SPACE: char = '▁';
+
+/// Where OpenAI publishes its tiktoken tables
+const TIKTOKEN_BASE_URL: &str = "https://openaipublic.blob.core.windows.net/encodings";
+
+/// SHA-256 of each tiktoken table, as pinned by the `tiktoken` package
+const TIKTOKEN_TABLES: &[(&str, &str)] = &[
+    (
+        "cl100k_base",
+        "223921b76ee99bde995b7ff738513eef100fb51d18c93597a113bcffe865b2a7",
+    ),
+    (
+        "o200k_base",
+        "446a9538cb6c348e3516120d7c08b09f57c36495e2acfffe59a5bf8b0cfb1a2d",
+    ),
+];
+
+/// Media type of the weights layer in an Ollama manifest
+const OLLAMA_MODEL_MEDIA_TYPE: &str = "application/vnd.ollama.image.model";
+
+/// Approximation of the GPT-2/cl100k pre-tokenization pattern
+/// (the original relies on look-ahead, which the regex crate does not support)
+static BYTE_LEVEL_PRETOKENIZE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i:'s|'t|'re|'ve|'m|'ll|'d)| ?\p{L}+| ?\p{N}{1,3}| ?[^\s\p{L}\p{N}]+[\r\n]*|\s*[\r\n]+|\s+").unwrap()
+});
+
+/// Splits SentencePiece input before every space so merges stay word-local
+static METASPACE_PRETOKENIZE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r" ?[^ ]+| +").unwrap());
+
+// Loaded tokenizers keyed by provider/model (vocabularies are expensive to parse)
+static TOKENIZER_CACHE: Lazy<RwLock<HashMap<String, Arc<dyn Tokenizer>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Counts tokens and locates token boundaries in text
+pub trait Tokenizer: Send + Sync {
+    /// Short description for logging (e.g., "gguf:gemma3", "tiktoken:cl100k_base")
+    fn name(&self) -> &str;
+
+    /// Byte ranges of the tokens in `text`, in order, covering the whole string
+    fn token_spans(&self, text: &str) -> Vec<Range<usize>>;
+
+    /// Number of tokens in `text`
+    fn count_tokens(&self, text: &str) -> usize {
+        self.token_spans(text).len()
+    }
+}
+
+// ============================================================================
+// Heuristic Fallback
+// ============================================================================
+
+/// Character-count estimate (~0.35 tokens per character)
+pub struct HeuristicTokenizer;
+
+impl Tokenizer for HeuristicTokenizer {
+    fn name(&self) -> &str {
+        "heuristic"
+    }
+
+    fn token_spans(&self, text: &str) -> Vec<Range<usize>> {
+        // Close a pseudo-token every ~2.85 characters
+        let mut spans = Vec::new();
+        let mut start = 0;
+        let mut weight = 0.0;
+        for (idx, ch) in text.char_indices() {
+            weight += HEURISTIC_TOKENS_PER_CHAR;
+            if weight >= 1.0 {
+                let end = idx + ch.len_utf8();
+                spans.push(start..end);
+                start = end;
+                weight -= 1.0;
+            }
+        }
+        if start < text.len() {
+            spans.push(start..text.len());
+        }
+        spans
+    }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        (text.chars().count() as f64 * HEURISTIC_TOKENS_PER_CHAR).ceil() as usize
+    }
+}
+
+// ============================================================================
+// BPE Tokenizer
+// ============================================================================
+
+/// How raw text is mapped to the symbols the vocabulary is written in
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Normalization {
+    /// GPT-2 style: every byte becomes one printable unicode character
+    ByteLevel,
+    /// SentencePiece style: spaces become `▁`, optionally with a leading `▁`
+    Metaspace { add_prefix_space: bool },
+}
+
+/// How to pick the next pair of symbols to merge
+enum MergeRanking {
+    /// tiktoken: merge the pair whose concatenation has the lowest rank
+    TokenRank(HashMap<String, u32>),
+    /// tokenizer.json / GGUF gpt2: merge the pair listed earliest in `merges`,
+    /// keyed by left then right symbol so lookups don't allocate
+    PairRank(HashMap<String, HashMap<String, u32>>),
+    /// SentencePiece: merge the pair whose concatenation has the highest score
+    Score(HashMap<String, f32>),
+}
+
+impl MergeRanking {
+    /// Priority of merging `left` + `right` (lower is better), None if not mergeable
+    ///
+    /// `scratch` holds the concatenation for vocabularies keyed by whole tokens.
+    fn priority(&self, left: &str, right: &str, scratch: &mut String) -> Option<f64> {
+        match self {
+            MergeRanking::TokenRank(ranks) => {
+                ranks.get(join_into(scratch, left, right)).map(|r| *r as f64)
+            }
+            MergeRanking::PairRank(ranks) => ranks.get(left)?.get(right).map(|r| *r as f64),
+            MergeRanking::Score(scores) => {
+                scores.get(join_into(scratch, left, right)).map(|s| -(*s as f64))
+            }
+        }
+    }
+}
+
+/// Concatenates two symbols into a reused buffer
+fn join_into<'a>(scratch: &'a mut String, left: &str, right: &str) -> &'a str {
+    scratch.clear();
+    scratch.push_str(left);
+    scratch.push_str(right);
+    scratch
+}
+
+/// A symbol during merging together with the number of source bytes it covers
+struct Symbol {
+    text: String,
+    source_len: usize,
+}
+
+/// A queued merge of two adjacent symbols
+///
+/// The lengths identify the pair as it was queued; symbols only grow, so a
+/// length mismatch means an earlier merge made the entry stale.
+struct MergeCandidate {
+    priority: f64,
+    left: usize,
+    right: usize,
+    left_len: usize,
+    right_len: usize,
+}
+
+impl Ord for MergeCandidate {
+    /// Best priority first, then leftmost, as a max-heap sees it
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .total_cmp(&self.priority)
+            .then_with(|| other.left.cmp(&self.left))
+    }
+}
+
+impl PartialOrd for MergeCandidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for MergeCandidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MergeCandidate {}
+
+/// Byte-pair-encoding tokenizer loaded from a real vocabulary
+pub struct BpeTokenizer {
+    name: String,
+    normalization: Normalization,
+    ranking: MergeRanking,
+}
+
+impl BpeTokenizer {
+    /// Load a tiktoken BPE table (`<base64 token> <rank>` per line)
+    pub fn from_tiktoken(name: &str, contents: &str) -> Result<Self> {
+        let byte_map = byte_to_unicode();
+        let mut ranks = HashMap::new();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (token_b64, rank) = line
+                .split_once(' ')
+                .ok_or_else(|| anyhow!("Malformed tiktoken line: {}", line))?;
+            let token_bytes = decode_base64(token_b64)
+                .ok_or_else(|| anyhow!("Invalid base64 token: {}", token_b64))?;
+            let rank: u32 = rank.trim().parse().context("Invalid tiktoken rank")?;
+            let token: String = token_bytes.iter().map(|b| byte_map[*b as usize]).collect();
+            ranks.insert(token, rank);
+        }
+
+        if ranks.is_empty() {
+            return Err(anyhow!("Empty tiktoken table"));
+        }
+
+        Ok(Self {
+            name: format!("tiktoken:{}", name),
+            normalization: Normalization::ByteLevel,
+            ranking: MergeRanking::TokenRank(ranks),
+        })
+    }
+
+    /// Load a Hugging Face `tokenizer.json` (BPE or Unigram models)
+    pub fn from_tokenizer_json(name: &str, contents: &str) -> Result<Self> {
+        let json: serde_json::Value =
+            serde_json::from_str(contents).context("Invalid tokenizer.json")?;
+        let model = json
+            .get("model")
+            .ok_or_else(|| anyhow!("tokenizer.json has no model section"))?;
+
+        let normalization = if json_mentions_type(&json["pre_tokenizer"], "ByteLevel") {
+            Normalization::ByteLevel
+        } else {
+            // Metaspace pre-tokenizer, or a normalizer that replaces spaces with ▁
+            let add_prefix_space = find_bool_field(&json["pre_tokenizer"], "add_prefix_space")
+                .or_else(|| {
+                    find_str_field(&json["pre_tokenizer"], "prepend_scheme").map(|s| s != "never")
+                })
+                .unwrap_or_else(|| json_mentions_type(&json["normalizer"], "Prepend"));
+            Normalization::Metaspace { add_prefix_space }
+        };
+
+        let ranking = match model.get("type").and_then(|t| t.as_str()).unwrap_or("BPE") {
+            "BPE" => {
+                let merges = model
+                    .get("merges")
+                    .and_then(|m| m.as_array())
+                    .ok_or_else(|| anyhow!("BPE tokenizer.json has no merges"))?;
+                let mut ranks: HashMap<String, HashMap<String, u32>> = HashMap::new();
+                for (rank, merge) in merges.iter().enumerate() {
+                    // Older files store "a b", newer ones store ["a", "b"]
+                    let pair = match merge {
+                        serde_json::Value::String(s) => s
+                            .split_once(' ')
+                            .map(|(a, b)| (a.to_string(), b.to_string())),
+                        serde_json::Value::Array(parts) if parts.len() == 2 => {
+                            match (parts[0].as_str(), parts[1].as_str()) {
+                                (Some(a), Some(b)) => Some((a.to_string(), b.to_string())),
+                                _ => None,
+                            }
+                        }
+                        _ => None,
+                    };
+                    if let Some((a, b)) = pair {
+                        ranks.entry(a).or_default().entry(b).or_insert(rank as u32);
+                    }
+                }
+                MergeRanking::PairRank(ranks)
+            }
+            "Unigram" => {
+                let vocab = model
+                    .get("vocab")
+                    .and_then(|v| v.as_array())
+                    .ok_or_else(|| anyhow!("Unigram tokenizer.json has no vocab"))?;
+                let scores = vocab
+                    .iter()
+                    .filter_map(|entry| {
+                        let entry = entry.as_array()?;
+                        Some((entry.first()?.as_str()?.to_string(), entry.get(1)?.as_f64()? as f32))
+                    })
+                    .collect();
+                MergeRanking::Score(scores)
+            }
+            other => return Err(anyhow!("Unsupported tokenizer model type: {}", other)),
+        };
+
+        Ok(Self {
+            name: format!("tokenizer.json:{}", name),
+            normalization,
+            ranking,
+        })
+    }
+
+    /// Load the tokenizer embedded in GGUF metadata
+    pub fn from_gguf(metadata: &gguf::GgufMetadata) -> Result<Self> {
+        let tokenizer_model = metadata
+            .get_str("tokenizer.ggml.model")
+            .ok_or_else(|| anyhow!("GGUF file has no tokenizer.ggml.model"))?;
+        let arch = metadata.architecture().unwrap_or("unknown");
+
+        match tokenizer_model {
+            "gpt2" => {
+                let merges = metadata
+                    .get_string_array("tokenizer.ggml.merges")
+                    .ok_or_else(|| anyhow!("GGUF gpt2 tokenizer has no merges"))?;
+                let mut ranks: HashMap<String, HashMap<String, u32>> = HashMap::new();
+                for (rank, merge) in merges.iter().enumerate() {
+                    if let Some((a, b)) = merge.split_once(' ') {
+                        ranks
+                            .entry(a.to_string())
+                            .or_default()
+                            .entry(b.to_string())
+                            .or_insert(rank as u32);
+                    }
+                }
+                Ok(Self {
+                    name: format!("gguf:{}", arch),
+                    normalization: Normalization::ByteLevel,
+                    ranking: MergeRanking::PairRank(ranks),
+                })
+            }
+            "llama" => {
+                let tokens = metadata
+                    .get_string_array("tokenizer.ggml.tokens")
+                    .ok_or_else(|| anyhow!("GGUF llama tokenizer has no tokens"))?;
+                let scores = metadata
+                    .get_f32_array("tokenizer.ggml.scores")
+                    .ok_or_else(|| anyhow!("GGUF llama tokenizer has no scores"))?;
+                let add_prefix_space = metadata
+                    .get("tokenizer.ggml.add_space_prefix")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true);
+                Ok(Self {
+                    name: format!("gguf:{}", arch),
+                    normalization: Normalization::Metaspace { add_prefix_space },
+                    ranking: MergeRanking::Score(tokens.into_iter().zip(scores).collect()),
+                })
+            }
+            other => Err(anyhow!("Unsupported GGUF tokenizer model: {}", other)),
+        }
+    }
+
+    /// Splits a pre-tokenized piece into single-character (or single-byte) symbols
+    fn initial_symbols(&self, piece: &str, at_text_start: bool) -> Vec<Symbol> {
+        match self.normalization {
+            Normalization::ByteLevel => {
+                let byte_map = byte_to_unicode();
+                piece
+                    .bytes()
+                    .map(|b| Symbol {
+                        text: byte_map[b as usize].to_string(),
+                        source_len: 1,
+                    })
+                    .collect()
+            }
+            Normalization::Metaspace { add_prefix_space } => {
+                let mut symbols = Vec::with_capacity(piece.len() + 1);
+                if add_prefix_space && at_text_start && !piece.starts_with(' ') {
+                    symbols.push(Symbol {
+                        text: SPM_SPACE.to_string(),
+                        source_len: 0,
+                    });
+                }
+                for ch in piece.chars() {
+                    let text = if ch == ' ' { SPM_SPACE } else { ch };
+                    symbols.push(Symbol {
+                        text: text.to_string(),
+                        source_len: ch.len_utf8(),
+                    });
+                }
+                symbols
+            }
+        }
+    }
+
+    /// Queues the merge of two adjacent symbols if the vocabulary has it
+    fn push_candidate(
+        &self,
+        queue: &mut BinaryHeap<MergeCandidate>,
+        symbols: &[Option<Symbol>],
+        left: usize,
+        right: usize,
+        scratch: &mut String,
+    ) {
+        let (Some(left_symbol), Some(right_symbol)) = (&symbols[left], &symbols[right]) else {
+            return;
+        };
+        if let Some(priority) = self
+            .ranking
+            .priority(&left_symbol.text, &right_symbol.text, scratch)
+        {
+            queue.push(MergeCandidate {
+                priority,
+                left,
+                right,
+                left_len: left_symbol.text.len(),
+                right_len: right_symbol.text.len(),
+            });
+        }
+    }
+
+    /// Applies merges, best pair first, until no adjacent pair is in the vocabulary
+    ///
+    /// Symbols form a linked list over their original positions and candidate
+    /// pairs wait in a priority queue, so a piece of n symbols takes O(n log n).
+    fn merge(&self, symbols: Vec<Symbol>) -> Vec<Symbol> {
+        let count = symbols.len();
+        if count < 2 {
+            return symbols;
+        }
+
+        let mut symbols: Vec<Option<Symbol>> = symbols.into_iter().map(Some).collect();
+        let mut next: Vec<Option<usize>> = (1..=count).map(|i| (i < count).then_some(i)).collect();
+        let mut prev: Vec<Option<usize>> = (0..count).map(|i| i.checked_sub(1)).collect();
+        let mut queue = BinaryHeap::new();
+        let mut scratch = String::new();
+        for left in 0..count - 1 {
+            self.push_candidate(&mut queue, &symbols, left, left + 1, &mut scratch);
+        }
+
+        while let Some(candidate) = queue.pop() {
+            let (left, right) = (candidate.left, candidate.right);
+            let current = match (&symbols[left], &symbols[right]) {
+                (Some(l), Some(r)) => {
+                    next[left] == Some(right)
+                        && l.text.len() == candidate.left_len
+                        && r.text.len() == candidate.right_len
+                }
+                _ => false,
+            };
+            if !current {
+                continue;
+            }
+
+            let Some(right_symbol) = symbols[right].take() else {
+                continue;
+            };
+            if let Some(left_symbol) = symbols[left].as_mut() {
+                left_symbol.text.push_str(&right_symbol.text);
+                left_symbol.source_len += right_symbol.source_len;
+            }
+            next[left] = next[right];
+            if let Some(after) = next[left] {
+                prev[after] = Some(left);
+            }
+
+            if let Some(before) = prev[left] {
+                self.push_candidate(&mut queue, &symbols, before, left, &mut scratch);
+            }
+            if let Some(after) = next[left] {
+                self.push_candidate(&mut queue, &symbols, left, after, &mut scratch);
+            }
+        }
+        symbols.into_iter().flatten().collect()
+    }
+}
+
+impl Tokenizer for BpeTokenizer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn token_spans(&self, text: &str) -> Vec<Range<usize>> {
+        let pretokenizer = match self.normalization {
+            Normalization::ByteLevel => &*BYTE_LEVEL_PRETOKENIZE_REGEX,
+            Normalization::Metaspace { .. } => &*METASPACE_PRETOKENIZE_REGEX,
+        };
+
+        let mut spans = Vec::new();
+        let mut covered = 0;
+        for piece in pretokenizer.find_iter(text) {
+            // Anything the pattern skipped becomes a token of its own
+            if piece.start() > covered {
+                spans.push(covered..piece.start());
+            }
+
+            let symbols = self.initial_symbols(piece.as_str(), piece.start() == 0);
+            let mut offset = piece.start();
+            for symbol in self.merge(symbols) {
+                spans.push(offset..offset + symbol.source_len);
+                offset += symbol.source_len;
+            }
+            covered = piece.end();
+        }
+        if covered < text.len() {
+            spans.push(covered..text.len());
+        }
+        spans
+    }
+}
+
+// ============================================================================
+// Resolution
+// ============================================================================
+
+/// Resolve the best available tokenizer for a provider/model pair
+///
+/// Lookup order:
+/// 1. User-supplied `tokenizer.json` at `<app_data>/tokenizers/<model>.json`
+///    (model names are sanitized, e.g. `llama3.2:latest` -> `llama3.2_latest.json`)
+/// 2. BuiltInAI: the tokenizer embedded in the model's GGUF file
+/// 3. Ollama: the tokenizer embedded in the model's GGUF blob, when the model
+///    was pulled on this machine
+/// 4. Cloud/OpenAI-compatible providers: the tiktoken table at
+///    `<app_data>/tokenizers/cl100k_base.tiktoken` (or `o200k_base.tiktoken`
+///    for `gpt-4o`, `gpt-4.1`, `gpt-5` and `o1`/`o3`/`o4` models), fetched by
+///    `ensure_tiktoken_table`
+/// 5. The character heuristic
+///
+/// Only real tokenizers are cached, so a table that is downloaded later is
+/// picked up on the next call.
+pub fn resolve_tokenizer(
+    provider: &LLMProvider,
+    model_name: &str,
+    app_data_dir: Option<&PathBuf>,
+) -> Arc<dyn Tokenizer> {
+    let cache_key = format!("{:?}:{}", provider, model_name);
+    if let Ok(cache) = TOKENIZER_CACHE.read() {
+        if let Some(tokenizer) = cache.get(&cache_key) {
+            return tokenizer.clone();
+        }
+    }
+
+    match load_tokenizer(provider, model_name, app_data_dir) {
+        Ok(tokenizer) => {
+            info!("Using {} tokenizer for {}", tokenizer.name(), model_name);
+            if let Ok(mut cache) = TOKENIZER_CACHE.write() {
+                cache.insert(cache_key, tokenizer.clone());
+            }
+            tokenizer
+        }
+        Err(e) => {
+            warn!(
+                "No tokenizer available for {} ({}), using character heuristic",
+                model_name, e
+            );
+            Arc::new(HeuristicTokenizer)
+        }
+    }
+}
+
+/// Download the tiktoken table `resolve_tokenizer` uses for a cloud model into
+/// `<app_data>/tokenizers`, unless it is already there
+///
+/// A no-op for built-in and Ollama models, which carry their own vocabulary.
+pub async fn ensure_tiktoken_table(
+    client: &reqwest::Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    app_data_dir: Option<&PathBuf>,
+) -> Result<()> {
+    if matches!(provider, LLMProvider::BuiltInAI | LLMProvider::Ollama) {
+        return Ok(());
+    }
+    let app_data_dir =
+        app_data_dir.ok_or_else(|| anyhow!("app_data_dir is required for tiktoken tables"))?;
+    let table = tiktoken_table_for(model_name);
+    let path = app_data_dir
+        .join("tokenizers")
+        .join(format!("{}.tiktoken", table));
+    if path.exists() {
+        return Ok(());
+    }
+
+    let (_, sha256) = TIKTOKEN_TABLES
+        .iter()
+        .find(|(name, _)| *name == table)
+        .ok_or_else(|| anyhow!("No pinned hash for {}", table))?;
+    let url = format!("{}/{}.tiktoken", TIKTOKEN_BASE_URL, table);
+    info!("Downloading {} tokenizer table", table);
+    download_tiktoken_table(client, &url, sha256, &path).await
+}
+
+/// Fetch a tiktoken table and store it at `path` if its hash matches
+async fn download_tiktoken_table(
+    client: &reqwest::Client,
+    url: &str,
+    expected_sha256: &str,
+    path: &Path,
+) -> Result<()> {
+    let bytes = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .with_context(|| format!("Failed to download {}", url))?
+        .bytes()
+        .await
+        .with_context(|| format!("Failed to download {}", url))?;
+
+    let mut hasher = DownloadHasher::new();
+    hasher.update(&bytes);
+    let actual = hasher.finalize();
+    if actual != expected_sha256 {
+        return Err(anyhow!(
+            "{} has SHA-256 {}, expected {}",
+            url,
+            actual,
+            expected_sha256
+        ));
+    }
+
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    // Write next to the target first so a crash never leaves a truncated table
+    let part = path.with_extension("tiktoken.part");
+    tokio::fs::write(&part, &bytes).await?;
+    tokio::fs::rename(&part, path).await?;
+    Ok(())
+}
+
+fn load_tokenizer(
+    provider: &LLMProvider,
+    model_name: &str,
+    app_data_dir: Option<&PathBuf>,
+) -> Result<Arc<dyn Tokenizer>> {
+    let user_dir = app_data_dir.map(|dir| dir.join("tokenizers"));
+
+    // 1. User override
+    if let Some(user_dir) = &user_dir {
+        let path = user_dir.join(format!("{}.json", sanitize_model_name(model_name)));
+        if path.exists() {
+            let contents = std::fs::read_to_string(&path)?;
+            return Ok(Arc::new(BpeTokenizer::from_tokenizer_json(model_name, &contents)?));
+        }
+        debug!("No user tokenizer at {:?}", path);
+    }
+
+    match provider {
+        // 2. Built-in models carry their vocabulary in the GGUF file
+        LLMProvider::BuiltInAI => {
+            let app_data_dir =
+                app_data_dir.ok_or_else(|| anyhow!("app_data_dir is required for BuiltInAI"))?;
+            let model_path = models::get_model_path(app_data_dir, model_name)?;
+            let metadata = gguf::read_metadata(&model_path)?;
+            Ok(Arc::new(BpeTokenizer::from_gguf(&metadata)?))
+        }
+        // 3. Locally pulled Ollama models are GGUF blobs as well
+        LLMProvider::Ollama => {
+            let models_dir = ollama_models_dir()
+                .ok_or_else(|| anyhow!("cannot locate the Ollama models directory"))?;
+            let blob = ollama_model_blob(&models_dir, model_name)?;
+            let metadata = gguf::read_metadata(&blob)?;
+            Ok(Arc::new(BpeTokenizer::from_gguf(&metadata)?))
+        }
+        // 4. OpenAI-style BPE tables
+        _ => {
+            let table = tiktoken_table_for(model_name);
+            let user_dir =
+                user_dir.ok_or_else(|| anyhow!("app_data_dir is required for tiktoken tables"))?;
+            let path = user_dir.join(format!("{}.tiktoken", table));
+            if !path.exists() {
+                return Err(anyhow!("no {}.tiktoken table", table));
+            }
+            load_tiktoken_file(&path, table)
+        }
+    }
+}
+
+fn load_tiktoken_file(path: &Path, name: &str) -> Result<Arc<dyn Tokenizer>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(Arc::new(BpeTokenizer::from_tiktoken(name, &contents)?))
+}
+
+/// Ollama's model store: `$OLLAMA_MODELS`, else `~/.ollama/models`
+fn ollama_models_dir() -> Option<PathBuf> {
+    match std::env::var_os("OLLAMA_MODELS") {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => dirs::home_dir().map(|home| home.join(".ollama").join("models")),
+    }
+}
+
+/// GGUF blob of a pulled Ollama model, found through its manifest
+///
+/// `gemma3:1b` lives under `manifests/registry.ollama.ai/library/gemma3/1b`,
+/// `user/model` under `registry.ollama.ai/user/model/latest` and names with a
+/// host under that host.
+fn ollama_model_blob(models_dir: &Path, model_name: &str) -> Result<PathBuf> {
+    let (name, tag) = match model_name.rsplit_once(':') {
+        Some((name, tag)) if !tag.contains('/') => (name, tag),
+        _ => (model_name, "latest"),
+    };
+    let repository = match name.split('/').count() {
+        1 => format!("registry.ollama.ai/library/{}", name),
+        2 => format!("registry.ollama.ai/{}", name),
+        _ => name.to_string(),
+    };
+
+    let mut manifest_path = models_dir.join("manifests");
+    manifest_path.extend(repository.split('/'));
+    manifest_path.push(tag);
+    let manifest: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(&manifest_path)
+            .with_context(|| format!("No Ollama manifest at {}", manifest_path.display()))?,
+    )
+    .context("Invalid Ollama manifest")?;
+
+    let digest = manifest["layers"]
+        .as_array()
+        .and_then(|layers| {
+            layers
+                .iter()
+                .find(|layer| layer["mediaType"] == OLLAMA_MODEL_MEDIA_TYPE)
+        })
+        .and_then(|layer| layer["digest"].as_str())
+        .ok_or_else(|| anyhow!("Ollama manifest for {} has no model layer", model_name))?;
+    // Blobs are stored as `sha256-<hex>` for a `sha256:<hex>` digest
+    Ok(models_dir.join("blobs").join(digest.replace(':', "-")))
+}
+
+/// Which bundled tiktoken table approximates a model best
+fn tiktoken_table_for(model_name: &str) -> &'static str {
+    let name = model_name.to_lowercase();
+    if name.starts_with("gpt-4o")
+        || name.starts_with("gpt-4.1")
+        || name.starts_with("gpt-5")
+        || name.starts_with("o1")
+        || name.starts_with("o3")
+        || name.starts_with("o4")
+    {
+        "o200k_base"
+    } else {
+        "cl100k_base"
+    }
+}
+
+/// Makes a model name safe to use as a file name ("llama3.2:latest" -> "llama3.2_latest")
+fn sanitize_model_name(model_name: &str) -> String {
+    model_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+        .collect()
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// GPT-2's reversible byte -> printable unicode character mapping
+fn byte_to_unicode() -> &'static [char; 256] {
+    static MAP: Lazy<[char; 256]> = Lazy::new(|| {
+        let mut map = ['\0'; 256];
+        let mut next = 256u32;
+        for b in 0..=255u8 {
+            let printable = (b'!'..=b'~').contains(&b) || (0xA1..=0xAC).contains(&b) || b >= 0xAE;
+            map[b as usize] = if printable {
+                b as char
+            } else {
+                let ch = char::from_u32(next).unwrap();
+                next += 1;
+                ch
+            };
+        }
+        map
+    });
+    &MAP
+}
+
+/// Standard base64 decoding (tiktoken tables only use the standard alphabet)
+fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    fn value(c: u8) -> Option<u32> {
+        match c {
+            b'A'..=b'Z' => Some((c - b'A') as u32),
+            b'a'..=b'z' => Some((c - b'a') as u32 + 26),
+            b'0'..=b'9' => Some((c - b'0') as u32 + 52),
+            b'+' => Some(62),
+            b'/' => Some(63),
+            _ => None,
+        }
+    }
+
+    let input = input.trim_end_matches('=').as_bytes();
+    let mut out = Vec::with_capacity(input.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &c in input {
+        buffer = (buffer << 6) | value(c)?;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(out)
+}
+
+/// Whether a tokenizer.json component (or any member of a Sequence) has the given type
+fn json_mentions_type(value: &serde_json::Value, type_name: &str) -> bool {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.get("type").and_then(|t| t.as_str()) == Some(type_name)
+                || map.values().any(|v| json_mentions_type(v, type_name))
+        }
+        serde_json::Value::Array(items) => items.iter().any(|v| json_mentions_type(v, type_name)),
+        _ => false,
+    }
+}
+
+fn find_bool_field(value: &serde_json::Value, field: &str) -> Option<bool> {
+    match value {
+        serde_json::Value::Object(map) => map
+            .get(field)
+            .and_then(|v| v.as_bool())
+            .or_else(|| map.values().find_map(|v| find_bool_field(v, field))),
+        serde_json::Value::Array(items) => items.iter().find_map(|v| find_bool_field(v, field)),
+        _ => None,
+    }
+}
+
+fn find_str_field<'a>(value: &'a serde_json::Value, field: &str) -> Option<&'a str> {
+    match value {
+        serde_json::Value::Object(map) => map
+            .get(field)
+            .and_then(|v| v.as_str())
+            .or_else(|| map.values().find_map(|v| find_str_field(v, field))),
+        serde_json::Value::Array(items) => items.iter().find_map(|v| find_str_field(v, field)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::summary_engine::gguf::{tests::build_gguf, GgufValue};
+    use crate::test_http::CannedServer;
+
+    fn spans_text<'a>(text: &'a str, spans: &[Range<usize>]) -> Vec<&'a str> {
+        spans.iter().map(|r| &text[r.clone()]).collect()
+    }
+
+    #[test]
+    fn test_heuristic_matches_rough_estimate() {
+        let text = "hello world, this is a test";
+        let count = HeuristicTokenizer.count_tokens(text);
+        assert_eq!(count, (text.chars().count() as f64 * 0.35).ceil() as usize);
+        let spans = HeuristicTokenizer.token_spans(text);
+        assert_eq!(spans.first().unwrap().start, 0);
+        assert_eq!(spans.last().unwrap().end, text.len());
+    }
+
+    #[test]
+    fn test_decode_base64() {
+        assert_eq!(decode_base64("aGVsbG8=").unwrap(), b"hello");
+        assert_eq!(decode_base64("IQ==").unwrap(), b"!");
+        assert!(decode_base64("a$b").is_none());
+    }
+
+    #[test]
+    fn test_tiktoken_merges_by_rank() {
+        // "h","e","l","o"," " plus merges "he", "ll", "llo", "hello", " w"
+        let table = "aA== 0\nZQ== 1\nbA== 2\nbw== 3\nIA== 4\ndw== 5\naGU= 6\nbGw= 7\nbGxv 8\naGVsbG8= 9\nIHc= 10\n";
+        let tokenizer = BpeTokenizer::from_tiktoken("test", table).unwrap();
+        let text = "hello wo";
+        let spans = tokenizer.token_spans(text);
+        assert_eq!(spans_text(text, &spans), vec!["hello", " w", "o"]);
+    }
+
+    #[test]
+    fn test_tokenizer_json_bpe_byte_level() {
+        let json = r#"{
+            "model": {"type": "BPE", "vocab": {}, "merges": ["a b", ["ab", "c"]]},
+            "pre_tokenizer": {"type": "ByteLevel", "add_prefix_space": false}
+        }"#;
+        let tokenizer = BpeTokenizer::from_tokenizer_json("test", json).unwrap();
+        let text = "abc ab";
+        let spans = tokenizer.token_spans(text);
+        // " ab" is a separate pre-token and the space is never merged
+        assert_eq!(spans_text(text, &spans), vec!["abc", " ", "ab"]);
+    }
+
+    #[test]
+    fn test_gguf_llama_tokenizer_counts_cjk() {
+        let tokens = ["▁会议", "会议", "会", "议", "▁", "开始", "开", "始"];
+        let scores = [-1.0f32, -3.0, -5.0, -5.0, -2.0, -1.5, -6.0, -6.0];
+        let bytes = build_gguf(&[
+            ("general.architecture", GgufValue::String("gemma3".to_string())),
+            ("tokenizer.ggml.model", GgufValue::String("llama".to_string())),
+            (
+                "tokenizer.ggml.tokens",
+                GgufValue::Array(tokens.iter().map(|t| GgufValue::String(t.to_string())).collect()),
+            ),
+            (
+                "tokenizer.ggml.scores",
+                GgufValue::Array(scores.iter().map(|s| GgufValue::F32(*s)).collect()),
+            ),
+            ("tokenizer.ggml.add_space_prefix", GgufValue::Bool(true)),
+        ]);
+        let metadata = gguf::read_metadata_from(&mut bytes.as_slice()).unwrap();
+        let tokenizer = BpeTokenizer::from_gguf(&metadata).unwrap();
+        assert_eq!(tokenizer.name(), "gguf:gemma3");
+
+        let text = "会议开始";
+        let spans = tokenizer.token_spans(text);
+        assert_eq!(spans_text(text, &spans), vec!["会议", "开始"]);
+        // The heuristic would guess ceil(4 * 0.35) = 2 here, but diverges on longer CJK text
+        assert_eq!(tokenizer.count_tokens(text), 2);
+    }
+
+    #[test]
+    fn test_ollama_tokenizer_comes_from_model_blob() {
+        let dir = tempfile::tempdir().unwrap();
+        let digest = "sha256:0123abcd";
+        let manifest_dir = dir.path().join("manifests/registry.ollama.ai/library/gemma3");
+        std::fs::create_dir_all(&manifest_dir).unwrap();
+        std::fs::write(
+            manifest_dir.join("1b"),
+            serde_json::json!({
+                "layers": [
+                    { "mediaType": "application/vnd.ollama.image.template", "digest": "sha256:ffff" },
+                    { "mediaType": OLLAMA_MODEL_MEDIA_TYPE, "digest": digest }
+                ]
+            })
+            .to_string(),
+        )
+        .unwrap();
+        std::fs::create_dir_all(dir.path().join("blobs")).unwrap();
+        let bytes = build_gguf(&[
+            ("general.architecture", GgufValue::String("gemma3".to_string())),
+            ("tokenizer.ggml.model", GgufValue::String("gpt2".to_string())),
+            (
+                "tokenizer.ggml.merges",
+                GgufValue::Array(vec![GgufValue::String("a b".to_string())]),
+            ),
+        ]);
+        std::fs::write(dir.path().join("blobs/sha256-0123abcd"), bytes).unwrap();
+
+        let blob = ollama_model_blob(dir.path(), "gemma3:1b").unwrap();
+        assert_eq!(blob, dir.path().join("blobs").join("sha256-0123abcd"));
+        let tokenizer = BpeTokenizer::from_gguf(&gguf::read_metadata(&blob).unwrap()).unwrap();
+        assert_eq!(tokenizer.name(), "gguf:gemma3");
+
+        assert!(ollama_model_blob(dir.path(), "gemma3").is_err());
+        assert!(ollama_model_blob(dir.path(), "llama3.2:latest").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tiktoken_table_download_checks_hash() {
+        let table = "aGVsbG8= 0\n";
+        let mut hasher = DownloadHasher::new();
+        hasher.update(table.as_bytes());
+        let sha256 = hasher.finalize();
+
+        let server = CannedServer::start(vec![(200, table), (200, table)]).await;
+        let client = reqwest::Client::new();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tokenizers/cl100k_base.tiktoken");
+        let url = format!("{}/cl100k_base.tiktoken", server.base_url);
+
+        let wrong = "0".repeat(64);
+        assert!(download_tiktoken_table(&client, &url, &wrong, &path)
+            .await
+            .is_err());
+        assert!(!path.exists());
+
+        download_tiktoken_table(&client, &url, &sha256, &path)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), table);
+    }
+
+    #[test]
+    fn test_sanitize_model_name() {
+        assert_eq!(sanitize_model_name("llama3.2:latest"), "llama3.2_latest");
+        assert_eq!(sanitize_model_name("org/model-7b"), "org_model-7b");
+    }
+}
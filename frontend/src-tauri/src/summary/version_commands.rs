@@ -0,0 +1,152 @@
+use crate::database::models::SummaryVersion;
+use crate::database::repositories::summary::SummaryProcessesRepository;
+use crate::database::repositories::summary_version::{
+    NewSummaryVersion, SummaryVersionsRepository,
+};
+use crate::export::commands::summary_markdown_from_result;
+use crate::state::AppState;
+use crate::summary::templates;
+use crate::summary::versions::{diff_summaries, SectionDiff};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tauri::Runtime;
+use tracing::{info, warn};
+
+/// Section-aware comparison of two versions of a meeting's summary
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummaryVersionDiff {
+    pub meeting_id: String,
+    pub from_version: i64,
+    pub to_version: i64,
+    /// Template whose section titles split the summaries, if it could be loaded
+    pub template_id: Option<String>,
+    pub sections: Vec<SectionDiff>,
+}
+
+/// Record a version that reuses `basis`'s generation details (provider,
+/// model, template, prompt) without its token stats, e.g. a user edit
+pub(crate) async fn record_derived_version(
+    pool: &SqlitePool,
+    meeting_id: &str,
+    source: &str,
+    result: String,
+    basis: Option<&SummaryVersion>,
+    restored_from: Option<i64>,
+) -> Result<SummaryVersion, sqlx::Error> {
+    let version = NewSummaryVersion {
+        source: source.to_string(),
+        result,
+        provider: basis.and_then(|b| b.provider.clone()),
+        model: basis.and_then(|b| b.model.clone()),
+        template_id: basis.and_then(|b| b.template_id.clone()),
+        custom_prompt: basis.and_then(|b| b.custom_prompt.clone()),
+        restored_from,
+        ..Default::default()
+    };
+    SummaryVersionsRepository::record(pool, meeting_id, &version).await
+}
+
+async fn load_version(
+    pool: &SqlitePool,
+    meeting_id: &str,
+    version: i64,
+) -> Result<SummaryVersion, String> {
+    SummaryVersionsRepository::get(pool, meeting_id, version)
+        .await
+        .map_err(|e| format!("Failed to load summary version: {}", e))?
+        .ok_or_else(|| format!("Summary version {} not found for {}", version, meeting_id))
+}
+
+/// Lists every recorded version of a meeting's summary, newest first
+#[tauri::command]
+pub async fn api_list_summary_versions<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<SummaryVersion>, String> {
+    info!(
+        "api_list_summary_versions called for meeting_id: {}",
+        meeting_id
+    );
+    SummaryVersionsRepository::list(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load summary versions: {}", e))
+}
+
+/// Makes an earlier version the current summary again
+///
+/// Restoring is recorded as a new version, so no history is lost.
+#[tauri::command]
+pub async fn api_restore_summary_version<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    version: i64,
+) -> Result<SummaryVersion, String> {
+    info!(
+        "api_restore_summary_version called for meeting_id: {}, version: {}",
+        meeting_id, version
+    );
+    let pool = state.db_manager.pool();
+    let target = load_version(pool, &meeting_id, version).await?;
+
+    let summary: serde_json::Value = serde_json::from_str(&target.result)
+        .map_err(|e| format!("Summary version {} is not valid JSON: {}", version, e))?;
+    match SummaryProcessesRepository::update_meeting_summary(pool, &meeting_id, &summary).await {
+        Ok(true) => {}
+        Ok(false) => return Err(format!("Meeting not found: {}", meeting_id)),
+        Err(e) => return Err(format!("Failed to restore summary: {}", e)),
+    }
+
+    record_derived_version(
+        pool,
+        &meeting_id,
+        "restored",
+        target.result.clone(),
+        Some(&target),
+        Some(target.version),
+    )
+    .await
+    .map_err(|e| format!("Failed to record restored version: {}", e))
+}
+
+/// Compares two versions section by section, using the template's section titles
+#[tauri::command]
+pub async fn api_diff_summary_versions<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    from_version: i64,
+    to_version: i64,
+) -> Result<SummaryVersionDiff, String> {
+    let pool = state.db_manager.pool();
+    let from = load_version(pool, &meeting_id, from_version).await?;
+    let to = load_version(pool, &meeting_id, to_version).await?;
+
+    // Prefer the newer version's template; fall back to the older one's
+    let template = [&to.template_id, &from.template_id]
+        .into_iter()
+        .flatten()
+        .find_map(|id| match templates::get_template(id) {
+            Ok(template) => Some((id.clone(), template)),
+            Err(e) => {
+                warn!("Template '{}' unavailable for summary diff: {}", id, e);
+                None
+            }
+        });
+    let section_titles: Vec<String> = template
+        .as_ref()
+        .map(|(_, t)| t.sections.iter().map(|s| s.title.clone()).collect())
+        .unwrap_or_default();
+
+    let markdown = |version: &SummaryVersion| {
+        summary_markdown_from_result(&version.result).unwrap_or_default()
+    };
+    Ok(SummaryVersionDiff {
+        sections: diff_summaries(&markdown(&from), &markdown(&to), &section_titles),
+        meeting_id,
+        from_version,
+        to_version,
+        template_id: template.map(|(id, _)| id),
+    })
+}
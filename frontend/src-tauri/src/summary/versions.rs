@@ -0,0 +1,276 @@
+// Section-aware comparison of two summary versions
+// Splits each summary's markdown at the template's section headings and diffs
+// the sections line by line, so a reworded action item shows up under
+// "Action Items" rather than as one long document diff
+
+use serde::{Deserialize, Serialize};
+
+/// How a section changed between two versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SectionStatus {
+    Added,
+    Removed,
+    Changed,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineChange {
+    Same,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub change: LineChange,
+    pub text: String,
+}
+
+/// One section of the diff; `title` is empty for text before the first heading
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SectionDiff {
+    pub title: String,
+    pub status: SectionStatus,
+    pub lines: Vec<DiffLine>,
+}
+
+/// A section of a summary: its heading and non-blank lines
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkdownSection {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+/// Normalized heading text used to match sections across versions
+fn section_key(title: &str) -> String {
+    title
+        .trim()
+        .trim_start_matches('#')
+        .trim()
+        .trim_matches('*')
+        .trim()
+        .trim_end_matches(':')
+        .trim()
+        .to_lowercase()
+}
+
+/// Heading text of a `# Title` or `**Title**` line
+fn heading_text(line: &str) -> Option<&str> {
+    if line.starts_with('#') {
+        return Some(line.trim_start_matches('#').trim());
+    }
+    let inner = line.strip_prefix("**")?.strip_suffix("**")?;
+    (!inner.contains("**")).then_some(inner.trim())
+}
+
+/// Split markdown into sections.
+///
+/// With `section_titles` (the template's), only headings matching one of them
+/// start a section and other headings stay in the body; without, every
+/// heading does.
+pub fn split_sections(markdown: &str, section_titles: &[String]) -> Vec<MarkdownSection> {
+    let known: Vec<String> = section_titles.iter().map(|t| section_key(t)).collect();
+    let mut sections = vec![MarkdownSection {
+        title: String::new(),
+        lines: Vec::new(),
+    }];
+
+    for line in markdown.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let heading = heading_text(line).filter(|text| {
+            let key = section_key(text);
+            !key.is_empty() && (known.is_empty() || known.contains(&key))
+        });
+        match heading {
+            Some(text) => {
+                // Prefer the template's spelling of the title
+                let key = section_key(text);
+                let title = section_titles
+                    .iter()
+                    .find(|t| section_key(t) == key)
+                    .cloned()
+                    .unwrap_or_else(|| text.trim_end_matches(':').trim().to_string());
+                sections.push(MarkdownSection {
+                    title,
+                    lines: Vec::new(),
+                });
+            }
+            None => {
+                if let Some(current) = sections.last_mut() {
+                    current.lines.push(line.to_string());
+                }
+            }
+        }
+    }
+
+    if sections[0].lines.is_empty() {
+        sections.remove(0);
+    }
+    sections
+}
+
+/// Line diff via longest common subsequence
+fn diff_lines(old: &[String], new: &[String]) -> Vec<DiffLine> {
+    let (n, m) = (old.len(), new.len());
+    // lcs[i][j] = LCS length of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        let line = if i < n && j < m && old[i] == new[j] {
+            i += 1;
+            j += 1;
+            (LineChange::Same, &new[j - 1])
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            i += 1;
+            (LineChange::Removed, &old[i - 1])
+        } else {
+            j += 1;
+            (LineChange::Added, &new[j - 1])
+        };
+        lines.push(DiffLine {
+            change: line.0,
+            text: line.1.clone(),
+        });
+    }
+    lines
+}
+
+/// Compare two summaries section by section.
+///
+/// Sections follow the template's order, then any others in the order they
+/// appear in the newer and then the older version.
+pub fn diff_summaries(
+    old_markdown: &str,
+    new_markdown: &str,
+    section_titles: &[String],
+) -> Vec<SectionDiff> {
+    let old_sections = split_sections(old_markdown, section_titles);
+    let new_sections = split_sections(new_markdown, section_titles);
+
+    let mut order: Vec<String> = Vec::new();
+    let candidates = new_sections
+        .iter()
+        .chain(old_sections.iter())
+        .map(|s| s.title.clone());
+    let template_first = section_titles.iter().filter(|t| {
+        let key = section_key(t);
+        new_sections
+            .iter()
+            .chain(old_sections.iter())
+            .any(|s| section_key(&s.title) == key)
+    });
+    for title in template_first.cloned().chain(candidates) {
+        if !order.iter().any(|t| section_key(t) == section_key(&title)) {
+            order.push(title);
+        }
+    }
+    // Text before the first heading always comes first
+    if let Some(pos) = order.iter().position(|t| t.is_empty()) {
+        let intro = order.remove(pos);
+        order.insert(0, intro);
+    }
+
+    let lines_of = |sections: &[MarkdownSection], title: &str| -> Option<Vec<String>> {
+        let key = section_key(title);
+        let matching: Vec<&MarkdownSection> = sections
+            .iter()
+            .filter(|s| section_key(&s.title) == key)
+            .collect();
+        (!matching.is_empty()).then(|| matching.iter().flat_map(|s| s.lines.clone()).collect())
+    };
+
+    order
+        .into_iter()
+        .map(|title| {
+            let old = lines_of(&old_sections, &title);
+            let new = lines_of(&new_sections, &title);
+            let lines = diff_lines(old.as_deref().unwrap_or(&[]), new.as_deref().unwrap_or(&[]));
+            let status = match (&old, &new) {
+                (None, _) => SectionStatus::Added,
+                (_, None) => SectionStatus::Removed,
+                _ if lines.iter().all(|l| l.change == LineChange::Same) => SectionStatus::Unchanged,
+                _ => SectionStatus::Changed,
+            };
+            SectionDiff {
+                title,
+                status,
+                lines,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn titles() -> Vec<String> {
+        ["Summary", "Key Decisions", "Action Items"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_split_sections_uses_template_headings() {
+        let markdown = "Intro line\n\n**Summary**\n\nWe met.\n\n### Details\n\nMore.\n\n## Action Items:\n\n- Ana: send deck";
+        let sections = split_sections(markdown, &titles());
+
+        let titles: Vec<&str> = sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["", "Summary", "Action Items"]);
+        assert_eq!(sections[1].lines, vec!["We met.", "### Details", "More."]);
+
+        // Without a template every heading starts a section
+        assert_eq!(split_sections(markdown, &[]).len(), 4);
+    }
+
+    #[test]
+    fn test_diff_summaries_by_section() {
+        let old = "**Summary**\n\nWe met.\n\n**Key Decisions**\n\n- Ship v2\n\n**Action Items**\n\n- Ana: send deck\n- Ben: book room";
+        let new =
+            "**Summary**\n\nWe met.\n\n**Action Items**\n\n- Ana: send the deck\n- Ben: book room";
+
+        let diff = diff_summaries(old, new, &titles());
+        let statuses: Vec<(&str, SectionStatus)> =
+            diff.iter().map(|s| (s.title.as_str(), s.status)).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("Summary", SectionStatus::Unchanged),
+                ("Key Decisions", SectionStatus::Removed),
+                ("Action Items", SectionStatus::Changed),
+            ]
+        );
+        assert_eq!(
+            diff_summaries(new, old, &titles())[1].status,
+            SectionStatus::Added
+        );
+
+        let changes: Vec<(LineChange, &str)> = diff[2]
+            .lines
+            .iter()
+            .map(|l| (l.change, l.text.as_str()))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (LineChange::Removed, "- Ana: send deck"),
+                (LineChange::Added, "- Ana: send the deck"),
+                (LineChange::Same, "- Ben: book room"),
+            ]
+        );
+    }
+}
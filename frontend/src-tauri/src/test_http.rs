@@ -0,0 +1,136 @@
+// Local HTTP servers standing in for remote APIs in tests
+// `serve` hands every request to a handler that writes the response itself;
+// `CannedServer` answers with canned `(status, body)` pairs in order and
+// records the raw requests it received
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// A request as received by a test server
+pub struct Request {
+    pub method: String,
+    /// Path and query, without the leading `/`
+    pub path: String,
+    /// Request line, headers and body as text
+    pub raw: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        let head = self.raw.split("\r\n\r\n").next().unwrap_or_default();
+        head.lines()
+            .skip(1)
+            .filter_map(|line| line.split_once(':'))
+            .find(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim())
+    }
+}
+
+/// Listen on a free local port and pass each request to `handle`
+pub async fn serve<F, Fut>(handle: F) -> SocketAddr
+where
+    F: Fn(Request, TcpStream) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let handle = Arc::new(handle);
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let handle = handle.clone();
+            tokio::spawn(async move {
+                if let Some(request) = read_request(&mut stream).await {
+                    handle(request, stream).await;
+                }
+            });
+        }
+    });
+    addr
+}
+
+/// Read the request head and as much body as Content-Length announces
+async fn read_request(stream: &mut TcpStream) -> Option<Request> {
+    let mut data = Vec::new();
+    let mut buffer = [0u8; 8192];
+    loop {
+        if let Some(end) = data.windows(4).position(|window| window == b"\r\n\r\n") {
+            let head = String::from_utf8_lossy(&data[..end]);
+            let length = head
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+                .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+                .unwrap_or(0);
+            if data.len() >= end + 4 + length {
+                break;
+            }
+        }
+        match stream.read(&mut buffer).await {
+            Ok(0) | Err(_) => return None,
+            Ok(read) => data.extend_from_slice(&buffer[..read]),
+        }
+    }
+
+    let raw = String::from_utf8_lossy(&data).to_string();
+    let mut request_line = raw.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches('/')
+        .to_string();
+    Some(Request { method, path, raw })
+}
+
+/// Answers each request with the next canned response; 404 once they run out
+pub struct CannedServer {
+    pub base_url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl CannedServer {
+    pub async fn start<B: Into<String>>(responses: Vec<(u16, B)>) -> Self {
+        let responses: VecDeque<(u16, String)> = responses
+            .into_iter()
+            .map(|(status, body)| (status, body.into()))
+            .collect();
+        let responses = Arc::new(Mutex::new(responses));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = requests.clone();
+        let addr = serve(move |request, mut stream| {
+            recorded.lock().unwrap().push(request.raw);
+            let (status, body) = responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or((404, String::new()));
+            async move {
+                let response = format!(
+                    "HTTP/1.1 {} Canned\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            }
+        })
+        .await;
+
+        Self {
+            base_url: format!("http://{}", addr),
+            requests,
+        }
+    }
+
+    /// Raw requests in arrival order
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
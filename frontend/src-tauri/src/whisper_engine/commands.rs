@@ -46,7 +46,9 @@ pub async fn whisper_init() -> Result<(), String> {
     let models_dir = get_models_directory();
     let engine = WhisperEngine::new_with_models_dir(models_dir)
         .map_err(|e| format!("Failed to initialize whisper engine: {}", e))?;
-    *guard = Some(Arc::new(engine));
+    let engine = Arc::new(engine);
+    crate::model_manager::registry().register(engine.clone());
+    *guard = Some(engine);
     Ok(())
 }
 
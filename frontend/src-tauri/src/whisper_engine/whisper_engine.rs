@@ -7,10 +7,32 @@ use tokio::sync::RwLock;
 use whisper_rs::{WhisperContext, WhisperContextParameters, FullParams, SamplingStrategy};
 use serde::{Serialize, Deserialize};
 use anyhow::{Result, anyhow};
-use reqwest::Client;
+use async_trait::async_trait;
 use tokio::fs;
-use tokio::io::AsyncWriteExt;
 use crate::{perf_debug, perf_trace};
+use crate::model_integrity::{self, ModelVerification};
+use crate::model_manager::{self, CatalogEntry, DownloadProgress, EngineKind, ModelEngine};
+use crate::audio::transcription::{align_words_to_text, merge_tokens_into_words, TimedToken, WordTimestamp};
+
+// Using standard ggerganov/whisper.cpp GGML models
+// (name, filename, size_mb, accuracy, speed, description)
+const MODEL_CONFIGS: [(&str, &str, u32, &str, &str, &str); 12] = [
+    // Standard f16 models (full precision)
+    ("tiny", "ggml-tiny.bin", 39, "Decent", "Very Fast", "Fastest processing, good for real-time use"),
+    ("base", "ggml-base.bin", 142, "Good", "Fast", "Good balance of speed and accuracy"),
+    ("small", "ggml-small.bin", 466, "Good", "Medium", "Better accuracy, moderate speed"),
+    ("medium", "ggml-medium.bin", 1420, "High", "Slow", "High accuracy for professional use"),
+    ("large-v3-turbo", "ggml-large-v3-turbo.bin", 809, "High", "Medium", "Best accuracy with improved speed"),
+    ("large-v3", "ggml-large-v3.bin", 2870, "High", "Slow", "Best accuracy, latest large model"),
+
+    // Q5_0 quantized models (balanced speed/accuracy)
+    ("tiny-q5_0", "ggml-tiny-q5_0.bin", 26, "Decent", "Very Fast", "Quantized tiny model, ~50% faster processing"),
+    ("base-q5_0", "ggml-base-q5_0.bin", 85, "Good", "Fast", "Quantized base model, good speed/accuracy balance"),
+    ("small-q5_0", "ggml-small-q5_0.bin", 280, "Good", "Fast", "Quantized small model, faster than f16 version"),
+    ("medium-q5_0", "ggml-medium-q5_0.bin", 852, "High", "Medium", "Quantized medium model, professional quality"),
+    ("large-v3-turbo-q5_0", "ggml-large-v3-turbo-q5_0.bin", 574, "High", "Medium", "Quantized large model, best balance"),
+    ("large-v3-q5_0", "ggml-large-v3-q5_0.bin", 1050, "High", "Slow", "Quantized large model, high accuracy"),
+];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ModelStatus {
@@ -42,10 +64,6 @@ pub struct WhisperEngine {
     short_audio_warning_logged: Arc<RwLock<bool>>,
     // Performance optimization: reduce logging frequency
     transcription_count: Arc<RwLock<u64>>,
-    // Download cancellation tracking
-    cancel_download_flag: Arc<RwLock<Option<String>>>, // Model name being cancelled
-    // Active downloads tracking to prevent concurrent downloads
-    active_downloads: Arc<RwLock<HashSet<String>>>, // Set of models currently being downloaded
 }
 
 impl WhisperEngine {
@@ -157,10 +175,6 @@ impl WhisperEngine {
             short_audio_warning_logged: Arc::new(RwLock::new(false)),
             // Performance optimization: reduce logging frequency
             transcription_count: Arc::new(RwLock::new(0)),
-            // Initialize cancellation tracking
-            cancel_download_flag: Arc::new(RwLock::new(None)),
-            // Initialize active downloads tracking
-            active_downloads: Arc::new(RwLock::new(HashSet::new())),
         };
         
         Ok(engine)
@@ -169,29 +183,13 @@ impl WhisperEngine {
     pub async fn discover_models(&self) -> Result<Vec<ModelInfo>> {
         let models_dir = &self.models_dir;
         let mut models = Vec::new();
-                // Using standard ggerganov/whisper.cpp GGML models
-        let model_configs = [
-            // Standard f16 models (full precision)
-            ("tiny", "ggml-tiny.bin", 39, "Decent", "Very Fast", "Fastest processing, good for real-time use"),
-            ("base", "ggml-base.bin", 142, "Good", "Fast", "Good balance of speed and accuracy"),
-            ("small", "ggml-small.bin", 466, "Good", "Medium", "Better accuracy, moderate speed"),
-            ("medium", "ggml-medium.bin", 1420, "High", "Slow", "High accuracy for professional use"),
-            ("large-v3-turbo", "ggml-large-v3-turbo.bin", 809, "High", "Medium", "Best accuracy with improved speed"),
-            ("large-v3", "ggml-large-v3.bin", 2870, "High", "Slow", "Best accuracy, latest large model"),
-
-            // Q5_0 quantized models (balanced speed/accuracy)
-            ("tiny-q5_0", "ggml-tiny-q5_0.bin", 26, "Decent", "Very Fast", "Quantized tiny model, ~50% faster processing"),
-            ("base-q5_0", "ggml-base-q5_0.bin", 85, "Good", "Fast", "Quantized base model, good speed/accuracy balance"),
-            ("small-q5_0", "ggml-small-q5_0.bin", 280, "Good", "Fast", "Quantized small model, faster than f16 version"),
-            ("medium-q5_0", "ggml-medium-q5_0.bin", 852, "High", "Medium", "Quantized medium model, professional quality"),
-            ("large-v3-turbo-q5_0", "ggml-large-v3-turbo-q5_0.bin", 574, "High", "Medium", "Quantized large model, best balance"),
-            ("large-v3-q5_0", "ggml-large-v3-q5_0.bin", 1050, "High", "Slow", "Quantized large model, high accuracy"),
-
-           ];
-        
-        for (name, filename, size_mb, accuracy, speed, description) in model_configs {
+
+        for (name, filename, size_mb, accuracy, speed, description) in MODEL_CONFIGS {
             let model_path = models_dir.join(filename);
-            let status = if model_path.exists() {
+            let status = if let Some(progress) = model_manager::registry().progress(EngineKind::Whisper, name) {
+                // Downloads go to a .part file, so the model file itself doesn't exist yet
+                ModelStatus::Downloading { progress: progress.percent }
+            } else if model_path.exists() {
                 // Check if file size is reasonable (at least 1MB for a valid model)
                 match std::fs::metadata(&model_path) {
                     Ok(metadata) => {
@@ -213,32 +211,12 @@ impl WhisperEngine {
                                 }
                             }
                         } else if file_size_mb > 0 {
-                            // File exists but is smaller than expected
-                            // Check if this model is currently being downloaded
-                            let models_guard = self.available_models.read().await;
-                            if let Some(existing_model) = models_guard.get(name) {
-                                match &existing_model.status {
-                                    ModelStatus::Downloading { progress } => {
-                                        log::debug!("Model {} appears to be downloading ({} MB so far, {}% complete)",
-                                                  filename, file_size_mb, progress);
-                                        ModelStatus::Downloading { progress: *progress }
-                                    }
-                                    _ => {
-                                        log::warn!("Model file {} exists but is corrupted ({} MB, expected ~{} MB)",
-                                                 filename, file_size_mb, size_mb);
-                                        ModelStatus::Corrupted {
-                                            file_size: file_size_bytes,
-                                            expected_min_size: (expected_min_size_mb * 1024 * 1024) as u64
-                                        }
-                                    }
-                                }
-                            } else {
-                                log::warn!("Model file {} exists but is corrupted ({} MB, expected ~{} MB)",
-                                         filename, file_size_mb, size_mb);
-                                ModelStatus::Corrupted {
-                                    file_size: file_size_bytes,
-                                    expected_min_size: (expected_min_size_mb * 1024 * 1024) as u64
-                                }
+                            // File exists but is smaller than expected (downloading again resumes it)
+                            log::warn!("Model file {} exists but is corrupted ({} MB, expected ~{} MB)",
+                                     filename, file_size_mb, size_mb);
+                            ModelStatus::Corrupted {
+                                file_size: file_size_bytes,
+                                expected_min_size: (expected_min_size_mb * 1024 * 1024) as u64
                             }
                         } else {
                             ModelStatus::Missing
@@ -253,7 +231,7 @@ impl WhisperEngine {
             let model_info = ModelInfo {
                 name: name.to_string(),
                 path: model_path,
-                size_mb: size_mb as u32,
+                size_mb,
                 accuracy: accuracy.to_string(),
                 speed: speed.to_string(),
                 status,
@@ -530,7 +508,8 @@ impl WhisperEngine {
     }
     
     /// Transcribe audio with streaming support for partial results and adaptive quality
-    pub async fn transcribe_audio_with_confidence(&self, audio_data: Vec<f32>, language: Option<String>) -> Result<(String, f32, bool)> {
+    /// Returns (text, confidence, is_partial, word timings in seconds from the start of `audio_data`)
+    pub async fn transcribe_audio_with_confidence(&self, audio_data: Vec<f32>, language: Option<String>) -> Result<(String, f32, bool, Vec<WordTimestamp>)> {
         let ctx_lock = self.current_context.read().await;
         let ctx = ctx_lock.as_ref()
             .ok_or_else(|| anyhow!("No model loaded. Please load a model first."))?;
@@ -608,6 +587,7 @@ impl WhisperEngine {
         let mut result = String::new();
         let mut total_confidence = 0.0;
         let mut segment_count = 0;
+        let mut words = Vec::new();
 
         let num_segments = num_segments?;
         for i in 0..num_segments {
@@ -616,6 +596,30 @@ impl WhisperEngine {
                 Err(_) => continue,
             };
 
+            // Token timings (t0/t1 are in 10ms units) for word-level timestamps
+            let mut segment_tokens = Vec::new();
+            for j in 0..state.full_n_tokens(i).unwrap_or(0) {
+                let (Ok(text), Ok(data)) = (
+                    state.full_get_token_text_lossy(i, j),
+                    state.full_get_token_data(i, j),
+                ) else {
+                    continue;
+                };
+                // Skip special tokens like [_BEG_], [_TT_150] or <|endoftext|>
+                if text.starts_with("[_") || text.starts_with("<|") {
+                    continue;
+                }
+                segment_tokens.push((text, data.t0 as f64 / 100.0, data.t1 as f64 / 100.0, data.p));
+            }
+            words.extend(merge_tokens_into_words(segment_tokens.iter().map(
+                |(text, start, end, p)| TimedToken {
+                    text,
+                    start: *start,
+                    end: *end,
+                    confidence: Some(*p),
+                },
+            )));
+
             // Calculate confidence based on segment length and duration (simplified approach)
             let segment_length = segment_text.len() as f32;
             let segment_confidence = if segment_length > 0.0 {
@@ -644,7 +648,10 @@ impl WhisperEngine {
             0.0
         };
 
-        Ok((cleaned_result, avg_confidence, is_partial))
+        // Repetition cleanup may have dropped words
+        let words = align_words_to_text(words, &cleaned_result);
+
+        Ok((cleaned_result, avg_confidence, is_partial, words))
     }
 
     pub async fn transcribe_audio(&self, audio_data: Vec<f32>, language: Option<String>) -> Result<String> {
@@ -911,32 +918,9 @@ impl WhisperEngine {
         }
     }
     
-    pub async fn download_model(&self, model_name: &str, progress_callback: Option<Box<dyn Fn(u8) + Send>>) -> Result<()> {
-        log::info!("Starting download for model: {}", model_name);
-
-        // Check if download is already in progress for this model
-        {
-            let active = self.active_downloads.read().await;
-            if active.contains(model_name) {
-                log::warn!("Download already in progress for model: {}", model_name);
-                return Err(anyhow!("Download already in progress for model: {}", model_name));
-            }
-        }
-
-        // Add to active downloads
-        {
-            let mut active = self.active_downloads.write().await;
-            active.insert(model_name.to_string());
-        }
-
-        // Clear any previous cancellation flag for this model
-        {
-            let mut cancel_flag = self.cancel_download_flag.write().await;
-            *cancel_flag = None;
-        }
-
-        // Official ggerganov/whisper.cpp model URLs from Hugging Face
-        let model_url = match model_name {
+    /// Official ggerganov/whisper.cpp model URLs from Hugging Face
+    fn download_url(model_name: &str) -> Option<&'static str> {
+        let url = match model_name {
             // Standard f16 models
             "tiny" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.bin",
             "base" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.bin",
@@ -951,200 +935,99 @@ impl WhisperEngine {
             "large-v3-q5_0" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3-q5_0.bin",
             // Quantized int8 models
             
-            _ => return Err(anyhow!("Unsupported model: {}", model_name))
+            _ => return None,
         };
-        
-        log::info!("Model URL for {}: {}", model_name, model_url);
-        
-        // Generate correct filename - all models follow ggml-{model_name}.bin pattern
-        let filename = format!("ggml-{}.bin", model_name);
-        let file_path = self.models_dir.join(&filename);
-        
-        log::info!("Downloading to file path: {}", file_path.display());
-        
-        // Create models directory if it doesn't exist
-        if !self.models_dir.exists() {
-            fs::create_dir_all(&self.models_dir).await
-                .map_err(|e| anyhow!("Failed to create models directory: {}", e))?;
-        }
-        
-        // Update model status to downloading
-        {
-            let mut models = self.available_models.write().await;
-            if let Some(model_info) = models.get_mut(model_name) {
-                model_info.status = ModelStatus::Downloading { progress: 0 };
-            }
-        }
-        
-        log::info!("Creating HTTP client and starting request...");
-        let client = Client::new();
-        
-        log::info!("Sending GET request to: {}", model_url);
-        let response = client.get(model_url).send().await
-            .map_err(|e| anyhow!("Failed to start download: {}", e))?;
-        
-        log::info!("Received response with status: {}", response.status());
-        if !response.status().is_success() {
-            // Remove from active downloads on error
-            let mut active = self.active_downloads.write().await;
-            active.remove(model_name);
-            return Err(anyhow!("Download failed with status: {}", response.status()));
-        }
-        
-        let total_size = response.content_length().unwrap_or(0);
-        log::info!("Response successful, content length: {} bytes ({:.1} MB)", total_size, total_size as f64 / (1024.0 * 1024.0));
-        
-        if total_size == 0 {
-            log::warn!("Content length is 0 or unknown - download may not show accurate progress");
-        }
-        
-        let mut file = fs::File::create(&file_path).await
-            .map_err(|e| anyhow!("Failed to create file: {}", e))?;
-        
-        log::info!("File created successfully at: {}", file_path.display());
-        
-        // Stream download with real progress reporting
-        log::info!("Starting streaming download...");
-        log::info!("Expected size: {:.1} MB", total_size as f64 / (1024.0 * 1024.0));
-
-        use futures_util::StreamExt;
-        let mut stream = response.bytes_stream();
-        let mut downloaded = 0u64;
-        let mut last_progress_report = 0u8;
-        let mut last_report_time = std::time::Instant::now();
-
-        // Emit initial 0% progress immediately
-        if let Some(ref callback) = progress_callback {
-            callback(0);
-        }
-
-        while let Some(chunk_result) = stream.next().await {
-            // Check for cancellation before processing chunk
-            {
-                let cancel_flag = self.cancel_download_flag.read().await;
-                if cancel_flag.as_ref() == Some(&model_name.to_string()) {
-                    log::info!("Download cancelled for {}", model_name);
-                    // Remove from active downloads on cancellation
-                    let mut active = self.active_downloads.write().await;
-                    active.remove(model_name);
-                    return Err(anyhow!("Download cancelled by user"));
-                }
-            }
-
-            let chunk = chunk_result
-                .map_err(|e| anyhow!("Failed to read chunk: {}", e))?;
-
-            file.write_all(&chunk).await
-                .map_err(|e| anyhow!("Failed to write chunk to file: {}", e))?;
-
-            downloaded += chunk.len() as u64;
-
-            // Calculate progress
-            let progress = if total_size > 0 {
-                ((downloaded as f64 / total_size as f64) * 100.0) as u8
-            } else {
-                0
-            };
-
-            // Report progress every 1% or every 2 seconds for better UI responsiveness
-            let time_since_last_report = last_report_time.elapsed().as_secs();
-            if progress >= last_progress_report + 1 || progress == 100 || time_since_last_report >= 2 {
-                log::info!("Download progress: {}% ({:.1} MB / {:.1} MB)",
-                         progress,
-                         downloaded as f64 / (1024.0 * 1024.0),
-                         total_size as f64 / (1024.0 * 1024.0));
-
-                // Update progress in model info
-                {
-                    let mut models = self.available_models.write().await;
-                    if let Some(model_info) = models.get_mut(model_name) {
-                        model_info.status = ModelStatus::Downloading { progress };
-                    }
-                }
+        Some(url)
+    }
 
-                // Call progress callback
-                if let Some(ref callback) = progress_callback {
-                    callback(progress);
-                }
+    /// Download a model through the shared model manager
+    /// Partial downloads are resumed; a download that fails SHA-256 verification is quarantined and downloaded again
+    pub async fn download_model(&self, model_name: &str, progress_callback: Option<Box<dyn Fn(u8) + Send + Sync>>) -> Result<()> {
+        log::info!("Starting download for model: {}", model_name);
 
-                last_progress_report = progress;
-                last_report_time = std::time::Instant::now();
+        let on_progress = |progress: &DownloadProgress| {
+            if let Some(ref callback) = progress_callback {
+                callback(progress.percent);
             }
-        }
+        };
+        model_manager::registry()
+            .download(EngineKind::Whisper, model_name, Some(&on_progress))
+            .await?;
 
-        log::info!("Streaming download completed: {} bytes", downloaded);
-        
-        // Ensure 100% progress is always reported
-        {
-            let mut models = self.available_models.write().await;
-            if let Some(model_info) = models.get_mut(model_name) {
-                model_info.status = ModelStatus::Downloading { progress: 100 };
-            }
-        }
-        
-        if let Some(ref callback) = progress_callback {
-            callback(100);
-        }
-        
-        file.flush().await
-            .map_err(|e| anyhow!("Failed to flush file: {}", e))?;
-        
         log::info!("Download completed for model: {}", model_name);
-        
-        // Update model status to available
-        {
-            let mut models = self.available_models.write().await;
-            if let Some(model_info) = models.get_mut(model_name) {
-                model_info.status = ModelStatus::Available;
-                model_info.path = file_path.clone();
+        Ok(())
+    }
+
+    /// Re-check the SHA-256 of every installed model file, then rediscover models
+    pub async fn verify_models(&self) -> Vec<ModelVerification> {
+        let models = match self.discover_models().await {
+            Ok(models) => models,
+            Err(e) => {
+                log::error!("Failed to discover models for verification: {}", e);
+                return Vec::new();
             }
+        };
+        let installed: Vec<ModelInfo> = models.into_iter()
+            .filter(|model| model.path.exists() && !matches!(model.status, ModelStatus::Downloading { .. }))
+            .collect();
+
+        let mut results = Vec::new();
+        for model in installed {
+            let filename = format!("ggml-{}.bin", model.name);
+            let check = model_integrity::verify_installed(&self.models_dir, &filename, Self::download_url(&model.name)).await;
+            results.push(ModelVerification::new("whisper", &model.name, vec![check]));
         }
 
-        // Remove from active downloads on completion
-        {
-            let mut active = self.active_downloads.write().await;
-            active.remove(model_name);
+        if let Err(e) = self.discover_models().await {
+            log::error!("Failed to rediscover models after verification: {}", e);
         }
-
-        Ok(())
+        results
     }
-    
+
+    /// Cancel a download; the partial file is kept so the next download resumes it
     pub async fn cancel_download(&self, model_name: &str) -> Result<()> {
         log::info!("Cancelling download for model: {}", model_name);
 
-        // Set cancellation flag to interrupt the download loop
-        {
-            let mut cancel_flag = self.cancel_download_flag.write().await;
-            *cancel_flag = Some(model_name.to_string());
+        if !model_manager::registry().cancel(EngineKind::Whisper, model_name) {
+            log::warn!("No download in progress for model: {}", model_name);
         }
+        Ok(())
+    }
+}
 
-        // Remove from active downloads
-        {
-            let mut active = self.active_downloads.write().await;
-            active.remove(model_name);
-        }
+#[async_trait]
+impl ModelEngine for WhisperEngine {
+    fn kind(&self) -> EngineKind {
+        EngineKind::Whisper
+    }
 
-        // Update model status to Missing (so it can be retried)
-        {
-            let mut models = self.available_models.write().await;
-            if let Some(model_info) = models.get_mut(model_name) {
-                model_info.status = ModelStatus::Missing;
-            }
-        }
+    fn models_dir(&self) -> PathBuf {
+        self.models_dir.clone()
+    }
 
-        // Clean up partially downloaded files
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await; // Brief delay to let download loop detect cancellation
+    /// Models with a download URL (the rest can only be installed by hand)
+    fn catalog(&self) -> Vec<CatalogEntry> {
+        MODEL_CONFIGS.iter()
+            .filter_map(|(name, filename, size_mb, _, _, description)| {
+                let url = Self::download_url(name)?;
+                Some(
+                    CatalogEntry::new(EngineKind::Whisper, name, &format!("Whisper {}", name), description)
+                        .with_file(filename, url, *size_mb as u64 * 1024 * 1024),
+                )
+            })
+            .collect()
+    }
 
-        let filename = format!("ggml-{}.bin", model_name);
-        let file_path = self.models_dir.join(&filename);
-        if file_path.exists() {
-            if let Err(e) = fs::remove_file(&file_path).await {
-                log::warn!("Failed to clean up cancelled download file: {}", e);
-            } else {
-                log::info!("Cleaned up cancelled download file: {}", file_path.display());
-            }
+    async fn validate(&self, entry: &CatalogEntry) -> Result<()> {
+        for file in &entry.files {
+            self.validate_model_file(&self.models_dir.join(&file.path)).await?;
         }
-
         Ok(())
     }
+
+    async fn refresh(&self) {
+        if let Err(e) = self.discover_models().await {
+            log::error!("Failed to rediscover models: {}", e);
+        }
+    }
 }
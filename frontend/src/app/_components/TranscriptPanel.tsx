@@ -31,7 +31,7 @@ export function TranscriptPanel({
   showModal
 }: TranscriptPanelProps) {
   // Contexts
-  const { transcripts, transcriptContainerRef, copyTranscript } = useTranscripts();
+  const { transcripts, partialTranscript, transcriptContainerRef, copyTranscript } = useTranscripts();
   const { transcriptModelConfig } = useConfig();
   const { isRecording, isPaused } = useRecordingState();
   const { checkPermissions, isChecking, hasSystemAudio, hasMicrophone } = usePermissionCheck();
@@ -49,6 +49,16 @@ export function TranscriptPanel({
     [transcripts]
   );
 
+  const partialSegment = useMemo(() =>
+    partialTranscript ? {
+      id: partialTranscript.id,
+      timestamp: partialTranscript.audio_start_time ?? 0,
+      endTime: partialTranscript.audio_end_time,
+      text: partialTranscript.text,
+    } : null,
+    [partialTranscript]
+  );
+
   return (
     <div ref={transcriptContainerRef} className="w-full border-r border-gray-200 bg-white flex flex-col overflow-y-auto">
       {/* Title area - Sticky header */}
@@ -113,6 +123,7 @@ export function TranscriptPanel({
               isStopping={isStopping}
               enableStreaming={isRecording}
               showConfidence={true}
+              partialSegment={partialSegment}
             />
           </div>
         </div>
@@ -9,6 +9,8 @@ import { SummaryGeneratorButtonGroup } from './SummaryGeneratorButtonGroup';
 import { SummaryUpdaterButtonGroup } from './SummaryUpdaterButtonGroup';
 import Analytics from '@/lib/analytics';
 import { RefObject } from 'react';
+import ReactMarkdown from 'react-markdown';
+import remarkGfm from 'remark-gfm';
 
 interface SummaryPanelProps {
   meeting: {
@@ -29,6 +31,8 @@ interface SummaryPanelProps {
   onOpenFolder: () => Promise<void>;
   aiSummary: Summary | null;
   summaryStatus: 'idle' | 'processing' | 'summarizing' | 'regenerating' | 'completed' | 'error';
+  // Partial summary streamed while it is being generated
+  streamingMarkdown?: string | null;
   transcripts: Transcript[];
   modelConfig: ModelConfig;
   setModelConfig: (config: ModelConfig | ((prev: ModelConfig) => ModelConfig)) => void;
@@ -65,6 +69,7 @@ export function SummaryPanel({
   onOpenFolder,
   aiSummary,
   summaryStatus,
+  streamingMarkdown,
   transcripts,
   modelConfig,
   setModelConfig,
@@ -160,13 +165,22 @@ export function SummaryPanel({
               onOpenModelSettings={onOpenModelSettings}
             />
           </div>
-          {/* Loading spinner */}
-          <div className="flex items-center justify-center flex-1">
-            <div className="text-center">
-              <div className="inline-block animate-spin rounded-full h-12 w-12 border-t-2 border-b-2 border-blue-500 mb-4"></div>
-              <p className="text-gray-600">Generating AI Summary...</p>
+          {streamingMarkdown ? (
+            /* Partial summary as it streams in */
+            <div className="flex-1 overflow-y-auto min-h-0 px-6 pb-6">
+              <div className="prose prose-sm max-w-none">
+                <ReactMarkdown remarkPlugins={[remarkGfm]}>{streamingMarkdown}</ReactMarkdown>
+              </div>
             </div>
-          </div>
+          ) : (
+            /* Loading spinner */
+            <div className="flex items-center justify-center flex-1">
+              <div className="text-center">
+                <div className="inline-block animate-spin rounded-full h-12 w-12 border-t-2 border-b-2 border-blue-500 mb-4"></div>
+                <p className="text-gray-600">Generating AI Summary...</p>
+              </div>
+            </div>
+          )}
         </div>
       ) : !aiSummary ? (
         <div className="flex flex-col h-full">
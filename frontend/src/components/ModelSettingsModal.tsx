@@ -22,7 +22,7 @@ import { cn, isOllamaNotInstalledError } from '@/lib/utils';
 import { toast } from 'sonner';
 
 export interface ModelConfig {
-  provider: 'ollama' | 'groq' | 'claude' | 'openai' | 'openrouter' | 'builtin-ai' | 'custom-openai';
+  provider: 'ollama' | 'groq' | 'claude' | 'openai' | 'openrouter' | 'builtin-ai' | 'custom-openai' | 'gemini';
   model: string;
   whisperModel: string;
   apiKey?: string | null;
@@ -43,6 +43,16 @@ interface OllamaModel {
   modified: string;
 }
 
+interface GeminiModel {
+  id: string;
+  name: string;
+  input_token_limit: number | null;
+  output_token_limit: number | null;
+}
+
+// Shown until the live model list has been fetched with the user's key
+const DEFAULT_GEMINI_MODELS = ['gemini-2.5-flash', 'gemini-2.5-pro', 'gemini-2.5-flash-lite'];
+
 interface OpenRouterModel {
   id: string;
   name: string;
@@ -79,6 +89,7 @@ export function ModelSettingsModal({
   const [openRouterModels, setOpenRouterModels] = useState<OpenRouterModel[]>([]);
   const [openRouterError, setOpenRouterError] = useState<string>('');
   const [isLoadingOpenRouter, setIsLoadingOpenRouter] = useState<boolean>(false);
+  const [geminiModels, setGeminiModels] = useState<GeminiModel[]>([]);
   const [ollamaEndpoint, setOllamaEndpoint] = useState<string>(modelConfig.ollamaEndpoint || '');
   const [isLoadingOllama, setIsLoadingOllama] = useState<boolean>(false);
   const [lastFetchedEndpoint, setLastFetchedEndpoint] = useState<string>(modelConfig.ollamaEndpoint || '');
@@ -195,6 +206,7 @@ export function ModelSettingsModal({
       'gpt-3.5-turbo-1106'
     ],
     openrouter: openRouterModels.map((m) => m.id),
+    gemini: geminiModels.length > 0 ? geminiModels.map((m) => m.id) : DEFAULT_GEMINI_MODELS,
     'builtin-ai': builtinAiModels.map((m) => m.name),
     'custom-openai': customOpenAIModel ? [customOpenAIModel] : [], // User specifies model manually
   };
@@ -203,7 +215,8 @@ export function ModelSettingsModal({
     modelConfig.provider === 'claude' ||
     modelConfig.provider === 'groq' ||
     modelConfig.provider === 'openai' ||
-    modelConfig.provider === 'openrouter';
+    modelConfig.provider === 'openrouter' ||
+    modelConfig.provider === 'gemini';
 
   // Check if Ollama endpoint has changed but models haven't been fetched yet
   const ollamaEndpointChanged = modelConfig.provider === 'ollama' &&
@@ -458,6 +471,28 @@ export function ModelSettingsModal({
     }
   };
 
+  // The Gemini model list depends on the key, so refetch once the key stops changing
+  useEffect(() => {
+    if (modelConfig.provider !== 'gemini' || !apiKey?.trim()) return;
+
+    let stale = false;
+    const timer = setTimeout(() => {
+      invoke<GeminiModel[]>('api_get_gemini_models', { apiKey })
+        .then((models) => {
+          if (!stale) setGeminiModels(models);
+        })
+        .catch((err) => {
+          console.error('Error loading Gemini models:', err);
+          if (!stale) setGeminiModels([]);
+        });
+    }, 500); // 500ms debounce
+
+    return () => {
+      stale = true;
+      clearTimeout(timer);
+    };
+  }, [modelConfig.provider, apiKey]);
+
   const loadBuiltinAiModels = async () => {
     if (builtinAiModels.length > 0) return; // Already loaded
 
@@ -718,6 +753,7 @@ export function ModelSettingsModal({
                 <SelectItem value="builtin-ai">Built-in AI (Offline, No API needed)</SelectItem>
                 <SelectItem value="claude">Claude</SelectItem>
                 <SelectItem value="custom-openai">Custom Server (OpenAI)</SelectItem>
+                <SelectItem value="gemini">Google Gemini</SelectItem>
                 <SelectItem value="groq">Groq</SelectItem>
                 <SelectItem value="ollama">Ollama</SelectItem>
                 <SelectItem value="openai">OpenAI</SelectItem>
@@ -12,6 +12,7 @@ export interface RecordingPreferences {
   file_format: string;
   preferred_mic_device: string | null;
   preferred_system_device: string | null;
+  interim_transcripts: boolean;
 }
 
 interface RecordingSettingsProps {
@@ -24,7 +25,8 @@ export function RecordingSettings({ onSave }: RecordingSettingsProps) {
     auto_save: true,
     file_format: 'mp4',
     preferred_mic_device: null,
-    preferred_system_device: null
+    preferred_system_device: null,
+    interim_transcripts: true
   });
   const [loading, setLoading] = useState(true);
   const [saving, setSaving] = useState(false);
@@ -79,6 +81,16 @@ export function RecordingSettings({ onSave }: RecordingSettingsProps) {
     });
   };
 
+  const handleInterimTranscriptsToggle = async (enabled: boolean) => {
+    const newPreferences = { ...preferences, interim_transcripts: enabled };
+    setPreferences(newPreferences);
+    await savePreferences(newPreferences);
+
+    await Analytics.track('interim_transcripts_toggled', {
+      enabled: enabled.toString()
+    });
+  };
+
   const handleDeviceChange = async (devices: SelectedDevices) => {
     const newPreferences = {
       ...preferences,
@@ -213,6 +225,21 @@ export function RecordingSettings({ onSave }: RecordingSettingsProps) {
         </div>
       )}
 
+      {/* Live Partial Transcripts Toggle */}
+      <div className="flex items-center justify-between p-4 border rounded-lg">
+        <div className="flex-1">
+          <div className="font-medium">Live Transcript Preview</div>
+          <div className="text-sm text-gray-600">
+            Show a preview of what is being said before the speaker pauses. Uses more CPU with local models; applies to the next recording.
+          </div>
+        </div>
+        <Switch
+          checked={preferences.interim_transcripts}
+          onCheckedChange={handleInterimTranscriptsToggle}
+          disabled={saving}
+        />
+      </div>
+
       {/* Recording Notification Toggle */}
       <div className="flex items-center justify-between p-4 border rounded-lg">
         <div className="flex-1">
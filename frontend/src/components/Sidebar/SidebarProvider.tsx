@@ -25,6 +25,12 @@ interface TranscriptSearchResult {
   title: string;
   matchContext: string;
   timestamp: string;
+  source: 'transcript' | 'summary' | 'notes';
+  // Snippet with matched terms wrapped in <mark></mark>
+  highlight: string;
+  segmentId?: string;
+  audioStartTime?: number;
+  rank: number;
 };
 
 interface SidebarContextType {
@@ -174,7 +180,11 @@ export function SidebarProvider({ children }: { children: React.ReactNode }) {
       setIsSearching(true);
 
 
-      const results = await invoke('api_search_transcripts', { query }) as TranscriptSearchResult[];
+      // Best match per meeting, so every matching meeting stays in the list
+      const results = await invoke('api_search_transcripts', {
+        query,
+        filters: { perMeeting: true },
+      }) as TranscriptSearchResult[];
       setSearchResults(results);
     } catch (error) {
       console.error('Error searching transcripts:', error);
@@ -37,6 +37,18 @@ interface SidebarItem {
   children?: SidebarItem[];
 }
 
+// Render a search snippet, emphasising the <mark>-wrapped terms without injecting HTML
+const renderHighlight = (snippet: string) =>
+  snippet.split(/(<mark>.*?<\/mark>)/g).map((part, index) =>
+    part.startsWith('<mark>') && part.endsWith('</mark>') ? (
+      <mark key={index} className="bg-yellow-200 text-gray-800 rounded-sm">
+        {part.slice(6, -7)}
+      </mark>
+    ) : (
+      part
+    )
+  );
+
 const Sidebar: React.FC = () => {
   const router = useRouter();
   const pathname = usePathname();
@@ -625,7 +637,7 @@ const Sidebar: React.FC = () => {
               {/* Show transcript match snippet if available */}
               {hasTranscriptMatch && (
                 <div className="mt-1 ml-8 text-xs text-gray-500 bg-yellow-50 p-1.5 rounded border border-yellow-100 line-clamp-2">
-                  <span className="font-medium text-yellow-600">Match:</span> {matchingResult.matchContext}
+                  <span className="font-medium text-yellow-600">Match:</span> {renderHighlight(matchingResult.highlight || matchingResult.matchContext)}
                 </div>
               )}
             </div>
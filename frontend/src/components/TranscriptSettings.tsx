@@ -5,14 +5,17 @@ import { Input } from './ui/input';
 import { Button } from './ui/button';
 import { Label } from './ui/label';
 import { Eye, EyeOff, Lock, Unlock } from 'lucide-react';
+import { toast } from 'sonner';
 import { ModelManager } from './WhisperModelManager';
 import { ParakeetModelManager } from './ParakeetModelManager';
 
 
 export interface TranscriptModelProps {
-    provider: 'localWhisper' | 'parakeet' | 'deepgram' | 'elevenLabs' | 'groq' | 'openai';
+    provider: 'localWhisper' | 'parakeet' | 'deepgram' | 'elevenLabs' | 'groq' | 'openai' | 'openaiCompatible';
     model: string;
     apiKey?: string | null;
+    // API root of a self-hosted server, only used by 'openaiCompatible'
+    endpoint?: string | null;
 }
 
 export interface TranscriptSettingsProps {
@@ -28,6 +31,12 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
     const [isLockButtonVibrating, setIsLockButtonVibrating] = useState<boolean>(false);
     const [selectedWhisperModel, setSelectedWhisperModel] = useState<string>(transcriptModelConfig.provider === 'localWhisper' ? transcriptModelConfig.model : 'small');
     const [selectedParakeetModel, setSelectedParakeetModel] = useState<string>(transcriptModelConfig.provider === 'parakeet' ? transcriptModelConfig.model : 'parakeet-tdt-0.6b-v3-int8');
+    const [endpoint, setEndpoint] = useState<string>(transcriptModelConfig.endpoint || '');
+    const [isSavingServer, setIsSavingServer] = useState<boolean>(false);
+
+    useEffect(() => {
+        setEndpoint(transcriptModelConfig.endpoint || '');
+    }, [transcriptModelConfig.endpoint]);
 
     useEffect(() => {
         if (transcriptModelConfig.provider === 'localWhisper' || transcriptModelConfig.provider === 'parakeet') {
@@ -49,12 +58,37 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
     const modelOptions = {
         localWhisper: [selectedWhisperModel],
         parakeet: [selectedParakeetModel],
-        deepgram: ['nova-2-phonecall'],
+        deepgram: ['nova-2', 'nova-2-phonecall', 'nova-3'],
         elevenLabs: ['eleven_multilingual_v2'],
-        groq: ['llama-3.3-70b-versatile'],
-        openai: ['gpt-4o'],
+        groq: ['whisper-large-v3-turbo', 'whisper-large-v3'],
+        openai: ['whisper-1', 'gpt-4o-transcribe', 'gpt-4o-mini-transcribe'],
+        // Self-hosted servers name their models freely; this is only the default
+        openaiCompatible: ['whisper-1'],
     };
     const requiresApiKey = transcriptModelConfig.provider === 'deepgram' || transcriptModelConfig.provider === 'elevenLabs' || transcriptModelConfig.provider === 'openai' || transcriptModelConfig.provider === 'groq';
+    const isSelfHosted = transcriptModelConfig.provider === 'openaiCompatible';
+
+    // The self-hosted server is saved together with its model and optional API key
+    const saveSelfHostedServer = async () => {
+        setIsSavingServer(true);
+        try {
+            await invoke('api_save_transcript_config', {
+                provider: 'openaiCompatible',
+                model: transcriptModelConfig.model,
+                apiKey: apiKey || null,
+                endpoint: endpoint.trim(),
+            });
+            setTranscriptModelConfig({ ...transcriptModelConfig, apiKey, endpoint: endpoint.trim() || null });
+            toast.success('Transcription server saved');
+        } catch (err) {
+            console.error('Error saving transcription server:', err);
+            toast.error('Failed to save transcription server', {
+                description: err instanceof Error ? err.message : String(err),
+            });
+        } finally {
+            setIsSavingServer(false);
+        }
+    };
 
     const handleInputClick = () => {
         if (isApiKeyLocked) {
@@ -120,14 +154,15 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
                                 <SelectContent>
                                     <SelectItem value="parakeet">⚡ Parakeet (Recommended - Real-time / Accurate)</SelectItem>
                                     <SelectItem value="localWhisper">🏠 Local Whisper (High Accuracy)</SelectItem>
-                                    {/* <SelectItem value="deepgram">☁️ Deepgram (Backup)</SelectItem>
-                                    <SelectItem value="elevenLabs">☁️ ElevenLabs</SelectItem>
                                     <SelectItem value="groq">☁️ Groq</SelectItem>
-                                    <SelectItem value="openai">☁️ OpenAI</SelectItem> */}
+                                    <SelectItem value="openai">☁️ OpenAI</SelectItem>
+                                    <SelectItem value="deepgram">☁️ Deepgram</SelectItem>
+                                    <SelectItem value="openaiCompatible">🖥️ Self-hosted (OpenAI-compatible)</SelectItem>
+                                    {/* <SelectItem value="elevenLabs">☁️ ElevenLabs</SelectItem> */}
                                 </SelectContent>
                             </Select>
 
-                            {transcriptModelConfig.provider !== 'localWhisper' && transcriptModelConfig.provider !== 'parakeet' && (
+                            {transcriptModelConfig.provider !== 'localWhisper' && transcriptModelConfig.provider !== 'parakeet' && !isSelfHosted && (
                                 <Select
                                     value={transcriptModelConfig.model}
                                     onValueChange={(value) => {
@@ -170,10 +205,40 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
                     )}
 
 
-                    {requiresApiKey && (
+                    {isSelfHosted && (
+                        <div className="space-y-4">
+                            <div>
+                                <Label className="block text-sm font-medium text-gray-700 mb-1">
+                                    Server URL
+                                </Label>
+                                <Input
+                                    className="mx-1 focus:ring-1 focus:ring-blue-500 focus:border-blue-500"
+                                    value={endpoint}
+                                    onChange={(e) => setEndpoint(e.target.value)}
+                                    placeholder="http://localhost:8000/v1"
+                                />
+                                <p className="mx-1 mt-1 text-xs text-gray-500">
+                                    API root of a server implementing <code>/audio/transcriptions</code>
+                                </p>
+                            </div>
+                            <div>
+                                <Label className="block text-sm font-medium text-gray-700 mb-1">
+                                    Model
+                                </Label>
+                                <Input
+                                    className="mx-1 focus:ring-1 focus:ring-blue-500 focus:border-blue-500"
+                                    value={transcriptModelConfig.model}
+                                    onChange={(e) => setTranscriptModelConfig({ ...transcriptModelConfig, model: e.target.value })}
+                                    placeholder="whisper-1"
+                                />
+                            </div>
+                        </div>
+                    )}
+
+                    {(requiresApiKey || isSelfHosted) && (
                         <div>
                             <Label className="block text-sm font-medium text-gray-700 mb-1">
-                                API Key
+                                {isSelfHosted ? 'API Key (optional)' : 'API Key'}
                             </Label>
                             <div className="relative mx-1">
                                 <Input
@@ -216,6 +281,17 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
                             </div>
                         </div>
                     )}
+
+                    {isSelfHosted && (
+                        <div className="flex justify-end mx-1">
+                            <Button
+                                onClick={saveSelfHostedServer}
+                                disabled={isSavingServer || !endpoint.trim() || !transcriptModelConfig.model.trim()}
+                            >
+                                {isSavingServer ? 'Saving...' : 'Save'}
+                            </Button>
+                        </div>
+                    )}
                 </div>
             </div>
         </div>
@@ -27,6 +27,8 @@ export interface VirtualizedTranscriptViewProps {
     showConfidence?: boolean;
     /** Completely disable auto-scroll behavior (for meeting details page) */
     disableAutoScroll?: boolean;
+    /** Live partial transcript of in-progress speech, shown after the segments */
+    partialSegment?: TranscriptSegmentData | null;
 
     // Pagination props (infinite scroll)
     hasMore?: boolean;
@@ -71,6 +73,7 @@ const TranscriptSegment = memo(function TranscriptSegment({
     confidence,
     isStreaming,
     showConfidence,
+    isPartial = false,
 }: {
     id: string;
     timestamp: number;
@@ -78,6 +81,7 @@ const TranscriptSegment = memo(function TranscriptSegment({
     confidence?: number;
     isStreaming: boolean;
     showConfidence: boolean;
+    isPartial?: boolean;
 }) {
     const displayText = cleanStopWords(text) || (text.trim() === '' ? '[Silence]' : text);
 
@@ -97,7 +101,9 @@ const TranscriptSegment = memo(function TranscriptSegment({
                     </TooltipContent>
                 </Tooltip>
                 <div className="flex-1">
-                    {isStreaming ? (
+                    {isPartial ? (
+                        <p className="text-base text-gray-400 italic leading-relaxed">{displayText}</p>
+                    ) : isStreaming ? (
                         <div className="bg-gray-100 border border-gray-200 rounded-lg px-3 py-2">
                             <p className="text-base text-gray-800 leading-relaxed">{displayText}</p>
                         </div>
@@ -119,6 +125,7 @@ export const VirtualizedTranscriptView: React.FC<VirtualizedTranscriptViewProps>
     enableStreaming = false,
     showConfidence = true,
     disableAutoScroll = false,
+    partialSegment = null,
     hasMore = false,
     isLoadingMore = false,
     totalCount = 0,
@@ -223,6 +230,18 @@ export const VirtualizedTranscriptView: React.FC<VirtualizedTranscriptViewProps>
     // Use simple rendering for small lists, virtualization for large lists
     const useVirtualization = segments.length >= VIRTUALIZATION_THRESHOLD;
 
+    // Partial transcript is rendered outside the list so the streaming effect ignores it
+    const partialRow = partialSegment && isRecording ? (
+        <TranscriptSegment
+            id={partialSegment.id}
+            timestamp={partialSegment.timestamp}
+            text={partialSegment.text}
+            isStreaming={false}
+            showConfidence={false}
+            isPartial
+        />
+    ) : null;
+
     return (
         <div ref={scrollRef} className="flex flex-col h-full overflow-y-auto px-4 py-2">
             {/* Recording Status Bar - Sticky at top, always visible when recording */}
@@ -236,7 +255,7 @@ export const VirtualizedTranscriptView: React.FC<VirtualizedTranscriptViewProps>
 
             {/* Content - add padding when recording to prevent overlap */}
             <div className={isRecording ? 'pt-2' : ''}>
-            {segments.length === 0 ? (
+            {segments.length === 0 && !partialRow ? (
                 // Empty state
                 <motion.div
                     initial={{ opacity: 0 }}
@@ -318,6 +337,8 @@ export const VirtualizedTranscriptView: React.FC<VirtualizedTranscriptViewProps>
                         </div>
                     )}
 
+                    {partialRow}
+
                     {/* Listening indicator when recording */}
                     {!isStopping && isRecording && !isPaused && !isProcessing && segments.length > 0 && (
                         <motion.div
@@ -374,6 +395,8 @@ export const VirtualizedTranscriptView: React.FC<VirtualizedTranscriptViewProps>
                         </div>
                     )}
 
+                    {partialRow}
+
                     {/* Listening indicator when recording */}
                     {!isStopping && isRecording && !isPaused && !isProcessing && segments.length > 0 && (
                         <motion.div
@@ -199,7 +199,8 @@ export function ConfigProvider({ children }: { children: ReactNode }) {
           setTranscriptModelConfig({
             provider: config.provider || 'parakeet',
             model: config.model || 'parakeet-tdt-0.6b-v3-int8',
-            apiKey: config.apiKey || null
+            apiKey: config.apiKey || null,
+            endpoint: config.endpoint || null
           });
         }
       } catch (error) {
@@ -324,6 +325,7 @@ export function ConfigProvider({ children }: { children: ReactNode }) {
     openai: ['gpt-4', 'gpt-4-turbo', 'gpt-3.5-turbo'],
     'builtin-ai': [],
     'custom-openai': [],
+    gemini: ['gemini-2.5-flash', 'gemini-2.5-pro'],
   };
 
   // Toggle confidence indicator with localStorage persistence
@@ -10,6 +10,7 @@ import { indexedDBService } from '@/services/indexedDBService';
 
 interface TranscriptContextType {
   transcripts: Transcript[];
+  partialTranscript: Transcript | null;
   transcriptsRef: MutableRefObject<Transcript[]>
   addTranscript: (update: TranscriptUpdate) => void;
   copyTranscript: () => void;
@@ -26,6 +27,8 @@ const TranscriptContext = createContext<TranscriptContextType | undefined>(undef
 
 export function TranscriptProvider({ children }: { children: ReactNode }) {
   const [transcripts, setTranscripts] = useState<Transcript[]>([]);
+  // Live preview of in-progress speech; replaced by the next final transcript
+  const [partialTranscript, setPartialTranscript] = useState<Transcript | null>(null);
   const [meetingTitle, setMeetingTitle] = useState('+ New Call');
   const [currentMeetingId, setCurrentMeetingId] = useState<string | null>(null);
 
@@ -183,6 +186,7 @@ export function TranscriptProvider({ children }: { children: ReactNode }) {
     let transcriptCounter = 0;
     let transcriptBuffer = new Map<number, Transcript>();
     let lastProcessedSequence = 0;
+    let lastFinalEndTime = 0;
     let processingTimer: NodeJS.Timeout | undefined;
 
     const processBufferedTranscripts = (forceFlush = false) => {
@@ -296,6 +300,33 @@ export function TranscriptProvider({ children }: { children: ReactNode }) {
             buffer_size_before: transcriptBuffer.size
           });
 
+          // Partials only drive the live preview - never buffered, stored or saved
+          if (update.is_partial) {
+            // Ignore partials that arrive after their utterance was finalized
+            if ((update.audio_start_time ?? 0) >= lastFinalEndTime) {
+              setPartialTranscript({
+                id: 'partial',
+                text: update.text,
+                timestamp: update.timestamp,
+                sequence_id: update.sequence_id,
+                chunk_start_time: update.chunk_start_time,
+                is_partial: true,
+                confidence: update.confidence,
+                audio_start_time: update.audio_start_time,
+                audio_end_time: update.audio_end_time,
+                duration: update.duration,
+              });
+            }
+            return;
+          }
+
+          // A final transcript replaces the partial of the same utterance
+          const finalEndTime = update.audio_end_time ?? 0;
+          lastFinalEndTime = Math.max(lastFinalEndTime, finalEndTime);
+          setPartialTranscript(prev =>
+            prev && (prev.audio_start_time ?? 0) < finalEndTime ? null : prev
+          );
+
           // Check for duplicate sequence_id before processing
           if (transcriptBuffer.has(update.sequence_id)) {
             console.log('🚫 MAIN LISTENER: Duplicate sequence_id, skipping buffer:', update.sequence_id);
@@ -315,6 +346,9 @@ export function TranscriptProvider({ children }: { children: ReactNode }) {
             audio_start_time: update.audio_start_time,
             audio_end_time: update.audio_end_time,
             duration: update.duration,
+            words: update.words,
+            speaker: update.speaker,
+            speaker_id: update.speaker_id,
           };
 
           // Add to buffer
@@ -358,6 +392,13 @@ export function TranscriptProvider({ children }: { children: ReactNode }) {
     };
   }, [currentMeetingId]); // Add currentMeetingId dependency
 
+  // Drop the live preview once recording stops
+  useEffect(() => {
+    if (!recordingState.isRecording) {
+      setPartialTranscript(null);
+    }
+  }, [recordingState.isRecording]);
+
   // Sync transcript history and meeting name from backend on reload
   // This fixes the issue where reloading during active recording causes state desync
   useEffect(() => {
@@ -383,6 +424,9 @@ export function TranscriptProvider({ children }: { children: ReactNode }) {
             audio_start_time: segment.audio_start_time,
             audio_end_time: segment.audio_end_time,
             duration: segment.duration,
+            words: segment.words,
+            speaker: segment.speaker,
+            speaker_id: segment.speaker_id,
           }));
 
           setTranscripts(formattedTranscripts);
@@ -424,6 +468,9 @@ export function TranscriptProvider({ children }: { children: ReactNode }) {
       audio_start_time: update.audio_start_time,
       audio_end_time: update.audio_end_time,
       duration: update.duration,
+      words: update.words,
+      speaker: update.speaker,
+      speaker_id: update.speaker_id,
     };
 
     setTranscripts(prev => {
@@ -483,6 +530,7 @@ export function TranscriptProvider({ children }: { children: ReactNode }) {
   // Clear transcripts (used when starting new recording)
   const clearTranscripts = useCallback(() => {
     setTranscripts([]);
+    setPartialTranscript(null);
     // Don't clear currentMeetingId here - it will be set by recording-started event
   }, []);
 
@@ -511,6 +559,7 @@ export function TranscriptProvider({ children }: { children: ReactNode }) {
 
   const value: TranscriptContextType = {
     transcripts,
+    partialTranscript,
     transcriptsRef,
     addTranscript,
     copyTranscript,
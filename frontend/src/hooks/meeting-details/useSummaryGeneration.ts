@@ -1,8 +1,9 @@
-import { useState, useCallback } from 'react';
+import { useState, useCallback, useEffect, useRef } from 'react';
 import { Transcript, Summary } from '@/types';
 import { ModelConfig } from '@/components/ModelSettingsModal';
 import { CurrentMeeting, useSidebar } from '@/components/Sidebar/SidebarProvider';
 import { invoke as invokeTauri } from '@tauri-apps/api/core';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import { toast } from 'sonner';
 import Analytics from '@/lib/analytics';
 import { isOllamaNotInstalledError } from '@/lib/utils';
@@ -10,6 +11,14 @@ import { BuiltInModelInfo } from '@/lib/builtin-ai';
 
 type SummaryStatus = 'idle' | 'processing' | 'summarizing' | 'regenerating' | 'completed' | 'error';
 
+// Payload of the backend's `summary-stream` event
+interface SummaryStreamEvent {
+  meeting_id: string;
+  delta: string;
+  // Full text so far; sent at the start of each provider attempt and periodically
+  markdown?: string;
+}
+
 interface UseSummaryGenerationProps {
   meeting: any;
   transcripts: Transcript[];
@@ -36,9 +45,34 @@ export function useSummaryGeneration({
   const [summaryStatus, setSummaryStatus] = useState<SummaryStatus>('idle');
   const [summaryError, setSummaryError] = useState<string | null>(null);
   const [originalTranscript, setOriginalTranscript] = useState<string>('');
+  const [streamingMarkdown, setStreamingMarkdown] = useState<string | null>(null);
+  const unlistenStreamRef = useRef<UnlistenFn | null>(null);
 
   const { startSummaryPolling, stopSummaryPolling } = useSidebar();
 
+  // Stop showing the partial summary from `summary-stream` events
+  const stopSummaryStream = useCallback(() => {
+    unlistenStreamRef.current?.();
+    unlistenStreamRef.current = null;
+    setStreamingMarkdown(null);
+  }, []);
+
+  // Show the final summary's markdown while it is being generated
+  const startSummaryStream = useCallback(async (meetingId: string) => {
+    stopSummaryStream();
+    let text = '';
+    unlistenStreamRef.current = await listen<SummaryStreamEvent>('summary-stream', (event) => {
+      if (event.payload.meeting_id !== meetingId) {
+        return;
+      }
+      text = event.payload.markdown ?? text + event.payload.delta;
+      setStreamingMarkdown(text);
+    });
+  }, [stopSummaryStream]);
+
+  // Don't leave the listener behind when the meeting view goes away
+  useEffect(() => stopSummaryStream, [stopSummaryStream]);
+
   // Helper to get status message
   const getSummaryStatusMessage = useCallback((status: SummaryStatus) => {
     switch (status) {
@@ -103,6 +137,9 @@ export function useSummaryGeneration({
         duration: 3000,
       });
 
+      // Listen before starting so no streamed text is missed
+      await startSummaryStream(meeting.id);
+
       // Process transcript and get process_id
       const result = await invokeTauri('api_process_transcript', {
         text: transcriptText,
@@ -125,6 +162,7 @@ export function useSummaryGeneration({
         // Handle cancellation
         if (pollingResult.status === 'cancelled') {
           console.log('Summary generation was cancelled');
+          stopSummaryStream();
 
           // Reload summary from database (backend has already restored from backup)
           try {
@@ -151,6 +189,7 @@ export function useSummaryGeneration({
         // Handle errors
         if (pollingResult.status === 'error' || pollingResult.status === 'failed') {
           console.error('Backend returned error:', pollingResult.error);
+          stopSummaryStream();
           const errorMessage = pollingResult.error || `Summary ${isRegeneration ? 'regeneration' : 'generation'} failed`;
 
           // If this was a regeneration, try to restore previous summary from database
@@ -220,6 +259,7 @@ export function useSummaryGeneration({
         // Handle successful completion
         if (pollingResult.status === 'completed' && pollingResult.data) {
           console.log('Summary generation completed:', pollingResult.data);
+          stopSummaryStream();
 
           // Update meeting title if available
           const meetingName = pollingResult.data.MeetingName || pollingResult.meetingName;
@@ -326,6 +366,7 @@ export function useSummaryGeneration({
       });
     } catch (error) {
       console.error(`Failed to ${isRegeneration ? 'regenerate' : 'generate'} summary:`, error);
+      stopSummaryStream();
       const errorMessage = error instanceof Error ? error.message : 'Unknown error';
       setSummaryError(errorMessage);
       setSummaryStatus('error');
@@ -349,6 +390,8 @@ export function useSummaryGeneration({
     modelConfig,
     selectedTemplate,
     startSummaryPolling,
+    startSummaryStream,
+    stopSummaryStream,
     setAiSummary,
     updateMeetingTitle,
     onMeetingUpdated,
@@ -558,7 +601,10 @@ export function useSummaryGeneration({
     };
 
     const fullTranscript = allTranscripts
-      .map(t => `${formatTime(t.audio_start_time, t.timestamp)} ${t.text}`)
+      .map(t => {
+        const speaker = t.speaker_label ? `${t.speaker_label}: ` : '';
+        return `${formatTime(t.audio_start_time, t.timestamp)} ${speaker}${t.text}`;
+      })
       .join('\n');
 
     await processSummary({ transcriptText: fullTranscript, customPrompt });
@@ -594,6 +640,7 @@ export function useSummaryGeneration({
 
     // Stop polling
     stopSummaryPolling(meeting.id);
+    stopSummaryStream();
 
     // Reset status to idle
     setSummaryStatus('idle');
@@ -604,11 +651,12 @@ export function useSummaryGeneration({
       description: 'You can generate a new summary anytime',
       duration: 3000,
     });
-  }, [meeting.id, stopSummaryPolling]);
+  }, [meeting.id, stopSummaryPolling, stopSummaryStream]);
 
   return {
     summaryStatus,
     summaryError,
+    streamingMarkdown,
     handleGenerateSummary,
     handleRegenerateSummary,
     handleStopGeneration,
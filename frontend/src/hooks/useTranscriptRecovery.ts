@@ -171,6 +171,9 @@ export function useTranscriptRecovery(): UseTranscriptRecoveryReturn {
         audio_start_time: (t as any).audio_start_time,
         audio_end_time: (t as any).audio_end_time,
         duration: (t as any).duration,
+        words: (t as any).words,
+        speaker: (t as any).speaker,
+        speaker_id: (t as any).speaker_id,
       }));
 
       // 6. Save to backend database using existing save utilities
@@ -7,7 +7,23 @@ export interface BuiltInModelInfo {
   size_mb: number;
   context_size: number;
   description: string;
-  gguf_file: string;
+  gguf_file: string;               // absolute path for custom models
+  custom: boolean;                 // registered from a local GGUF file
+}
+
+// Metadata of a local GGUF file, shown before registering it
+export interface GgufInspection {
+  path: string;
+  display_name: string;
+  architecture: string | null;
+  context_length: number | null;   // as declared by the model, before capping
+  layer_count: number | null;
+  size_mb: number;
+  has_embedded_template: boolean;
+  template: {
+    template: string;              // 'gemma3' | 'chatml' | 'qwen' | 'llama3' | 'mistral' | 'phi' | 'jinja'
+    stop_tokens: string[];
+  };
 }
 
 export type BuiltInModelStatus =
@@ -92,6 +108,19 @@ export class BuiltInAIAPI {
     await invoke('builtin_ai_delete_model', { modelName });
   }
 
+  static async inspectGguf(path: string): Promise<GgufInspection> {
+    return await invoke('builtin_ai_inspect_gguf', { path });
+  }
+
+  static async registerModel(path: string, displayName?: string): Promise<BuiltInModelInfo> {
+    return await invoke('builtin_ai_register_model', { path, displayName });
+  }
+
+  // Removes a custom model from the list; its file is kept
+  static async unregisterModel(modelName: string): Promise<boolean> {
+    return await invoke('builtin_ai_unregister_model', { modelName });
+  }
+
   static async getModelsDirectory(): Promise<string> {
     return await invoke('builtin_ai_get_models_directory');
   }
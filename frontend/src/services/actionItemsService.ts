@@ -0,0 +1,76 @@
+/**
+ * Action Items Service
+ *
+ * Handles action item Tauri backend calls and events.
+ * Pure 1-to-1 wrapper - no error handling changes, exact same behavior as direct invoke/listen calls.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
+
+export interface ActionItem {
+  id: string;
+  meeting_id: string;
+  meeting_title: string;
+  task: string;
+  owner: string | null;
+  due_date: string | null;        // as written in the summary ("Friday", "2026-10-24")
+  source_time: number | null;     // seconds from the start of the recording
+  source_segment_id: string | null;
+  completed: boolean;
+  completed_at: string | null;
+  edited: boolean;                // edited items survive summary regeneration
+  created_at: string;
+  updated_at: string;
+}
+
+export interface ActionItemFilters {
+  meetingId?: string;
+  owner?: string;                 // case-insensitive substring
+  completed?: boolean;            // omit for both open and completed items
+  query?: string;                 // case-insensitive substring of the task
+  fromDate?: string;              // "YYYY-MM-DD"
+  toDate?: string;                // "YYYY-MM-DD"
+  limit?: number;
+}
+
+/**
+ * Action Items Service
+ * Singleton service for action items tracked across meetings
+ */
+export class ActionItemsService {
+  /**
+   * Action items across meetings, open ones first
+   */
+  async list(filters?: ActionItemFilters): Promise<ActionItem[]> {
+    return invoke<ActionItem[]>('api_list_action_items', { filters });
+  }
+
+  async setCompleted(id: string, completed: boolean): Promise<ActionItem> {
+    return invoke<ActionItem>('api_set_action_item_completed', { id, completed });
+  }
+
+  async update(id: string, task: string, owner?: string | null, dueDate?: string | null): Promise<ActionItem> {
+    return invoke<ActionItem>('api_update_action_item', { id, task, owner, dueDate });
+  }
+
+  async delete(id: string): Promise<void> {
+    return invoke('api_delete_action_item', { id });
+  }
+
+  /**
+   * Re-extract action items from a meeting's saved summary
+   */
+  async extract(meetingId: string): Promise<ActionItem[]> {
+    return invoke<ActionItem[]>('api_extract_action_items', { meetingId });
+  }
+
+  async onUpdated(callback: (meetingId: string) => void): Promise<UnlistenFn> {
+    return listen<{ meetingId: string }>('action-items-updated', (event) => {
+      callback(event.payload.meetingId);
+    });
+  }
+}
+
+// Export singleton instance
+export const actionItemsService = new ActionItemsService();
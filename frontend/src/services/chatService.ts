@@ -0,0 +1,77 @@
+/**
+ * Chat Service
+ *
+ * Handles per-meeting chat thread Tauri backend calls.
+ * Pure 1-to-1 wrapper - no error handling changes, exact same behavior as direct invoke calls.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface ChatThread {
+  id: string;
+  meeting_id: string;
+  title: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface TimestampCitation {
+  label: string;                  // the [mm:ss] marker as written in the answer
+  seconds: number;                // seconds from the start of the recording
+  segmentId?: string;             // transcript segment playing at that time
+}
+
+export interface ChatMessage {
+  id: string;
+  threadId: string;
+  role: 'user' | 'assistant';
+  content: string;
+  citations: TimestampCitation[];
+  modelProvider?: string;
+  modelName?: string;
+  createdAt: string;
+}
+
+export interface SendMessageOptions {
+  modelProvider?: string;         // default: summary provider from settings
+  modelName?: string;
+}
+
+/**
+ * Chat Service
+ * Singleton service for follow-up conversations about a meeting
+ */
+export class ChatService {
+  async createThread(meetingId: string, title?: string): Promise<ChatThread> {
+    return invoke<ChatThread>('api_chat_create_thread', { meetingId, title });
+  }
+
+  /**
+   * Threads of a meeting, most recently active first
+   */
+  async listThreads(meetingId: string): Promise<ChatThread[]> {
+    return invoke<ChatThread[]>('api_chat_list_threads', { meetingId });
+  }
+
+  async getMessages(threadId: string): Promise<ChatMessage[]> {
+    return invoke<ChatMessage[]>('api_chat_get_messages', { threadId });
+  }
+
+  async renameThread(threadId: string, title: string): Promise<void> {
+    return invoke('api_chat_rename_thread', { threadId, title });
+  }
+
+  async deleteThread(threadId: string): Promise<void> {
+    return invoke('api_chat_delete_thread', { threadId });
+  }
+
+  /**
+   * Ask a follow-up question; resolves with the assistant's reply
+   */
+  async sendMessage(threadId: string, message: string, options: SendMessageOptions = {}): Promise<ChatMessage> {
+    return invoke<ChatMessage>('api_chat_send_message', { threadId, message, ...options });
+  }
+}
+
+// Export singleton instance
+export const chatService = new ChatService();
@@ -9,7 +9,7 @@ import { invoke } from '@tauri-apps/api/core';
 import { TranscriptModelProps } from '@/components/TranscriptSettings';
 
 export interface ModelConfig {
-  provider: 'ollama' | 'groq' | 'claude' | 'openrouter' | 'openai' | 'builtin-ai' | 'custom-openai';
+  provider: 'ollama' | 'groq' | 'claude' | 'openrouter' | 'openai' | 'builtin-ai' | 'custom-openai' | 'gemini';
   model: string;
   whisperModel: string;
   apiKey?: string | null;
@@ -32,6 +32,12 @@ export interface CustomOpenAIConfig {
   topP: number | null;
 }
 
+// One step of the summary fallback chain, tried after the selected provider fails
+export interface FallbackTarget {
+  provider: ModelConfig['provider'];
+  model: string;
+}
+
 export interface RecordingPreferences {
   preferred_mic_device: string | null;
   preferred_system_device: string | null;
@@ -120,6 +126,23 @@ export class ConfigService {
       model,
     });
   }
+
+  /**
+   * Get the providers tried, in order, when summary generation fails
+   * @returns Promise with the fallback chain (empty when not configured)
+   */
+  async getSummaryFallbackChain(): Promise<FallbackTarget[]> {
+    return invoke<FallbackTarget[]>('api_get_summary_fallback_chain');
+  }
+
+  /**
+   * Replace the summary fallback chain; an empty list turns fallback off
+   * @param chain - Ordered provider/model pairs
+   * @returns Promise with the chain as saved (normalized and de-duplicated)
+   */
+  async saveSummaryFallbackChain(chain: FallbackTarget[]): Promise<FallbackTarget[]> {
+    return invoke<FallbackTarget[]>('api_save_summary_fallback_chain', { chain });
+  }
 }
 
 // Export singleton instance
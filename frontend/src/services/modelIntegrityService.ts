@@ -0,0 +1,43 @@
+/**
+ * Model Integrity Service
+ *
+ * Handles model checksum verification Tauri backend calls.
+ * Pure 1-to-1 wrapper - no error handling changes, exact same behavior as direct invoke calls.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export type CheckStatus = 'verified' | 'unverified' | 'mismatch' | 'failed';
+
+export interface FileCheck {
+  file: string;                   // relative to the engine's models directory
+  status: CheckStatus;
+  sha256: string | null;
+  expected_sha256: string | null;
+  size: number | null;
+  quarantined_to: string | null;  // set when a mismatching file was moved aside
+  error: string | null;
+}
+
+export interface ModelVerification {
+  engine: 'whisper' | 'parakeet' | 'builtin_ai' | 'diarization' | 'semantic';
+  model: string;
+  files: FileCheck[];
+}
+
+/**
+ * Model Integrity Service
+ * Singleton service for SHA-256 verification of installed models
+ */
+export class ModelIntegrityService {
+  /**
+   * Re-hash every installed model file.
+   * Mismatching files are quarantined; their models then need downloading again.
+   */
+  async verifyModels(): Promise<ModelVerification[]> {
+    return invoke<ModelVerification[]>('verify_models');
+  }
+}
+
+// Export singleton instance
+export const modelIntegrityService = new ModelIntegrityService();
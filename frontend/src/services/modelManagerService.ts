@@ -0,0 +1,143 @@
+/**
+ * Model Manager Service
+ *
+ * Handles shared model manager Tauri backend calls (Whisper, Parakeet, built-in AI, speaker diarization and semantic search models).
+ * Pure 1-to-1 wrapper - no error handling changes, exact same behavior as direct invoke/listen calls.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
+import type { ModelVerification } from './modelIntegrityService';
+
+export type EngineKind = 'whisper' | 'parakeet' | 'builtin_ai' | 'diarization' | 'semantic';
+
+export interface CatalogFile {
+  path: string;                   // relative to the engine's models directory
+  url: string;
+  size_bytes: number;             // approximate for some models
+}
+
+export interface DownloadProgress {
+  downloaded_bytes: number;
+  total_bytes: number;
+  downloaded_mb: number;
+  total_mb: number;
+  speed_mbps: number;
+  percent: number;                // 0-100
+}
+
+export type ModelState =
+  | { state: 'not_downloaded' }
+  | { state: 'partial'; downloaded_bytes: number }   // downloading again resumes
+  | { state: 'queued' }                              // waiting for a free download slot
+  | { state: 'downloading'; progress: DownloadProgress }
+  | { state: 'installed' }
+  | { state: 'invalid'; reason: string };
+
+export interface ModelEntry {
+  engine: EngineKind;
+  name: string;
+  display_name: string;
+  description: string;
+  files: CatalogFile[];
+  state: ModelState;
+}
+
+export type DownloadPhase = 'queued' | 'downloading' | 'verifying' | 'completed' | 'cancelled' | 'failed';
+
+export interface DownloadEvent extends DownloadProgress {
+  engine: EngineKind;
+  model: string;
+  phase: DownloadPhase;
+  error?: string;                 // set when phase is 'failed'
+}
+
+export const MODEL_DOWNLOAD_EVENT = 'model-manager-download';
+
+export interface ModelRef {
+  engine: EngineKind;
+  model: string;
+}
+
+export interface SkippedItem {
+  name: string;                   // model name, or path inside the bundle for unrecognized files
+  engine: EngineKind | null;
+  reason: string;
+}
+
+export interface ImportReport {
+  installed: ModelVerification[];  // checksum result of each installed file
+  skipped: SkippedItem[];
+}
+
+export interface BundleModel {
+  engine: EngineKind;
+  name: string;
+  files: { path: string; sha256: string; size: number; source: 'published' | 'recorded' }[];
+}
+
+export interface ExportReport {
+  path: string;
+  size_bytes: number;
+  models: BundleModel[];
+}
+
+/**
+ * Model Manager Service
+ * Singleton service for listing, downloading, deleting, importing and exporting models of every engine
+ */
+export class ModelManagerService {
+  /**
+   * Downloadable models with their state, for all engines or one engine
+   */
+  async list(engine?: EngineKind): Promise<ModelEntry[]> {
+    return invoke<ModelEntry[]>('models_list', { engine });
+  }
+
+  /**
+   * Download (or resume) a model. Resolves when the download is installed;
+   * progress arrives through onDownloadEvent.
+   */
+  async download(engine: EngineKind, model: string): Promise<void> {
+    return invoke('models_download', { engine, model });
+  }
+
+  /**
+   * Cancel a running or queued download. Partial files are kept for resuming.
+   * @returns false if the model wasn't downloading
+   */
+  async cancelDownload(engine: EngineKind, model: string): Promise<boolean> {
+    return invoke<boolean>('models_cancel_download', { engine, model });
+  }
+
+  async delete(engine: EngineKind, model: string): Promise<void> {
+    return invoke('models_delete', { engine, model });
+  }
+
+  /**
+   * Install models from a folder or a .tar, .tar.gz, .tgz or .zip archive (for offline machines)
+   */
+  async importBundle(source: string): Promise<ImportReport> {
+    return invoke<ImportReport>('models_import', { source });
+  }
+
+  /**
+   * Package installed models as a tar archive; exports every installed model when none are given
+   */
+  async exportBundle(destination: string, models?: ModelRef[]): Promise<ExportReport> {
+    return invoke<ExportReport>('models_export', { destination, models });
+  }
+
+  /**
+   * Listen for download progress and phase changes of all engines
+   * @returns Promise that resolves to unlisten function
+   */
+  async onDownloadEvent(callback: (event: DownloadEvent) => void): Promise<UnlistenFn> {
+    return listen<DownloadEvent>(MODEL_DOWNLOAD_EVENT, (event) => {
+      callback(event.payload);
+    });
+  }
+}
+
+// Export singleton instance
+export const modelManagerService = new ModelManagerService();
@@ -0,0 +1,90 @@
+/**
+ * Notes Service
+ *
+ * Handles meeting notes Tauri backend calls.
+ * Pure 1-to-1 wrapper - no error handling changes, exact same behavior as direct invoke calls.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface NoteAnchor {
+  id: string;
+  time: number;                   // seconds from the start of the recording
+  block_id: string | null;        // editor block the anchor is attached to
+  text: string;
+}
+
+export interface MeetingNotes {
+  meeting_id: string;
+  notes_markdown: string | null;
+  notes_json: string | null;      // editor document
+  anchors: string | null;         // JSON array of NoteAnchor
+  created_at: string;
+  updated_at: string;
+}
+
+export interface NotesDraft {
+  notes_markdown: string | null;
+  notes_json: string | null;
+  anchors: NoteAnchor[];
+}
+
+/**
+ * Notes Service
+ * Singleton service for the user's own meeting notes
+ */
+export class NotesService {
+  async get(meetingId: string): Promise<MeetingNotes | null> {
+    return invoke<MeetingNotes | null>('api_get_meeting_notes', { meetingId });
+  }
+
+  async save(meetingId: string, draft: NotesDraft): Promise<MeetingNotes> {
+    return invoke<MeetingNotes>('api_save_meeting_notes', {
+      meetingId,
+      notesMarkdown: draft.notes_markdown,
+      notesJson: draft.notes_json,
+      anchors: draft.anchors,
+    });
+  }
+
+  async delete(meetingId: string): Promise<boolean> {
+    return invoke<boolean>('api_delete_meeting_notes', { meetingId });
+  }
+
+  /**
+   * Queue a debounced save; call on every editor change.
+   * Pass no meetingId while recording - the notes are attached to the meeting when its transcript is saved.
+   */
+  async autosave(draft: NotesDraft, meetingId?: string): Promise<void> {
+    return invoke('api_autosave_meeting_notes', {
+      meetingId,
+      notesMarkdown: draft.notes_markdown,
+      notesJson: draft.notes_json,
+      anchors: draft.anchors,
+    });
+  }
+
+  /**
+   * Write a pending autosave now (e.g. when the editor closes)
+   */
+  async flush(meetingId?: string): Promise<boolean> {
+    return invoke<boolean>('api_flush_meeting_notes', { meetingId });
+  }
+
+  /**
+   * Notes of the recording in progress, for restoring the editor after a reload
+   */
+  async getRecordingNotes(): Promise<NotesDraft | null> {
+    return invoke<NotesDraft | null>('api_get_recording_notes');
+  }
+
+  /**
+   * Anchor a note to the current point of the recording
+   */
+  async createAnchor(text?: string, blockId?: string): Promise<NoteAnchor> {
+    return invoke<NoteAnchor>('api_create_note_anchor', { text, blockId });
+  }
+}
+
+// Export singleton instance
+export const notesService = new NotesService();
@@ -22,6 +22,20 @@ export interface RecordingStoppedPayload {
   meeting_name?: string;
 }
 
+export interface ImportProgress {
+  stage: 'decoding' | 'detecting_speech' | 'transcribing' | 'saving';
+  progress: number; // 0-100
+  message: string;
+}
+
+export interface ImportResult {
+  meeting_id: string;
+  title: string;
+  folder_path: string;
+  segment_count: number;
+  duration_seconds: number;
+}
+
 /**
  * Recording Service
  * Singleton service for managing recording lifecycle operations
@@ -164,6 +178,35 @@ export class RecordingService {
   async onSpeechDetected(callback: () => void): Promise<UnlistenFn> {
     return listen('speech-detected', callback);
   }
+
+  /**
+   * Import an existing audio/video file and transcribe it into a new meeting
+   * @param filePath - Absolute path of the file to import
+   * @param title - Optional meeting title (defaults to the file name)
+   * @returns Promise with the created meeting
+   */
+  async importAudioFile(filePath: string, title?: string): Promise<ImportResult> {
+    return invoke<ImportResult>('import_audio_file', { filePath, title });
+  }
+
+  /**
+   * Cancel the running file import
+   * @returns Promise<void>
+   */
+  async cancelAudioImport(): Promise<void> {
+    return invoke('cancel_audio_import');
+  }
+
+  /**
+   * Listen for file import progress
+   * @param callback - Function to call with progress updates
+   * @returns Promise that resolves to unlisten function
+   */
+  async onImportProgress(callback: (progress: ImportProgress) => void): Promise<UnlistenFn> {
+    return listen<ImportProgress>('import-progress', (event) => {
+      callback(event.payload);
+    });
+  }
 }
 
 // Export singleton instance
@@ -0,0 +1,105 @@
+/**
+ * Semantic Search Service
+ *
+ * Handles semantic search and "ask your meetings" Tauri backend calls and events.
+ * Pure 1-to-1 wrapper - no error handling changes, exact same behavior as direct invoke/listen calls.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
+
+export interface EmbeddingConfig {
+  backend?: 'local' | 'ollama';   // default 'local'
+  ollamaModel?: string;           // default 'nomic-embed-text'
+  ollamaEndpoint?: string;        // default: saved Ollama endpoint
+}
+
+export interface RetrievedPassage {
+  meetingId: string;
+  meetingTitle: string;
+  source: 'transcript' | 'summary';
+  segmentId?: string;
+  text: string;
+  timestamp: string;
+  audioStartTime?: number;        // seconds from the start of the recording
+  score: number;                  // cosine similarity
+}
+
+export interface Citation extends RetrievedPassage {
+  index: number;                  // the [n] marker used in the answer
+}
+
+export interface MeetingAnswer {
+  answer: string;
+  citations: Citation[];
+}
+
+export interface SemanticModelStatus {
+  downloaded: boolean;
+  path: string | null;
+  size_mb: number | null;
+}
+
+export interface SemanticIndexStatus {
+  model: string;
+  indexed: number;
+  total: number;
+}
+
+export interface AskOptions {
+  topK?: number;
+  meetingId?: string;
+  config?: EmbeddingConfig;
+  modelProvider?: string;         // default: summary provider from settings
+  modelName?: string;
+}
+
+/**
+ * Semantic Search Service
+ * Singleton service for embedding-based retrieval and Q&A over meetings
+ */
+export class SemanticSearchService {
+  async getModelStatus(): Promise<SemanticModelStatus> {
+    return invoke<SemanticModelStatus>('semantic_get_model_status');
+  }
+
+  async downloadModel(): Promise<void> {
+    return invoke('semantic_download_model');
+  }
+
+  /**
+   * Embed everything not yet indexed (or everything, with rebuild)
+   */
+  async index(config?: EmbeddingConfig, rebuild?: boolean): Promise<SemanticIndexStatus> {
+    return invoke<SemanticIndexStatus>('api_semantic_index', { config, rebuild });
+  }
+
+  /**
+   * Transcript segments and summary passages closest in meaning to the query
+   */
+  async search(query: string, topK?: number, meetingId?: string, config?: EmbeddingConfig): Promise<RetrievedPassage[]> {
+    return invoke<RetrievedPassage[]>('api_semantic_search', { query, topK, meetingId, config });
+  }
+
+  /**
+   * Answer a question from the most relevant passages, with [n] citations
+   */
+  async ask(question: string, options: AskOptions = {}): Promise<MeetingAnswer> {
+    return invoke<MeetingAnswer>('api_ask_meetings', { question, ...options });
+  }
+
+  async onIndexProgress(callback: (indexed: number) => void): Promise<UnlistenFn> {
+    return listen<{ indexed: number }>('semantic-index-progress', (event) => {
+      callback(event.payload.indexed);
+    });
+  }
+
+  async onModelDownloadProgress(callback: (progress: number) => void): Promise<UnlistenFn> {
+    return listen<{ progress: number }>('semantic-model-download-progress', (event) => {
+      callback(event.payload.progress);
+    });
+  }
+}
+
+// Export singleton instance
+export const semanticSearchService = new SemanticSearchService();
@@ -0,0 +1,72 @@
+/**
+ * Summary Versions Service
+ *
+ * Handles summary version history Tauri backend calls.
+ * Pure 1-to-1 wrapper - no error handling changes, exact same behavior as direct invoke calls.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface SummaryVersion {
+  id: string;
+  meeting_id: string;
+  version: number;
+  source: 'generated' | 'edited' | 'restored';
+  result: string;                 // summary JSON, as returned by api_get_summary's data
+  provider: string | null;
+  model: string | null;
+  template_id: string | null;
+  custom_prompt: string | null;
+  input_tokens: number | null;    // estimated
+  output_tokens: number | null;   // estimated
+  chunk_count: number | null;
+  processing_time: number | null; // seconds
+  restored_from: number | null;
+  created_at: string;
+}
+
+export interface DiffLine {
+  change: 'same' | 'added' | 'removed';
+  text: string;
+}
+
+export interface SectionDiff {
+  title: string;                  // empty for text before the first section
+  status: 'added' | 'removed' | 'changed' | 'unchanged';
+  lines: DiffLine[];
+}
+
+export interface SummaryVersionDiff {
+  meeting_id: string;
+  from_version: number;
+  to_version: number;
+  template_id: string | null;
+  sections: SectionDiff[];
+}
+
+/**
+ * Summary Versions Service
+ * Singleton service for summary history, restore and diff
+ */
+export class SummaryVersionsService {
+  /**
+   * Every version of a meeting's summary, newest first
+   */
+  async list(meetingId: string): Promise<SummaryVersion[]> {
+    return invoke<SummaryVersion[]>('api_list_summary_versions', { meetingId });
+  }
+
+  /**
+   * Make an earlier version current again (recorded as a new version)
+   */
+  async restore(meetingId: string, version: number): Promise<SummaryVersion> {
+    return invoke<SummaryVersion>('api_restore_summary_version', { meetingId, version });
+  }
+
+  async diff(meetingId: string, fromVersion: number, toVersion: number): Promise<SummaryVersionDiff> {
+    return invoke<SummaryVersionDiff>('api_diff_summary_versions', { meetingId, fromVersion, toVersion });
+  }
+}
+
+// Export singleton instance
+export const summaryVersionsService = new SummaryVersionsService();
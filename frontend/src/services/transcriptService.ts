@@ -25,6 +25,16 @@ export interface ModelDownloadCompletePayload {
   modelName: string;
 }
 
+export type ExportFormat = 'srt' | 'vtt' | 'text' | 'markdown' | 'json';
+
+export interface ExportOptions {
+  include_speakers?: boolean;     // default true
+  include_timestamps?: boolean;   // [mm:ss] markers in text/markdown, default true
+  merge_short_segments?: boolean; // default false
+  min_segment_secs?: number;      // default 3.0
+  max_merge_gap_secs?: number;    // default 1.5
+}
+
 /**
  * Transcript Service
  * Singleton service for managing transcription operations and transcript history
@@ -46,6 +56,22 @@ export class TranscriptService {
     return invoke<TranscriptionStatus>('get_transcription_status');
   }
 
+  /**
+   * Export a saved meeting's transcript (plus summary for markdown/json)
+   * @returns Promise with the exported content
+   */
+  async exportMeetingTranscript(meetingId: string, format: ExportFormat, options?: ExportOptions): Promise<string> {
+    return invoke<string>('export_meeting_transcript', { meetingId, format, options });
+  }
+
+  /**
+   * Export a saved meeting to a file (defaults to the meeting folder)
+   * @returns Promise with the written file path
+   */
+  async exportMeetingToFile(meetingId: string, format: ExportFormat, options?: ExportOptions, path?: string): Promise<string> {
+    return invoke<string>('export_meeting_to_file', { meetingId, format, options, path });
+  }
+
   // Event Listeners
 
   /**
@@ -4,6 +4,14 @@ export interface Message {
   timestamp: string;
 }
 
+// Word-level timing, seconds from recording start
+export interface WordTimestamp {
+  text: string;
+  start: number;
+  end: number;
+  confidence?: number;
+}
+
 export interface Transcript {
   id: string;
   text: string;
@@ -16,6 +24,10 @@ export interface Transcript {
   audio_start_time?: number; // Seconds from recording start (e.g., 125.3)
   audio_end_time?: number;   // Seconds from recording start (e.g., 128.6)
   duration?: number;          // Segment duration in seconds (e.g., 3.3)
+  words?: WordTimestamp[];     // Word-level timing (Whisper/Parakeet)
+  speaker?: string;            // Capture stream: "mic" or "system"
+  speaker_id?: string;         // Stable per-meeting ID from diarization ("me", "speaker_1", ...)
+  speaker_label?: string;      // Display name, editable via api_rename_speaker
 }
 
 export interface TranscriptUpdate {
@@ -30,6 +42,9 @@ export interface TranscriptUpdate {
   audio_start_time: number; // Seconds from recording start
   audio_end_time: number;   // Seconds from recording start
   duration: number;          // Segment duration in seconds
+  words?: WordTimestamp[];   // Word-level timing, omitted if the engine has none
+  speaker?: string;          // Capture stream: "mic" or "system"
+  speaker_id?: string;       // Diarized speaker ID, omitted when unknown
 }
 
 export interface Block {
@@ -0,0 +1,413 @@
+// JSON Schema -> GBNF grammar conversion
+// Covers the subset of JSON Schema used for structured output: objects with
+// properties/required, arrays, primitive types, enum/const, anyOf/oneOf and local $refs
+
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+/// Whitespace between tokens, bounded so the model can't pad forever
+const WS_RULE: &str = r#"| " " | "\n" [ \t]{0,20}"#;
+
+const PRIMITIVE_RULES: &[(&str, &str)] = &[
+    ("boolean", r#"("true" | "false") ws"#),
+    ("null", r#""null" ws"#),
+    ("integer", r#"("-"? ([0-9] | [1-9] [0-9]{0,15})) ws"#),
+    (
+        "number",
+        r#"("-"? ([0-9] | [1-9] [0-9]{0,15})) ("." [0-9]+)? ([eE] [-+]? [0-9]{1,15})? ws"#,
+    ),
+    ("char", r#"[^"\\\x7F\x00-\x1F] | [\\] (["\\bfnrt] | "u" [0-9a-fA-F]{4})"#),
+    ("string", r#""\"" char* "\"" ws"#),
+    ("value", r#"object | array | string | number | boolean | null"#),
+    (
+        "object",
+        r#""{" ws ( string ":" ws value ("," ws string ":" ws value)* )? "}" ws"#,
+    ),
+    ("array", r#""[" ws ( value ("," ws value)* )? "]" ws"#),
+];
+
+/// Converts a JSON schema into a GBNF grammar whose root rule is `root`
+pub fn json_schema_to_gbnf(schema: &Value) -> Result<String> {
+    let mut converter = Converter {
+        root_schema: schema,
+        rules: Vec::new(),
+        primitives: HashSet::new(),
+        resolving_refs: HashSet::new(),
+    };
+
+    let root = converter.visit(schema, "root")?;
+    if root != "root" {
+        converter.add_rule("root", root);
+    }
+    converter.use_primitive("ws");
+
+    let mut grammar = String::new();
+    for (name, body) in &converter.rules {
+        grammar.push_str(&format!("{} ::= {}\n", name, body));
+    }
+    // Primitives are emitted in a fixed order so the grammar is deterministic
+    if converter.primitives.contains("ws") {
+        grammar.push_str(&format!("ws ::= {}\n", WS_RULE));
+    }
+    for (name, body) in PRIMITIVE_RULES {
+        if converter.primitives.contains(*name) {
+            grammar.push_str(&format!("{} ::= {}\n", name, body));
+        }
+    }
+    Ok(grammar)
+}
+
+struct Converter<'a> {
+    root_schema: &'a Value,
+    /// Named rules in creation order
+    rules: Vec<(String, String)>,
+    /// Built-in rules referenced so far
+    primitives: HashSet<&'static str>,
+    /// `$ref` targets currently being expanded (guards against infinite recursion)
+    resolving_refs: HashSet<String>,
+}
+
+impl<'a> Converter<'a> {
+    /// Returns a grammar expression (rule reference or inline alternation) for `schema`
+    fn visit(&mut self, schema: &'a Value, name: &str) -> Result<String> {
+        let schema = match schema {
+            Value::Bool(true) => return Ok(self.use_primitive("value")),
+            Value::Bool(false) => return Err(anyhow!("Schema 'false' can never match")),
+            Value::Object(map) => map,
+            _ => return Err(anyhow!("Invalid schema at '{}'", name)),
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(|r| r.as_str()) {
+            return self.visit_ref(reference);
+        }
+
+        if let Some(constant) = schema.get("const") {
+            return Ok(format!("{} ws", json_literal(constant)?));
+        }
+
+        if let Some(values) = schema.get("enum").and_then(|e| e.as_array()) {
+            let alternatives = values
+                .iter()
+                .map(json_literal)
+                .collect::<Result<Vec<_>>>()?;
+            self.use_primitive("ws");
+            return Ok(self.add_rule(name, format!("({}) ws", alternatives.join(" | "))));
+        }
+
+        for key in ["anyOf", "oneOf"] {
+            if let Some(options) = schema.get(key).and_then(|o| o.as_array()) {
+                let alternatives = options
+                    .iter()
+                    .enumerate()
+                    .map(|(i, option)| self.visit(option, &format!("{}-{}", name, i)))
+                    .collect::<Result<Vec<_>>>()?;
+                return Ok(self.add_rule(name, alternatives.join(" | ")));
+            }
+        }
+
+        match schema.get("type") {
+            Some(Value::String(type_name)) => self.visit_type(schema, type_name, name),
+            Some(Value::Array(types)) => {
+                let alternatives = types
+                    .iter()
+                    .map(|t| {
+                        let type_name = t
+                            .as_str()
+                            .ok_or_else(|| anyhow!("Invalid type list at '{}'", name))?;
+                        self.visit_type(schema, type_name, &format!("{}-{}", name, type_name))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(self.add_rule(name, alternatives.join(" | ")))
+            }
+            Some(_) => Err(anyhow!("Invalid type at '{}'", name)),
+            None if schema.contains_key("properties") => self.visit_type(schema, "object", name),
+            None if schema.contains_key("items") => self.visit_type(schema, "array", name),
+            None => Ok(self.use_primitive("value")),
+        }
+    }
+
+    fn visit_type(
+        &mut self,
+        schema: &'a serde_json::Map<String, Value>,
+        type_name: &str,
+        name: &str,
+    ) -> Result<String> {
+        match type_name {
+            "string" => Ok(self.use_primitive("string")),
+            "number" => Ok(self.use_primitive("number")),
+            "integer" => Ok(self.use_primitive("integer")),
+            "boolean" => Ok(self.use_primitive("boolean")),
+            "null" => Ok(self.use_primitive("null")),
+            "array" => {
+                let item = match schema.get("items") {
+                    Some(items) => self.visit(items, &format!("{}-item", name))?,
+                    None => self.use_primitive("value"),
+                };
+                let min_items = schema.get("minItems").and_then(|m| m.as_u64()).unwrap_or(0);
+                self.use_primitive("ws");
+                let body = if min_items > 0 {
+                    format!(r#""[" ws {item} ("," ws {item})* "]" ws"#, item = item)
+                } else {
+                    format!(r#""[" ws ( {item} ("," ws {item})* )? "]" ws"#, item = item)
+                };
+                Ok(self.add_rule(name, body))
+            }
+            "object" => self.visit_object(schema, name),
+            other => Err(anyhow!("Unsupported type '{}' at '{}'", other, name)),
+        }
+    }
+
+    fn visit_object(&mut self, schema: &'a serde_json::Map<String, Value>, name: &str) -> Result<String> {
+        let properties = match schema.get("properties").and_then(|p| p.as_object()) {
+            Some(properties) if !properties.is_empty() => properties,
+            // No declared properties: any JSON object
+            _ => return Ok(self.use_primitive("object")),
+        };
+
+        let required: HashSet<&str> = schema
+            .get("required")
+            .and_then(|r| r.as_array())
+            .map(|r| r.iter().filter_map(|k| k.as_str()).collect())
+            .unwrap_or_default();
+
+        self.use_primitive("ws");
+        let mut required_kvs = Vec::new();
+        let mut optional_kvs = Vec::new();
+        for (key, property_schema) in properties {
+            let value = self.visit(property_schema, &format!("{}-{}", name, sanitize_rule_name(key)))?;
+            let kv = format!(r#"{} ws ":" ws {}"#, json_literal(&Value::String(key.clone()))?, value);
+            if required.contains(key.as_str()) {
+                required_kvs.push(kv);
+            } else {
+                optional_kvs.push(kv);
+            }
+        }
+
+        let mut body = String::from(r#""{" ws "#);
+        body.push_str(&required_kvs.join(r#" "," ws "#));
+
+        if !optional_kvs.is_empty() {
+            if required_kvs.is_empty() {
+                // Any subset of the optional properties, in declaration order
+                let first = self.optional_properties_rule(name, &optional_kvs, false);
+                body.push_str(&format!("{}?", first));
+            } else {
+                let rest = self.optional_properties_rule(name, &optional_kvs, true);
+                body.push_str(&format!(" {}", rest));
+            }
+        }
+
+        body.push_str(r#" "}" ws"#);
+        Ok(self.add_rule(name, body))
+    }
+
+    /// Rules accepting any in-order subset of `kvs`
+    ///
+    /// With `after_first` every present property is preceded by a comma;
+    /// otherwise the first present property is not.
+    fn optional_properties_rule(&mut self, name: &str, kvs: &[String], after_first: bool) -> String {
+        // Tail rules: each remaining property is optional and comma-prefixed
+        // (without `after_first` the first property is reached via a head rule)
+        let first_tail = if after_first { 0 } else { 1 };
+        let mut tail_names = vec![String::new(); kvs.len() + 1];
+        for i in (first_tail..kvs.len()).rev() {
+            let next = &tail_names[i + 1];
+            let body = format!(r#"("," ws {})? {}"#, kvs[i], next).trim().to_string();
+            tail_names[i] = self.add_rule(&format!("{}-tail-{}", name, i), body);
+        }
+        if after_first {
+            return tail_names[0].clone();
+        }
+
+        // Head rules: pick the first present property, then continue with the tail
+        let mut head = String::new();
+        for i in (0..kvs.len()).rev() {
+            let alternative = format!("{} {}", kvs[i], tail_names[i + 1]).trim().to_string();
+            let body = if head.is_empty() {
+                alternative
+            } else {
+                format!("{} | {}", alternative, head)
+            };
+            head = self.add_rule(&format!("{}-head-{}", name, i), body);
+        }
+        head
+    }
+
+    fn visit_ref(&mut self, reference: &str) -> Result<String> {
+        let path = reference
+            .strip_prefix("#/")
+            .ok_or_else(|| anyhow!("Only local $refs are supported: {}", reference))?;
+        let rule_name = format!("ref-{}", sanitize_rule_name(path));
+
+        // Already generated, or currently being generated (recursive schema)
+        if self.rules.iter().any(|(n, _)| *n == rule_name) || self.resolving_refs.contains(&rule_name) {
+            return Ok(rule_name);
+        }
+
+        let target = path
+            .split('/')
+            .try_fold(self.root_schema, |node, segment| node.get(segment))
+            .ok_or_else(|| anyhow!("Unresolved $ref: {}", reference))?;
+
+        self.resolving_refs.insert(rule_name.clone());
+        let expression = self.visit(target, &format!("{}-body", rule_name))?;
+        self.resolving_refs.remove(&rule_name);
+        self.rules.push((rule_name.clone(), expression));
+        Ok(rule_name)
+    }
+
+    /// Adds a named rule and returns its name (names are made unique)
+    fn add_rule(&mut self, name: &str, body: String) -> String {
+        let base = sanitize_rule_name(name);
+        let mut rule_name = base.clone();
+        let mut suffix = 2;
+        while self.rules.iter().any(|(n, _)| *n == rule_name) || is_primitive(&rule_name) {
+            rule_name = format!("{}{}", base, suffix);
+            suffix += 1;
+        }
+        self.rules.push((rule_name.clone(), body));
+        rule_name
+    }
+
+    /// Marks a built-in rule (and the rules it depends on) as used
+    fn use_primitive(&mut self, name: &'static str) -> String {
+        self.primitives.insert(name);
+        self.primitives.insert("ws");
+        match name {
+            "string" => {
+                self.primitives.insert("char");
+            }
+            "value" | "object" | "array" => {
+                for dependency in ["object", "array", "string", "char", "number", "boolean", "null", "value"] {
+                    self.primitives.insert(dependency);
+                }
+            }
+            _ => {}
+        }
+        name.to_string()
+    }
+}
+
+fn is_primitive(name: &str) -> bool {
+    name == "ws" || PRIMITIVE_RULES.iter().any(|(n, _)| *n == name)
+}
+
+/// GBNF rule names may only contain letters, digits and dashes
+fn sanitize_rule_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    if sanitized.is_empty() {
+        "rule".to_string()
+    } else {
+        sanitized
+    }
+}
+
+/// GBNF string literal matching the JSON serialization of `value`
+fn json_literal(value: &Value) -> Result<String> {
+    let json = serde_json::to_string(value)?;
+    let mut literal = String::with_capacity(json.len() + 2);
+    literal.push('"');
+    for c in json.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    Ok(literal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_object_with_required_properties() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "items": { "type": "array", "items": { "type": "string" } },
+                "title": { "type": "string" }
+            },
+            "required": ["items", "title"],
+            "additionalProperties": false
+        });
+        let grammar = json_schema_to_gbnf(&schema).unwrap();
+        assert!(grammar.starts_with(
+            r#"root-items ::= "[" ws ( string ("," ws string)* )? "]" ws
+root ::= "{" ws "\"items\"" ws ":" ws root-items "," ws "\"title\"" ws ":" ws string "}" ws
+"#
+        ));
+        assert!(grammar.contains("\nws ::= "));
+        assert!(grammar.contains("\nstring ::= "));
+        assert!(grammar.contains("\nchar ::= "));
+        assert!(!grammar.contains("\nnumber ::= "));
+    }
+
+    #[test]
+    fn test_optional_properties() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "a": { "type": "integer" }, "b": { "type": "boolean" } }
+        });
+        let grammar = json_schema_to_gbnf(&schema).unwrap();
+        assert!(grammar.contains(r#"root-tail-1 ::= ("," ws "\"b\"" ws ":" ws boolean)?"#));
+        assert!(grammar.contains(r#"root-head-1 ::= "\"b\"" ws ":" ws boolean"#));
+        assert!(grammar.contains(
+            r#"root-head-0 ::= "\"a\"" ws ":" ws integer root-tail-1 | root-head-1"#
+        ));
+        assert!(grammar.contains(r#"root ::= "{" ws root-head-0? "}" ws"#));
+    }
+
+    #[test]
+    fn test_enum_and_nullable_type() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "status": { "enum": ["open", "done"] },
+                "note": { "type": ["string", "null"] }
+            },
+            "required": ["note", "status"]
+        });
+        let grammar = json_schema_to_gbnf(&schema).unwrap();
+        assert!(grammar.contains(r#"root-status ::= ("\"open\"" | "\"done\"") ws"#));
+        assert!(grammar.contains("root-note ::= string | null"));
+    }
+
+    #[test]
+    fn test_local_refs_and_recursion() {
+        let schema = json!({
+            "$defs": {
+                "node": {
+                    "type": "object",
+                    "properties": {
+                        "children": { "type": "array", "items": { "$ref": "#/$defs/node" } }
+                    },
+                    "required": ["children"]
+                }
+            },
+            "$ref": "#/$defs/node"
+        });
+        let grammar = json_schema_to_gbnf(&schema).unwrap();
+        assert!(grammar.contains("ref--defs-node ::= ref--defs-node-body"));
+        assert!(grammar.contains(r#"root ::= ref--defs-node"#));
+        assert!(grammar.contains(r#"( ref--defs-node ("," ws ref--defs-node)* )?"#));
+    }
+
+    #[test]
+    fn test_unsupported_schemas_are_rejected() {
+        assert!(json_schema_to_gbnf(&json!(false)).is_err());
+        assert!(json_schema_to_gbnf(&json!({ "type": "tuple" })).is_err());
+        assert!(json_schema_to_gbnf(&json!({ "$ref": "http://example.com/schema" })).is_err());
+    }
+}
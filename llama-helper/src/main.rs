@@ -6,15 +6,21 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use encoding_rs;
 use llama_cpp_2::context::params::LlamaContextParams;
+use llama_cpp_2::context::LlamaContext;
 use llama_cpp_2::llama_backend::LlamaBackend;
 use llama_cpp_2::llama_batch::LlamaBatch;
 use llama_cpp_2::model::params::LlamaModelParams;
 use llama_cpp_2::model::{AddBos, LlamaModel, Special};
+use llama_cpp_2::sampling::LlamaSampler;
+use llama_cpp_2::token::LlamaToken;
+use self_cell::self_cell;
 use serde::{Deserialize, Serialize};
 
+mod json_schema;
+
 // ============================================================================
 // Protocol Messages (JSON over stdin/stdout)
 // ============================================================================
@@ -32,18 +38,37 @@ enum Request {
         top_k: Option<i32>,
         top_p: Option<f32>,
         stop_tokens: Option<Vec<String>>,
+        // Emit incremental token frames before the final response
+        stream: Option<bool>,
+        // Constrain sampling with a GBNF grammar (root rule `root`)
+        grammar: Option<String>,
+        // Constrain sampling to JSON matching this schema (ignored if `grammar` is set)
+        json_schema: Option<serde_json::Value>,
     },
     Ping,
+    /// Drop the prompt cache and delete its session files (they hold prompt text)
+    ForgetSessions,
     Shutdown,
 }
 
 #[derive(Debug, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum Response {
-    Response { text: String, error: Option<String> },
+    Token {
+        text: String,
+    },
+    Response {
+        text: String,
+        error: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cache: Option<CacheStats>,
+    },
     Pong,
+    SessionsForgotten,
     Goodbye,
-    Error { message: String },
+    Error {
+        message: String,
+    },
 }
 
 // ============================================================================
@@ -201,22 +226,66 @@ fn get_default_gpu_layers(model_path: &PathBuf, context_size: u32) -> u32 {
 // Model State Management
 // ============================================================================
 
+/// Prompt cache statistics reported back with every generation
+#[derive(Debug, Default, Clone, Serialize)]
+struct CacheStats {
+    prompt_tokens: usize,
+    /// Prompt tokens whose KV cache entries were reused
+    reused_tokens: usize,
+    /// Prompt tokens that had to be evaluated
+    evaluated_tokens: usize,
+    /// The reused prefix was restored from a session file on disk
+    restored_from_disk: bool,
+}
+
+/// Context kept alive between requests so the KV cache of a shared
+/// prompt prefix (e.g. the same system prompt for every chunk) can be reused
+struct Session<'model> {
+    ctx: LlamaContext<'model>,
+    /// Tokens whose keys/values are in the KV cache, at positions 0..len
+    tokens: Vec<LlamaToken>,
+}
+
+/// Session files kept on disk; the least recently written ones are deleted
+const MAX_SESSION_FILES: usize = 2;
+
+/// The session of a loaded model, created on first use
+type SessionSlot<'model> = Option<Session<'model>>;
+
+self_cell!(
+    /// A loaded model together with the session context borrowing it;
+    /// the session is always dropped before the model
+    struct LoadedModel {
+        owner: LlamaModel,
+
+        #[not_covariant]
+        dependent: SessionSlot,
+    }
+);
+
 struct ModelState {
     backend: LlamaBackend,
-    model: Option<LlamaModel>,
+    loaded: Option<LoadedModel>,
     model_path: Option<PathBuf>,
     context_size: u32,
+    /// Save the session to disk on shutdown and restore it on the next start
+    /// (opt-in with `LLAMA_SESSION_CACHE=1`)
+    persist_sessions: bool,
     last_activity: Arc<AtomicU64>,
 }
 
 impl ModelState {
     fn new() -> Result<Self> {
         let backend = LlamaBackend::init().context("Failed to init LlamaBackend")?;
+        let persist_sessions = std::env::var("LLAMA_SESSION_CACHE")
+            .map(|v| v == "1")
+            .unwrap_or(false);
         Ok(Self {
             backend,
-            model: None,
+            loaded: None,
             model_path: None,
             context_size: 2048,
+            persist_sessions,
             last_activity: Arc::new(AtomicU64::new(Self::current_timestamp())),
         })
     }
@@ -247,6 +316,10 @@ impl ModelState {
             }
         }
 
+        // Keep the prompt cache of the outgoing model for its next use
+        self.save_session();
+        self.unload_model();
+
         eprintln!("📥 Loading model: {}", model_path.display());
 
         // Detect GPU layers
@@ -259,7 +332,7 @@ impl ModelState {
         let model = LlamaModel::load_from_file(&self.backend, model_path.clone(), &model_params)
             .with_context(|| format!("unable to load model at {:?}", model_path))?;
 
-        self.model = Some(model);
+        self.loaded = Some(LoadedModel::new(model, |_| None));
         self.model_path = Some(model_path);
         self.context_size = context_size;
         self.update_activity();
@@ -268,6 +341,79 @@ impl ModelState {
         Ok(())
     }
 
+    /// Drops the cached session and frees the model
+    fn unload_model(&mut self) {
+        self.loaded = None;
+        self.model_path = None;
+    }
+
+    /// Drops the cached session, keeping the model loaded
+    fn reset_session(&mut self) {
+        if let Some(loaded) = self.loaded.as_mut() {
+            loaded.with_dependent_mut(|_, session| *session = None);
+        }
+    }
+
+    /// Session file for the loaded model and context size
+    /// (`<models dir>/sessions/<model file>-ctx<size>.session`)
+    fn session_file_path(&self) -> Option<PathBuf> {
+        if !self.persist_sessions {
+            return None;
+        }
+        let model_path = self.model_path.as_ref()?;
+        let file_name = model_path.file_name()?.to_string_lossy();
+        Some(
+            model_path
+                .parent()?
+                .join("sessions")
+                .join(format!("{}-ctx{}.session", file_name, self.context_size)),
+        )
+    }
+
+    /// Writes the current KV cache to disk so a restarted sidecar can reuse it
+    fn save_session(&self) {
+        let (Some(loaded), Some(path)) = (self.loaded.as_ref(), self.session_file_path()) else {
+            return;
+        };
+        loaded.with_dependent(|_, session| {
+            let Some(session) = session.as_ref().filter(|s| !s.tokens.is_empty()) else {
+                return;
+            };
+            if let Some(dir) = path.parent() {
+                if let Err(e) = std::fs::create_dir_all(dir) {
+                    eprintln!("⚠️ Failed to create session directory: {}", e);
+                    return;
+                }
+            }
+            match session.ctx.save_session_file(&path, &session.tokens) {
+                Ok(()) => eprintln!(
+                    "💾 Saved prompt cache ({} tokens) to {}",
+                    session.tokens.len(),
+                    path.display()
+                ),
+                Err(e) => eprintln!("⚠️ Failed to save prompt cache: {:?}", e),
+            }
+        });
+        if let Some(dir) = path.parent() {
+            prune_session_files(dir, MAX_SESSION_FILES);
+        }
+    }
+
+    /// Drops the cached session and deletes the session files next to the
+    /// loaded model, so no earlier prompt survives on disk or in memory
+    fn forget_sessions(&mut self) {
+        self.reset_session();
+        let Some(dir) = self
+            .model_path
+            .as_ref()
+            .and_then(|path| path.parent())
+            .map(|dir| dir.join("sessions"))
+        else {
+            return;
+        };
+        prune_session_files(&dir, 0);
+    }
+
     fn generate(
         &mut self,
         prompt: String,
@@ -276,71 +422,133 @@ impl ModelState {
         top_k: i32,
         top_p: f32,
         stop_tokens: Vec<String>,
-    ) -> Result<String> {
-        let start_time = Instant::now();
-        let model = self.model.as_ref().context("Model not loaded")?;
-
-        // Calculate thread count (conservative default: max(1, (Cores / 2) + 2))
-        // This ensures the UI thread is never starved
-        let threads: i32 = std::thread::available_parallelism()
-            .map(|n| {
-                let cores = n.get() as i32;
-                ((cores / 2) + 2).max(1)
-            })
-            .unwrap_or(2);
-
-        let ctx_params = LlamaContextParams::default()
-            .with_n_ctx(Some(
-                NonZeroU32::new(self.context_size).context("Invalid ctx size")?,
-            ))
-            .with_n_batch(self.context_size)
-            .with_n_threads(threads)
-            .with_n_threads_batch(threads);
-
-        let mut ctx = model
-            .new_context(&self.backend, ctx_params)
-            .context("unable to create the llama_context")?;
-
-        let tokens_list = model
-            .str_to_token(&prompt, AddBos::Always)
-            .with_context(|| "failed to tokenize prompt")?;
-
-        eprintln!("📝 Tokenized prompt: {} tokens", tokens_list.len());
-
-        // Use context size for batch capacity to handle long prompts
-        let batch_size = self.context_size as usize;
-        let mut batch = LlamaBatch::new(batch_size, 1);
-
-        let last_index: i32 = (tokens_list.len() - 1) as i32;
-        for (i, token) in (0_i32..).zip(tokens_list.into_iter()) {
-            let is_last = i == last_index;
-            batch
-                .add(token, i, &[0], is_last)
-                .context("Failed to add token to batch")?;
+        grammar: Option<&str>,
+        on_token: &mut dyn FnMut(&str) -> Result<()>,
+    ) -> Result<(String, CacheStats)> {
+        let result = self.generate_with_session(
+            prompt,
+            max_tokens,
+            temperature,
+            top_k,
+            top_p,
+            stop_tokens,
+            grammar,
+            on_token,
+        );
+        if result.is_err() {
+            // The KV cache may no longer match the recorded tokens
+            self.reset_session();
         }
+        self.update_activity();
+        result
+    }
 
-        ctx.decode(&mut batch).context("llama_decode() failed")?;
-        let prompt_time = start_time.elapsed();
+    fn generate_with_session(
+        &mut self,
+        prompt: String,
+        max_tokens: i32,
+        temperature: f32,
+        top_k: i32,
+        top_p: f32,
+        stop_tokens: Vec<String>,
+        grammar: Option<&str>,
+        on_token: &mut dyn FnMut(&str) -> Result<()>,
+    ) -> Result<(String, CacheStats)> {
+        let start_time = Instant::now();
+        let context_size = self.context_size;
+        let session_file = self.session_file_path();
+        let backend = &self.backend;
+        let loaded = self.loaded.as_mut().context("Model not loaded")?;
+
+        loaded.with_dependent_mut(|model, slot| {
+            let tokens_list = model
+                .str_to_token(&prompt, AddBos::Always)
+                .with_context(|| "failed to tokenize prompt")?;
+            if tokens_list.is_empty() {
+                return Err(anyhow!("Prompt produced no tokens"));
+            }
 
-        let n_prompt_tokens = batch.n_tokens();
-        let mut n_cur = n_prompt_tokens;
-        let mut decoder = encoding_rs::UTF_8.new_decoder();
-        let mut output = String::new();
+            eprintln!("📝 Tokenized prompt: {} tokens", tokens_list.len());
+
+            let restored = ensure_session(slot, model, backend, context_size, session_file)?;
+            let session = slot.as_mut().context("Session not initialized")?;
+
+            // Reuse the KV cache for the shared prefix; at least one token is
+            // always evaluated so there are logits to sample from
+            let mut reused =
+                common_prefix_len(&session.tokens, &tokens_list).min(tokens_list.len() - 1);
+            if reused < session.tokens.len() {
+                let trimmed = session
+                    .ctx
+                    .clear_kv_cache_seq(Some(0), Some(reused as u32), None)
+                    .unwrap_or(false);
+                if !trimmed {
+                    session.ctx.clear_kv_cache();
+                    reused = 0;
+                }
+                session.tokens.truncate(reused);
+            }
 
-        eprintln!("🔄 Starting generation (max_tokens: {})", max_tokens);
+            let stats = CacheStats {
+                prompt_tokens: tokens_list.len(),
+                reused_tokens: reused,
+                evaluated_tokens: tokens_list.len() - reused,
+                restored_from_disk: restored && reused > 0,
+            };
+            eprintln!(
+                "♻️ Prompt cache: reusing {}/{} tokens{}",
+                reused,
+                tokens_list.len(),
+                if stats.restored_from_disk {
+                    " (restored from disk)"
+                } else {
+                    ""
+                }
+            );
 
-        loop {
-            // Check if we've generated enough tokens
-            if (n_cur - n_prompt_tokens) >= max_tokens {
-                eprintln!("✓ Reached max_tokens limit");
-                break;
-            }
+            // Use context size for batch capacity to handle long prompts
+            let batch_size = context_size as usize;
+            let mut batch = LlamaBatch::new(batch_size, 1);
 
-            use llama_cpp_2::sampling::LlamaSampler;
+            let last_index = tokens_list.len() - 1;
+            for (i, token) in tokens_list.iter().enumerate().skip(reused) {
+                batch
+                    .add(*token, i as i32, &[0], i == last_index)
+                    .context("Failed to add token to batch")?;
+            }
 
-            let sampler = if temperature <= 0.0 {
+            session
+                .ctx
+                .decode(&mut batch)
+                .context("llama_decode() failed")?;
+            session.tokens.extend_from_slice(&tokens_list[reused..]);
+            let prompt_time = start_time.elapsed();
+
+            let n_prompt_tokens = tokens_list.len() as i32;
+            let mut n_cur = n_prompt_tokens;
+            let mut decoder = encoding_rs::UTF_8.new_decoder();
+            let mut output = String::new();
+            // Bytes of `output` already handed to `on_token`
+            let mut emitted_len = 0;
+
+            eprintln!("🔄 Starting generation (max_tokens: {})", max_tokens);
+
+            // The sampler lives for the whole generation so a grammar can track
+            // which tokens it has already accepted
+            let mut samplers = Vec::new();
+            if let Some(grammar) = grammar {
+                eprintln!(
+                    "📐 Constraining output with grammar ({} bytes)",
+                    grammar.len()
+                );
+                samplers.push(
+                    LlamaSampler::grammar(model, grammar, "root")
+                        .map_err(|e| anyhow!("Invalid grammar: {:?}", e))?,
+                );
+            }
+            if temperature <= 0.0 {
                 // Greedy sampling for temp <= 0
-                LlamaSampler::chain_simple([LlamaSampler::greedy()])
+                samplers.push(LlamaSampler::greedy());
             } else {
                 // Random sampling with temperature/top_k/top_p
                 let seed = SystemTime::now()
@@ -348,84 +556,209 @@ impl ModelState {
                     .unwrap_or_default()
                     .as_millis() as u32;
 
-                LlamaSampler::chain_simple([
+                samplers.extend([
                     LlamaSampler::top_k(top_k),
                     LlamaSampler::top_p(top_p, 1),
                     LlamaSampler::temp(temperature),
                     LlamaSampler::dist(seed),
-                ])
-            };
-
-            let mut sampler = pin!(sampler);
-            let token = sampler.as_mut().sample(&ctx, batch.n_tokens() - 1);
-            sampler.as_mut().accept(token);
-
-            if model.is_eog_token(token) {
-                eprintln!(
-                    "✓ End-of-generation token reached (generated {} chars)",
-                    output.len()
-                );
-                break;
+                ]);
             }
+            let mut sampler = pin!(LlamaSampler::chain_simple(samplers));
 
-            let output_bytes = model
-                .token_to_bytes(token, Special::Tokenize)
-                .context("Failed to convert token to bytes")?;
+            loop {
+                // Check if we've generated enough tokens
+                if (n_cur - n_prompt_tokens) >= max_tokens {
+                    eprintln!("✓ Reached max_tokens limit");
+                    break;
+                }
 
-            let mut token_text = String::with_capacity(32);
-            let _ = decoder.decode_to_string(&output_bytes, &mut token_text, false);
-            output.push_str(&token_text);
+                // `sample` also accepts the token, advancing the grammar
+                let token = sampler.as_mut().sample(&session.ctx, batch.n_tokens() - 1);
 
-            // Check for model-specific stop tokens
-            let mut should_stop = false;
-            for stop_token in &stop_tokens {
-                if output.contains(stop_token) {
+                if model.is_eog_token(token) {
                     eprintln!(
-                        "✓ Stop token '{}' detected (generated {} chars)",
-                        stop_token,
+                        "✓ End-of-generation token reached (generated {} chars)",
                         output.len()
                     );
-                    // Remove the stop token from output
-                    output = output.replace(stop_token, "").trim_end().to_string();
-                    should_stop = true;
                     break;
                 }
+
+                let output_bytes = model
+                    .token_to_bytes(token, Special::Tokenize)
+                    .context("Failed to convert token to bytes")?;
+
+                let mut token_text = String::with_capacity(32);
+                let _ = decoder.decode_to_string(&output_bytes, &mut token_text, false);
+                output.push_str(&token_text);
+
+                // Check for model-specific stop tokens
+                let mut should_stop = false;
+                for stop_token in &stop_tokens {
+                    if output.contains(stop_token) {
+                        eprintln!(
+                            "✓ Stop token '{}' detected (generated {} chars)",
+                            stop_token,
+                            output.len()
+                        );
+                        // Remove the stop token from output
+                        output = output.replace(stop_token, "").trim_end().to_string();
+                        should_stop = true;
+                        break;
+                    }
+                }
+                if should_stop {
+                    break;
+                }
+
+                // Hold back any tail that could still turn into a stop token
+                let safe_len = streamable_len(&output, &stop_tokens);
+                if safe_len > emitted_len {
+                    on_token(&output[emitted_len..safe_len])?;
+                    emitted_len = safe_len;
+                }
+
+                batch.clear();
+                batch
+                    .add(token, n_cur, &[0], true)
+                    .context("Failed to add generated token to batch")?;
+                n_cur += 1;
+                session.ctx.decode(&mut batch).context("failed to eval")?;
+                session.tokens.push(token);
             }
-            if should_stop {
-                break;
+
+            // Flush whatever was held back (stop tokens are already stripped)
+            if output.len() > emitted_len {
+                on_token(&output[emitted_len..])?;
             }
 
-            batch.clear();
-            batch
-                .add(token, n_cur, &[0], true)
-                .context("Failed to add generated token to batch")?;
-            n_cur += 1;
-            ctx.decode(&mut batch).context("failed to eval")?;
+            // Generation statistics
+            let total_time = start_time.elapsed();
+            let gen_time = total_time.saturating_sub(prompt_time);
+            let output_tokens = (n_cur - n_prompt_tokens) as u64;
+            let prompt_tokens = n_prompt_tokens as u64;
+
+            let tokens_per_sec = if gen_time.as_secs_f64() > 0.0 {
+                output_tokens as f64 / gen_time.as_secs_f64()
+            } else {
+                0.0
+            };
+
+            eprintln!("📊 Generation Statistics:");
+            eprintln!(
+                "   • Prompt tokens: {} ({} reused)",
+                prompt_tokens, stats.reused_tokens
+            );
+            eprintln!("   • Output tokens: {}", output_tokens);
+            eprintln!("   • Prompt processing: {:.2}s", prompt_time.as_secs_f64());
+            eprintln!("   • Generation time: {:.2}s", gen_time.as_secs_f64());
+            eprintln!("   • Total time: {:.2}s", total_time.as_secs_f64());
+            eprintln!("   • Speed: {:.2} tokens/sec", tokens_per_sec);
+
+            Ok((output, stats))
+        })
+    }
+}
+
+/// Creates the session context if needed, restoring it from disk when possible
+///
+/// Returns true if the session was restored from a session file.
+fn ensure_session<'model>(
+    slot: &mut SessionSlot<'model>,
+    model: &'model LlamaModel,
+    backend: &LlamaBackend,
+    context_size: u32,
+    session_file: Option<PathBuf>,
+) -> Result<bool> {
+    if slot.is_some() {
+        return Ok(false);
+    }
+
+    // Calculate thread count (conservative default: max(1, (Cores / 2) + 2))
+    // This ensures the UI thread is never starved
+    let threads: i32 = std::thread::available_parallelism()
+        .map(|n| {
+            let cores = n.get() as i32;
+            ((cores / 2) + 2).max(1)
+        })
+        .unwrap_or(2);
+
+    let ctx_params = LlamaContextParams::default()
+        .with_n_ctx(Some(
+            NonZeroU32::new(context_size).context("Invalid ctx size")?,
+        ))
+        .with_n_batch(context_size)
+        .with_n_threads(threads)
+        .with_n_threads_batch(threads);
+
+    let mut ctx = model
+        .new_context(backend, ctx_params)
+        .context("unable to create the llama_context")?;
+
+    let mut tokens = Vec::new();
+    let mut restored = false;
+    if let Some(path) = session_file.filter(|p| p.exists()) {
+        match ctx.load_session_file(&path, context_size as usize) {
+            Ok(saved) => {
+                eprintln!("💾 Restored prompt cache ({} tokens)", saved.len());
+                tokens = saved;
+                restored = true;
+            }
+            Err(e) => {
+                eprintln!("⚠️ Discarding unusable prompt cache: {:?}", e);
+                ctx.clear_kv_cache();
+                let _ = std::fs::remove_file(&path);
+            }
         }
+    }
 
-        // Generation statistics
-        let total_time = start_time.elapsed();
-        let gen_time = total_time.saturating_sub(prompt_time);
-        let output_tokens = (n_cur - n_prompt_tokens) as u64;
-        let prompt_tokens = n_prompt_tokens as u64;
+    *slot = Some(Session { ctx, tokens });
+    Ok(restored)
+}
 
-        let tokens_per_sec = if gen_time.as_secs_f64() > 0.0 {
-            output_tokens as f64 / gen_time.as_secs_f64()
-        } else {
-            0.0
-        };
+/// Deletes all but the `keep` most recently written session files in `dir`
+fn prune_session_files(dir: &std::path::Path, keep: usize) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut files: Vec<(SystemTime, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "session"))
+        .map(|path| {
+            let modified = std::fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .unwrap_or(UNIX_EPOCH);
+            (modified, path)
+        })
+        .collect();
+    files.sort_by(|a, b| b.0.cmp(&a.0));
+    for (_, path) in files.into_iter().skip(keep) {
+        match std::fs::remove_file(&path) {
+            Ok(()) => eprintln!("🗑️ Removed prompt cache {}", path.display()),
+            Err(e) => eprintln!("⚠️ Failed to remove {}: {}", path.display(), e),
+        }
+    }
+}
 
-        eprintln!("📊 Generation Statistics:");
-        eprintln!("   • Prompt tokens: {}", prompt_tokens);
-        eprintln!("   • Output tokens: {}", output_tokens);
-        eprintln!("   • Prompt processing: {:.2}s", prompt_time.as_secs_f64());
-        eprintln!("   • Generation time: {:.2}s", gen_time.as_secs_f64());
-        eprintln!("   • Total time: {:.2}s", total_time.as_secs_f64());
-        eprintln!("   • Speed: {:.2} tokens/sec", tokens_per_sec);
+/// Number of leading tokens two sequences have in common
+fn common_prefix_len(a: &[LlamaToken], b: &[LlamaToken]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
 
-        self.update_activity();
-        Ok(output)
+/// Length of the prefix of `output` that can be streamed without risking
+/// a partially generated stop token leaking to the client
+fn streamable_len(output: &str, stop_tokens: &[String]) -> usize {
+    let mut safe_len = output.len();
+    for stop_token in stop_tokens {
+        // Longest suffix of `output` that is a proper prefix of the stop token
+        for (idx, _) in stop_token.char_indices().skip(1) {
+            let prefix = &stop_token[..idx];
+            if output.ends_with(prefix) {
+                safe_len = safe_len.min(output.len() - prefix.len());
+            }
+        }
     }
+    safe_len
 }
 
 // ============================================================================
@@ -461,6 +794,7 @@ fn main() -> Result<()> {
         // Check idle timeout
         if state.seconds_since_activity() > idle_timeout_secs {
             eprintln!("💤 Idle timeout reached, shutting down");
+            state.save_session();
             send_response(&Response::Goodbye)?;
             break;
         }
@@ -471,6 +805,7 @@ fn main() -> Result<()> {
             Ok(0) => {
                 // EOF reached
                 eprintln!("📪 EOF received, shutting down");
+                state.save_session();
                 break;
             }
             Ok(_) => {
@@ -490,6 +825,9 @@ fn main() -> Result<()> {
                         top_k,
                         top_p,
                         stop_tokens,
+                        stream,
+                        grammar,
+                        json_schema,
                     }) => {
                         let max_tokens = max_tokens.unwrap_or(512);
                         let context_size = context_size.unwrap_or(2048);
@@ -499,6 +837,25 @@ fn main() -> Result<()> {
                         let top_k = top_k.unwrap_or(64);
                         let top_p = top_p.unwrap_or(0.95);
                         let stop_tokens = stop_tokens.unwrap_or_else(Vec::new);
+                        let stream = stream.unwrap_or(false);
+
+                        // An explicit grammar wins over a JSON schema
+                        let grammar = match (grammar, json_schema) {
+                            (Some(grammar), _) => Some(grammar),
+                            (None, Some(schema)) => match json_schema::json_schema_to_gbnf(&schema)
+                            {
+                                Ok(grammar) => Some(grammar),
+                                Err(e) => {
+                                    send_response(&Response::Response {
+                                        text: String::new(),
+                                        error: Some(format!("Invalid JSON schema: {}", e)),
+                                        cache: None,
+                                    })?;
+                                    continue;
+                                }
+                            },
+                            (None, None) => None,
+                        };
 
                         // Load model if path provided
                         if let Some(path_str) = model_path {
@@ -507,11 +864,22 @@ fn main() -> Result<()> {
                                 send_response(&Response::Response {
                                     text: String::new(),
                                     error: Some(format!("Failed to load model: {}", e)),
+                                    cache: None,
                                 })?;
                                 continue;
                             }
                         }
 
+                        // Stream token frames as they are produced when requested
+                        let mut on_token = |text: &str| -> Result<()> {
+                            if stream {
+                                send_response(&Response::Token {
+                                    text: text.to_string(),
+                                })?;
+                            }
+                            Ok(())
+                        };
+
                         // Generate response with sampling parameters
                         match state.generate(
                             prompt,
@@ -520,14 +888,21 @@ fn main() -> Result<()> {
                             top_k,
                             top_p,
                             stop_tokens,
+                            grammar.as_deref(),
+                            &mut on_token,
                         ) {
-                            Ok(text) => {
-                                send_response(&Response::Response { text, error: None })?;
+                            Ok((text, cache)) => {
+                                send_response(&Response::Response {
+                                    text,
+                                    error: None,
+                                    cache: Some(cache),
+                                })?;
                             }
                             Err(e) => {
                                 send_response(&Response::Response {
                                     text: String::new(),
                                     error: Some(format!("Generation failed: {}", e)),
+                                    cache: None,
                                 })?;
                             }
                         }
@@ -536,8 +911,13 @@ fn main() -> Result<()> {
                         state.update_activity();
                         send_response(&Response::Pong)?;
                     }
+                    Ok(Request::ForgetSessions) => {
+                        state.forget_sessions();
+                        send_response(&Response::SessionsForgotten)?;
+                    }
                     Ok(Request::Shutdown) => {
                         eprintln!("🛑 Shutdown requested");
+                        state.save_session();
                         send_response(&Response::Goodbye)?;
                         break;
                     }
@@ -559,3 +939,290 @@ fn main() -> Result<()> {
     eprintln!("👋 llama-helper exiting");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes a tiny llama model with zeroed weights and a vocabulary of one
+    /// token per byte, so generation can run without a downloaded model
+    fn write_byte_vocab_model(path: &std::path::Path) {
+        const N_EMBD: u64 = 64;
+        const N_FF: u64 = 128;
+        const ALIGNMENT: usize = 32;
+        // GGUF value types
+        const U32: u32 = 4;
+        const I32: u32 = 5;
+        const F32: u32 = 6;
+        const STRING: u32 = 8;
+        const ARRAY: u32 = 9;
+
+        fn put_str(out: &mut Vec<u8>, s: &str) {
+            out.extend((s.len() as u64).to_le_bytes());
+            out.extend(s.as_bytes());
+        }
+        fn put_key(out: &mut Vec<u8>, key: &str, kind: u32) {
+            put_str(out, key);
+            out.extend(kind.to_le_bytes());
+        }
+        fn put_array_header(out: &mut Vec<u8>, key: &str, kind: u32, len: usize) {
+            put_key(out, key, ARRAY);
+            out.extend(kind.to_le_bytes());
+            out.extend((len as u64).to_le_bytes());
+        }
+
+        // <unk>, <s>, </s>, then <0x00>..<0xFF>
+        let mut tokens = vec!["<unk>".to_string(), "<s>".to_string(), "</s>".to_string()];
+        tokens.extend((0..=255u8).map(|b| format!("<0x{:02X}>", b)));
+        let n_vocab = tokens.len() as u64;
+        // Token types: unknown, control, control, then byte
+        let token_types = [2i32, 3, 3]
+            .into_iter()
+            .chain(std::iter::repeat(6).take(256));
+
+        let mut kv = Vec::new();
+        put_key(&mut kv, "general.architecture", STRING);
+        put_str(&mut kv, "llama");
+        for (key, value) in [
+            ("llama.context_length", 512u32),
+            ("llama.embedding_length", N_EMBD as u32),
+            ("llama.block_count", 1),
+            ("llama.feed_forward_length", N_FF as u32),
+            ("llama.attention.head_count", 4),
+            ("llama.attention.head_count_kv", 4),
+            ("tokenizer.ggml.unknown_token_id", 0),
+            ("tokenizer.ggml.bos_token_id", 1),
+            ("tokenizer.ggml.eos_token_id", 2),
+        ] {
+            put_key(&mut kv, key, U32);
+            kv.extend(value.to_le_bytes());
+        }
+        put_key(&mut kv, "llama.attention.layer_norm_rms_epsilon", F32);
+        kv.extend(1e-5f32.to_le_bytes());
+        put_key(&mut kv, "tokenizer.ggml.model", STRING);
+        put_str(&mut kv, "llama");
+        put_array_header(&mut kv, "tokenizer.ggml.tokens", STRING, tokens.len());
+        for token in &tokens {
+            put_str(&mut kv, token);
+        }
+        put_array_header(&mut kv, "tokenizer.ggml.scores", F32, tokens.len());
+        for _ in &tokens {
+            kv.extend(0f32.to_le_bytes());
+        }
+        put_array_header(&mut kv, "tokenizer.ggml.token_type", I32, tokens.len());
+        for token_type in token_types {
+            kv.extend(token_type.to_le_bytes());
+        }
+        let n_kv = 1 + 9 + 1 + 1 + 3;
+
+        let tensors: [(&str, &[u64]); 11] = [
+            ("token_embd.weight", &[N_EMBD, n_vocab]),
+            ("output_norm.weight", &[N_EMBD]),
+            ("blk.0.attn_norm.weight", &[N_EMBD]),
+            ("blk.0.attn_q.weight", &[N_EMBD, N_EMBD]),
+            ("blk.0.attn_k.weight", &[N_EMBD, N_EMBD]),
+            ("blk.0.attn_v.weight", &[N_EMBD, N_EMBD]),
+            ("blk.0.attn_output.weight", &[N_EMBD, N_EMBD]),
+            ("blk.0.ffn_norm.weight", &[N_EMBD]),
+            ("blk.0.ffn_gate.weight", &[N_EMBD, N_FF]),
+            ("blk.0.ffn_down.weight", &[N_FF, N_EMBD]),
+            ("blk.0.ffn_up.weight", &[N_EMBD, N_FF]),
+        ];
+
+        let mut out = Vec::new();
+        out.extend(b"GGUF");
+        out.extend(3u32.to_le_bytes());
+        out.extend((tensors.len() as u64).to_le_bytes());
+        out.extend((n_kv as u64).to_le_bytes());
+        out.extend(kv);
+
+        // F32 tensors; every size is a multiple of the alignment
+        let mut offset = 0u64;
+        for (name, dims) in &tensors {
+            put_str(&mut out, name);
+            out.extend((dims.len() as u32).to_le_bytes());
+            for dim in dims.iter() {
+                out.extend(dim.to_le_bytes());
+            }
+            out.extend(0u32.to_le_bytes());
+            out.extend(offset.to_le_bytes());
+            offset += dims.iter().product::<u64>() * 4;
+        }
+        out.resize(out.len().next_multiple_of(ALIGNMENT), 0);
+        out.resize(out.len() + offset as usize, 0);
+
+        std::fs::write(path, out).unwrap();
+    }
+
+    #[test]
+    fn test_generation_follows_grammar() {
+        let dir = std::env::temp_dir().join(format!("llama-helper-grammar-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let model_path = dir.join("byte-vocab.gguf");
+        write_byte_vocab_model(&model_path);
+
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "done": { "type": "boolean" },
+                "status": { "enum": ["open", "closed"] }
+            },
+            "required": ["done", "status"],
+            "additionalProperties": false
+        });
+        let grammar = json_schema::json_schema_to_gbnf(&schema).unwrap();
+
+        let mut state = ModelState::new().unwrap();
+        state.persist_sessions = false;
+        state.load_model_if_needed(model_path, 512).unwrap();
+        // The zeroed model gives every token the same logit, so sampling picks
+        // at random among the tokens the grammar allows
+        let result = state.generate(
+            "Is the task done?".to_string(),
+            256,
+            0.8,
+            300,
+            1.0,
+            Vec::new(),
+            Some(&grammar),
+            &mut |_| Ok(()),
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+        let (output, _) = result.unwrap();
+
+        let parsed: serde_json::Value = serde_json::from_str(&output)
+            .unwrap_or_else(|e| panic!("Output is not valid JSON ({}): {}", e, output));
+        assert!(parsed["done"].is_boolean());
+        assert!(parsed["status"] == "open" || parsed["status"] == "closed");
+    }
+
+    /// Runs against a small GGUF model, e.g.
+    /// `LLAMA_HELPER_TEST_MODEL=/path/to/tiny.gguf cargo test -- --ignored`
+    #[test]
+    #[ignore = "requires a GGUF fixture model in LLAMA_HELPER_TEST_MODEL"]
+    fn test_json_schema_constrains_output() {
+        let model_path = PathBuf::from(
+            std::env::var("LLAMA_HELPER_TEST_MODEL").expect("LLAMA_HELPER_TEST_MODEL not set"),
+        );
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "title": { "type": "string" },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            },
+            "required": ["tags", "title"],
+            "additionalProperties": false
+        });
+        let grammar = json_schema::json_schema_to_gbnf(&schema).unwrap();
+
+        let mut state = ModelState::new().unwrap();
+        state.load_model_if_needed(model_path, 512).unwrap();
+        let (output, _) = state
+            .generate(
+                "Describe a meeting about the quarterly budget.".to_string(),
+                256,
+                0.8,
+                40,
+                0.95,
+                Vec::new(),
+                Some(&grammar),
+                &mut |_| Ok(()),
+            )
+            .unwrap();
+
+        let parsed: serde_json::Value = serde_json::from_str(&output)
+            .unwrap_or_else(|e| panic!("Output is not valid JSON ({}): {}", e, output));
+        assert!(parsed["title"].is_string());
+        assert!(parsed["tags"].is_array());
+    }
+
+    #[test]
+    #[ignore = "requires a GGUF fixture model in LLAMA_HELPER_TEST_MODEL"]
+    fn test_shared_prefix_reuses_kv_cache() {
+        let model_path = PathBuf::from(
+            std::env::var("LLAMA_HELPER_TEST_MODEL").expect("LLAMA_HELPER_TEST_MODEL not set"),
+        );
+        let mut state = ModelState::new().unwrap();
+        state.persist_sessions = false;
+        state.load_model_if_needed(model_path, 512).unwrap();
+
+        let system = "You are a meeting assistant. Summarize the transcript below.\n\n";
+        let mut run = |prompt: String| {
+            state
+                .generate(prompt, 8, 0.0, 40, 0.95, Vec::new(), None, &mut |_| Ok(()))
+                .unwrap()
+                .1
+        };
+
+        let first = run(format!("{}Alice: the budget is approved.", system));
+        assert_eq!(first.reused_tokens, 0);
+
+        let second = run(format!("{}Bob: the launch moves to May.", system));
+        assert!(second.reused_tokens > 0);
+        assert_eq!(
+            second.reused_tokens + second.evaluated_tokens,
+            second.prompt_tokens
+        );
+    }
+
+    #[test]
+    fn test_prune_session_files_keeps_newest() {
+        let dir =
+            std::env::temp_dir().join(format!("llama-helper-sessions-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (i, name) in [
+            "a.gguf-ctx512.session",
+            "b.gguf-ctx512.session",
+            "c.gguf-ctx512.session",
+        ]
+        .iter()
+        .enumerate()
+        {
+            let file = std::fs::File::create(dir.join(name)).unwrap();
+            file.set_modified(UNIX_EPOCH + std::time::Duration::from_secs(1000 + i as u64))
+                .unwrap();
+        }
+        std::fs::write(dir.join("notes.txt"), "keep").unwrap();
+
+        prune_session_files(&dir, 2);
+        assert!(!dir.join("a.gguf-ctx512.session").exists());
+        assert!(dir.join("b.gguf-ctx512.session").exists());
+        assert!(dir.join("c.gguf-ctx512.session").exists());
+
+        prune_session_files(&dir, 0);
+        assert!(!dir.join("c.gguf-ctx512.session").exists());
+        assert!(dir.join("notes.txt").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_common_prefix_len() {
+        let tokens = |ids: &[i32]| {
+            ids.iter()
+                .map(|&id| LlamaToken::new(id))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            common_prefix_len(&tokens(&[1, 2, 3]), &tokens(&[1, 2, 4])),
+            2
+        );
+        assert_eq!(common_prefix_len(&tokens(&[1, 2]), &tokens(&[1, 2, 3])), 2);
+        assert_eq!(common_prefix_len(&tokens(&[]), &tokens(&[1])), 0);
+    }
+
+    #[test]
+    fn test_generate_request_grammar_fields() {
+        let line = r#"{"type":"generate","prompt":"hi","max_tokens":null,"context_size":null,"model_path":null,"temperature":null,"top_k":null,"top_p":null,"stop_tokens":null,"stream":null,"json_schema":{"type":"string"}}"#;
+        match serde_json::from_str::<Request>(line).unwrap() {
+            Request::Generate {
+                grammar,
+                json_schema,
+                ..
+            } => {
+                assert!(grammar.is_none());
+                assert_eq!(json_schema, Some(serde_json::json!({ "type": "string" })));
+            }
+            other => panic!("Unexpected request: {:?}", other),
+        }
+    }
+}
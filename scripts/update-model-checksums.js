@@ -0,0 +1,68 @@
+#!/usr/bin/env node
+/**
+ * Pin SHA-256 hashes of downloadable models
+ *
+ * Downloads every catalog file listed in the checked-in checksum table and
+ * stores its SHA-256 and size, so the app can verify model downloads even when
+ * the server publishes no checksum. Run it after adding a model to a catalog
+ * (add its download URL to the table first) or when an upstream file changes.
+ *
+ * Usage:
+ *   node scripts/update-model-checksums.js [--all]
+ *
+ * Without --all only entries that have no hash yet are downloaded.
+ */
+
+const fs = require('fs');
+const path = require('path');
+const crypto = require('crypto');
+
+const TABLE = path.join(__dirname, '..', 'frontend', 'src-tauri', 'src', 'model_integrity', 'known_checksums.json');
+const refreshAll = process.argv.includes('--all');
+
+async function hashUrl(url) {
+  const response = await fetch(url, { redirect: 'follow' });
+  if (!response.ok) {
+    throw new Error(`HTTP ${response.status}`);
+  }
+
+  const hash = crypto.createHash('sha256');
+  let size = 0;
+  for await (const chunk of response.body) {
+    hash.update(chunk);
+    size += chunk.length;
+  }
+  return { sha256: hash.digest('hex'), size };
+}
+
+async function main() {
+  const table = JSON.parse(fs.readFileSync(TABLE, 'utf8'));
+  let failed = 0;
+
+  for (const [url, entry] of Object.entries(table)) {
+    if (entry.sha256 && !refreshAll) continue;
+
+    process.stdout.write(`${url} ... `);
+    try {
+      const digest = await hashUrl(url);
+      if (entry.sha256 && entry.sha256 !== digest.sha256) {
+        process.stdout.write(`changed (was ${entry.sha256}) `);
+      }
+      table[url] = digest;
+      console.log(`${digest.sha256} (${digest.size} bytes)`);
+    } catch (err) {
+      failed += 1;
+      console.log(`failed: ${err.message}`);
+    }
+
+    // Save after every file, these downloads take a while
+    fs.writeFileSync(TABLE, JSON.stringify(table, null, 2) + '\n');
+  }
+
+  if (failed > 0) {
+    console.error(`${failed} file(s) could not be hashed`);
+    process.exit(1);
+  }
+}
+
+main();